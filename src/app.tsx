@@ -23,7 +23,14 @@ import { BucketTagPicker } from "./components/tags/BucketTagPicker";
 import { BucketColorPicker } from "./components/colors";
 import { LifecycleRulesPanel } from "./components/lifecycle";
 import { LocalUploadsToggle } from "./components/local-uploads";
+import { BucketSettingsPanel } from "./components/bucket-settings";
+import { EventNotificationsPanel } from "./components/event-notifications";
 import type { BucketColor } from "./utils/bucketColors";
+import {
+  LOCATION_HINT_LABELS,
+  type R2LocationHint,
+  type R2StorageClass,
+} from "./types/bucket-settings";
 import "./styles/metrics.css";
 import "./styles/tags.css";
 import type { FileRejection, FileWithPath } from "react-dropzone";
@@ -50,6 +57,26 @@ const WebhookManager = lazy(() =>
     default: m.WebhookManager,
   })),
 );
+const SignedLinksManager = lazy(() =>
+  import("./components/signed-links/SignedLinksManager").then((m) => ({
+    default: m.SignedLinksManager,
+  })),
+);
+const SharesManager = lazy(() =>
+  import("./components/shares/SharesManager").then((m) => ({
+    default: m.SharesManager,
+  })),
+);
+const AccessManager = lazy(() =>
+  import("./components/access/AccessManager").then((m) => ({
+    default: m.AccessManager,
+  })),
+);
+const ApiTokensManager = lazy(() =>
+  import("./components/api-tokens/ApiTokensManager").then((m) => ({
+    default: m.ApiTokensManager,
+  })),
+);
 
 // Loading fallback for lazy-loaded components
 const LazyLoadingFallback = (): JSX.Element => (
@@ -65,7 +92,11 @@ type ActiveView =
   | "health"
   | "s3-import"
   | "job-history"
-  | "webhooks";
+  | "webhooks"
+  | "signed-links"
+  | "shares"
+  | "api-tokens"
+  | "access";
 type BucketsSubView = "list" | "file-search" | "tag-search";
 
 // API response types
@@ -128,6 +159,11 @@ export default function BucketManager(): JSX.Element {
   const [buckets, setBuckets] = useState<BucketObject[]>([]);
   const [selectedBucket, setSelectedBucket] = useState<string | null>(null);
   const [newBucketName, setNewBucketName] = useState("");
+  const [newBucketLocation, setNewBucketLocation] = useState<
+    R2LocationHint | ""
+  >("");
+  const [newBucketStorageClass, setNewBucketStorageClass] =
+    useState<R2StorageClass>("Standard");
   const [isCreatingBucket, setIsCreatingBucket] = useState(false);
   const [error, setError] = useState("");
   const [isUploading, setIsUploading] = useState(false);
@@ -155,6 +191,11 @@ export default function BucketManager(): JSX.Element {
     {},
   );
   const [lifecycleBucket, setLifecycleBucket] = useState<string | null>(null);
+  const [settingsBucket, setSettingsBucket] = useState<string | null>(null);
+  const [notificationsBucket, setNotificationsBucket] = useState<
+    string | null
+  >(null);
+  const [isAccessAdmin, setIsAccessAdmin] = useState(false);
 
   // Debug: Log currentPath changes
   useEffect(() => {
@@ -235,6 +276,17 @@ export default function BucketManager(): JSX.Element {
     }
   }, []);
 
+  // Load the current user's role to decide whether to show the Access tab
+  const loadAccess = useCallback(async (): Promise<void> => {
+    try {
+      const access = await api.getMyAccess();
+      setIsAccessAdmin(access.isAdmin);
+    } catch (err) {
+      logger.error("App", "Error loading access", err);
+      // Non-critical, the Access tab just stays hidden
+    }
+  }, []);
+
   // Handle bucket color change
   const handleBucketColorChange = useCallback(
     async (bucketName: string, color: BucketColor): Promise<void> => {
@@ -453,8 +505,9 @@ export default function BucketManager(): JSX.Element {
     queueMicrotask(() => {
       void loadBuckets();
       void loadBucketColors();
+      void loadAccess();
     });
-  }, [loadBuckets, loadBucketColors]);
+  }, [loadBuckets, loadBucketColors, loadAccess]);
 
   const createBucket = async (): Promise<void> => {
     if (!newBucketName.trim()) return;
@@ -463,7 +516,10 @@ export default function BucketManager(): JSX.Element {
     setError("");
 
     try {
-      await api.createBucket(newBucketName.trim());
+      await api.createBucket(newBucketName.trim(), {
+        ...(newBucketLocation !== "" && { locationHint: newBucketLocation }),
+        storageClass: newBucketStorageClass,
+      });
       await loadBuckets(true); // Force refresh after mutation
       setNewBucketName("");
     } catch (err) {
@@ -914,6 +970,91 @@ export default function BucketManager(): JSX.Element {
             </svg>
             Webhooks
           </button>
+          <button
+            className={`nav-tab ${activeView === "signed-links" ? "active" : ""}`}
+            onClick={() => setActiveView("signed-links")}
+          >
+            <svg
+              xmlns="http://www.w3.org/2000/svg"
+              width="16"
+              height="16"
+              viewBox="0 0 24 24"
+              fill="none"
+              stroke="currentColor"
+              strokeWidth="2"
+              strokeLinecap="round"
+              strokeLinejoin="round"
+            >
+              <path d="M10 13a5 5 0 0 0 7.54.54l3-3a5 5 0 0 0-7.07-7.07l-1.72 1.71" />
+              <path d="M14 11a5 5 0 0 0-7.54-.54l-3 3a5 5 0 0 0 7.07 7.07l1.71-1.71" />
+            </svg>
+            Signed Links
+          </button>
+          <button
+            className={`nav-tab ${activeView === "shares" ? "active" : ""}`}
+            onClick={() => setActiveView("shares")}
+          >
+            <svg
+              xmlns="http://www.w3.org/2000/svg"
+              width="16"
+              height="16"
+              viewBox="0 0 24 24"
+              fill="none"
+              stroke="currentColor"
+              strokeWidth="2"
+              strokeLinecap="round"
+              strokeLinejoin="round"
+            >
+              <circle cx="18" cy="5" r="3" />
+              <circle cx="6" cy="12" r="3" />
+              <circle cx="18" cy="19" r="3" />
+              <line x1="8.59" y1="13.51" x2="15.42" y2="17.49" />
+              <line x1="15.41" y1="6.51" x2="8.59" y2="10.49" />
+            </svg>
+            Shares
+          </button>
+          <button
+            className={`nav-tab ${activeView === "api-tokens" ? "active" : ""}`}
+            onClick={() => setActiveView("api-tokens")}
+          >
+            <svg
+              xmlns="http://www.w3.org/2000/svg"
+              width="16"
+              height="16"
+              viewBox="0 0 24 24"
+              fill="none"
+              stroke="currentColor"
+              strokeWidth="2"
+              strokeLinecap="round"
+              strokeLinejoin="round"
+            >
+              <circle cx="7.5" cy="15.5" r="5.5" />
+              <path d="m21 2-9.6 9.6" />
+              <path d="m15.5 7.5 3 3L22 7l-3-3" />
+            </svg>
+            API Tokens
+          </button>
+          {isAccessAdmin && (
+            <button
+              className={`nav-tab ${activeView === "access" ? "active" : ""}`}
+              onClick={() => setActiveView("access")}
+            >
+              <svg
+                xmlns="http://www.w3.org/2000/svg"
+                width="16"
+                height="16"
+                viewBox="0 0 24 24"
+                fill="none"
+                stroke="currentColor"
+                strokeWidth="2"
+                strokeLinecap="round"
+                strokeLinejoin="round"
+              >
+                <path d="M12 22s8-4 8-10V5l-8-3-8 3v7c0 6 8 10 8 10z" />
+              </svg>
+              Access
+            </button>
+          )}
         </div>
       )}
 
@@ -958,6 +1099,34 @@ export default function BucketManager(): JSX.Element {
         </Suspense>
       )}
 
+      {/* Signed Links View */}
+      {!selectedBucket && activeView === "signed-links" && (
+        <Suspense fallback={<LazyLoadingFallback />}>
+          <SignedLinksManager />
+        </Suspense>
+      )}
+
+      {/* Shares View */}
+      {!selectedBucket && activeView === "shares" && (
+        <Suspense fallback={<LazyLoadingFallback />}>
+          <SharesManager />
+        </Suspense>
+      )}
+
+      {/* API Tokens View */}
+      {!selectedBucket && activeView === "api-tokens" && (
+        <Suspense fallback={<LazyLoadingFallback />}>
+          <ApiTokensManager buckets={buckets.map((b) => b.name)} />
+        </Suspense>
+      )}
+
+      {/* Access Control View */}
+      {!selectedBucket && activeView === "access" && (
+        <Suspense fallback={<LazyLoadingFallback />}>
+          <AccessManager buckets={buckets.map((b) => b.name)} />
+        </Suspense>
+      )}
+
       {/* Buckets View */}
       {!selectedBucket && activeView === "buckets" && (
         <>
@@ -1047,6 +1216,34 @@ export default function BucketManager(): JSX.Element {
                   className="bucket-input"
                   aria-label="New bucket name"
                 />
+                <select
+                  value={newBucketLocation}
+                  onChange={(e) =>
+                    setNewBucketLocation(e.target.value as R2LocationHint | "")
+                  }
+                  className="bucket-input bucket-create-select"
+                  aria-label="Location hint"
+                  title="Where the bucket's data is stored"
+                >
+                  <option value="">Automatic location</option>
+                  {Object.entries(LOCATION_HINT_LABELS).map(([hint, label]) => (
+                    <option key={hint} value={hint}>
+                      {label}
+                    </option>
+                  ))}
+                </select>
+                <select
+                  value={newBucketStorageClass}
+                  onChange={(e) =>
+                    setNewBucketStorageClass(e.target.value as R2StorageClass)
+                  }
+                  className="bucket-input bucket-create-select"
+                  aria-label="Default storage class"
+                  title="Storage class of new objects"
+                >
+                  <option value="Standard">Standard</option>
+                  <option value="InfrequentAccess">Infrequent Access</option>
+                </select>
                 <button
                   type="submit"
                   disabled={isCreatingBucket || !newBucketName.trim()}
@@ -1358,6 +1555,24 @@ export default function BucketManager(): JSX.Element {
                                   >
                                     Lifecycle
                                   </button>
+                                  <button
+                                    onClick={() =>
+                                      setNotificationsBucket(bucket.name)
+                                    }
+                                    className="bucket-list-action-btn"
+                                    title="Event Notifications"
+                                  >
+                                    Notifications
+                                  </button>
+                                  <button
+                                    onClick={() =>
+                                      setSettingsBucket(bucket.name)
+                                    }
+                                    className="bucket-list-action-btn"
+                                    title="CORS, domains and public access"
+                                  >
+                                    Settings
+                                  </button>
                                   <LocalUploadsToggle
                                     bucketName={bucket.name}
                                   />
@@ -1509,6 +1724,26 @@ export default function BucketManager(): JSX.Element {
                               >
                                 Lifecycle
                               </button>
+                              <button
+                                onClick={(e) => {
+                                  e.stopPropagation();
+                                  setNotificationsBucket(bucket.name);
+                                }}
+                                className="bucket-notifications"
+                                title="Event Notifications"
+                              >
+                                Notifications
+                              </button>
+                              <button
+                                onClick={(e) => {
+                                  e.stopPropagation();
+                                  setSettingsBucket(bucket.name);
+                                }}
+                                className="bucket-settings"
+                                title="CORS, domains and public access"
+                              >
+                                Settings
+                              </button>
                               <button
                                 onClick={(e) => {
                                   e.stopPropagation();
@@ -1579,6 +1814,42 @@ export default function BucketManager(): JSX.Element {
             </div>
           )}
 
+          {/* Event Notifications Modal */}
+          {notificationsBucket !== null && (
+            <div
+              className="modal-overlay"
+              onClick={() => setNotificationsBucket(null)}
+            >
+              <div
+                className="event-notifications-modal-container"
+                onClick={(e) => e.stopPropagation()}
+              >
+                <EventNotificationsPanel
+                  bucketName={notificationsBucket}
+                  onClose={() => setNotificationsBucket(null)}
+                />
+              </div>
+            </div>
+          )}
+
+          {/* Bucket Settings Modal */}
+          {settingsBucket !== null && (
+            <div
+              className="modal-overlay"
+              onClick={() => setSettingsBucket(null)}
+            >
+              <div
+                className="bucket-settings-modal-container"
+                onClick={(e) => e.stopPropagation()}
+              >
+                <BucketSettingsPanel
+                  bucketName={settingsBucket}
+                  onClose={() => setSettingsBucket(null)}
+                />
+              </div>
+            </div>
+          )}
+
           {/* Delete confirmation modal - always render outside view-specific code */}
           {deleteConfirmState && (
             <div
@@ -6,6 +6,7 @@ import {
   Loader2,
   Activity,
   Package,
+  CloudDownload,
 } from "lucide-react";
 import { MetricsChart, MetricsBarChart } from "./MetricsChart";
 import { StorageTab } from "./StorageTab";
@@ -33,6 +34,7 @@ interface BucketMetricsSummary {
   classAOperations: number;
   classBOperations: number;
   currentStorageBytes?: number;
+  sippyObjectsMigrated?: number;
 }
 
 interface MetricsResponse {
@@ -46,6 +48,7 @@ interface MetricsResponse {
     totalBytesDownloaded: number;
     totalStorageBytes: number;
     totalObjectCount?: number;
+    totalSippyObjectsMigrated?: number;
     bucketCount: number;
   };
   byBucket: BucketMetricsSummary[];
@@ -222,6 +225,8 @@ export function MetricsDashboard({
         color: "#10b981",
       })) ?? [];
 
+  const hasSippy = metrics?.summary.totalSippyObjectsMigrated !== undefined;
+
   return (
     <div className="metrics-container">
       {/* Header */}
@@ -409,6 +414,21 @@ export function MetricsDashboard({
               </div>
               <div className="metric-detail">Reads, Heads</div>
             </div>
+
+            {metrics.summary.totalSippyObjectsMigrated !== undefined && (
+              <div className="metric-card">
+                <div className="metric-header">
+                  <span>Migrated via Sippy</span>
+                  <CloudDownload />
+                </div>
+                <div className="metric-value">
+                  {formatNumber(metrics.summary.totalSippyObjectsMigrated)}
+                </div>
+                <div className="metric-detail">
+                  Estimated from object count growth
+                </div>
+              </div>
+            )}
           </div>
 
           {/* Charts */}
@@ -468,6 +488,7 @@ export function MetricsDashboard({
                     <th>Class A</th>
                     <th>Class B</th>
                     <th>Storage</th>
+                    {hasSippy && <th>Sippy</th>}
                   </tr>
                 </thead>
                 <tbody>
@@ -489,11 +510,18 @@ export function MetricsDashboard({
                           ? formatBytes(bucket.currentStorageBytes)
                           : "N/A"}
                       </td>
+                      {hasSippy && (
+                        <td>
+                          {bucket.sippyObjectsMigrated !== undefined
+                            ? formatNumber(bucket.sippyObjectsMigrated)
+                            : "—"}
+                        </td>
+                      )}
                     </tr>
                   ))}
                   {metrics.byBucket.length === 0 && (
                     <tr>
-                      <td colSpan={6} className="no-data">
+                      <td colSpan={hasSippy ? 7 : 6} className="no-data">
                         No bucket metrics available for this time period
                       </td>
                     </tr>
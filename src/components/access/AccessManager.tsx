@@ -0,0 +1,288 @@
+/**
+ * AccessManager Component
+ *
+ * Admin panel for role grants. Each grant gives a user, or every member of an
+ * identity provider group, the viewer, editor or admin role on every bucket,
+ * one bucket, or a key prefix within a bucket. Access control is enforced
+ * once the first grant exists.
+ */
+
+import { useCallback, useEffect, useState, type JSX } from "react";
+import {
+  api,
+  type AccessGrant,
+  type AccessGroupMapping,
+  type AccessRole,
+} from "../../services/api";
+import { logger } from "../../services/logger";
+import "../../styles/access.css";
+
+const ROLE_DESCRIPTIONS: Record<AccessRole, string> = {
+  viewer: "Browse, preview and download",
+  editor: "Also upload, rename, move and delete",
+  admin: "Also manage buckets, settings and access",
+};
+
+const ALL_BUCKETS = "";
+
+type GrantSubject = "users" | "groups";
+
+interface AccessManagerProps {
+  buckets: string[];
+}
+
+const describeScope = (grant: AccessGrant | AccessGroupMapping): string =>
+  grant.bucket_name === null
+    ? "All buckets"
+    : `${grant.bucket_name}/${grant.prefix}*`;
+
+export function AccessManager({ buckets }: AccessManagerProps): JSX.Element {
+  const [subject, setSubject] = useState<GrantSubject>("users");
+  const [grants, setGrants] = useState<AccessGrant[]>([]);
+  const [mappings, setMappings] = useState<AccessGroupMapping[]>([]);
+  const [loading, setLoading] = useState(true);
+  const [error, setError] = useState("");
+  const [member, setMember] = useState("");
+  const [role, setRole] = useState<AccessRole>("viewer");
+  const [bucketName, setBucketName] = useState(ALL_BUCKETS);
+  const [prefix, setPrefix] = useState("");
+  const [saving, setSaving] = useState(false);
+  const [removing, setRemoving] = useState<number | null>(null);
+
+  const loadGrants = useCallback(async (): Promise<void> => {
+    try {
+      setLoading(true);
+      setError("");
+      const [grantList, mappingList] = await Promise.all([
+        api.listAccessGrants(),
+        api.listAccessGroupMappings(),
+      ]);
+      setGrants(grantList);
+      setMappings(mappingList);
+    } catch (err) {
+      logger.error("AccessManager", "Failed to load access grants", err);
+      setError(
+        err instanceof Error ? err.message : "Failed to load access grants",
+      );
+    } finally {
+      setLoading(false);
+    }
+  }, []);
+
+  useEffect(() => {
+    queueMicrotask(() => {
+      void loadGrants();
+    });
+  }, [loadGrants]);
+
+  const handleGrant = async (): Promise<void> => {
+    if (member.trim() === "") {
+      setError(
+        subject === "users"
+          ? "Enter the email address to grant access to"
+          : "Enter the identity provider group to grant access to",
+      );
+      return;
+    }
+
+    const scope = {
+      role,
+      bucketName: bucketName === ALL_BUCKETS ? null : bucketName,
+      ...(bucketName !== ALL_BUCKETS && prefix !== "" && { prefix }),
+    };
+
+    try {
+      setSaving(true);
+      setError("");
+      if (subject === "users") {
+        await api.createAccessGrant({ userEmail: member.trim(), ...scope });
+      } else {
+        await api.createAccessGroupMapping({
+          groupName: member.trim(),
+          ...scope,
+        });
+      }
+      setMember("");
+      setPrefix("");
+      await loadGrants();
+    } catch (err) {
+      logger.error("AccessManager", "Failed to save access grant", err);
+      setError(
+        err instanceof Error ? err.message : "Failed to save access grant",
+      );
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const handleRemove = async (grantId: number): Promise<void> => {
+    try {
+      setRemoving(grantId);
+      setError("");
+      if (subject === "users") {
+        await api.deleteAccessGrant(grantId);
+      } else {
+        await api.deleteAccessGroupMapping(grantId);
+      }
+      await loadGrants();
+    } catch (err) {
+      logger.error("AccessManager", "Failed to remove access grant", err);
+      setError(
+        err instanceof Error ? err.message : "Failed to remove access grant",
+      );
+    } finally {
+      setRemoving(null);
+    }
+  };
+
+  const rows: (AccessGrant | AccessGroupMapping)[] =
+    subject === "users" ? grants : mappings;
+
+  return (
+    <div className="access-container">
+      <div className="access-header">
+        <div>
+          <h2>Access</h2>
+          <p className="access-subtitle">
+            Roles for users signed in through Cloudflare Access, granted per
+            user or per identity provider group. Grants apply to every bucket,
+            one bucket, or a folder prefix within a bucket.
+          </p>
+        </div>
+        <div className="access-header-actions">
+          <div className="access-tabs">
+            <button
+              className={`access-tab ${subject === "users" ? "active" : ""}`}
+              onClick={() => setSubject("users")}
+            >
+              Users
+            </button>
+            <button
+              className={`access-tab ${subject === "groups" ? "active" : ""}`}
+              onClick={() => setSubject("groups")}
+            >
+              IdP Groups
+            </button>
+          </div>
+          <button
+            className="access-btn"
+            onClick={() => void loadGrants()}
+            disabled={loading}
+          >
+            Refresh
+          </button>
+        </div>
+      </div>
+
+      {!loading && grants.length === 0 && mappings.length === 0 && (
+        <div className="access-notice">
+          Access control is not enforced yet: every signed-in user can manage
+          every bucket. Grant yourself the admin role on all buckets first, or
+          list your email in ADMIN_EMAILS, before adding other users.
+        </div>
+      )}
+
+      <div className="access-form">
+        <label className="access-field">
+          {subject === "users" ? "User email" : "Group name or ID"}
+          <input
+            type={subject === "users" ? "email" : "text"}
+            value={member}
+            onChange={(e) => setMember(e.target.value)}
+            placeholder={
+              subject === "users" ? "user@example.com" : "engineering-interns"
+            }
+          />
+        </label>
+        <label className="access-field">
+          Role
+          <select
+            value={role}
+            onChange={(e) => setRole(e.target.value as AccessRole)}
+            title={ROLE_DESCRIPTIONS[role]}
+          >
+            <option value="viewer">Viewer</option>
+            <option value="editor">Editor</option>
+            <option value="admin">Admin</option>
+          </select>
+        </label>
+        <label className="access-field">
+          Bucket
+          <select
+            value={bucketName}
+            onChange={(e) => setBucketName(e.target.value)}
+          >
+            <option value={ALL_BUCKETS}>All buckets</option>
+            {buckets.map((bucket) => (
+              <option key={bucket} value={bucket}>
+                {bucket}
+              </option>
+            ))}
+          </select>
+        </label>
+        <label className="access-field">
+          Prefix (optional)
+          <input
+            type="text"
+            value={prefix}
+            onChange={(e) => setPrefix(e.target.value)}
+            placeholder={
+              bucketName === ALL_BUCKETS ? "Choose a bucket first" : "assets/"
+            }
+            disabled={bucketName === ALL_BUCKETS}
+          />
+        </label>
+        <button
+          className="access-btn primary"
+          onClick={() => void handleGrant()}
+          disabled={saving}
+        >
+          {saving ? "Saving..." : "Grant"}
+        </button>
+      </div>
+
+      {error && <div className="access-error">{error}</div>}
+
+      {loading && grants.length === 0 && mappings.length === 0 ? (
+        <div className="access-empty">Loading...</div>
+      ) : rows.length === 0 ? (
+        <div className="access-empty">
+          {subject === "users"
+            ? "No user grants."
+            : "No group mappings. Groups are read from the Access JWT claims set in ACCESS_GROUP_CLAIMS."}
+        </div>
+      ) : (
+        <div className="access-list">
+          {rows.map((grant) => (
+            <div key={grant.id} className="access-card">
+              <div className="access-main">
+                <div className="access-email">
+                  {"user_email" in grant
+                    ? grant.user_email
+                    : `👥 ${grant.group_name}`}
+                </div>
+                <div className="access-scope" title={describeScope(grant)}>
+                  {describeScope(grant)}
+                </div>
+                <div className="access-meta">
+                  <span className={`access-role ${grant.role}`}>
+                    {grant.role}
+                  </span>
+                  <span>{ROLE_DESCRIPTIONS[grant.role]}</span>
+                  <span>Granted by {grant.created_by}</span>
+                </div>
+              </div>
+              <button
+                className="access-btn danger"
+                onClick={() => void handleRemove(grant.id)}
+                disabled={removing === grant.id}
+              >
+                {removing === grant.id ? "Removing..." : "Remove"}
+              </button>
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,355 @@
+/**
+ * ApiTokensManager Component
+ *
+ * Creates, lists and revokes the current user's API tokens. Tokens let
+ * scripts and CI pipelines call the API with "Authorization: Bearer", acting
+ * as the user who created them but limited to the chosen buckets and
+ * permissions. A token is only shown once, right after it is created, along
+ * with the S3 credentials derived from it.
+ */
+
+import { useCallback, useEffect, useState, type JSX } from "react";
+import {
+  api,
+  type ApiToken,
+  type ApiTokenPermission,
+  type ApiTokenStatus,
+  type CreatedApiToken,
+} from "../../services/api";
+import { logger } from "../../services/logger";
+import "../../styles/api-tokens.css";
+
+const STATUS_LABELS: Record<ApiTokenStatus, string> = {
+  active: "Active",
+  expired: "Expired",
+  revoked: "Revoked",
+};
+
+const PERMISSIONS: ApiTokenPermission[] = ["read", "write", "delete"];
+
+const EXPIRY_OPTIONS = [
+  { label: "7 days", seconds: 7 * 24 * 60 * 60 },
+  { label: "30 days", seconds: 30 * 24 * 60 * 60 },
+  { label: "90 days", seconds: 90 * 24 * 60 * 60 },
+  { label: "1 year", seconds: 365 * 24 * 60 * 60 },
+];
+
+const DEFAULT_EXPIRY_SECONDS = 90 * 24 * 60 * 60;
+
+const formatDate = (value: string): string =>
+  new Date(value).toLocaleString();
+
+interface ApiTokensManagerProps {
+  buckets: string[];
+}
+
+export function ApiTokensManager({
+  buckets,
+}: ApiTokensManagerProps): JSX.Element {
+  const [tokens, setTokens] = useState<ApiToken[]>([]);
+  const [loading, setLoading] = useState(true);
+  const [error, setError] = useState("");
+  const [includeInactive, setIncludeInactive] = useState(false);
+  const [revoking, setRevoking] = useState<string | null>(null);
+  const [name, setName] = useState("");
+  const [permissions, setPermissions] = useState<ApiTokenPermission[]>([
+    "read",
+  ]);
+  const [allBuckets, setAllBuckets] = useState(true);
+  const [selectedBuckets, setSelectedBuckets] = useState<string[]>([]);
+  const [expiresIn, setExpiresIn] = useState(DEFAULT_EXPIRY_SECONDS);
+  const [creating, setCreating] = useState(false);
+  const [created, setCreated] = useState<CreatedApiToken | null>(null);
+  const [copied, setCopied] = useState(false);
+
+  const loadTokens = useCallback(async (): Promise<void> => {
+    try {
+      setLoading(true);
+      setError("");
+      setTokens(await api.listApiTokens(includeInactive));
+    } catch (err) {
+      logger.error("ApiTokensManager", "Failed to load API tokens", err);
+      setError(
+        err instanceof Error ? err.message : "Failed to load API tokens",
+      );
+    } finally {
+      setLoading(false);
+    }
+  }, [includeInactive]);
+
+  useEffect(() => {
+    queueMicrotask(() => {
+      void loadTokens();
+    });
+  }, [loadTokens]);
+
+  const togglePermission = (permission: ApiTokenPermission): void => {
+    setPermissions((current) =>
+      current.includes(permission)
+        ? current.filter((p) => p !== permission)
+        : [...current, permission],
+    );
+  };
+
+  const toggleBucket = (bucket: string): void => {
+    setSelectedBuckets((current) =>
+      current.includes(bucket)
+        ? current.filter((b) => b !== bucket)
+        : [...current, bucket],
+    );
+  };
+
+  const handleCreate = async (): Promise<void> => {
+    if (name.trim() === "") {
+      setError("Enter a name for the token");
+      return;
+    }
+    if (permissions.length === 0) {
+      setError("Choose at least one permission");
+      return;
+    }
+    if (!allBuckets && selectedBuckets.length === 0) {
+      setError("Choose at least one bucket");
+      return;
+    }
+
+    try {
+      setCreating(true);
+      setError("");
+      setCopied(false);
+      setCreated(
+        await api.createApiToken({
+          name: name.trim(),
+          permissions,
+          buckets: allBuckets ? null : selectedBuckets,
+          expiresIn,
+        }),
+      );
+      setName("");
+      await loadTokens();
+    } catch (err) {
+      logger.error("ApiTokensManager", "Failed to create API token", err);
+      setError(
+        err instanceof Error ? err.message : "Failed to create API token",
+      );
+    } finally {
+      setCreating(false);
+    }
+  };
+
+  const handleCopy = async (token: string): Promise<void> => {
+    try {
+      await navigator.clipboard.writeText(token);
+      setCopied(true);
+    } catch (err) {
+      logger.error("ApiTokensManager", "Failed to copy API token", err);
+    }
+  };
+
+  const handleRevoke = async (tokenId: string): Promise<void> => {
+    try {
+      setRevoking(tokenId);
+      setError("");
+      await api.revokeApiToken(tokenId);
+      await loadTokens();
+    } catch (err) {
+      logger.error("ApiTokensManager", "Failed to revoke API token", err);
+      setError(
+        err instanceof Error ? err.message : "Failed to revoke API token",
+      );
+    } finally {
+      setRevoking(null);
+    }
+  };
+
+  return (
+    <div className="api-tokens-container">
+      <div className="api-tokens-header">
+        <div>
+          <h2>API Tokens</h2>
+          <p className="api-tokens-subtitle">
+            Tokens for scripts and CI pipelines. Send a token as
+            &quot;Authorization: Bearer &lt;token&gt;&quot;; requests act as
+            you, limited to the token&apos;s buckets and permissions.
+          </p>
+        </div>
+        <div className="api-tokens-header-actions">
+          <label className="api-tokens-toggle">
+            <input
+              type="checkbox"
+              checked={includeInactive}
+              onChange={(e) => setIncludeInactive(e.target.checked)}
+            />
+            Show expired and revoked
+          </label>
+          <button
+            className="api-tokens-btn"
+            onClick={() => void loadTokens()}
+            disabled={loading}
+          >
+            Refresh
+          </button>
+        </div>
+      </div>
+
+      <div className="api-tokens-form">
+        <div className="api-tokens-form-row">
+          <label className="api-tokens-field">
+            Name
+            <input
+              type="text"
+              value={name}
+              onChange={(e) => setName(e.target.value)}
+              placeholder="CI artifact upload"
+              maxLength={100}
+            />
+          </label>
+          <label className="api-tokens-field">
+            Expires after
+            <select
+              value={expiresIn}
+              onChange={(e) => setExpiresIn(Number(e.target.value))}
+            >
+              {EXPIRY_OPTIONS.map((option) => (
+                <option key={option.seconds} value={option.seconds}>
+                  {option.label}
+                </option>
+              ))}
+            </select>
+          </label>
+        </div>
+        <div className="api-tokens-field">
+          Permissions
+          <div className="api-tokens-choices">
+            {PERMISSIONS.map((permission) => (
+              <label key={permission} className="api-tokens-choice">
+                <input
+                  type="checkbox"
+                  checked={permissions.includes(permission)}
+                  onChange={() => togglePermission(permission)}
+                />
+                {permission}
+              </label>
+            ))}
+          </div>
+        </div>
+        <div className="api-tokens-field">
+          Buckets
+          <div className="api-tokens-choices">
+            <label className="api-tokens-choice">
+              <input
+                type="checkbox"
+                checked={allBuckets}
+                onChange={(e) => setAllBuckets(e.target.checked)}
+              />
+              All buckets
+            </label>
+            {!allBuckets &&
+              buckets.map((bucket) => (
+                <label key={bucket} className="api-tokens-choice">
+                  <input
+                    type="checkbox"
+                    checked={selectedBuckets.includes(bucket)}
+                    onChange={() => toggleBucket(bucket)}
+                  />
+                  {bucket}
+                </label>
+              ))}
+          </div>
+        </div>
+        <div>
+          <button
+            className="api-tokens-btn primary"
+            onClick={() => void handleCreate()}
+            disabled={creating}
+          >
+            {creating ? "Creating..." : "Create Token"}
+          </button>
+        </div>
+      </div>
+
+      {created && (
+        <div className="api-tokens-created">
+          <div>
+            Copy the token for <strong>{created.name}</strong> now. It will not
+            be shown again.
+          </div>
+          <div className="api-tokens-secret">
+            <code>{created.token}</code>
+            <button
+              className="api-tokens-btn"
+              onClick={() => void handleCopy(created.token)}
+            >
+              {copied ? "Copied" : "Copy"}
+            </button>
+            <button className="api-tokens-btn" onClick={() => setCreated(null)}>
+              Done
+            </button>
+          </div>
+          <div className="api-tokens-s3">
+            <div>
+              S3 credentials, for the S3-compatible endpoint at{" "}
+              <code>{`${window.location.origin}/s3`}</code> (region{" "}
+              <code>auto</code>):
+            </div>
+            <dl>
+              <dt>Access key ID</dt>
+              <dd>
+                <code>{created.s3.accessKeyId}</code>
+              </dd>
+              <dt>Secret access key</dt>
+              <dd>
+                <code>{created.s3.secretAccessKey}</code>
+              </dd>
+            </dl>
+          </div>
+        </div>
+      )}
+
+      {error && <div className="api-tokens-error">{error}</div>}
+
+      {loading && tokens.length === 0 ? (
+        <div className="api-tokens-empty">Loading...</div>
+      ) : tokens.length === 0 ? (
+        <div className="api-tokens-empty">No API tokens.</div>
+      ) : (
+        <div className="api-tokens-list">
+          {tokens.map((token) => (
+            <div key={token.token_id} className="api-token-card">
+              <div className="api-token-main">
+                <div className="api-token-name">{token.name}</div>
+                <div className="api-token-prefix">{token.token_prefix}…</div>
+                <div className="api-token-meta">
+                  <span className={`api-token-status ${token.status}`}>
+                    {STATUS_LABELS[token.status]}
+                  </span>
+                  <span>{token.permissions.join(", ")}</span>
+                  <span>
+                    {token.buckets === null
+                      ? "All buckets"
+                      : token.buckets.join(", ")}
+                  </span>
+                  <span>Expires {formatDate(token.expires_at)}</span>
+                  <span>
+                    {token.last_used_at !== null
+                      ? `Last used ${formatDate(token.last_used_at)}`
+                      : "Never used"}
+                  </span>
+                </div>
+              </div>
+              {token.status === "active" && (
+                <button
+                  className="api-tokens-btn danger"
+                  onClick={() => void handleRevoke(token.token_id)}
+                  disabled={revoking === token.token_id}
+                >
+                  {revoking === token.token_id ? "Revoking..." : "Revoke"}
+                </button>
+              )}
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+}
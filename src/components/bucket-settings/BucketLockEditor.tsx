@@ -0,0 +1,265 @@
+import { useState, useEffect, useCallback, type JSX } from "react";
+import { api } from "../../services/api";
+import type {
+  BucketLockCondition,
+  BucketLockRule,
+} from "../../types/bucket-settings";
+
+interface BucketLockEditorProps {
+  bucketName: string;
+}
+
+const SECONDS_PER_DAY = 86400;
+
+/**
+ * Editable form of a bucket lock rule. The retention is edited in days or
+ * as a calendar date and converted when the rules are saved.
+ */
+interface LockRuleDraft {
+  key: number;
+  id: string;
+  enabled: boolean;
+  prefix: string;
+  type: BucketLockCondition["type"];
+  days: string;
+  date: string;
+}
+
+let nextDraftKey = 0;
+
+function toDraft(rule: BucketLockRule): LockRuleDraft {
+  const condition = rule.condition;
+  return {
+    key: nextDraftKey++,
+    id: rule.id,
+    enabled: rule.enabled,
+    prefix: rule.prefix ?? "",
+    type: condition.type,
+    days:
+      condition.type === "Age"
+        ? String(condition.maxAgeSeconds / SECONDS_PER_DAY)
+        : "30",
+    date: condition.type === "Date" ? condition.date.slice(0, 10) : "",
+  };
+}
+
+function fromDraft(draft: LockRuleDraft): BucketLockRule {
+  let condition: BucketLockCondition;
+  if (draft.type === "Age") {
+    condition = {
+      type: "Age",
+      maxAgeSeconds: Math.round(Number(draft.days) * SECONDS_PER_DAY),
+    };
+  } else if (draft.type === "Date") {
+    condition = { type: "Date", date: `${draft.date}T00:00:00Z` };
+  } else {
+    condition = { type: "Indefinite" };
+  }
+  return {
+    id: draft.id.trim(),
+    enabled: draft.enabled,
+    ...(draft.prefix !== "" && { prefix: draft.prefix }),
+    condition,
+  };
+}
+
+function emptyDraft(): LockRuleDraft {
+  return {
+    key: nextDraftKey++,
+    id: `lock-${crypto.randomUUID().slice(0, 8)}`,
+    enabled: true,
+    prefix: "",
+    type: "Age",
+    days: "30",
+    date: "",
+  };
+}
+
+export function BucketLockEditor({
+  bucketName,
+}: BucketLockEditorProps): JSX.Element {
+  const [drafts, setDrafts] = useState<LockRuleDraft[]>([]);
+  const [isLoading, setIsLoading] = useState(true);
+  const [isSaving, setIsSaving] = useState(false);
+  const [isDirty, setIsDirty] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const [saved, setSaved] = useState(false);
+
+  const loadRules = useCallback(async () => {
+    setIsLoading(true);
+    setError(null);
+    try {
+      const rules = await api.getBucketLock(bucketName);
+      setDrafts(rules.map(toDraft));
+      setIsDirty(false);
+    } catch (err) {
+      setError(
+        err instanceof Error ? err.message : "Failed to load bucket lock",
+      );
+    } finally {
+      setIsLoading(false);
+    }
+  }, [bucketName]);
+
+  useEffect(() => {
+    queueMicrotask(() => {
+      void loadRules();
+    });
+  }, [loadRules]);
+
+  const updateDraft = (key: number, changes: Partial<LockRuleDraft>): void => {
+    setDrafts((prev) =>
+      prev.map((d) => (d.key === key ? { ...d, ...changes } : d)),
+    );
+    setIsDirty(true);
+    setSaved(false);
+  };
+
+  const handleSave = async (): Promise<void> => {
+    setIsSaving(true);
+    setError(null);
+    try {
+      await api.setBucketLock(bucketName, drafts.map(fromDraft));
+      setIsDirty(false);
+      setSaved(true);
+    } catch (err) {
+      setError(
+        err instanceof Error ? err.message : "Failed to save bucket lock",
+      );
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  if (isLoading) {
+    return (
+      <div className="bucket-settings-loading">
+        Loading bucket lock rules...
+      </div>
+    );
+  }
+
+  return (
+    <div className="bucket-settings-section">
+      <p className="bucket-settings-hint">
+        Bucket lock rules keep objects from being deleted or overwritten for a
+        retention period after upload, until a date, or indefinitely. They
+        apply to every client, including R2 Manager and lifecycle rules.
+      </p>
+
+      {error !== null && <div className="bucket-settings-error">{error}</div>}
+
+      {drafts.length === 0 && (
+        <div className="bucket-settings-empty">No bucket lock rules</div>
+      )}
+
+      {drafts.map((draft) => (
+        <div key={draft.key} className="cors-rule">
+          <div className="cors-rule-header">
+            <span>{draft.id}</span>
+            <div className="bucket-settings-actions">
+              <button
+                className={`bucket-settings-toggle ${draft.enabled ? "enabled" : ""}`}
+                onClick={() =>
+                  updateDraft(draft.key, { enabled: !draft.enabled })
+                }
+              >
+                {draft.enabled ? "Enabled" : "Disabled"}
+              </button>
+              <button
+                className="bucket-settings-danger-btn"
+                onClick={() => {
+                  setDrafts((prev) => prev.filter((d) => d.key !== draft.key));
+                  setIsDirty(true);
+                  setSaved(false);
+                }}
+              >
+                Remove
+              </button>
+            </div>
+          </div>
+
+          <label className="bucket-settings-field">
+            <span>Prefix (empty for all objects)</span>
+            <input
+              type="text"
+              value={draft.prefix}
+              onChange={(e) =>
+                updateDraft(draft.key, { prefix: e.target.value })
+              }
+              placeholder="records/"
+            />
+          </label>
+
+          <label className="bucket-settings-field">
+            <span>Retention</span>
+            <select
+              value={draft.type}
+              onChange={(e) =>
+                updateDraft(draft.key, {
+                  type: e.target.value as BucketLockCondition["type"],
+                })
+              }
+            >
+              <option value="Age">For a number of days after upload</option>
+              <option value="Date">Until a date</option>
+              <option value="Indefinite">Indefinitely</option>
+            </select>
+          </label>
+
+          {draft.type === "Age" && (
+            <label className="bucket-settings-field">
+              <span>Days</span>
+              <input
+                type="number"
+                min={1}
+                value={draft.days}
+                onChange={(e) =>
+                  updateDraft(draft.key, { days: e.target.value })
+                }
+              />
+            </label>
+          )}
+
+          {draft.type === "Date" && (
+            <label className="bucket-settings-field">
+              <span>Locked until</span>
+              <input
+                type="date"
+                value={draft.date}
+                onChange={(e) =>
+                  updateDraft(draft.key, { date: e.target.value })
+                }
+              />
+            </label>
+          )}
+        </div>
+      ))}
+
+      <div className="bucket-settings-actions">
+        <button
+          className="bucket-settings-secondary-btn"
+          onClick={() => {
+            setDrafts((prev) => [...prev, emptyDraft()]);
+            setIsDirty(true);
+            setSaved(false);
+          }}
+        >
+          Add Rule
+        </button>
+        <button
+          className="bucket-settings-primary-btn"
+          onClick={() => void handleSave()}
+          disabled={
+            !isDirty ||
+            isSaving ||
+            drafts.some((d) => d.type === "Date" && d.date === "")
+          }
+        >
+          {isSaving ? "Saving..." : "Save Lock Rules"}
+        </button>
+        {saved && <span className="bucket-settings-saved">Saved</span>}
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,94 @@
+import { useState, type JSX } from "react";
+import { BucketLockEditor } from "./BucketLockEditor";
+import { CorsRulesEditor } from "./CorsRulesEditor";
+import { PublicAccessSettings } from "./PublicAccessSettings";
+import "./bucket-settings.css";
+
+interface BucketSettingsPanelProps {
+  bucketName: string;
+  onClose: () => void;
+}
+
+type SettingsTab = "cors" | "lock" | "public-access";
+
+/**
+ * Bucket configuration that is otherwise only available in the Cloudflare
+ * dashboard: CORS rules, bucket lock rules, r2.dev public access and custom
+ * domains.
+ */
+export function BucketSettingsPanel({
+  bucketName,
+  onClose,
+}: BucketSettingsPanelProps): JSX.Element {
+  const [activeTab, setActiveTab] = useState<SettingsTab>("cors");
+
+  return (
+    <div className="bucket-settings-panel">
+      <div className="bucket-settings-header">
+        <div className="bucket-settings-title">
+          <svg
+            className="bucket-settings-icon"
+            viewBox="0 0 24 24"
+            fill="none"
+            stroke="currentColor"
+            strokeWidth="2"
+          >
+            <circle cx="12" cy="12" r="3" />
+            <path d="M19.4 15a1.65 1.65 0 0 0 .33 1.82l.06.06a2 2 0 1 1-2.83 2.83l-.06-.06a1.65 1.65 0 0 0-1.82-.33 1.65 1.65 0 0 0-1 1.51V21a2 2 0 1 1-4 0v-.09A1.65 1.65 0 0 0 9 19.4a1.65 1.65 0 0 0-1.82.33l-.06.06a2 2 0 1 1-2.83-2.83l.06-.06A1.65 1.65 0 0 0 4.68 15a1.65 1.65 0 0 0-1.51-1H3a2 2 0 1 1 0-4h.09A1.65 1.65 0 0 0 4.6 9a1.65 1.65 0 0 0-.33-1.82l-.06-.06a2 2 0 1 1 2.83-2.83l.06.06A1.65 1.65 0 0 0 9 4.68a1.65 1.65 0 0 0 1-1.51V3a2 2 0 1 1 4 0v.09a1.65 1.65 0 0 0 1 1.51 1.65 1.65 0 0 0 1.82-.33l.06-.06a2 2 0 1 1 2.83 2.83l-.06.06A1.65 1.65 0 0 0 19.4 9a1.65 1.65 0 0 0 1.51 1H21a2 2 0 1 1 0 4h-.09a1.65 1.65 0 0 0-1.51 1z" />
+          </svg>
+          <h2>Bucket Settings</h2>
+          <span className="bucket-settings-badge">{bucketName}</span>
+        </div>
+        <button
+          className="bucket-settings-close"
+          onClick={onClose}
+          aria-label="Close"
+        >
+          <svg
+            viewBox="0 0 24 24"
+            fill="none"
+            stroke="currentColor"
+            strokeWidth="2"
+          >
+            <path d="M18 6 6 18M6 6l12 12" />
+          </svg>
+        </button>
+      </div>
+
+      <div className="bucket-settings-tabs" role="tablist">
+        <button
+          role="tab"
+          aria-selected={activeTab === "cors"}
+          className={`bucket-settings-tab ${activeTab === "cors" ? "active" : ""}`}
+          onClick={() => setActiveTab("cors")}
+        >
+          CORS
+        </button>
+        <button
+          role="tab"
+          aria-selected={activeTab === "lock"}
+          className={`bucket-settings-tab ${activeTab === "lock" ? "active" : ""}`}
+          onClick={() => setActiveTab("lock")}
+        >
+          Bucket Lock
+        </button>
+        <button
+          role="tab"
+          aria-selected={activeTab === "public-access"}
+          className={`bucket-settings-tab ${activeTab === "public-access" ? "active" : ""}`}
+          onClick={() => setActiveTab("public-access")}
+        >
+          Public Access
+        </button>
+      </div>
+
+      <div className="bucket-settings-content">
+        {activeTab === "cors" && <CorsRulesEditor bucketName={bucketName} />}
+        {activeTab === "lock" && <BucketLockEditor bucketName={bucketName} />}
+        {activeTab === "public-access" && (
+          <PublicAccessSettings bucketName={bucketName} />
+        )}
+      </div>
+    </div>
+  );
+}
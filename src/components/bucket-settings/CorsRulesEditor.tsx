@@ -0,0 +1,256 @@
+import { useState, useEffect, useCallback, type JSX } from "react";
+import { api } from "../../services/api";
+import {
+  CORS_METHODS,
+  type BucketCorsRule,
+  type CorsMethod,
+} from "../../types/bucket-settings";
+
+interface CorsRulesEditorProps {
+  bucketName: string;
+}
+
+/**
+ * Editable form of a CORS rule. List fields are kept as comma-separated
+ * text until the rules are saved.
+ */
+interface CorsRuleDraft {
+  key: number;
+  origins: string;
+  methods: CorsMethod[];
+  headers: string;
+  exposeHeaders: string;
+  maxAgeSeconds: string;
+}
+
+let nextDraftKey = 0;
+
+function splitList(value: string): string[] {
+  return value
+    .split(/[,\s]+/)
+    .map((item) => item.trim())
+    .filter((item) => item !== "");
+}
+
+function toDraft(rule: BucketCorsRule): CorsRuleDraft {
+  return {
+    key: nextDraftKey++,
+    origins: rule.allowed.origins.join(", "),
+    methods: rule.allowed.methods,
+    headers: (rule.allowed.headers ?? []).join(", "),
+    exposeHeaders: (rule.exposeHeaders ?? []).join(", "),
+    maxAgeSeconds:
+      rule.maxAgeSeconds !== undefined ? String(rule.maxAgeSeconds) : "",
+  };
+}
+
+function fromDraft(draft: CorsRuleDraft): BucketCorsRule {
+  const headers = splitList(draft.headers);
+  const exposeHeaders = splitList(draft.exposeHeaders);
+  return {
+    allowed: {
+      origins: splitList(draft.origins),
+      methods: draft.methods,
+      ...(headers.length > 0 && { headers }),
+    },
+    ...(exposeHeaders.length > 0 && { exposeHeaders }),
+    ...(draft.maxAgeSeconds.trim() !== "" && {
+      maxAgeSeconds: Number(draft.maxAgeSeconds),
+    }),
+  };
+}
+
+function emptyDraft(): CorsRuleDraft {
+  return {
+    key: nextDraftKey++,
+    origins: "",
+    methods: ["GET", "HEAD"],
+    headers: "",
+    exposeHeaders: "",
+    maxAgeSeconds: "3600",
+  };
+}
+
+export function CorsRulesEditor({
+  bucketName,
+}: CorsRulesEditorProps): JSX.Element {
+  const [drafts, setDrafts] = useState<CorsRuleDraft[]>([]);
+  const [isLoading, setIsLoading] = useState(true);
+  const [isSaving, setIsSaving] = useState(false);
+  const [isDirty, setIsDirty] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const [saved, setSaved] = useState(false);
+
+  const loadRules = useCallback(async () => {
+    setIsLoading(true);
+    setError(null);
+    try {
+      const rules = await api.getBucketCors(bucketName);
+      setDrafts(rules.map(toDraft));
+      setIsDirty(false);
+    } catch (err) {
+      setError(err instanceof Error ? err.message : "Failed to load CORS");
+    } finally {
+      setIsLoading(false);
+    }
+  }, [bucketName]);
+
+  useEffect(() => {
+    queueMicrotask(() => {
+      void loadRules();
+    });
+  }, [loadRules]);
+
+  const updateDraft = (key: number, changes: Partial<CorsRuleDraft>): void => {
+    setDrafts((prev) =>
+      prev.map((d) => (d.key === key ? { ...d, ...changes } : d)),
+    );
+    setIsDirty(true);
+    setSaved(false);
+  };
+
+  const toggleMethod = (draft: CorsRuleDraft, method: CorsMethod): void => {
+    updateDraft(draft.key, {
+      methods: draft.methods.includes(method)
+        ? draft.methods.filter((m) => m !== method)
+        : [...draft.methods, method],
+    });
+  };
+
+  const handleSave = async (): Promise<void> => {
+    setIsSaving(true);
+    setError(null);
+    try {
+      await api.setBucketCors(bucketName, drafts.map(fromDraft));
+      setIsDirty(false);
+      setSaved(true);
+    } catch (err) {
+      setError(err instanceof Error ? err.message : "Failed to save CORS");
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  if (isLoading) {
+    return <div className="bucket-settings-loading">Loading CORS rules...</div>;
+  }
+
+  return (
+    <div className="bucket-settings-section">
+      <p className="bucket-settings-hint">
+        CORS rules let browsers on other origins read from this bucket through
+        its public r2.dev URL, custom domains and presigned S3 URLs. They do
+        not apply to R2 Manager itself.
+      </p>
+
+      {error !== null && <div className="bucket-settings-error">{error}</div>}
+
+      {drafts.length === 0 && (
+        <div className="bucket-settings-empty">No CORS rules configured</div>
+      )}
+
+      {drafts.map((draft, index) => (
+        <div key={draft.key} className="cors-rule">
+          <div className="cors-rule-header">
+            <span>Rule {index + 1}</span>
+            <button
+              className="bucket-settings-danger-btn"
+              onClick={() => {
+                setDrafts((prev) => prev.filter((d) => d.key !== draft.key));
+                setIsDirty(true);
+                setSaved(false);
+              }}
+            >
+              Remove
+            </button>
+          </div>
+
+          <label className="bucket-settings-field">
+            <span>Allowed origins</span>
+            <input
+              type="text"
+              value={draft.origins}
+              onChange={(e) =>
+                updateDraft(draft.key, { origins: e.target.value })
+              }
+              placeholder="https://example.com, https://app.example.com"
+            />
+          </label>
+
+          <div className="bucket-settings-field">
+            <span>Allowed methods</span>
+            <div className="cors-methods">
+              {CORS_METHODS.map((method) => (
+                <label key={method} className="cors-method">
+                  <input
+                    type="checkbox"
+                    checked={draft.methods.includes(method)}
+                    onChange={() => toggleMethod(draft, method)}
+                  />
+                  {method}
+                </label>
+              ))}
+            </div>
+          </div>
+
+          <label className="bucket-settings-field">
+            <span>Allowed headers</span>
+            <input
+              type="text"
+              value={draft.headers}
+              onChange={(e) =>
+                updateDraft(draft.key, { headers: e.target.value })
+              }
+              placeholder="content-type, x-amz-*"
+            />
+          </label>
+
+          <label className="bucket-settings-field">
+            <span>Exposed headers</span>
+            <input
+              type="text"
+              value={draft.exposeHeaders}
+              onChange={(e) =>
+                updateDraft(draft.key, { exposeHeaders: e.target.value })
+              }
+              placeholder="ETag"
+            />
+          </label>
+
+          <label className="bucket-settings-field">
+            <span>Preflight cache (seconds)</span>
+            <input
+              type="number"
+              min={0}
+              value={draft.maxAgeSeconds}
+              onChange={(e) =>
+                updateDraft(draft.key, { maxAgeSeconds: e.target.value })
+              }
+            />
+          </label>
+        </div>
+      ))}
+
+      <div className="bucket-settings-actions">
+        <button
+          className="bucket-settings-secondary-btn"
+          onClick={() => {
+            setDrafts((prev) => [...prev, emptyDraft()]);
+            setIsDirty(true);
+            setSaved(false);
+          }}
+        >
+          Add Rule
+        </button>
+        <button
+          className="bucket-settings-primary-btn"
+          onClick={() => void handleSave()}
+          disabled={!isDirty || isSaving}
+        >
+          {isSaving ? "Saving..." : "Save CORS Rules"}
+        </button>
+        {saved && <span className="bucket-settings-saved">Saved</span>}
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,241 @@
+import { useState, useEffect, useCallback, type JSX } from "react";
+import { api } from "../../services/api";
+import type { BucketDomains, CustomDomain } from "../../types/bucket-settings";
+
+interface PublicAccessSettingsProps {
+  bucketName: string;
+}
+
+function isDomainActive(domain: CustomDomain): boolean {
+  return (
+    domain.status.ownership === "active" && domain.status.ssl === "active"
+  );
+}
+
+export function PublicAccessSettings({
+  bucketName,
+}: PublicAccessSettingsProps): JSX.Element {
+  const [domains, setDomains] = useState<BucketDomains | null>(null);
+  const [isLoading, setIsLoading] = useState(true);
+  const [busy, setBusy] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [newDomain, setNewDomain] = useState("");
+  const [newMinTLS, setNewMinTLS] = useState("");
+
+  const loadDomains = useCallback(async () => {
+    setIsLoading(true);
+    setError(null);
+    try {
+      setDomains(await api.getBucketDomains(bucketName));
+    } catch (err) {
+      setError(err instanceof Error ? err.message : "Failed to load domains");
+    } finally {
+      setIsLoading(false);
+    }
+  }, [bucketName]);
+
+  useEffect(() => {
+    queueMicrotask(() => {
+      void loadDomains();
+    });
+  }, [loadDomains]);
+
+  const run = async (
+    key: string,
+    action: () => Promise<unknown>,
+    fallback: string,
+  ): Promise<void> => {
+    setBusy(key);
+    setError(null);
+    try {
+      await action();
+      await loadDomains();
+    } catch (err) {
+      setError(err instanceof Error ? err.message : fallback);
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  const handleManagedToggle = async (enabled: boolean): Promise<void> => {
+    if (
+      enabled &&
+      !confirm(
+        `Anyone with the r2.dev URL will be able to read every object in "${bucketName}". Continue?`,
+      )
+    ) {
+      return;
+    }
+    await run(
+      "managed",
+      () => api.setManagedDomain(bucketName, enabled),
+      "Failed to update r2.dev access",
+    );
+  };
+
+  const handleAttach = async (): Promise<void> => {
+    const domain = newDomain.trim().toLowerCase();
+    if (domain === "") return;
+    await run(
+      "attach",
+      async () => {
+        await api.attachCustomDomain(
+          bucketName,
+          domain,
+          newMinTLS !== "" ? newMinTLS : undefined,
+        );
+        setNewDomain("");
+      },
+      "Failed to attach domain",
+    );
+  };
+
+  const handleRemove = async (domain: string): Promise<void> => {
+    if (
+      !confirm(`Detach ${domain}? Objects will stop being served from it.`)
+    ) {
+      return;
+    }
+    await run(
+      domain,
+      () => api.removeCustomDomain(bucketName, domain),
+      "Failed to remove domain",
+    );
+  };
+
+  if (isLoading && domains === null) {
+    return <div className="bucket-settings-loading">Loading domains...</div>;
+  }
+
+  const managed = domains?.managed ?? null;
+  const custom = domains?.custom ?? [];
+
+  return (
+    <div className="bucket-settings-section">
+      {error !== null && <div className="bucket-settings-error">{error}</div>}
+
+      <h3 className="bucket-settings-subtitle">r2.dev URL</h3>
+      {managed === null ? (
+        <p className="bucket-settings-hint">
+          This bucket has no r2.dev URL (buckets in a jurisdiction cannot use
+          one).
+        </p>
+      ) : (
+        <div className="public-access-row">
+          <div>
+            <div className="public-access-domain">
+              {managed.enabled ? (
+                <a
+                  href={`https://${managed.domain}`}
+                  target="_blank"
+                  rel="noopener noreferrer"
+                >
+                  {managed.domain}
+                </a>
+              ) : (
+                managed.domain
+              )}
+            </div>
+            <div className="bucket-settings-hint">
+              {managed.enabled
+                ? "Public and rate limited - use a custom domain for production traffic."
+                : "Public access through r2.dev is disabled."}
+            </div>
+          </div>
+          <button
+            className={`bucket-settings-toggle ${managed.enabled ? "enabled" : ""}`}
+            onClick={() => void handleManagedToggle(!managed.enabled)}
+            disabled={busy !== null}
+          >
+            {managed.enabled ? "Enabled" : "Disabled"}
+          </button>
+        </div>
+      )}
+
+      <h3 className="bucket-settings-subtitle">Custom Domains</h3>
+      {custom.length === 0 ? (
+        <div className="bucket-settings-empty">No custom domains attached</div>
+      ) : (
+        custom.map((domain) => (
+          <div key={domain.domain} className="public-access-row">
+            <div>
+              <div className="public-access-domain">{domain.domain}</div>
+              <div className="bucket-settings-hint">
+                <span
+                  className={`public-access-status ${isDomainActive(domain) ? "active" : "pending"}`}
+                >
+                  {isDomainActive(domain) ? "Active" : "Pending"}
+                </span>{" "}
+                Ownership: {domain.status.ownership} · SSL: {domain.status.ssl}
+                {domain.minTLS !== undefined && ` · Min TLS ${domain.minTLS}`}
+              </div>
+            </div>
+            <div className="public-access-actions">
+              <button
+                className={`bucket-settings-toggle ${domain.enabled ? "enabled" : ""}`}
+                onClick={() =>
+                  void run(
+                    domain.domain,
+                    () =>
+                      api.setCustomDomainEnabled(
+                        bucketName,
+                        domain.domain,
+                        !domain.enabled,
+                      ),
+                    "Failed to update domain",
+                  )
+                }
+                disabled={busy !== null}
+              >
+                {domain.enabled ? "Enabled" : "Disabled"}
+              </button>
+              <button
+                className="bucket-settings-danger-btn"
+                onClick={() => void handleRemove(domain.domain)}
+                disabled={busy !== null}
+              >
+                {busy === domain.domain ? "Working..." : "Remove"}
+              </button>
+            </div>
+          </div>
+        ))
+      )}
+
+      <form
+        className="public-access-add"
+        onSubmit={(e) => {
+          e.preventDefault();
+          void handleAttach();
+        }}
+      >
+        <input
+          type="text"
+          value={newDomain}
+          onChange={(e) => setNewDomain(e.target.value)}
+          placeholder="files.example.com"
+          aria-label="Custom domain"
+        />
+        <select
+          value={newMinTLS}
+          onChange={(e) => setNewMinTLS(e.target.value)}
+          aria-label="Minimum TLS version"
+        >
+          <option value="">Default TLS</option>
+          <option value="1.2">TLS 1.2+</option>
+          <option value="1.3">TLS 1.3</option>
+        </select>
+        <button
+          type="submit"
+          className="bucket-settings-primary-btn"
+          disabled={newDomain.trim() === "" || busy !== null}
+        >
+          {busy === "attach" ? "Attaching..." : "Attach Domain"}
+        </button>
+      </form>
+      <p className="bucket-settings-hint">
+        The domain must belong to a zone in this Cloudflare account. Ownership
+        and certificate checks usually finish within a few minutes.
+      </p>
+    </div>
+  );
+}
@@ -0,0 +1 @@
+export { BucketSettingsPanel } from "./BucketSettingsPanel";
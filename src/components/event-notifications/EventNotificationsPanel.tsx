@@ -0,0 +1,309 @@
+import { useState, useEffect, useCallback, type JSX } from "react";
+import { api } from "../../services/api";
+import {
+  EVENT_ACTION_LABELS,
+  type EventNotificationAction,
+  type EventNotificationQueue,
+  type EventNotificationRule,
+} from "../../types/event-notifications";
+import "./event-notifications.css";
+
+interface EventNotificationsPanelProps {
+  bucketName: string;
+  onClose: () => void;
+}
+
+const ALL_ACTIONS = Object.keys(
+  EVENT_ACTION_LABELS,
+) as EventNotificationAction[];
+
+export function EventNotificationsPanel({
+  bucketName,
+  onClose,
+}: EventNotificationsPanelProps): JSX.Element {
+  const [rules, setRules] = useState<EventNotificationRule[]>([]);
+  const [queues, setQueues] = useState<EventNotificationQueue[]>([]);
+  const [isLoading, setIsLoading] = useState(true);
+  const [error, setError] = useState<string | null>(null);
+  const [deletingRuleId, setDeletingRuleId] = useState<string | null>(null);
+
+  const [showForm, setShowForm] = useState(false);
+  const [queueId, setQueueId] = useState("");
+  const [actions, setActions] = useState<EventNotificationAction[]>([
+    "PutObject",
+    "CompleteMultipartUpload",
+  ]);
+  const [prefix, setPrefix] = useState("");
+  const [suffix, setSuffix] = useState("");
+  const [description, setDescription] = useState("");
+  const [isCreating, setIsCreating] = useState(false);
+
+  const loadRules = useCallback(async () => {
+    setIsLoading(true);
+    setError(null);
+    try {
+      const response = await api.getEventNotifications(bucketName);
+      setRules(response.rules);
+      setQueues(response.queues);
+    } catch (err) {
+      setError(
+        err instanceof Error ? err.message : "Failed to load notifications",
+      );
+    } finally {
+      setIsLoading(false);
+    }
+  }, [bucketName]);
+
+  useEffect(() => {
+    queueMicrotask(() => {
+      void loadRules();
+    });
+  }, [loadRules]);
+
+  const toggleAction = (action: EventNotificationAction): void => {
+    setActions((prev) =>
+      prev.includes(action)
+        ? prev.filter((a) => a !== action)
+        : [...prev, action],
+    );
+  };
+
+  const handleCreate = async (): Promise<void> => {
+    setIsCreating(true);
+    setError(null);
+    try {
+      await api.createEventNotification(bucketName, {
+        queueId: queueId.trim(),
+        actions,
+        ...(prefix !== "" && { prefix }),
+        ...(suffix !== "" && { suffix }),
+        ...(description.trim() !== "" && { description: description.trim() }),
+      });
+      setShowForm(false);
+      setPrefix("");
+      setSuffix("");
+      setDescription("");
+      await loadRules();
+    } catch (err) {
+      setError(err instanceof Error ? err.message : "Failed to create rule");
+    } finally {
+      setIsCreating(false);
+    }
+  };
+
+  const handleDelete = async (rule: EventNotificationRule): Promise<void> => {
+    if (!confirm(`Stop sending these events to ${rule.queueName}?`)) return;
+
+    setDeletingRuleId(rule.ruleId);
+    try {
+      await api.deleteEventNotification(bucketName, rule.queueId, rule.ruleId);
+      await loadRules();
+    } catch (err) {
+      setError(err instanceof Error ? err.message : "Failed to delete rule");
+    } finally {
+      setDeletingRuleId(null);
+    }
+  };
+
+  return (
+    <div className="event-notifications-panel">
+      <div className="event-notifications-header">
+        <div className="event-notifications-title">
+          <svg
+            className="event-notifications-icon"
+            viewBox="0 0 24 24"
+            fill="none"
+            stroke="currentColor"
+            strokeWidth="2"
+          >
+            <path d="M18 8A6 6 0 0 0 6 8c0 7-3 9-3 9h18s-3-2-3-9" />
+            <path d="M13.73 21a2 2 0 0 1-3.46 0" />
+          </svg>
+          <h2>Event Notifications</h2>
+          <span className="event-notifications-badge">{bucketName}</span>
+        </div>
+        <button
+          className="event-notifications-close"
+          onClick={onClose}
+          aria-label="Close"
+        >
+          <svg
+            viewBox="0 0 24 24"
+            fill="none"
+            stroke="currentColor"
+            strokeWidth="2"
+          >
+            <path d="M18 6 6 18M6 6l12 12" />
+          </svg>
+        </button>
+      </div>
+
+      <div className="event-notifications-info">
+        Send a message to a Cloudflare Queue when objects are created or
+        deleted. Rules that share an action must not match the same keys.
+      </div>
+
+      {error !== null && (
+        <div className="event-notifications-error">
+          <span>{error}</span>
+          <button onClick={() => setError(null)} aria-label="Dismiss error">
+            ×
+          </button>
+        </div>
+      )}
+
+      <div className="event-notifications-content">
+        {showForm ? (
+          <form
+            className="event-notifications-form"
+            onSubmit={(e) => {
+              e.preventDefault();
+              void handleCreate();
+            }}
+          >
+            <label>
+              <span>Queue</span>
+              {queues.length > 0 ? (
+                <select
+                  value={queueId}
+                  onChange={(e) => setQueueId(e.target.value)}
+                >
+                  <option value="">Select a queue...</option>
+                  {queues.map((queue) => (
+                    <option key={queue.queueId} value={queue.queueId}>
+                      {queue.queueName}
+                    </option>
+                  ))}
+                </select>
+              ) : (
+                <input
+                  type="text"
+                  value={queueId}
+                  onChange={(e) => setQueueId(e.target.value)}
+                  placeholder="Queue ID"
+                />
+              )}
+            </label>
+
+            <div className="event-notifications-actions-field">
+              <span>Events</span>
+              {ALL_ACTIONS.map((action) => (
+                <label key={action}>
+                  <input
+                    type="checkbox"
+                    checked={actions.includes(action)}
+                    onChange={() => toggleAction(action)}
+                  />
+                  {EVENT_ACTION_LABELS[action]}
+                </label>
+              ))}
+            </div>
+
+            <label>
+              <span>Prefix (optional)</span>
+              <input
+                type="text"
+                value={prefix}
+                onChange={(e) => setPrefix(e.target.value)}
+                placeholder="uploads/"
+              />
+            </label>
+            <label>
+              <span>Suffix (optional)</span>
+              <input
+                type="text"
+                value={suffix}
+                onChange={(e) => setSuffix(e.target.value)}
+                placeholder=".jpg"
+              />
+            </label>
+            <label>
+              <span>Description (optional)</span>
+              <input
+                type="text"
+                value={description}
+                onChange={(e) => setDescription(e.target.value)}
+              />
+            </label>
+
+            <div className="event-notifications-form-buttons">
+              <button
+                type="button"
+                className="event-notifications-secondary-btn"
+                onClick={() => setShowForm(false)}
+                disabled={isCreating}
+              >
+                Cancel
+              </button>
+              <button
+                type="submit"
+                className="event-notifications-primary-btn"
+                disabled={
+                  isCreating || queueId.trim() === "" || actions.length === 0
+                }
+              >
+                {isCreating ? "Creating..." : "Create Rule"}
+              </button>
+            </div>
+          </form>
+        ) : (
+          <button
+            className="event-notifications-primary-btn"
+            onClick={() => setShowForm(true)}
+          >
+            Add Rule
+          </button>
+        )}
+
+        {isLoading ? (
+          <div className="event-notifications-empty">Loading rules...</div>
+        ) : rules.length === 0 ? (
+          <div className="event-notifications-empty">
+            No event notification rules configured
+          </div>
+        ) : (
+          <div className="event-notifications-list">
+            {rules.map((rule) => (
+              <div key={rule.ruleId} className="event-notifications-rule">
+                <div>
+                  <div className="event-notifications-rule-queue">
+                    → {rule.queueName}
+                  </div>
+                  <div className="event-notifications-rule-filters">
+                    {rule.prefix !== undefined && (
+                      <code>prefix: {rule.prefix}</code>
+                    )}
+                    {rule.suffix !== undefined && (
+                      <code>suffix: {rule.suffix}</code>
+                    )}
+                    {rule.prefix === undefined &&
+                      rule.suffix === undefined && <span>All objects</span>}
+                  </div>
+                  <div className="event-notifications-rule-actions">
+                    {rule.actions.map((action) => (
+                      <span key={action} className="event-notifications-tag">
+                        {EVENT_ACTION_LABELS[action]}
+                      </span>
+                    ))}
+                  </div>
+                  {rule.description !== undefined && (
+                    <div className="event-notifications-rule-description">
+                      {rule.description}
+                    </div>
+                  )}
+                </div>
+                <button
+                  className="event-notifications-delete"
+                  onClick={() => void handleDelete(rule)}
+                  disabled={deletingRuleId === rule.ruleId}
+                >
+                  {deletingRuleId === rule.ruleId ? "Deleting..." : "Delete"}
+                </button>
+              </div>
+            ))}
+          </div>
+        )}
+      </div>
+    </div>
+  );
+}
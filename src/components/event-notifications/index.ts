@@ -0,0 +1 @@
+export { EventNotificationsPanel } from "./EventNotificationsPanel";
@@ -8,6 +8,8 @@ interface ContextMenuProps {
   onClose: () => void;
   onRename: () => void;
   onCopyLink?: (() => void) | undefined;
+  onCreateLink?: (() => void) | undefined;
+  onShare?: (() => void) | undefined;
 }
 
 export const ContextMenu = ({
@@ -18,6 +20,8 @@ export const ContextMenu = ({
   onClose,
   onRename,
   onCopyLink,
+  onCreateLink,
+  onShare,
 }: ContextMenuProps): JSX.Element | null => {
   if (!show) return null;
 
@@ -45,6 +49,26 @@ export const ContextMenu = ({
             🔗 Copy Link
           </button>
         )}
+        {itemType === "file" && onCreateLink && (
+          <button
+            onClick={() => {
+              onCreateLink();
+              onClose();
+            }}
+          >
+            🔒 Create Signed Link...
+          </button>
+        )}
+        {onShare && (
+          <button
+            onClick={() => {
+              onShare();
+              onClose();
+            }}
+          >
+            🌐 Create Share Link...
+          </button>
+        )}
       </div>
     </>
   );
@@ -0,0 +1,182 @@
+import { useState, type JSX } from "react";
+import {
+  api,
+  type CreateShareOptions,
+  type ShareType,
+} from "../../services/api";
+import { logger } from "../../services/logger";
+
+const EXPIRY_OPTIONS: { label: string; seconds: number }[] = [
+  { label: "1 day", seconds: 24 * 60 * 60 },
+  { label: "7 days", seconds: 7 * 24 * 60 * 60 },
+  { label: "14 days", seconds: 14 * 24 * 60 * 60 },
+  { label: "30 days", seconds: 30 * 24 * 60 * 60 },
+];
+
+const MIN_PASSWORD_LENGTH = 6;
+
+interface ShareModalProps {
+  bucketName: string;
+  itemKey: string;
+  itemType: ShareType;
+  onClose: () => void;
+}
+
+export const ShareModal = ({
+  bucketName,
+  itemKey,
+  itemType,
+  onClose,
+}: ShareModalProps): JSX.Element => {
+  const [expiresIn, setExpiresIn] = useState(7 * 24 * 60 * 60);
+  const [password, setPassword] = useState("");
+  const [maxDownloads, setMaxDownloads] = useState("");
+  const [allowZip, setAllowZip] = useState(true);
+  const [creating, setCreating] = useState(false);
+  const [link, setLink] = useState<string | null>(null);
+  const [copied, setCopied] = useState(false);
+  const [error, setError] = useState("");
+
+  const handleCreate = async (): Promise<void> => {
+    const limit = maxDownloads.trim() === "" ? undefined : Number(maxDownloads);
+    if (limit !== undefined && (!Number.isInteger(limit) || limit < 1)) {
+      setError("Download limit must be a positive whole number");
+      return;
+    }
+    if (password !== "" && password.length < MIN_PASSWORD_LENGTH) {
+      setError(
+        `Password must be at least ${MIN_PASSWORD_LENGTH} characters long`,
+      );
+      return;
+    }
+
+    const options: CreateShareOptions = {
+      expiresIn,
+      ...(password !== "" && { password }),
+      ...(limit !== undefined && { maxDownloads: limit }),
+      ...(itemType === "folder" && { allowZip }),
+    };
+
+    try {
+      setCreating(true);
+      setError("");
+      const share = await api.createShare(
+        bucketName,
+        itemKey,
+        itemType,
+        options,
+      );
+      setLink(share.url);
+      await navigator.clipboard.writeText(share.url);
+      setCopied(true);
+    } catch (err) {
+      logger.error("ShareModal", "Failed to create share", err);
+      setError(err instanceof Error ? err.message : "Failed to create share");
+    } finally {
+      setCreating(false);
+    }
+  };
+
+  return (
+    <div className="modal-overlay" onClick={onClose}>
+      <div className="modal-dialog" onClick={(e) => e.stopPropagation()}>
+        <h2>Create Share Link</h2>
+
+        <div className="rename-input-container signed-link-form">
+          <div className="rename-current-name-section">
+            <span className="rename-label">
+              {itemType === "folder" ? "Folder:" : "File:"}
+            </span>
+            <p className="current-name">{itemKey}</p>
+          </div>
+
+          <label htmlFor="share-expiry">Expires after:</label>
+          <select
+            id="share-expiry"
+            value={expiresIn}
+            onChange={(e) => setExpiresIn(Number(e.target.value))}
+            disabled={link !== null}
+          >
+            {EXPIRY_OPTIONS.map((option) => (
+              <option key={option.seconds} value={option.seconds}>
+                {option.label}
+              </option>
+            ))}
+          </select>
+
+          <label htmlFor="share-password">Password (optional):</label>
+          <input
+            id="share-password"
+            type="password"
+            autoComplete="new-password"
+            value={password}
+            onChange={(e) => setPassword(e.target.value)}
+            placeholder="No password"
+            disabled={link !== null}
+          />
+
+          <label htmlFor="share-max-downloads">
+            Download limit (optional):
+          </label>
+          <input
+            id="share-max-downloads"
+            type="number"
+            min={1}
+            value={maxDownloads}
+            onChange={(e) => setMaxDownloads(e.target.value)}
+            placeholder="Unlimited"
+            disabled={link !== null}
+          />
+
+          {itemType === "folder" && (
+            <label className="signed-link-checkbox">
+              <input
+                type="checkbox"
+                checked={allowZip}
+                onChange={(e) => setAllowZip(e.target.checked)}
+                disabled={link !== null}
+              />
+              Allow downloading the whole folder as a ZIP
+            </label>
+          )}
+
+          {link && (
+            <>
+              <label htmlFor="share-url">
+                {copied ? "Link (copied to clipboard):" : "Link:"}
+              </label>
+              <input
+                id="share-url"
+                type="text"
+                value={link}
+                readOnly
+                onFocus={(e) => e.target.select()}
+              />
+            </>
+          )}
+
+          {error && (
+            <p className="error-message" role="alert">
+              {error}
+            </p>
+          )}
+        </div>
+
+        <div className="modal-actions">
+          <button className="modal-button cancel" onClick={onClose}>
+            {link ? "Done" : "Cancel"}
+          </button>
+          {!link && (
+            <button
+              className="modal-button"
+              onClick={() => void handleCreate()}
+              disabled={creating}
+            >
+              {creating ? "Creating..." : "Create & Copy Link"}
+            </button>
+          )}
+        </div>
+      </div>
+    </div>
+  );
+};
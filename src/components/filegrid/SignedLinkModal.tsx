@@ -0,0 +1,163 @@
+import { useState, type JSX } from "react";
+import { api, type SignedUrlOptions } from "../../services/api";
+import { logger } from "../../services/logger";
+
+const EXPIRY_OPTIONS: { label: string; seconds: number }[] = [
+  { label: "1 hour", seconds: 60 * 60 },
+  { label: "24 hours", seconds: 24 * 60 * 60 },
+  { label: "3 days", seconds: 3 * 24 * 60 * 60 },
+  { label: "7 days", seconds: 7 * 24 * 60 * 60 },
+];
+
+interface SignedLinkModalProps {
+  bucketName: string;
+  fileKey: string;
+  onClose: () => void;
+}
+
+export const SignedLinkModal = ({
+  bucketName,
+  fileKey,
+  onClose,
+}: SignedLinkModalProps): JSX.Element => {
+  const [expiresIn, setExpiresIn] = useState(24 * 60 * 60);
+  const [maxDownloads, setMaxDownloads] = useState("");
+  const [restrictToMyIp, setRestrictToMyIp] = useState(false);
+  const [disposition, setDisposition] = useState<"attachment" | "inline">(
+    "attachment",
+  );
+  const [creating, setCreating] = useState(false);
+  const [link, setLink] = useState<string | null>(null);
+  const [copied, setCopied] = useState(false);
+  const [error, setError] = useState("");
+
+  const handleCreate = async (): Promise<void> => {
+    const limit = maxDownloads.trim() === "" ? undefined : Number(maxDownloads);
+    if (limit !== undefined && (!Number.isInteger(limit) || limit < 1)) {
+      setError("Download limit must be a positive whole number");
+      return;
+    }
+
+    const options: SignedUrlOptions = {
+      expiresIn,
+      restrictToMyIp,
+      disposition,
+      ...(limit !== undefined && { maxDownloads: limit }),
+    };
+
+    try {
+      setCreating(true);
+      setError("");
+      const url = await api.getSignedUrl(bucketName, fileKey, options);
+      setLink(url);
+      await navigator.clipboard.writeText(url);
+      setCopied(true);
+    } catch (err) {
+      logger.error("SignedLinkModal", "Failed to create signed link", err);
+      setError(err instanceof Error ? err.message : "Failed to create link");
+    } finally {
+      setCreating(false);
+    }
+  };
+
+  return (
+    <div className="modal-overlay" onClick={onClose}>
+      <div className="modal-dialog" onClick={(e) => e.stopPropagation()}>
+        <h2>Create Signed Link</h2>
+
+        <div className="rename-input-container signed-link-form">
+          <div className="rename-current-name-section">
+            <span className="rename-label">File:</span>
+            <p className="current-name">{fileKey}</p>
+          </div>
+
+          <label htmlFor="signed-link-expiry">Expires after:</label>
+          <select
+            id="signed-link-expiry"
+            value={expiresIn}
+            onChange={(e) => setExpiresIn(Number(e.target.value))}
+            disabled={link !== null}
+          >
+            {EXPIRY_OPTIONS.map((option) => (
+              <option key={option.seconds} value={option.seconds}>
+                {option.label}
+              </option>
+            ))}
+          </select>
+
+          <label htmlFor="signed-link-max-downloads">
+            Download limit (optional):
+          </label>
+          <input
+            id="signed-link-max-downloads"
+            type="number"
+            min={1}
+            value={maxDownloads}
+            onChange={(e) => setMaxDownloads(e.target.value)}
+            placeholder="Unlimited"
+            disabled={link !== null}
+          />
+
+          <label htmlFor="signed-link-disposition">Open as:</label>
+          <select
+            id="signed-link-disposition"
+            value={disposition}
+            onChange={(e) =>
+              setDisposition(e.target.value as "attachment" | "inline")
+            }
+            disabled={link !== null}
+          >
+            <option value="attachment">Download (attachment)</option>
+            <option value="inline">View in browser (inline)</option>
+          </select>
+
+          <label className="signed-link-checkbox">
+            <input
+              type="checkbox"
+              checked={restrictToMyIp}
+              onChange={(e) => setRestrictToMyIp(e.target.checked)}
+              disabled={link !== null}
+            />
+            Only allow downloads from my current IP address
+          </label>
+
+          {link && (
+            <>
+              <label htmlFor="signed-link-url">
+                {copied ? "Link (copied to clipboard):" : "Link:"}
+              </label>
+              <input
+                id="signed-link-url"
+                type="text"
+                value={link}
+                readOnly
+                onFocus={(e) => e.target.select()}
+              />
+            </>
+          )}
+
+          {error && (
+            <p className="error-message" role="alert">
+              {error}
+            </p>
+          )}
+        </div>
+
+        <div className="modal-actions">
+          <button className="modal-button cancel" onClick={onClose}>
+            {link ? "Done" : "Cancel"}
+          </button>
+          {!link && (
+            <button
+              className="modal-button"
+              onClick={() => void handleCreate()}
+              disabled={creating}
+            >
+              {creating ? "Creating..." : "Create & Copy Link"}
+            </button>
+          )}
+        </div>
+      </div>
+    </div>
+  );
+};
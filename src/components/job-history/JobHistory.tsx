@@ -181,6 +181,23 @@ const AlertCircleIcon = (): JSX.Element => (
   </svg>
 );
 
+const PauseIcon = (): JSX.Element => (
+  <svg
+    xmlns="http://www.w3.org/2000/svg"
+    width="12"
+    height="12"
+    viewBox="0 0 24 24"
+    fill="none"
+    stroke="currentColor"
+    strokeWidth="2"
+    strokeLinecap="round"
+    strokeLinejoin="round"
+  >
+    <rect x="6" y="4" width="4" height="16" />
+    <rect x="14" y="4" width="4" height="16" />
+  </svg>
+);
+
 const BucketIcon = (): JSX.Element => (
   <svg
     xmlns="http://www.w3.org/2000/svg"
@@ -576,6 +593,13 @@ export function JobHistory({ buckets }: JobHistoryProps): JSX.Element {
             Running
           </span>
         );
+      case "paused":
+        return (
+          <span className="job-status-badge paused">
+            <PauseIcon />
+            Paused
+          </span>
+        );
       case "queued":
         return (
           <span className="job-status-badge queued">
@@ -708,6 +732,7 @@ export function JobHistory({ buckets }: JobHistoryProps): JSX.Element {
               <option value="completed">Completed</option>
               <option value="failed">Failed</option>
               <option value="running">Running</option>
+              <option value="paused">Paused</option>
               <option value="queued">Queued</option>
               <option value="cancelled">Cancelled</option>
             </select>
@@ -959,7 +984,9 @@ export function JobHistory({ buckets }: JobHistoryProps): JSX.Element {
         <JobHistoryDialog
           open={!!selectedJobId}
           jobId={selectedJobId}
+          job={jobs.find((job) => job.job_id === selectedJobId)}
           onClose={() => setSelectedJobId(null)}
+          onJobUpdated={() => void loadJobs(true)}
         />
       )}
     </div>
@@ -1,5 +1,12 @@
 import { useCallback, useEffect, useState, type JSX } from "react";
-import { api, type JobEvent, type JobEventDetails } from "../../services/api";
+import {
+  api,
+  type JobEvent,
+  type JobEventDetails,
+  type JobListItem,
+  type JobOperationType,
+} from "../../services/api";
+import { JobItemsPanel } from "./JobItemsPanel";
 
 // Icons
 const LoaderIcon = (): JSX.Element => (
@@ -121,20 +128,75 @@ const BanIcon = (): JSX.Element => (
   </svg>
 );
 
+const PauseIcon = (): JSX.Element => (
+  <svg
+    xmlns="http://www.w3.org/2000/svg"
+    width="16"
+    height="16"
+    viewBox="0 0 24 24"
+    fill="none"
+    stroke="currentColor"
+    strokeWidth="2"
+    strokeLinecap="round"
+    strokeLinejoin="round"
+  >
+    <rect x="6" y="4" width="4" height="16" />
+    <rect x="14" y="4" width="4" height="16" />
+  </svg>
+);
+
+const RetryIcon = (): JSX.Element => (
+  <svg
+    xmlns="http://www.w3.org/2000/svg"
+    width="16"
+    height="16"
+    viewBox="0 0 24 24"
+    fill="none"
+    stroke="currentColor"
+    strokeWidth="2"
+    strokeLinecap="round"
+    strokeLinejoin="round"
+  >
+    <path d="M3 12a9 9 0 1 0 9-9 9.75 9.75 0 0 0-6.74 2.74L3 8" />
+    <path d="M3 3v5h5" />
+  </svg>
+);
+
+type JobControl = "cancel" | "pause" | "resume" | "retry";
+
+// Operations run by the worker's background job runner
+const BACKGROUND_JOB_OPERATIONS: readonly JobOperationType[] = [
+  "folder_copy",
+  "folder_move",
+  "folder_rename",
+  "folder_delete",
+  "bucket_delete",
+  "bucket_rename",
+];
+
 interface JobHistoryDialogProps {
   open: boolean;
   jobId: string;
+  /** Job summary, used to decide which controls to show */
+  job?: JobListItem | undefined;
   onClose: () => void;
+  /** Called after a control action changed the job */
+  onJobUpdated?: () => void;
 }
 
 export function JobHistoryDialog({
   open,
   jobId,
+  job,
   onClose,
+  onJobUpdated,
 }: JobHistoryDialogProps): JSX.Element | null {
   const [events, setEvents] = useState<JobEvent[]>([]);
   const [loading, setLoading] = useState(true);
   const [error, setError] = useState<string | null>(null);
+  const [controlling, setControlling] = useState<JobControl | null>(null);
+  const [controlError, setControlError] = useState<string | null>(null);
+  const [view, setView] = useState<"events" | "items">("events");
 
   const loadEvents = useCallback(async (): Promise<void> => {
     try {
@@ -159,10 +221,59 @@ export function JobHistoryDialog({
     }
   }, [open, jobId, loadEvents]);
 
+  const handleControl = async (control: JobControl): Promise<void> => {
+    try {
+      setControlling(control);
+      setControlError(null);
+      switch (control) {
+        case "cancel":
+          await api.cancelJob(jobId);
+          break;
+        case "pause":
+          await api.pauseJob(jobId);
+          break;
+        case "resume":
+          await api.resumeJob(jobId);
+          break;
+        case "retry":
+          await api.retryFailedJob(jobId);
+          break;
+      }
+      onJobUpdated?.();
+      await loadEvents();
+    } catch (err) {
+      setControlError(
+        err instanceof Error ? err.message : `Failed to ${control} job`,
+      );
+    } finally {
+      setControlling(null);
+    }
+  };
+
+  // Audit-log entries are not bulk jobs and cannot be controlled; only
+  // background jobs can be paused and have failed items to retry
+  const status = job && !job.job_id.startsWith("audit-") ? job.status : null;
+  const isBackgroundJob =
+    job !== undefined && BACKGROUND_JOB_OPERATIONS.includes(job.operation_type);
+  const canPause =
+    isBackgroundJob && (status === "queued" || status === "running");
+  const canResume = status === "paused";
+  const canCancel =
+    status === "queued" || status === "running" || status === "paused";
+  const canRetry =
+    isBackgroundJob &&
+    (status === "completed" || status === "failed") &&
+    (job.error_count ?? 0) > 0;
+
   const getEventIcon = (eventType: string): JSX.Element => {
     switch (eventType) {
       case "started":
+      case "resumed":
         return <PlayIcon />;
+      case "paused":
+        return <PauseIcon />;
+      case "retry":
+        return <RetryIcon />;
       case "progress":
         return <CircleIcon />;
       case "completed":
@@ -178,8 +289,16 @@ export function JobHistoryDialog({
 
   const getEventLabel = (eventType: string): string => {
     switch (eventType) {
+      case "queued":
+        return "Queued";
       case "started":
         return "Started";
+      case "paused":
+        return "Paused";
+      case "resumed":
+        return "Resumed";
+      case "retry":
+        return "Retrying Failed Items";
       case "progress":
         return "Progress Update";
       case "completed":
@@ -239,6 +358,9 @@ export function JobHistoryDialog({
     if (details.percentage !== undefined) {
       items.push(`${details.percentage.toFixed(1)}%`);
     }
+    if (typeof details["items"] === "number") {
+      items.push(`Items: ${details["items"].toLocaleString()}`);
+    }
     if (details.error_message) {
       items.push(`Error: ${details.error_message}`);
     }
@@ -262,8 +384,32 @@ export function JobHistoryDialog({
           <div className="job-dialog-job-id">Job ID: {jobId}</div>
         </div>
 
+        {!jobId.startsWith("audit-") && (
+          <div className="job-dialog-tabs" role="tablist">
+            <button
+              role="tab"
+              aria-selected={view === "events"}
+              className={`job-dialog-tab ${view === "events" ? "active" : ""}`}
+              onClick={() => setView("events")}
+            >
+              Events
+            </button>
+            <button
+              role="tab"
+              aria-selected={view === "items"}
+              className={`job-dialog-tab ${view === "items" ? "active" : ""}`}
+              onClick={() => setView("items")}
+            >
+              Items
+              {job?.error_count ? ` (${job.error_count} failed)` : ""}
+            </button>
+          </div>
+        )}
+
         <div className="job-dialog-content">
-          {loading ? (
+          {view === "items" ? (
+            <JobItemsPanel jobId={jobId} />
+          ) : loading ? (
             <div className="job-dialog-loading">
               <LoaderIcon />
             </div>
@@ -315,7 +461,49 @@ export function JobHistoryDialog({
           )}
         </div>
 
+        {controlError && (
+          <div className="job-dialog-control-error">{controlError}</div>
+        )}
+
         <div className="job-dialog-actions">
+          <div className="job-dialog-controls">
+            {canPause && (
+              <button
+                className="job-dialog-control-btn"
+                disabled={controlling !== null}
+                onClick={() => void handleControl("pause")}
+              >
+                {controlling === "pause" ? "Pausing..." : "Pause"}
+              </button>
+            )}
+            {canResume && (
+              <button
+                className="job-dialog-control-btn"
+                disabled={controlling !== null}
+                onClick={() => void handleControl("resume")}
+              >
+                {controlling === "resume" ? "Resuming..." : "Resume"}
+              </button>
+            )}
+            {canCancel && (
+              <button
+                className="job-dialog-control-btn danger"
+                disabled={controlling !== null}
+                onClick={() => void handleControl("cancel")}
+              >
+                {controlling === "cancel" ? "Cancelling..." : "Cancel Job"}
+              </button>
+            )}
+            {canRetry && (
+              <button
+                className="job-dialog-control-btn"
+                disabled={controlling !== null}
+                onClick={() => void handleControl("retry")}
+              >
+                {controlling === "retry" ? "Retrying..." : "Retry Failed"}
+              </button>
+            )}
+          </div>
           <button className="job-dialog-close-btn" onClick={onClose}>
             Close
           </button>
@@ -0,0 +1,224 @@
+import { useCallback, useEffect, useState, type JSX } from "react";
+import {
+  api,
+  type JobItem,
+  type JobItemErrorCode,
+  type JobItemsFilter,
+  type JobItemsResponse,
+  type JobItemStatus,
+} from "../../services/api";
+
+const PAGE_SIZE = 100;
+
+const ERROR_CODE_LABELS: Record<JobItemErrorCode, string> = {
+  not_found: "Not Found",
+  access_denied: "Access Denied",
+  rate_limited: "Rate Limited",
+  timeout: "Timeout",
+  storage_error: "Storage Error",
+  object_locked: "Object Locked",
+  unknown: "Unknown",
+};
+
+interface JobItemsPanelProps {
+  jobId: string;
+}
+
+/**
+ * Per-item outcome manifest of a job, with filters and CSV/NDJSON export
+ */
+export function JobItemsPanel({ jobId }: JobItemsPanelProps): JSX.Element {
+  const [data, setData] = useState<JobItemsResponse | null>(null);
+  const [items, setItems] = useState<JobItem[]>([]);
+  const [loading, setLoading] = useState(true);
+  const [error, setError] = useState<string | null>(null);
+  const [statusFilter, setStatusFilter] = useState<JobItemStatus | "all">(
+    "failed",
+  );
+  const [errorCodeFilter, setErrorCodeFilter] = useState<
+    JobItemErrorCode | "all"
+  >("all");
+  const [prefixInput, setPrefixInput] = useState("");
+  const [prefix, setPrefix] = useState("");
+  const [exporting, setExporting] = useState(false);
+
+  // Debounce prefix search
+  useEffect(() => {
+    const timer = setTimeout(() => {
+      setPrefix(prefixInput);
+    }, 500);
+    return () => clearTimeout(timer);
+  }, [prefixInput]);
+
+  const buildFilter = useCallback((): JobItemsFilter => {
+    return {
+      ...(statusFilter !== "all" && { status: statusFilter }),
+      ...(errorCodeFilter !== "all" && { error_code: errorCodeFilter }),
+      ...(prefix !== "" && { prefix }),
+    };
+  }, [statusFilter, errorCodeFilter, prefix]);
+
+  const loadItems = useCallback(
+    async (offset: number): Promise<void> => {
+      try {
+        setLoading(true);
+        setError(null);
+        const result = await api.getJobItems(jobId, {
+          ...buildFilter(),
+          limit: PAGE_SIZE,
+          offset,
+        });
+        setData(result);
+        setItems((prev) =>
+          offset === 0 ? result.items : [...prev, ...result.items],
+        );
+      } catch (err) {
+        setError(
+          err instanceof Error ? err.message : "Failed to load job items",
+        );
+      } finally {
+        setLoading(false);
+      }
+    },
+    [jobId, buildFilter],
+  );
+
+  useEffect(() => {
+    queueMicrotask(() => {
+      void loadItems(0);
+    });
+  }, [loadItems]);
+
+  const handleExport = async (format: "csv" | "ndjson"): Promise<void> => {
+    try {
+      setExporting(true);
+      await api.exportJobItems(jobId, format, buildFilter());
+    } catch (err) {
+      setError(
+        err instanceof Error ? err.message : "Failed to export job items",
+      );
+    } finally {
+      setExporting(false);
+    }
+  };
+
+  const errorCodes = Object.entries(data?.summary.by_error_code ?? {}) as [
+    JobItemErrorCode,
+    number,
+  ][];
+
+  return (
+    <div className="job-items-panel">
+      {data && (
+        <div className="job-items-summary">
+          <span>Failed: {data.summary.failed.toLocaleString()}</span>
+          <span>Retried OK: {data.summary.succeeded.toLocaleString()}</span>
+          {errorCodes.map(([code, count]) => (
+            <span key={code} className="job-items-code">
+              {ERROR_CODE_LABELS[code]}:{" "}
+              {count.toLocaleString()}
+            </span>
+          ))}
+        </div>
+      )}
+
+      <div className="job-items-filters">
+        <select
+          className="job-filter-select"
+          value={statusFilter}
+          onChange={(e) =>
+            setStatusFilter(e.target.value as JobItemStatus | "all")
+          }
+          aria-label="Item status"
+        >
+          <option value="failed">Failed</option>
+          <option value="succeeded">Retried OK</option>
+          <option value="all">All Items</option>
+        </select>
+        <select
+          className="job-filter-select"
+          value={errorCodeFilter}
+          onChange={(e) =>
+            setErrorCodeFilter(e.target.value as JobItemErrorCode | "all")
+          }
+          aria-label="Error code"
+        >
+          <option value="all">All Errors</option>
+          {Object.entries(ERROR_CODE_LABELS).map(([code, label]) => (
+            <option key={code} value={code}>
+              {label}
+            </option>
+          ))}
+        </select>
+        <input
+          type="text"
+          className="job-filter-input"
+          placeholder="Key prefix..."
+          value={prefixInput}
+          onChange={(e) => setPrefixInput(e.target.value)}
+          aria-label="Key prefix"
+        />
+        <button
+          className="job-dialog-control-btn"
+          disabled={exporting || items.length === 0}
+          onClick={() => void handleExport("csv")}
+        >
+          CSV
+        </button>
+        <button
+          className="job-dialog-control-btn"
+          disabled={exporting || items.length === 0}
+          onClick={() => void handleExport("ndjson")}
+        >
+          NDJSON
+        </button>
+      </div>
+
+      {error ? (
+        <div className="job-dialog-error">
+          <p>Error:</p>
+          <p>{error}</p>
+        </div>
+      ) : !loading && items.length === 0 ? (
+        <div className="job-dialog-empty">No items match these filters</div>
+      ) : (
+        <div className="job-items-list">
+          {items.map((item) => (
+            <div key={item.object_key} className="job-item-row">
+              <div className="job-item-key" title={item.object_key}>
+                {item.object_key}
+              </div>
+              <div className="job-item-meta">
+                <span className={`job-item-status ${item.status}`}>
+                  {item.status === "failed" ? "Failed" : "Retried OK"}
+                </span>
+                {item.error_code && (
+                  <span className="job-items-code">
+                    {ERROR_CODE_LABELS[item.error_code]}
+                  </span>
+                )}
+                <span>
+                  {item.attempts} attempt{item.attempts === 1 ? "" : "s"}
+                </span>
+              </div>
+              {item.error && (
+                <div className="job-item-error">{item.error}</div>
+              )}
+            </div>
+          ))}
+          {data && items.length < data.total && (
+            <button
+              className="job-dialog-control-btn"
+              disabled={loading}
+              onClick={() => void loadItems(items.length)}
+            >
+              {loading
+                ? "Loading..."
+                : `Load More (${items.length} of ${data.total})`}
+            </button>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
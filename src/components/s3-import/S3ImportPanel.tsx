@@ -3,6 +3,7 @@ import { api } from "../../services/api";
 import type { S3ImportJob, S3ImportJobsListResponse } from "../../services/api";
 import { S3CredentialsForm } from "./S3CredentialsForm";
 import { MigrationJobsList } from "./MigrationJobsList";
+import { SippySettings } from "./SippySettings";
 import "./s3-import.css";
 
 interface S3ImportPanelProps {
@@ -11,7 +12,7 @@ interface S3ImportPanelProps {
   onJobCreated?: () => void;
 }
 
-type TabType = "new" | "active" | "history";
+type TabType = "new" | "active" | "history" | "sippy";
 
 export function S3ImportPanel({
   buckets,
@@ -45,7 +46,7 @@ export function S3ImportPanel({
     (tab: TabType) => {
       setActiveTab(tab);
       setError(null);
-      if (tab !== "new") {
+      if (tab === "active" || tab === "history") {
         void loadJobs(false);
       }
     },
@@ -147,6 +148,12 @@ export function S3ImportPanel({
         >
           History
         </button>
+        <button
+          className={`s3-import-tab ${activeTab === "sippy" ? "active" : ""}`}
+          onClick={() => handleTabChange("sippy")}
+        >
+          Sippy
+        </button>
       </div>
 
       <div className="s3-import-content">
@@ -199,6 +206,10 @@ export function S3ImportPanel({
             emptyMessage="No migration history yet."
           />
         )}
+
+        {activeTab === "sippy" && (
+          <SippySettings buckets={buckets} onError={setError} />
+        )}
       </div>
     </div>
   );
@@ -0,0 +1,167 @@
+/**
+ * SharesManager Component
+ *
+ * Lists the public share links the current user has created for files and
+ * folders, with their expiry, protection and download counts, and lets the
+ * user copy or revoke them.
+ */
+
+import { useCallback, useEffect, useState, type JSX } from "react";
+import { api, type Share, type ShareStatus } from "../../services/api";
+import { logger } from "../../services/logger";
+import "../../styles/shares.css";
+
+const STATUS_LABELS: Record<ShareStatus, string> = {
+  active: "Active",
+  expired: "Expired",
+  exhausted: "Limit Reached",
+  revoked: "Revoked",
+};
+
+const formatDate = (value: string): string =>
+  new Date(value).toLocaleString();
+
+export function SharesManager(): JSX.Element {
+  const [shares, setShares] = useState<Share[]>([]);
+  const [loading, setLoading] = useState(true);
+  const [error, setError] = useState("");
+  const [includeInactive, setIncludeInactive] = useState(false);
+  const [revoking, setRevoking] = useState<string | null>(null);
+  const [copiedId, setCopiedId] = useState<string | null>(null);
+
+  const loadShares = useCallback(async (): Promise<void> => {
+    try {
+      setLoading(true);
+      setError("");
+      setShares(await api.listShares(includeInactive));
+    } catch (err) {
+      logger.error("SharesManager", "Failed to load shares", err);
+      setError(err instanceof Error ? err.message : "Failed to load shares");
+    } finally {
+      setLoading(false);
+    }
+  }, [includeInactive]);
+
+  useEffect(() => {
+    queueMicrotask(() => {
+      void loadShares();
+    });
+  }, [loadShares]);
+
+  const handleCopy = async (share: Share): Promise<void> => {
+    try {
+      await navigator.clipboard.writeText(share.url);
+      setCopiedId(share.share_id);
+      setTimeout(() => setCopiedId(null), 2000);
+    } catch (err) {
+      logger.error("SharesManager", "Failed to copy share link", err);
+      setError("Failed to copy link to clipboard");
+    }
+  };
+
+  const handleRevoke = async (shareId: string): Promise<void> => {
+    try {
+      setRevoking(shareId);
+      setError("");
+      await api.revokeShare(shareId);
+      await loadShares();
+    } catch (err) {
+      logger.error("SharesManager", "Failed to revoke share", err);
+      setError(err instanceof Error ? err.message : "Failed to revoke share");
+    } finally {
+      setRevoking(null);
+    }
+  };
+
+  return (
+    <div className="shares-container">
+      <div className="shares-header">
+        <div>
+          <h2>Shares</h2>
+          <p className="shares-subtitle">
+            Public links to files and folders. Anyone with the link (and
+            password, if set) can open it until it expires or is revoked.
+          </p>
+        </div>
+        <div className="shares-header-actions">
+          <label className="shares-toggle">
+            <input
+              type="checkbox"
+              checked={includeInactive}
+              onChange={(e) => setIncludeInactive(e.target.checked)}
+            />
+            Show expired and revoked
+          </label>
+          <button
+            className="shares-btn"
+            onClick={() => void loadShares()}
+            disabled={loading}
+          >
+            Refresh
+          </button>
+        </div>
+      </div>
+
+      {error && <div className="shares-error">{error}</div>}
+
+      {loading && shares.length === 0 ? (
+        <div className="shares-empty">Loading...</div>
+      ) : shares.length === 0 ? (
+        <div className="shares-empty">
+          No shares. Right-click a file or folder and choose "Create Share
+          Link" to share it.
+        </div>
+      ) : (
+        <div className="shares-list">
+          {shares.map((share) => (
+            <div key={share.share_id} className="share-card">
+              <div className="share-main">
+                <div className="share-key" title={share.object_key}>
+                  {share.share_type === "folder" ? "📁 " : "📄 "}
+                  {share.bucket_name}/{share.object_key}
+                </div>
+                <div className="share-meta">
+                  <span className={`share-status ${share.status}`}>
+                    {STATUS_LABELS[share.status]}
+                  </span>
+                  <span>Expires {formatDate(share.expires_at)}</span>
+                  <span>
+                    {share.download_count}
+                    {share.max_downloads !== null
+                      ? ` / ${share.max_downloads}`
+                      : ""}{" "}
+                    download{share.download_count === 1 ? "" : "s"}
+                  </span>
+                  {share.has_password && <span>🔒 Password</span>}
+                  {share.allow_zip && <span>ZIP enabled</span>}
+                  {share.last_accessed_at && (
+                    <span>
+                      Last opened {formatDate(share.last_accessed_at)}
+                    </span>
+                  )}
+                </div>
+              </div>
+              {share.status === "active" && (
+                <div className="share-actions">
+                  <button
+                    className="shares-btn"
+                    onClick={() => void handleCopy(share)}
+                  >
+                    {copiedId === share.share_id ? "Copied!" : "Copy Link"}
+                  </button>
+                  <button
+                    className="shares-btn danger"
+                    onClick={() => void handleRevoke(share.share_id)}
+                    disabled={revoking === share.share_id}
+                  >
+                    {revoking === share.share_id ? "Revoking..." : "Revoke"}
+                  </button>
+                </div>
+              )}
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,148 @@
+/**
+ * SignedLinksManager Component
+ *
+ * Lists the signed download links the current user has issued, with their
+ * expiry, restrictions and download counts, and lets the user revoke them.
+ */
+
+import { useCallback, useEffect, useState, type JSX } from "react";
+import {
+  api,
+  type SignedLink,
+  type SignedLinkStatus,
+} from "../../services/api";
+import { logger } from "../../services/logger";
+import "../../styles/signed-links.css";
+
+const STATUS_LABELS: Record<SignedLinkStatus, string> = {
+  active: "Active",
+  expired: "Expired",
+  exhausted: "Limit Reached",
+  revoked: "Revoked",
+};
+
+const formatDate = (value: string): string =>
+  new Date(value).toLocaleString();
+
+export function SignedLinksManager(): JSX.Element {
+  const [links, setLinks] = useState<SignedLink[]>([]);
+  const [loading, setLoading] = useState(true);
+  const [error, setError] = useState("");
+  const [includeInactive, setIncludeInactive] = useState(false);
+  const [revoking, setRevoking] = useState<string | null>(null);
+
+  const loadLinks = useCallback(async (): Promise<void> => {
+    try {
+      setLoading(true);
+      setError("");
+      setLinks(await api.listSignedLinks(includeInactive));
+    } catch (err) {
+      logger.error("SignedLinksManager", "Failed to load signed links", err);
+      setError(
+        err instanceof Error ? err.message : "Failed to load signed links",
+      );
+    } finally {
+      setLoading(false);
+    }
+  }, [includeInactive]);
+
+  useEffect(() => {
+    queueMicrotask(() => {
+      void loadLinks();
+    });
+  }, [loadLinks]);
+
+  const handleRevoke = async (linkId: string): Promise<void> => {
+    try {
+      setRevoking(linkId);
+      setError("");
+      await api.revokeSignedLink(linkId);
+      await loadLinks();
+    } catch (err) {
+      logger.error("SignedLinksManager", "Failed to revoke signed link", err);
+      setError(
+        err instanceof Error ? err.message : "Failed to revoke signed link",
+      );
+    } finally {
+      setRevoking(null);
+    }
+  };
+
+  return (
+    <div className="signed-links-container">
+      <div className="signed-links-header">
+        <div>
+          <h2>Signed Links</h2>
+          <p className="signed-links-subtitle">
+            Download links you have shared. Revoking a link stops it working
+            immediately.
+          </p>
+        </div>
+        <div className="signed-links-header-actions">
+          <label className="signed-links-toggle">
+            <input
+              type="checkbox"
+              checked={includeInactive}
+              onChange={(e) => setIncludeInactive(e.target.checked)}
+            />
+            Show expired and revoked
+          </label>
+          <button
+            className="signed-links-btn"
+            onClick={() => void loadLinks()}
+            disabled={loading}
+          >
+            Refresh
+          </button>
+        </div>
+      </div>
+
+      {error && <div className="signed-links-error">{error}</div>}
+
+      {loading && links.length === 0 ? (
+        <div className="signed-links-empty">Loading...</div>
+      ) : links.length === 0 ? (
+        <div className="signed-links-empty">
+          No signed links. Right-click a file and choose "Create Signed Link"
+          to share one.
+        </div>
+      ) : (
+        <div className="signed-links-list">
+          {links.map((link) => (
+            <div key={link.link_id} className="signed-link-card">
+              <div className="signed-link-main">
+                <div className="signed-link-key" title={link.object_key}>
+                  {link.bucket_name}/{link.object_key}
+                </div>
+                <div className="signed-link-meta">
+                  <span className={`signed-link-status ${link.status}`}>
+                    {STATUS_LABELS[link.status]}
+                  </span>
+                  <span>Expires {formatDate(link.expires_at)}</span>
+                  <span>
+                    {link.download_count}
+                    {link.max_downloads !== null
+                      ? ` / ${link.max_downloads}`
+                      : ""}{" "}
+                    download{link.download_count === 1 ? "" : "s"}
+                  </span>
+                  {link.ip && <span>IP {link.ip}</span>}
+                  {link.disposition === "inline" && <span>Inline</span>}
+                </div>
+              </div>
+              {link.status === "active" && (
+                <button
+                  className="signed-links-btn danger"
+                  onClick={() => void handleRevoke(link.link_id)}
+                  disabled={revoking === link.link_id}
+                >
+                  {revoking === link.link_id ? "Revoking..." : "Revoke"}
+                </button>
+              )}
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+}
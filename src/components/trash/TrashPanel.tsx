@@ -0,0 +1,304 @@
+import { useState, useEffect, useCallback, type JSX } from "react";
+import { api, type TrashItem, type TrashSettings } from "../../services/api";
+import { formatFileSize } from "../../utils/fileUtils";
+import "./trash.css";
+
+interface TrashPanelProps {
+  bucketName: string;
+  onClose: () => void;
+  /** Called after an item is restored, so the file list can refresh */
+  onRestored: () => void;
+}
+
+export function TrashPanel({
+  bucketName,
+  onClose,
+  onRestored,
+}: TrashPanelProps): JSX.Element {
+  const [items, setItems] = useState<TrashItem[]>([]);
+  const [settings, setSettings] = useState<TrashSettings | null>(null);
+  const [retentionInput, setRetentionInput] = useState("30");
+  const [isLoading, setIsLoading] = useState(true);
+  const [isSaving, setIsSaving] = useState(false);
+  const [isEmptying, setIsEmptying] = useState(false);
+  const [busyItemId, setBusyItemId] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const loadTrash = useCallback(async () => {
+    setIsLoading(true);
+    setError(null);
+    try {
+      const listing = await api.getTrash(bucketName);
+      setItems(listing.items);
+      setSettings(listing.settings);
+      setRetentionInput(String(listing.settings.retentionDays));
+    } catch (err) {
+      setError(err instanceof Error ? err.message : "Failed to load trash");
+    } finally {
+      setIsLoading(false);
+    }
+  }, [bucketName]);
+
+  useEffect(() => {
+    queueMicrotask(() => {
+      void loadTrash();
+    });
+  }, [loadTrash]);
+
+  const saveSettings = useCallback(
+    async (enabled: boolean) => {
+      const retentionDays = Number(retentionInput);
+      if (!Number.isInteger(retentionDays) || retentionDays < 1) {
+        setError("Retention must be a whole number of days");
+        return;
+      }
+
+      setIsSaving(true);
+      setError(null);
+      try {
+        const updated = await api.updateTrashSettings(bucketName, {
+          enabled,
+          retentionDays,
+        });
+        setSettings(updated);
+      } catch (err) {
+        setError(
+          err instanceof Error ? err.message : "Failed to update trash settings",
+        );
+      } finally {
+        setIsSaving(false);
+      }
+    },
+    [bucketName, retentionInput],
+  );
+
+  const handleRestore = useCallback(
+    async (item: TrashItem) => {
+      setBusyItemId(item.item_id);
+      setError(null);
+      try {
+        const result = await api.restoreTrashItem(bucketName, item.item_id);
+        if (result === "exists") {
+          if (
+            !confirm(
+              `"${item.original_key}" already exists. Replace it with the trashed version? The current object will be moved to the trash.`,
+            )
+          ) {
+            return;
+          }
+          await api.restoreTrashItem(bucketName, item.item_id, true);
+        }
+        onRestored();
+        await loadTrash();
+      } catch (err) {
+        setError(err instanceof Error ? err.message : "Failed to restore item");
+      } finally {
+        setBusyItemId(null);
+      }
+    },
+    [bucketName, loadTrash, onRestored],
+  );
+
+  const handlePurge = useCallback(
+    async (item: TrashItem) => {
+      if (!confirm(`Permanently delete "${item.original_key}"?`)) return;
+
+      setBusyItemId(item.item_id);
+      setError(null);
+      try {
+        await api.purgeTrashItem(bucketName, item.item_id);
+        setItems((prev) => prev.filter((i) => i.item_id !== item.item_id));
+      } catch (err) {
+        setError(err instanceof Error ? err.message : "Failed to purge item");
+      } finally {
+        setBusyItemId(null);
+      }
+    },
+    [bucketName],
+  );
+
+  const handleEmpty = useCallback(async () => {
+    if (
+      !confirm(
+        `Permanently delete every item in the trash of "${bucketName}"? This cannot be undone.`,
+      )
+    ) {
+      return;
+    }
+
+    setIsEmptying(true);
+    setError(null);
+    try {
+      let remaining = true;
+      while (remaining) {
+        ({ remaining } = await api.emptyTrash(bucketName));
+      }
+      await loadTrash();
+    } catch (err) {
+      setError(err instanceof Error ? err.message : "Failed to empty trash");
+    } finally {
+      setIsEmptying(false);
+    }
+  }, [bucketName, loadTrash]);
+
+  const enabled = settings?.enabled ?? false;
+
+  return (
+    <div className="trash-panel">
+      <div className="trash-header">
+        <div className="trash-title">
+          <svg
+            className="trash-icon"
+            viewBox="0 0 24 24"
+            fill="none"
+            stroke="currentColor"
+            strokeWidth="2"
+          >
+            <polyline points="3 6 5 6 21 6" />
+            <path d="M19 6l-1 14a2 2 0 0 1-2 2H8a2 2 0 0 1-2-2L5 6" />
+            <path d="M10 11v6M14 11v6" />
+            <path d="M9 6V4a1 1 0 0 1 1-1h4a1 1 0 0 1 1 1v2" />
+          </svg>
+          <h2>Trash</h2>
+          <span className="trash-bucket-badge">{bucketName}</span>
+        </div>
+        <button className="trash-close" onClick={onClose} aria-label="Close">
+          <svg
+            viewBox="0 0 24 24"
+            fill="none"
+            stroke="currentColor"
+            strokeWidth="2"
+          >
+            <path d="M18 6 6 18M6 6l12 12" />
+          </svg>
+        </button>
+      </div>
+
+      <div className="trash-settings">
+        <button
+          className={`trash-toggle ${enabled ? "enabled" : ""}`}
+          onClick={() => void saveSettings(!enabled)}
+          disabled={isLoading || isSaving}
+          aria-label={enabled ? "Disable trash" : "Enable trash"}
+        >
+          {enabled ? "Enabled" : "Disabled"}
+        </button>
+        <label className="trash-retention">
+          Keep items for
+          <input
+            type="number"
+            min={1}
+            max={365}
+            value={retentionInput}
+            onChange={(e) => setRetentionInput(e.target.value)}
+            disabled={isLoading || isSaving}
+          />
+          days
+        </label>
+        {enabled && (
+          <button
+            className="trash-secondary-btn"
+            onClick={() => void saveSettings(true)}
+            disabled={
+              isLoading ||
+              isSaving ||
+              retentionInput === String(settings?.retentionDays)
+            }
+          >
+            {isSaving ? "Saving..." : "Save"}
+          </button>
+        )}
+      </div>
+
+      <div className="trash-info">
+        <span>
+          {enabled
+            ? "Deleted and overwritten objects are kept here until their retention period ends."
+            : "Trash is off - deleted objects are removed immediately. Items already in the trash can still be restored until they expire."}
+        </span>
+      </div>
+
+      {error !== null && (
+        <div className="trash-error">
+          <span>{error}</span>
+          <button onClick={() => setError(null)} aria-label="Dismiss error">
+            ×
+          </button>
+        </div>
+      )}
+
+      <div className="trash-actions">
+        <button
+          className="trash-secondary-btn"
+          onClick={() => void loadTrash()}
+          disabled={isLoading}
+        >
+          Refresh
+        </button>
+        <button
+          className="trash-empty-btn"
+          onClick={() => void handleEmpty()}
+          disabled={isLoading || isEmptying || items.length === 0}
+        >
+          {isEmptying ? "Emptying..." : "Empty Trash"}
+        </button>
+      </div>
+
+      <div className="trash-content">
+        {isLoading ? (
+          <div className="trash-loading">Loading trash...</div>
+        ) : items.length === 0 ? (
+          <div className="trash-empty">
+            <p>The trash is empty</p>
+          </div>
+        ) : (
+          <table className="trash-table">
+            <thead>
+              <tr>
+                <th>Original location</th>
+                <th>Size</th>
+                <th>Removed</th>
+                <th>Expires</th>
+                <th />
+              </tr>
+            </thead>
+            <tbody>
+              {items.map((item) => (
+                <tr key={item.item_id}>
+                  <td className="trash-key" title={item.original_key}>
+                    {item.original_key}
+                    {item.reason === "overwrite" && (
+                      <span className="trash-reason">overwritten</span>
+                    )}
+                  </td>
+                  <td>{formatFileSize(item.size)}</td>
+                  <td title={item.deleted_by}>
+                    {new Date(item.deleted_at).toLocaleString()}
+                    <div className="trash-deleted-by">{item.deleted_by}</div>
+                  </td>
+                  <td>{new Date(item.expires_at).toLocaleDateString()}</td>
+                  <td className="trash-item-actions">
+                    <button
+                      className="trash-secondary-btn"
+                      onClick={() => void handleRestore(item)}
+                      disabled={busyItemId === item.item_id}
+                    >
+                      Restore
+                    </button>
+                    <button
+                      className="trash-purge-btn"
+                      onClick={() => void handlePurge(item)}
+                      disabled={busyItemId === item.item_id}
+                    >
+                      Purge
+                    </button>
+                  </td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        )}
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1 @@
+export { TrashPanel } from "./TrashPanel";
@@ -2,20 +2,26 @@
  * WebhookManager Component
  *
  * Provides a UI for managing webhook configurations.
- * Supports creating, editing, deleting, and testing webhooks.
+ * Supports creating, editing, deleting, and testing webhooks, rotating
+ * their secrets, and viewing and redelivering their delivery attempts.
  */
 
 import { useState, useEffect, useCallback, type ReactElement } from "react";
 import { webhookApi } from "../../services/webhookApi";
 import type {
   Webhook,
+  WebhookDelivery,
   WebhookEventType,
+  WebhookFilters,
+  WebhookFormat,
   WebhookInput,
 } from "../../types/webhook";
 import {
   ALL_WEBHOOK_EVENTS,
+  ALL_WEBHOOK_FORMATS,
   WEBHOOK_EVENT_LABELS,
   WEBHOOK_EVENT_DESCRIPTIONS,
+  WEBHOOK_FORMAT_LABELS,
 } from "../../types/webhook";
 import "../../styles/webhooks.css";
 
@@ -112,6 +118,44 @@ const PlayIcon = ({ className }: { className?: string }): ReactElement => (
   </svg>
 );
 
+const HistoryIcon = ({ className }: { className?: string }): ReactElement => (
+  <svg
+    className={className}
+    xmlns="http://www.w3.org/2000/svg"
+    width="16"
+    height="16"
+    viewBox="0 0 24 24"
+    fill="none"
+    stroke="currentColor"
+    strokeWidth="2"
+    strokeLinecap="round"
+    strokeLinejoin="round"
+  >
+    <path d="M3 12a9 9 0 1 0 9-9 9.75 9.75 0 0 0-6.74 2.74L3 8" />
+    <path d="M3 3v5h5" />
+    <path d="M12 7v5l4 2" />
+  </svg>
+);
+
+const KeyIcon = ({ className }: { className?: string }): ReactElement => (
+  <svg
+    className={className}
+    xmlns="http://www.w3.org/2000/svg"
+    width="16"
+    height="16"
+    viewBox="0 0 24 24"
+    fill="none"
+    stroke="currentColor"
+    strokeWidth="2"
+    strokeLinecap="round"
+    strokeLinejoin="round"
+  >
+    <circle cx="7.5" cy="15.5" r="5.5" />
+    <path d="m21 2-9.6 9.6" />
+    <path d="m15.5 7.5 3 3L22 7l-3-3" />
+  </svg>
+);
+
 const BellIcon = ({ className }: { className?: string }): ReactElement => (
   <svg
     className={className}
@@ -205,6 +249,126 @@ const LoaderIcon = ({ className }: { className?: string }): ReactElement => (
   </svg>
 );
 
+const BYTES_PER_MB = 1024 * 1024;
+
+/** Grace periods offered when rotating a secret, in hours */
+const ROTATION_GRACE_OPTIONS = [
+  { hours: 0, label: "None (stop using it now)" },
+  { hours: 1, label: "1 hour" },
+  { hours: 24, label: "24 hours" },
+  { hours: 72, label: "3 days" },
+  { hours: 168, label: "7 days" },
+];
+
+/**
+ * Editable form of a webhook's filters: lists are comma-separated and sizes
+ * are in MB
+ */
+interface FilterDraft {
+  buckets: string;
+  bucketTags: string;
+  keyPatterns: string;
+  userEmails: string;
+  minSizeMb: string;
+  maxSizeMb: string;
+}
+
+const EMPTY_FILTER_DRAFT: FilterDraft = {
+  buckets: "",
+  bucketTags: "",
+  keyPatterns: "",
+  userEmails: "",
+  minSizeMb: "",
+  maxSizeMb: "",
+};
+
+function parseFilters(filtersJson: string | null): WebhookFilters {
+  if (!filtersJson) return {};
+  try {
+    return JSON.parse(filtersJson) as WebhookFilters;
+  } catch {
+    return {};
+  }
+}
+
+function toFilterDraft(filtersJson: string | null): FilterDraft {
+  const filters = parseFilters(filtersJson);
+  return {
+    buckets: filters.buckets?.join(", ") ?? "",
+    bucketTags: filters.bucketTags?.join(", ") ?? "",
+    keyPatterns: filters.keyPatterns?.join(", ") ?? "",
+    userEmails: filters.userEmails?.join(", ") ?? "",
+    minSizeMb:
+      filters.minSize !== undefined
+        ? String(filters.minSize / BYTES_PER_MB)
+        : "",
+    maxSizeMb:
+      filters.maxSize !== undefined
+        ? String(filters.maxSize / BYTES_PER_MB)
+        : "",
+  };
+}
+
+function fromFilterDraft(draft: FilterDraft): WebhookFilters | null {
+  const list = (value: string): string[] =>
+    value
+      .split(",")
+      .map((entry) => entry.trim())
+      .filter((entry) => entry !== "");
+  const size = (value: string): number | undefined =>
+    value.trim() !== "" ? Math.round(Number(value) * BYTES_PER_MB) : undefined;
+
+  const filters: WebhookFilters = {};
+  const buckets = list(draft.buckets);
+  const bucketTags = list(draft.bucketTags);
+  const keyPatterns = list(draft.keyPatterns);
+  const userEmails = list(draft.userEmails);
+  const minSize = size(draft.minSizeMb);
+  const maxSize = size(draft.maxSizeMb);
+  if (buckets.length > 0) filters.buckets = buckets;
+  if (bucketTags.length > 0) filters.bucketTags = bucketTags;
+  if (keyPatterns.length > 0) filters.keyPatterns = keyPatterns;
+  if (userEmails.length > 0) filters.userEmails = userEmails;
+  if (minSize !== undefined) filters.minSize = minSize;
+  if (maxSize !== undefined) filters.maxSize = maxSize;
+  return Object.keys(filters).length > 0 ? filters : null;
+}
+
+function isFilterDraftValid(draft: FilterDraft): boolean {
+  return [draft.minSizeMb, draft.maxSizeMb].every(
+    (value) =>
+      value.trim() === "" ||
+      (Number.isFinite(Number(value)) && Number(value) >= 0),
+  );
+}
+
+/**
+ * One-line description of a webhook's filters, or null if it has none
+ */
+function describeFilters(filtersJson: string | null): string | null {
+  const filters = parseFilters(filtersJson);
+  const parts: string[] = [];
+  if (filters.buckets?.length) {
+    parts.push(`Buckets: ${filters.buckets.join(", ")}`);
+  }
+  if (filters.bucketTags?.length) {
+    parts.push(`Tags: ${filters.bucketTags.join(", ")}`);
+  }
+  if (filters.keyPatterns?.length) {
+    parts.push(`Keys: ${filters.keyPatterns.join(", ")}`);
+  }
+  if (filters.minSize !== undefined) {
+    parts.push(`Min ${String(filters.minSize / BYTES_PER_MB)} MB`);
+  }
+  if (filters.maxSize !== undefined) {
+    parts.push(`Max ${String(filters.maxSize / BYTES_PER_MB)} MB`);
+  }
+  if (filters.userEmails?.length) {
+    parts.push(`Users: ${filters.userEmails.join(", ")}`);
+  }
+  return parts.length > 0 ? parts.join(" · ") : null;
+}
+
 export function WebhookManager(): ReactElement {
   const [webhooks, setWebhooks] = useState<Webhook[]>([]);
   const [loading, setLoading] = useState(true);
@@ -219,6 +383,18 @@ export function WebhookManager(): ReactElement {
     success: boolean;
     message: string;
   } | null>(null);
+  const [deliveriesWebhook, setDeliveriesWebhook] = useState<Webhook | null>(
+    null,
+  );
+  const [deliveries, setDeliveries] = useState<WebhookDelivery[]>([]);
+  const [deliveriesLoading, setDeliveriesLoading] = useState(false);
+  const [redelivering, setRedelivering] = useState<string | null>(null);
+  const [rotatingWebhook, setRotatingWebhook] = useState<Webhook | null>(null);
+  const [rotateGraceHours, setRotateGraceHours] = useState(24);
+  const [rotatedSecret, setRotatedSecret] = useState<{
+    secret: string;
+    previousExpiresAt: string | null;
+  } | null>(null);
 
   // Form states
   const [formName, setFormName] = useState("");
@@ -226,7 +402,16 @@ export function WebhookManager(): ReactElement {
   const [formSecret, setFormSecret] = useState("");
   const [formEvents, setFormEvents] = useState<WebhookEventType[]>([]);
   const [formEnabled, setFormEnabled] = useState(true);
+  const [formFormat, setFormFormat] = useState<WebhookFormat>("raw");
+  const [formTemplate, setFormTemplate] = useState("");
+  const [formFilters, setFormFilters] =
+    useState<FilterDraft>(EMPTY_FILTER_DRAFT);
   const [submitting, setSubmitting] = useState(false);
+  const [preview, setPreview] = useState<{
+    success: boolean;
+    text: string;
+  } | null>(null);
+  const [previewing, setPreviewing] = useState(false);
 
   const loadWebhooks = useCallback(async (): Promise<void> => {
     try {
@@ -253,6 +438,10 @@ export function WebhookManager(): ReactElement {
     setFormSecret("");
     setFormEvents([]);
     setFormEnabled(true);
+    setFormFormat("raw");
+    setFormTemplate("");
+    setFormFilters(EMPTY_FILTER_DRAFT);
+    setPreview(null);
   };
 
   const openCreateDialog = (): void => {
@@ -270,11 +459,21 @@ export function WebhookManager(): ReactElement {
       setFormEvents([]);
     }
     setFormEnabled(webhook.enabled === 1);
+    setFormFormat(webhook.format);
+    setFormTemplate(webhook.template ?? "");
+    setFormFilters(toFilterDraft(webhook.filters));
+    setPreview(null);
     setEditingWebhook(webhook);
   };
 
   const handleCreateWebhook = async (): Promise<void> => {
-    if (!formName.trim() || !formUrl.trim() || formEvents.length === 0) {
+    if (
+      !formName.trim() ||
+      !formUrl.trim() ||
+      formEvents.length === 0 ||
+      (formFormat === "custom" && !formTemplate.trim()) ||
+      !isFilterDraftValid(formFilters)
+    ) {
       return;
     }
 
@@ -286,6 +485,9 @@ export function WebhookManager(): ReactElement {
         secret: formSecret.trim() || null,
         events: formEvents,
         enabled: formEnabled,
+        format: formFormat,
+        template: formTemplate.trim() || null,
+        filters: fromFilterDraft(formFilters),
       };
       await webhookApi.create(input);
       setShowCreateDialog(false);
@@ -303,7 +505,9 @@ export function WebhookManager(): ReactElement {
       !editingWebhook ||
       !formName.trim() ||
       !formUrl.trim() ||
-      formEvents.length === 0
+      formEvents.length === 0 ||
+      (formFormat === "custom" && !formTemplate.trim()) ||
+      !isFilterDraftValid(formFilters)
     ) {
       return;
     }
@@ -316,6 +520,9 @@ export function WebhookManager(): ReactElement {
         secret: formSecret.trim() || null,
         events: formEvents,
         enabled: formEnabled,
+        format: formFormat,
+        template: formTemplate.trim() || null,
+        filters: fromFilterDraft(formFilters),
       };
       await webhookApi.update(editingWebhook.id, input);
       setEditingWebhook(null);
@@ -343,6 +550,34 @@ export function WebhookManager(): ReactElement {
     }
   };
 
+  const handlePreview = async (): Promise<void> => {
+    if (!editingWebhook) return;
+
+    setPreviewing(true);
+    try {
+      const result = await webhookApi.test(editingWebhook.id, {
+        format: formFormat,
+        template: formTemplate.trim() || null,
+        dryRun: true,
+      });
+      const body = result.body ?? "";
+      let text = body;
+      try {
+        text = JSON.stringify(JSON.parse(body), null, 2);
+      } catch {
+        // Show the body as rendered
+      }
+      setPreview({ success: true, text });
+    } catch (err) {
+      setPreview({
+        success: false,
+        text: err instanceof Error ? err.message : "Preview failed",
+      });
+    } finally {
+      setPreviewing(false);
+    }
+  };
+
   const handleTestWebhook = async (webhookId: string): Promise<void> => {
     setTestingWebhook(webhookId);
     setTestResult(null);
@@ -359,6 +594,75 @@ export function WebhookManager(): ReactElement {
     }
   };
 
+  const loadDeliveries = async (webhook: Webhook): Promise<void> => {
+    setDeliveriesLoading(true);
+    try {
+      setDeliveries(await webhookApi.listDeliveries(webhook.id));
+    } catch (err) {
+      setError(
+        err instanceof Error ? err.message : "Failed to load deliveries",
+      );
+    } finally {
+      setDeliveriesLoading(false);
+    }
+  };
+
+  const openDeliveriesDialog = (webhook: Webhook): void => {
+    setDeliveries([]);
+    setDeliveriesWebhook(webhook);
+    void loadDeliveries(webhook);
+  };
+
+  const handleRedeliver = async (delivery: WebhookDelivery): Promise<void> => {
+    if (!deliveriesWebhook) return;
+
+    setRedelivering(delivery.delivery_id);
+    try {
+      const attempt = await webhookApi.redeliver(
+        deliveriesWebhook.id,
+        delivery.delivery_id,
+      );
+      setDeliveries((prev) => [attempt, ...prev]);
+    } catch (err) {
+      setError(err instanceof Error ? err.message : "Failed to redeliver");
+    } finally {
+      setRedelivering(null);
+    }
+  };
+
+  const openRotateDialog = (webhook: Webhook): void => {
+    setRotateGraceHours(24);
+    setRotatedSecret(null);
+    setRotatingWebhook(webhook);
+  };
+
+  const closeRotateDialog = (): void => {
+    setRotatingWebhook(null);
+    setRotatedSecret(null);
+  };
+
+  const handleRotateSecret = async (): Promise<void> => {
+    if (!rotatingWebhook) return;
+
+    setSubmitting(true);
+    try {
+      const result = await webhookApi.rotateSecret(
+        rotatingWebhook.id,
+        rotateGraceHours,
+      );
+      setRotatedSecret({
+        secret: result.secret,
+        previousExpiresAt: result.webhook.previous_secret_expires_at,
+      });
+      await loadWebhooks();
+    } catch (err) {
+      setError(err instanceof Error ? err.message : "Failed to rotate secret");
+      closeRotateDialog();
+    } finally {
+      setSubmitting(false);
+    }
+  };
+
   const handleToggleEnabled = async (webhook: Webhook): Promise<void> => {
     try {
       await webhookApi.update(webhook.id, { enabled: webhook.enabled !== 1 });
@@ -368,6 +672,10 @@ export function WebhookManager(): ReactElement {
     }
   };
 
+  const updateFilter = (changes: Partial<FilterDraft>): void => {
+    setFormFilters((prev) => ({ ...prev, ...changes }));
+  };
+
   const toggleEvent = (event: WebhookEventType): void => {
     setFormEvents((prev) =>
       prev.includes(event) ? prev.filter((e) => e !== event) : [...prev, event],
@@ -382,6 +690,13 @@ export function WebhookManager(): ReactElement {
     }
   };
 
+  const eventLabel = (event: string): string =>
+    WEBHOOK_EVENT_LABELS[event as WebhookEventType];
+
+  const isRotating = (webhook: Webhook): boolean =>
+    webhook.previous_secret_expires_at !== null &&
+    new Date(webhook.previous_secret_expires_at).getTime() > Date.now();
+
   const formatDate = (dateString: string): string => {
     return new Date(dateString).toLocaleString("en-US", {
       year: "numeric",
@@ -490,6 +805,7 @@ export function WebhookManager(): ReactElement {
         <div className="webhook-list">
           {webhooks.map((webhook) => {
             const events = parseEvents(webhook.events);
+            const filterSummary = describeFilters(webhook.filters);
             return (
               <div
                 key={webhook.id}
@@ -506,7 +822,11 @@ export function WebhookManager(): ReactElement {
                         {webhook.secret && (
                           <span
                             className="webhook-secure-badge"
-                            title="HMAC signature enabled"
+                            title={
+                              isRotating(webhook)
+                                ? `HMAC signature enabled; the previous secret also signs until ${formatDate(webhook.previous_secret_expires_at ?? "")}`
+                                : "HMAC signature enabled"
+                            }
                           >
                             <ShieldIcon />
                           </span>
@@ -523,6 +843,11 @@ export function WebhookManager(): ReactElement {
                     {webhook.enabled === 1 ? "Enabled" : "Disabled"}
                   </span>
                 </div>
+                {filterSummary !== null && (
+                  <div className="webhook-filter-summary">
+                    Filters: {filterSummary}
+                  </div>
+                )}
                 <div className="webhook-events">
                   {events.map((event) => (
                     <span
@@ -557,6 +882,20 @@ export function WebhookManager(): ReactElement {
                     >
                       {webhook.enabled === 1 ? "Disable" : "Enable"}
                     </button>
+                    <button
+                      className="webhook-action-btn"
+                      onClick={() => openDeliveriesDialog(webhook)}
+                    >
+                      <HistoryIcon />
+                      Deliveries
+                    </button>
+                    <button
+                      className="webhook-action-btn"
+                      onClick={() => openRotateDialog(webhook)}
+                    >
+                      <KeyIcon />
+                      Rotate Secret
+                    </button>
                     <button
                       className="webhook-action-btn"
                       onClick={() => openEditDialog(webhook)}
@@ -636,9 +975,79 @@ export function WebhookManager(): ReactElement {
                   onChange={(e) => setFormSecret(e.target.value)}
                 />
                 <span className="webhook-form-hint">
-                  If set, requests will include an X-Webhook-Signature header
+                  If set, requests are signed in the X-Webhook-Signature header
+                  over the X-Webhook-Timestamp header and the body
                 </span>
               </div>
+              <div className="webhook-form-group">
+                <label htmlFor="webhook-format">Format</label>
+                <select
+                  id="webhook-format"
+                  className="webhook-form-input"
+                  value={formFormat}
+                  onChange={(e) => {
+                    setFormFormat(e.target.value as WebhookFormat);
+                    setPreview(null);
+                  }}
+                >
+                  {ALL_WEBHOOK_FORMATS.map((format) => (
+                    <option key={format} value={format}>
+                      {WEBHOOK_FORMAT_LABELS[format]}
+                    </option>
+                  ))}
+                </select>
+                <span className="webhook-form-hint">
+                  Slack, Discord and Teams post a message to the app&apos;s
+                  incoming webhook URL; Raw JSON sends the event payload as is
+                </span>
+              </div>
+              {formFormat === "custom" && (
+                <div className="webhook-form-group">
+                  <label htmlFor="webhook-template">Template</label>
+                  <textarea
+                    id="webhook-template"
+                    className="webhook-form-input webhook-template-input"
+                    placeholder={
+                      '{"text": "{{title}}: {{data.file_name}} in {{data.bucket_name}}"}'
+                    }
+                    rows={6}
+                    value={formTemplate}
+                    onChange={(e) => {
+                      setFormTemplate(e.target.value);
+                      setPreview(null);
+                    }}
+                  />
+                  <span className="webhook-form-hint">
+                    JSON body with {"{{event}}"}, {"{{title}}"},{" "}
+                    {"{{timestamp}}"} and {"{{data.<field>}}"} placeholders.
+                    Text values are escaped for use inside quotes; objects are
+                    inserted as JSON.
+                  </span>
+                </div>
+              )}
+              {editingWebhook !== null && (
+                <div className="webhook-form-group">
+                  <button
+                    type="button"
+                    className="webhook-btn webhook-btn-secondary"
+                    onClick={() => void handlePreview()}
+                    disabled={
+                      previewing ||
+                      (formFormat === "custom" && !formTemplate.trim())
+                    }
+                  >
+                    {previewing && <LoaderIcon className="spinning" />}
+                    Preview Test Payload
+                  </button>
+                  {preview && (
+                    <pre
+                      className={`webhook-format-preview ${preview.success ? "" : "error"}`}
+                    >
+                      {preview.text}
+                    </pre>
+                  )}
+                </div>
+              )}
               <div className="webhook-form-group">
                 <label>Events</label>
                 <div className="webhook-events-grid">
@@ -661,6 +1070,78 @@ export function WebhookManager(): ReactElement {
                   ))}
                 </div>
               </div>
+              <div className="webhook-form-group">
+                <label>Filters (optional)</label>
+                <span className="webhook-form-hint">
+                  Only send events matching every filter that is set. Separate
+                  entries with commas; * and ? work as wildcards in buckets,
+                  keys and emails.
+                </span>
+                <div className="webhook-filters-grid">
+                  <input
+                    aria-label="Buckets"
+                    type="text"
+                    className="webhook-form-input"
+                    placeholder="Buckets, e.g. media, logs-*"
+                    value={formFilters.buckets}
+                    onChange={(e) => updateFilter({ buckets: e.target.value })}
+                  />
+                  <input
+                    aria-label="Bucket tags"
+                    type="text"
+                    className="webhook-form-input"
+                    placeholder="Bucket tags, e.g. production"
+                    value={formFilters.bucketTags}
+                    onChange={(e) =>
+                      updateFilter({ bucketTags: e.target.value })
+                    }
+                  />
+                  <input
+                    aria-label="Object keys"
+                    type="text"
+                    className="webhook-form-input"
+                    placeholder="Object keys, e.g. incoming/*, *.jpg"
+                    value={formFilters.keyPatterns}
+                    onChange={(e) =>
+                      updateFilter({ keyPatterns: e.target.value })
+                    }
+                  />
+                  <input
+                    aria-label="User emails"
+                    type="text"
+                    className="webhook-form-input"
+                    placeholder="User emails, e.g. *@example.com"
+                    value={formFilters.userEmails}
+                    onChange={(e) =>
+                      updateFilter({ userEmails: e.target.value })
+                    }
+                  />
+                  <input
+                    aria-label="Minimum size in MB"
+                    type="number"
+                    min={0}
+                    step="any"
+                    className="webhook-form-input"
+                    placeholder="Min size (MB)"
+                    value={formFilters.minSizeMb}
+                    onChange={(e) =>
+                      updateFilter({ minSizeMb: e.target.value })
+                    }
+                  />
+                  <input
+                    aria-label="Maximum size in MB"
+                    type="number"
+                    min={0}
+                    step="any"
+                    className="webhook-form-input"
+                    placeholder="Max size (MB)"
+                    value={formFilters.maxSizeMb}
+                    onChange={(e) =>
+                      updateFilter({ maxSizeMb: e.target.value })
+                    }
+                  />
+                </div>
+              </div>
               <div className="webhook-checkbox-wrapper">
                 <input
                   id="webhook-enabled"
@@ -699,7 +1180,9 @@ export function WebhookManager(): ReactElement {
                   submitting ||
                   !formName.trim() ||
                   !formUrl.trim() ||
-                  formEvents.length === 0
+                  formEvents.length === 0 ||
+                  (formFormat === "custom" && !formTemplate.trim()) ||
+                  !isFilterDraftValid(formFilters)
                 }
               >
                 {submitting && <LoaderIcon className="spinning" />}
@@ -710,6 +1193,191 @@ export function WebhookManager(): ReactElement {
         </div>
       )}
 
+      {/* Deliveries Dialog */}
+      {deliveriesWebhook !== null && (
+        <div
+          className="webhook-dialog-overlay"
+          onClick={(e) => {
+            if (e.target === e.currentTarget) {
+              setDeliveriesWebhook(null);
+            }
+          }}
+        >
+          <div className="webhook-dialog webhook-deliveries-dialog">
+            <div className="webhook-dialog-header">
+              <h3>Deliveries</h3>
+              <p>
+                Recent delivery attempts for "{deliveriesWebhook.name}". Failed
+                deliveries are retried automatically with increasing delays.
+              </p>
+            </div>
+            <div className="webhook-dialog-body">
+              {deliveriesLoading && (
+                <div className="webhook-loading">
+                  <LoaderIcon />
+                </div>
+              )}
+              {!deliveriesLoading && deliveries.length === 0 && (
+                <p className="webhook-form-hint">No deliveries yet</p>
+              )}
+              {!deliveriesLoading &&
+                deliveries.map((delivery) => (
+                  <div key={delivery.id} className="webhook-delivery">
+                    <div className="webhook-delivery-header">
+                      <span
+                        className={`webhook-delivery-status ${delivery.status === "succeeded" ? "success" : "error"}`}
+                      >
+                        {delivery.status === "succeeded" ? (
+                          <CheckCircleIcon />
+                        ) : (
+                          <XCircleIcon />
+                        )}
+                        {delivery.status_code !== null
+                          ? `HTTP ${String(delivery.status_code)}`
+                          : "No response"}
+                      </span>
+                      <span className="webhook-event-tag">
+                        {eventLabel(delivery.event)}
+                      </span>
+                      <button
+                        className={`webhook-action-btn ${redelivering === delivery.delivery_id ? "testing" : ""}`}
+                        onClick={() => void handleRedeliver(delivery)}
+                        disabled={redelivering !== null}
+                      >
+                        {redelivering === delivery.delivery_id ? (
+                          <LoaderIcon />
+                        ) : (
+                          <RefreshIcon />
+                        )}
+                        Redeliver
+                      </button>
+                    </div>
+                    <div className="webhook-card-meta">
+                      {formatDate(delivery.created_at)} · Attempt{" "}
+                      {delivery.attempt} ({delivery.reason})
+                      {delivery.latency_ms !== null &&
+                        ` · ${String(delivery.latency_ms)} ms`}
+                      {delivery.next_retry_at !== null &&
+                        ` · Next retry ${formatDate(delivery.next_retry_at)}`}
+                    </div>
+                    {(delivery.error ?? delivery.response_body) !== null && (
+                      <pre className="webhook-delivery-response">
+                        {delivery.error ?? delivery.response_body}
+                      </pre>
+                    )}
+                  </div>
+                ))}
+            </div>
+            <div className="webhook-dialog-actions">
+              <button
+                className="webhook-btn webhook-btn-secondary"
+                onClick={() => void loadDeliveries(deliveriesWebhook)}
+                disabled={deliveriesLoading}
+              >
+                <RefreshIcon />
+                Refresh
+              </button>
+              <button
+                className="webhook-btn webhook-btn-secondary"
+                onClick={() => setDeliveriesWebhook(null)}
+              >
+                Close
+              </button>
+            </div>
+          </div>
+        </div>
+      )}
+
+      {/* Rotate Secret Dialog */}
+      {rotatingWebhook !== null && (
+        <div
+          className="webhook-dialog-overlay"
+          onClick={(e) => {
+            if (e.target === e.currentTarget) {
+              closeRotateDialog();
+            }
+          }}
+        >
+          <div className="webhook-dialog">
+            <div className="webhook-dialog-header">
+              <h3>Rotate Secret</h3>
+              <p>
+                Replace the signing secret of "{rotatingWebhook.name}" with a
+                new random secret.
+              </p>
+            </div>
+            <div className="webhook-dialog-body">
+              {rotatedSecret === null ? (
+                <div className="webhook-form-group">
+                  <label htmlFor="webhook-rotate-grace">
+                    Keep signing with the old secret for
+                  </label>
+                  <select
+                    id="webhook-rotate-grace"
+                    className="webhook-form-input"
+                    value={rotateGraceHours}
+                    onChange={(e) =>
+                      setRotateGraceHours(Number(e.target.value))
+                    }
+                  >
+                    {ROTATION_GRACE_OPTIONS.map((option) => (
+                      <option key={option.hours} value={option.hours}>
+                        {option.label}
+                      </option>
+                    ))}
+                  </select>
+                  <span className="webhook-form-hint">
+                    During the grace period requests carry a signature for both
+                    secrets, so the receiver can switch to the new one without
+                    rejecting requests.
+                  </span>
+                </div>
+              ) : (
+                <div className="webhook-form-group">
+                  <label>New secret</label>
+                  <code className="webhook-secret-value">
+                    {rotatedSecret.secret}
+                  </code>
+                  <span className="webhook-form-hint">
+                    {rotatedSecret.previousExpiresAt !== null
+                      ? `The old secret keeps signing until ${formatDate(rotatedSecret.previousExpiresAt)}.`
+                      : "The old secret no longer signs requests."}{" "}
+                    Update the receiver with the new secret.
+                  </span>
+                </div>
+              )}
+            </div>
+            <div className="webhook-dialog-actions">
+              {rotatedSecret === null ? (
+                <>
+                  <button
+                    className="webhook-btn webhook-btn-secondary"
+                    onClick={closeRotateDialog}
+                  >
+                    Cancel
+                  </button>
+                  <button
+                    className="webhook-btn webhook-btn-primary"
+                    onClick={() => void handleRotateSecret()}
+                    disabled={submitting}
+                  >
+                    {submitting && <LoaderIcon className="spinning" />}
+                    Rotate
+                  </button>
+                </>
+              ) : (
+                <button
+                  className="webhook-btn webhook-btn-primary"
+                  onClick={closeRotateDialog}
+                >
+                  Done
+                </button>
+              )}
+            </div>
+          </div>
+        </div>
+      )}
+
       {/* Delete Confirmation Dialog */}
       {deletingWebhook !== null && (
         <div
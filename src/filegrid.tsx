@@ -20,11 +20,14 @@ import { VideoPlayer } from "./components/filegrid/VideoPlayer";
 import { CreateFolderModal } from "./components/filegrid/CreateFolderModal";
 import { TransferModal } from "./components/filegrid/TransferModal";
 import { RenameModal } from "./components/filegrid/RenameModal";
+import { SignedLinkModal } from "./components/filegrid/SignedLinkModal";
+import { ShareModal } from "./components/filegrid/ShareModal";
 import { Breadcrumb } from "./components/filegrid/Breadcrumb";
 import { ContextMenu } from "./components/filegrid/ContextMenu";
 import { SortDropdown } from "./components/filegrid/SortDropdown";
 import { TransferDropdown } from "./components/filegrid/TransferDropdown";
 import { BucketDropdown } from "./components/filegrid/BucketDropdown";
+import { TrashPanel } from "./components/trash";
 import { useFileSort } from "./hooks/useFileSort";
 import { useModalState } from "./hooks/useModalState";
 import { useFileFilters } from "./hooks/useFileFilters";
@@ -51,6 +54,11 @@ interface FileGridProps {
   onPathChange?: (path: string) => void;
 }
 
+interface ShareTarget {
+  itemType: "file" | "folder";
+  itemKey: string;
+}
+
 interface DownloadProgress {
   progress: number;
   status: "preparing" | "downloading" | "complete" | "error";
@@ -124,6 +132,7 @@ export function FileGrid({
     localStorage.setItem("r2_manager_view_mode", viewMode);
   }, [viewMode]);
   const [shouldRefresh, setShouldRefresh] = useState(false);
+  const [showTrash, setShowTrash] = useState(false);
   const [transferState, setTransferState] = useState<{
     isDialogOpen: boolean;
     mode: "move" | "copy" | null;
@@ -138,6 +147,8 @@ export function FileGrid({
   );
   const [copyingUrl, setCopyingUrl] = useState<string | null>(null);
   const [copiedUrl, setCopiedUrl] = useState<string | null>(null);
+  const [signedLinkKey, setSignedLinkKey] = useState<string | null>(null);
+  const [shareTarget, setShareTarget] = useState<ShareTarget | null>(null);
 
   const gridRef = useRef<HTMLDivElement>(null);
   const transferButtonRef = useRef<HTMLButtonElement>(null);
@@ -1343,6 +1354,24 @@ export function FileGrid({
               onSortChange={updateSortState}
               getSortLabel={getSortLabel}
             />
+            <button
+              onClick={() => setShowTrash((prev) => !prev)}
+              className={`view-mode-toggle-button ${showTrash ? "active" : ""}`}
+              title={showTrash ? "Back to files" : "Show trash"}
+            >
+              <svg
+                xmlns="http://www.w3.org/2000/svg"
+                viewBox="0 0 24 24"
+                fill="none"
+                stroke="currentColor"
+                strokeWidth="2"
+                className="view-icon"
+              >
+                <polyline points="3 6 5 6 21 6" />
+                <path d="M19 6l-1 14a2 2 0 0 1-2 2H8a2 2 0 0 1-2-2L5 6" />
+              </svg>
+              <span>Trash</span>
+            </button>
             <button
               onClick={toggleViewMode}
               className="view-mode-toggle-button"
@@ -1394,12 +1423,20 @@ export function FileGrid({
       {infoMessage && <div className="info-message">{infoMessage}</div>}
 
       {/* Breadcrumb Navigation */}
-      <Breadcrumb
-        currentPath={currentPath}
-        onNavigate={handleBreadcrumbClick}
-      />
+      {!showTrash && (
+        <Breadcrumb
+          currentPath={currentPath}
+          onNavigate={handleBreadcrumbClick}
+        />
+      )}
 
-      {paginationState.isInitialLoad ? (
+      {showTrash ? (
+        <TrashPanel
+          bucketName={bucketName}
+          onClose={() => setShowTrash(false)}
+          onRestored={() => setShouldRefresh(true)}
+        />
+      ) : paginationState.isInitialLoad ? (
         <div className="loading-state">Loading...</div>
       ) : viewMode === ViewMode.Preview ? (
         <div
@@ -1803,7 +1840,8 @@ export function FileGrid({
         </div>
       )}
 
-      {filteredFiles.length === 0 &&
+      {!showTrash &&
+        filteredFiles.length === 0 &&
         filteredFolders.length === 0 &&
         !paginationState.isLoading && (
           <div className="empty-state">
@@ -1906,8 +1944,41 @@ export function FileGrid({
               }
             : undefined
         }
+        onCreateLink={
+          contextMenu?.itemType === "file"
+            ? () => {
+                if (contextMenu !== null) setSignedLinkKey(contextMenu.itemKey);
+                setContextMenu(null);
+              }
+            : undefined
+        }
+        onShare={() => {
+          if (contextMenu !== null)
+            setShareTarget({
+              itemType: contextMenu.itemType,
+              itemKey: contextMenu.itemKey,
+            });
+          setContextMenu(null);
+        }}
       />
 
+      {signedLinkKey !== null && (
+        <SignedLinkModal
+          bucketName={bucketName}
+          fileKey={signedLinkKey}
+          onClose={() => setSignedLinkKey(null)}
+        />
+      )}
+
+      {shareTarget !== null && (
+        <ShareModal
+          bucketName={bucketName}
+          itemKey={shareTarget.itemKey}
+          itemType={shareTarget.itemType}
+          onClose={() => setShareTarget(null)}
+        />
+      )}
+
       {renameState && (
         <RenameModal
           show={true}
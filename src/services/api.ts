@@ -74,6 +74,38 @@ interface UploadChunkResponse {
   success?: boolean;
 }
 
+interface MultipartCreateResponse {
+  uploadId: string;
+  key?: string;
+}
+
+export interface UploadSessionPart {
+  part_number: number;
+  etag: string;
+  md5: string | null;
+  uploaded_at: string;
+}
+
+export interface UploadSession {
+  upload_id: string;
+  bucket_name: string;
+  object_key: string;
+  file_fingerprint: string | null;
+  file_size: number | null;
+  part_size: number | null;
+  total_parts: number | null;
+  content_type: string | null;
+  status: "in_progress" | "completed" | "aborted";
+  user_email: string;
+  created_at: string;
+  updated_at: string;
+  parts: UploadSessionPart[];
+}
+
+interface UploadSessionsResponse {
+  sessions: UploadSession[];
+}
+
 interface ApiErrorResponse {
   error?: string;
 }
@@ -83,10 +115,182 @@ interface TransferResponse {
   copied?: number;
   moved?: number;
   failed?: number;
+  jobId?: string;
+  queued?: boolean;
 }
 
 interface SignedUrlResponse {
   url: string;
+  expiresAt?: string;
+  linkId?: string;
+}
+
+export interface SignedUrlOptions {
+  /** Link lifetime in seconds (default 24 hours, max 7 days) */
+  expiresIn?: number;
+  /** Only allow downloads from the IP address creating the link */
+  restrictToMyIp?: boolean;
+  maxDownloads?: number;
+  disposition?: "attachment" | "inline";
+}
+
+export type SignedLinkStatus = "active" | "expired" | "exhausted" | "revoked";
+
+export interface SignedLink {
+  link_id: string;
+  bucket_name: string;
+  object_key: string;
+  created_by: string;
+  created_at: string;
+  expires_at: string;
+  ip: string | null;
+  max_downloads: number | null;
+  disposition: "attachment" | "inline";
+  download_count: number;
+  last_download_at: string | null;
+  revoked_at: string | null;
+  status: SignedLinkStatus;
+}
+
+export type ShareType = "file" | "folder";
+
+export type ShareStatus = "active" | "expired" | "exhausted" | "revoked";
+
+export interface CreateShareOptions {
+  password?: string;
+  /** Share lifetime in seconds (default 7 days, max 30 days) */
+  expiresIn?: number;
+  maxDownloads?: number;
+  /** Let visitors download a shared folder as a single ZIP */
+  allowZip?: boolean;
+}
+
+export interface Share {
+  share_id: string;
+  bucket_name: string;
+  object_key: string;
+  share_type: ShareType;
+  created_by: string;
+  created_at: string;
+  expires_at: string;
+  has_password: boolean;
+  max_downloads: number | null;
+  download_count: number;
+  allow_zip: boolean;
+  last_accessed_at: string | null;
+  revoked_at: string | null;
+  status: ShareStatus;
+  url: string;
+}
+
+// Access Control Types
+export type AccessRole = "viewer" | "editor" | "admin";
+
+export interface AccessGrant {
+  id: number;
+  user_email: string;
+  role: AccessRole;
+  /** null grants the role on every bucket */
+  bucket_name: string | null;
+  prefix: string;
+  created_by: string;
+  created_at: string;
+}
+
+export interface CreateAccessGrantOptions {
+  userEmail: string;
+  role: AccessRole;
+  bucketName?: string | null;
+  prefix?: string;
+}
+
+export interface AccessGroupMapping {
+  id: number;
+  group_name: string;
+  role: AccessRole;
+  /** null maps the group to the role on every bucket */
+  bucket_name: string | null;
+  prefix: string;
+  created_by: string;
+  created_at: string;
+}
+
+export interface CreateAccessGroupMappingOptions {
+  groupName: string;
+  role: AccessRole;
+  bucketName?: string | null;
+  prefix?: string;
+}
+
+export interface AccessInfo {
+  email: string;
+  /** Identity provider groups read from the Access JWT */
+  groups: string[];
+  /** false until the first grant is created (or for ADMIN_EMAILS users) */
+  enforced: boolean;
+  grants: Pick<AccessGrant, "role" | "bucket_name" | "prefix">[];
+  role: AccessRole | null;
+  isAdmin: boolean;
+}
+
+export type ApiTokenPermission = "read" | "write" | "delete";
+
+export type ApiTokenStatus = "active" | "expired" | "revoked";
+
+export interface ApiToken {
+  token_id: string;
+  name: string;
+  /** First characters of the token, to help recognise it */
+  token_prefix: string;
+  created_by: string;
+  created_at: string;
+  expires_at: string;
+  /** null when the token can use every bucket */
+  buckets: string[] | null;
+  permissions: ApiTokenPermission[];
+  last_used_at: string | null;
+  revoked_at: string | null;
+  status: ApiTokenStatus;
+}
+
+export interface CreatedApiToken extends ApiToken {
+  /** The token itself - only returned when it is created */
+  token: string;
+  /** SigV4 credentials for the S3-compatible endpoint (/s3/) */
+  s3: {
+    accessKeyId: string;
+    secretAccessKey: string;
+  };
+}
+
+export interface CreateApiTokenOptions {
+  name: string;
+  permissions: ApiTokenPermission[];
+  buckets?: string[] | null;
+  /** Lifetime in seconds (default 90 days) */
+  expiresIn?: number;
+}
+
+export interface TrashItem {
+  item_id: string;
+  bucket_name: string;
+  original_key: string;
+  size: number;
+  /** "overwrite" when an upload replaced the object */
+  reason: "delete" | "overwrite";
+  deleted_by: string;
+  deleted_at: string;
+  expires_at: string;
+}
+
+export interface TrashSettings {
+  enabled: boolean;
+  retentionDays: number;
+}
+
+export interface TrashListing {
+  settings: TrashSettings;
+  items: TrashItem[];
 }
 
 // AI Search Types
@@ -221,6 +425,7 @@ export interface AISearchResponse {
 export type JobStatus =
   | "queued"
   | "running"
+  | "paused"
   | "completed"
   | "failed"
   | "cancelled";
@@ -250,6 +455,8 @@ export type JobOperationType =
   // AI Search
   | "ai_search_sync";
 
+export type JobControlAction = "cancel" | "pause" | "resume" | "retry-failed";
+
 export interface JobListItem {
   job_id: string;
   bucket_name: string;
@@ -295,6 +502,46 @@ export interface JobEventsResponse {
   events: JobEvent[];
 }
 
+export type JobItemStatus = "failed" | "succeeded";
+
+export type JobItemErrorCode =
+  | "not_found"
+  | "access_denied"
+  | "rate_limited"
+  | "timeout"
+  | "storage_error"
+  | "object_locked"
+  | "unknown";
+
+export interface JobItem {
+  job_id: string;
+  object_key: string;
+  status: JobItemStatus;
+  error_code: JobItemErrorCode | null;
+  error: string | null;
+  attempts: number;
+  updated_at: string;
+}
+
+export interface JobItemsFilter {
+  status?: JobItemStatus;
+  error_code?: JobItemErrorCode;
+  prefix?: string;
+  limit?: number;
+  offset?: number;
+}
+
+export interface JobItemsResponse {
+  job_id: string;
+  items: JobItem[];
+  total: number;
+  summary: {
+    failed: number;
+    succeeded: number;
+    by_error_code: Partial<Record<JobItemErrorCode, number>>;
+  };
+}
+
 // S3 Import Types (Super Slurper)
 export type S3ImportJobStatus =
   | "pending"
@@ -357,6 +604,36 @@ export interface S3ImportJobResponse {
   dashboardUrl?: string;
 }
 
+// Sippy Types (incremental migration)
+export type SippyProvider = "aws" | "gcs";
+
+export interface SippyConfig {
+  enabled: boolean;
+  source?: {
+    provider: SippyProvider;
+    bucket: string;
+    region?: string;
+  };
+}
+
+export interface EnableSippyRequest {
+  provider: SippyProvider;
+  sourceBucket: string;
+  region?: string;
+  accessKeyId?: string;
+  secretAccessKey?: string;
+  clientEmail?: string;
+  privateKey?: string;
+  r2AccessKeyId: string;
+  r2SecretAccessKey: string;
+}
+
+export interface SippyResponse {
+  success: boolean;
+  result?: SippyConfig;
+  error?: string;
+}
+
 interface FileTypeConfig {
   maxSize: number;
   description: string;
@@ -375,6 +652,8 @@ function delay(ms: number): Promise<void> {
   return new Promise((resolve) => setTimeout(resolve, ms));
 }
 
+const JOB_POLL_INTERVAL = 2000; // 2 seconds
+
 /**
  * Fetch with retry and exponential backoff for rate limits
  * Includes retry with exponential backoff for rate limit (429) errors
@@ -455,6 +734,7 @@ class APIService {
   private readonly DEFAULT_MAX_RETRIES = 3;
   private readonly DEFAULT_RETRY_DELAY = 1000;
   private readonly CHUNK_SIZE = 10 * 1024 * 1024; // 10MB chunks
+  private readonly MAX_PARTS = 10000; // R2 multipart part limit
 
   // File type configurations
   private readonly FILE_TYPES: Record<string, FileTypeConfig> = {
@@ -895,6 +1175,7 @@ class APIService {
 
     const uploadFileName = fileName || file.name;
     let lastError: Error | null;
+    let lockedError: Error | null = null;
 
     // Calculate MD5 for this chunk
     const chunkMD5 = await this.calculateMD5(chunk);
@@ -919,6 +1200,17 @@ class APIService {
           }),
         );
 
+        // The object is retention-locked, so retrying cannot succeed
+        if (response.status === 409) {
+          const errorData = (await response
+            .json()
+            .catch(() => ({}))) as ApiErrorResponse;
+          lockedError = new Error(
+            errorData.error ?? "Object is retention-locked",
+          );
+          throw lockedError;
+        }
+
         if (!response.ok) {
           throw new Error(`Upload failed with status: ${response.status}`);
         }
@@ -929,6 +1221,9 @@ class APIService {
           md5: chunkMD5,
         };
       } catch (error) {
+        if (lockedError !== null) {
+          throw lockedError;
+        }
         lastError =
           error instanceof Error
             ? error
@@ -948,6 +1243,189 @@ class APIService {
     throw new Error("Upload failed unexpectedly");
   }
 
+  private async getFileFingerprint(file: File): Promise<string> {
+    // Name, size and mtime identify the file; the MD5 of its first MiB guards
+    // against a different file that happens to share those attributes
+    const headMD5 = await this.calculateMD5(file.slice(0, 1024 * 1024));
+    return `${file.name}:${file.size}:${file.lastModified}:${headMD5}`;
+  }
+
+  async listUploadSessions(
+    bucketName: string,
+    filters: { key?: string; fingerprint?: string } = {},
+  ): Promise<UploadSession[]> {
+    const params = new URLSearchParams();
+    if (filters.key) params.set("key", filters.key);
+    if (filters.fingerprint) params.set("fingerprint", filters.fingerprint);
+    const query = params.toString();
+
+    const response = await fetchWithRetry(
+      `${WORKER_API}/api/files/${bucketName}/uploads${query ? `?${query}` : ""}`,
+      this.getFetchOptions({
+        headers: this.getHeaders(),
+      }),
+    );
+
+    await this.handleResponse(response);
+    const data = (await response.json()) as UploadSessionsResponse;
+    return data.sessions;
+  }
+
+  async abortUploadSession(
+    bucketName: string,
+    key: string,
+    uploadId: string,
+  ): Promise<void> {
+    await this.abortMultipartUpload(bucketName, key, uploadId);
+  }
+
+  private async createMultipartUpload(
+    bucketName: string,
+    key: string,
+    contentType: string,
+    session: {
+      fileFingerprint: string;
+      fileSize: number;
+      partSize: number;
+      totalParts: number;
+    },
+  ): Promise<string> {
+    const response = await fetchWithRetry(
+      `${WORKER_API}/api/files/${bucketName}/multipart/create`,
+      this.getFetchOptions({
+        method: "POST",
+        headers: {
+          ...this.getHeaders(),
+          "Content-Type": "application/json",
+        },
+        body: JSON.stringify({ key, contentType, ...session }),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to start multipart upload: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to start multipart upload");
+    }
+
+    const result = (await response.json()) as MultipartCreateResponse;
+    return result.uploadId;
+  }
+
+  private async uploadPartWithRetry(
+    bucketName: string,
+    key: string,
+    uploadId: string,
+    part: Blob,
+    partNumber: number,
+    options: UploadOptions = {},
+  ): Promise<{ etag: string; md5: string }> {
+    const {
+      maxRetries = this.DEFAULT_MAX_RETRIES,
+      retryDelay = this.DEFAULT_RETRY_DELAY,
+      onRetry,
+    } = options;
+
+    let lastError: Error | null;
+
+    // Calculate MD5 for this part
+    const partMD5 = await this.calculateMD5(part);
+
+    const params = new URLSearchParams({
+      key,
+      uploadId,
+      partNumber: partNumber.toString(),
+    });
+
+    for (let attempt = 0; attempt < maxRetries; attempt++) {
+      try {
+        const response = await fetch(
+          `${WORKER_API}/api/files/${bucketName}/multipart/part?${params.toString()}`,
+          this.getFetchOptions({
+            method: "POST",
+            headers: {
+              ...this.getHeaders(),
+              "Content-Type": "application/octet-stream",
+              "X-Chunk-MD5": partMD5,
+            },
+            body: part,
+          }),
+        );
+
+        if (!response.ok) {
+          throw new Error(`Part upload failed with status: ${response.status}`);
+        }
+
+        const result = (await response.json()) as UploadChunkResponse;
+        return {
+          etag: result.etag ?? "",
+          md5: partMD5,
+        };
+      } catch (error) {
+        lastError =
+          error instanceof Error
+            ? error
+            : new Error("Unknown error during upload");
+        if (attempt < maxRetries - 1) {
+          onRetry?.(attempt + 1, partNumber - 1, lastError);
+          await this.sleep(retryDelay * Math.pow(2, attempt));
+          continue;
+        }
+        throw new Error(
+          `Failed to upload part ${partNumber} after ${maxRetries} attempts: ${lastError.message}`,
+          { cause: error },
+        );
+      }
+    }
+
+    throw new Error("Upload failed unexpectedly");
+  }
+
+  private async completeMultipartUpload(
+    bucketName: string,
+    key: string,
+    uploadId: string,
+    parts: { partNumber: number; etag: string }[],
+  ): Promise<void> {
+    const response = await fetchWithRetry(
+      `${WORKER_API}/api/files/${bucketName}/multipart/complete`,
+      this.getFetchOptions({
+        method: "POST",
+        headers: {
+          ...this.getHeaders(),
+          "Content-Type": "application/json",
+        },
+        body: JSON.stringify({ key, uploadId, parts }),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to complete multipart upload: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to complete multipart upload");
+    }
+  }
+
+  private async abortMultipartUpload(
+    bucketName: string,
+    key: string,
+    uploadId: string,
+  ): Promise<void> {
+    await fetch(
+      `${WORKER_API}/api/files/${bucketName}/multipart/abort`,
+      this.getFetchOptions({
+        method: "POST",
+        headers: {
+          ...this.getHeaders(),
+          "Content-Type": "application/json",
+        },
+        body: JSON.stringify({ key, uploadId }),
+      }),
+    );
+  }
+
   async uploadFile(
     bucketName: string,
     file: File,
@@ -971,9 +1449,15 @@ class APIService {
       finalFileName: fileName,
     });
 
-    const totalChunks = Math.ceil(file.size / this.CHUNK_SIZE);
+    // R2 allows at most 10,000 parts, so grow the part size for huge files
+    const partSize = Math.max(
+      this.CHUNK_SIZE,
+      Math.ceil(file.size / this.MAX_PARTS),
+    );
+    const totalChunks = Math.ceil(file.size / partSize);
     const uploadedChunks = new Set<number>();
     const chunkResults: { etag: string; md5: string }[] = [];
+    let multipartUploadId: string | null = null;
 
     try {
       if (file.size <= this.CHUNK_SIZE) {
@@ -1005,20 +1489,72 @@ class APIService {
         return;
       }
 
-      // Upload all chunks
+      // Large files use a real R2 multipart upload: initiate, upload parts, complete.
+      // If an earlier attempt at this same file left a session behind, resume it.
+      const fileFingerprint = await this.getFileFingerprint(file);
+      const existingSession = await this.listUploadSessions(bucketName, {
+        key: fileName,
+        fingerprint: fileFingerprint,
+      })
+        .then((sessions) =>
+          sessions.find(
+            (session) =>
+              session.object_key === fileName &&
+              session.part_size === partSize,
+          ),
+        )
+        .catch((sessionError: unknown) => {
+          logger.warn("API", "Could not look up resumable uploads", {
+            fileName,
+            sessionError,
+          });
+          return undefined;
+        });
+
+      let uploadId: string;
+      if (existingSession !== undefined) {
+        uploadId = existingSession.upload_id;
+        for (const part of existingSession.parts) {
+          const chunkIndex = part.part_number - 1;
+          if (chunkIndex < 0 || chunkIndex >= totalChunks) continue;
+          chunkResults[chunkIndex] = { etag: part.etag, md5: part.md5 ?? "" };
+          uploadedChunks.add(chunkIndex);
+        }
+        logger.info("API", "Resuming multipart upload", {
+          fileName,
+          uploadId,
+          completedParts: uploadedChunks.size,
+          totalChunks,
+        });
+        onProgress?.(Math.min((uploadedChunks.size / totalChunks) * 95, 95));
+      } else {
+        uploadId = await this.createMultipartUpload(
+          bucketName,
+          fileName,
+          file.type,
+          {
+            fileFingerprint,
+            fileSize: file.size,
+            partSize,
+            totalParts: totalChunks,
+          },
+        );
+      }
+      multipartUploadId = uploadId;
+
       for (let chunkIndex = 0; chunkIndex < totalChunks; chunkIndex++) {
         if (uploadedChunks.has(chunkIndex)) continue;
 
-        const start = chunkIndex * this.CHUNK_SIZE;
-        const end = Math.min(start + this.CHUNK_SIZE, file.size);
+        const start = chunkIndex * partSize;
+        const end = Math.min(start + partSize, file.size);
         const chunk = file.slice(start, end);
 
-        const result = await this.uploadChunkWithRetry(
+        const result = await this.uploadPartWithRetry(
           bucketName,
-          file,
+          fileName,
+          uploadId,
           chunk,
-          chunkIndex,
-          totalChunks,
+          chunkIndex + 1,
           {
             maxRetries: maxRetries ?? this.DEFAULT_MAX_RETRIES,
             retryDelay: retryDelay ?? this.DEFAULT_RETRY_DELAY,
@@ -1028,7 +1564,6 @@ class APIService {
                 /* no-op */
               }),
           },
-          fileName,
         );
 
         chunkResults[chunkIndex] = result;
@@ -1041,21 +1576,33 @@ class APIService {
       onProgress?.(96);
       onVerification?.("verifying");
 
-      // For multipart uploads, verify that we have all chunks
+      // For multipart uploads, verify that we have all parts before completing
       if (chunkResults.length === totalChunks) {
         // R2 uses compound ETags for multipart (hash of MD5s + part count)
-        // We verify that all chunks uploaded successfully
+        // We verify that all parts uploaded successfully; parts resumed from
+        // a session recorded without checksums only carry their ETag
         const allChunksVerified = chunkResults.every(
-          (result) => result.etag && result.md5,
+          (result) => result.etag !== "",
         );
         if (!allChunksVerified) {
           throw new Error("Verification failed: Some chunks missing ETag");
         }
-        onVerification?.("verified");
       } else {
         throw new Error("Verification failed: Chunk count mismatch");
       }
 
+      await this.completeMultipartUpload(
+        bucketName,
+        fileName,
+        uploadId,
+        chunkResults.map((result, index) => ({
+          partNumber: index + 1,
+          etag: result.etag,
+        })),
+      );
+      multipartUploadId = null;
+      onVerification?.("verified");
+
       onProgress?.(100);
 
       // Invalidate file list and bucket list cache after successful upload
@@ -1075,16 +1622,36 @@ class APIService {
         error,
       });
 
-      if (
-        error instanceof Error &&
-        error.message.includes("Verification failed")
-      ) {
+      const isVerificationError =
+        error instanceof Error && error.message.includes("Verification failed");
+      if (isVerificationError) {
         onVerification?.("failed");
       }
 
+      // Keep the session for resumption unless the uploaded data is suspect;
+      // re-selecting the same file will skip the parts that already succeeded
+      if (multipartUploadId !== null && isVerificationError) {
+        await this.abortMultipartUpload(
+          bucketName,
+          fileName,
+          multipartUploadId,
+        ).catch((abortError: unknown) => {
+          logger.warn("API", "Failed to abort multipart upload", {
+            fileName,
+            abortError,
+          });
+        });
+      }
+
+      const resumeHint =
+        multipartUploadId !== null && !isVerificationError
+          ? " Select the same file again to resume."
+          : "";
+
       throw new Error(
         `Upload failed: ${failedChunks.length} chunks remaining. ` +
-          `Last error: ${error instanceof Error ? error.message : "Unknown error"}`,
+          `Last error: ${error instanceof Error ? error.message : "Unknown error"}.` +
+          resumeHint,
         { cause: error },
       );
     }
@@ -1164,6 +1731,7 @@ class APIService {
 
   async createBucket(
     name: string,
+    options: CreateBucketOptions = {},
   ): Promise<{ name: string; creation_date: string }> {
     const response = await fetchWithRetry(
       `${WORKER_API}/api/buckets`,
@@ -1173,7 +1741,7 @@ class APIService {
           ...this.getHeaders(),
           "Content-Type": "application/json",
         },
-        body: JSON.stringify({ name }),
+        body: JSON.stringify({ name, ...options }),
       }),
     );
 
@@ -1217,18 +1785,33 @@ class APIService {
       success?: boolean;
       error?: string;
       errors?: { message: string }[];
+      jobId?: string;
+      queued?: boolean;
     };
     try {
       data = (await response.json()) as {
         success?: boolean;
         error?: string;
         errors?: { message: string }[];
+        jobId?: string;
+        queued?: boolean;
       };
     } catch {
       // If response can't be parsed as JSON, return a generic error
       data = { error: `Failed to delete bucket (HTTP ${response.status})` };
     }
 
+    // Force deletes of non-empty buckets run as background jobs
+    if (data.queued && data.jobId) {
+      try {
+        await this.waitForJob(data.jobId);
+      } catch (err) {
+        data = {
+          error: err instanceof Error ? err.message : "Failed to delete bucket",
+        };
+      }
+    }
+
     // Invalidate both bucket list and file list for this bucket
     if (data.success) {
       invalidateBucketListCache();
@@ -1293,8 +1876,15 @@ class APIService {
     const result = (await response.json()) as {
       success: boolean;
       newName?: string;
+      jobId?: string;
+      queued?: boolean;
     };
 
+    if (result.queued && result.jobId) {
+      await this.waitForJob(result.jobId);
+      result.newName = newName.trim();
+    }
+
     // Invalidate both old and new bucket caches
     if (result.success) {
       invalidateBucketListCache();
@@ -1367,7 +1957,10 @@ class APIService {
     );
 
     if (!response.ok) {
-      throw new Error(`Delete failed: ${response.status}`);
+      const errorData = (await response.json().catch(() => ({
+        error: `Delete failed: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? `Delete failed: ${response.status}`);
     }
 
     // Invalidate file list and bucket list cache
@@ -1742,9 +2335,24 @@ class APIService {
     return { valid: true };
   }
 
-  async getSignedUrl(bucketName: string, fileName: string): Promise<string> {
+  async getSignedUrl(
+    bucketName: string,
+    fileName: string,
+    options: SignedUrlOptions = {},
+  ): Promise<string> {
+    const query = new URLSearchParams();
+    if (options.expiresIn !== undefined) {
+      query.set("expires_in", String(options.expiresIn));
+    }
+    if (options.restrictToMyIp) query.set("ip", "auto");
+    if (options.maxDownloads !== undefined) {
+      query.set("max_downloads", String(options.maxDownloads));
+    }
+    if (options.disposition) query.set("disposition", options.disposition);
+    const queryString = query.toString();
+
     const response = await fetch(
-      `${WORKER_API}/api/files/${bucketName}/signed-url/${encodeURIComponent(fileName)}`,
+      `${WORKER_API}/api/files/${bucketName}/signed-url/${encodeURIComponent(fileName)}${queryString ? `?${queryString}` : ""}`,
       this.getFetchOptions({
         method: "GET",
         headers: this.getHeaders(),
@@ -1762,8 +2370,479 @@ class APIService {
     return data.url;
   }
 
-  // Folder Management Methods
-
+  async listSignedLinks(includeInactive = false): Promise<SignedLink[]> {
+    const response = await fetch(
+      `${WORKER_API}/api/signed-links${includeInactive ? "?status=all" : ""}`,
+      this.getFetchOptions({
+        headers: this.getHeaders(),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to list signed links: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to list signed links");
+    }
+
+    const data = (await response.json()) as {
+      result: { links: SignedLink[] };
+      success: boolean;
+    };
+    return data.result.links;
+  }
+
+  async revokeSignedLink(linkId: string): Promise<void> {
+    const response = await fetch(
+      `${WORKER_API}/api/signed-links/${encodeURIComponent(linkId)}`,
+      this.getFetchOptions({
+        method: "DELETE",
+        headers: this.getHeaders(),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to revoke signed link: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to revoke signed link");
+    }
+  }
+
+  // Share Link Methods
+
+  async createShare(
+    bucketName: string,
+    key: string,
+    type: ShareType,
+    options: CreateShareOptions = {},
+  ): Promise<Share> {
+    const response = await fetch(
+      `${WORKER_API}/api/shares`,
+      this.getFetchOptions({
+        method: "POST",
+        headers: {
+          ...this.getHeaders(),
+          "Content-Type": "application/json",
+        },
+        body: JSON.stringify({ bucket: bucketName, key, type, ...options }),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to create share: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to create share");
+    }
+
+    const data = (await response.json()) as {
+      result: { share: Share };
+      success: boolean;
+    };
+    return data.result.share;
+  }
+
+  async listShares(includeInactive = false): Promise<Share[]> {
+    const response = await fetch(
+      `${WORKER_API}/api/shares${includeInactive ? "?status=all" : ""}`,
+      this.getFetchOptions({
+        headers: this.getHeaders(),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to list shares: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to list shares");
+    }
+
+    const data = (await response.json()) as {
+      result: { shares: Share[] };
+      success: boolean;
+    };
+    return data.result.shares;
+  }
+
+  async revokeShare(shareId: string): Promise<void> {
+    const response = await fetch(
+      `${WORKER_API}/api/shares/${encodeURIComponent(shareId)}`,
+      this.getFetchOptions({
+        method: "DELETE",
+        headers: this.getHeaders(),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to revoke share: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to revoke share");
+    }
+  }
+
+  // Access Control Methods
+
+  async getMyAccess(): Promise<AccessInfo> {
+    const response = await fetch(
+      `${WORKER_API}/api/access/me`,
+      this.getFetchOptions({
+        headers: this.getHeaders(),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to load access: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to load access");
+    }
+
+    const data = (await response.json()) as {
+      result: AccessInfo;
+      success: boolean;
+    };
+    return data.result;
+  }
+
+  async listAccessGrants(): Promise<AccessGrant[]> {
+    const response = await fetch(
+      `${WORKER_API}/api/access/grants`,
+      this.getFetchOptions({
+        headers: this.getHeaders(),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to list access grants: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to list access grants");
+    }
+
+    const data = (await response.json()) as {
+      result: { grants: AccessGrant[] };
+      success: boolean;
+    };
+    return data.result.grants;
+  }
+
+  async createAccessGrant(
+    options: CreateAccessGrantOptions,
+  ): Promise<AccessGrant> {
+    const response = await fetch(
+      `${WORKER_API}/api/access/grants`,
+      this.getFetchOptions({
+        method: "POST",
+        headers: {
+          ...this.getHeaders(),
+          "Content-Type": "application/json",
+        },
+        body: JSON.stringify(options),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to save access grant: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to save access grant");
+    }
+
+    const data = (await response.json()) as {
+      result: AccessGrant;
+      success: boolean;
+    };
+    return data.result;
+  }
+
+  async deleteAccessGrant(grantId: number): Promise<void> {
+    const response = await fetch(
+      `${WORKER_API}/api/access/grants/${grantId}`,
+      this.getFetchOptions({
+        method: "DELETE",
+        headers: this.getHeaders(),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to remove access grant: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to remove access grant");
+    }
+  }
+
+  async listAccessGroupMappings(): Promise<AccessGroupMapping[]> {
+    const response = await fetch(
+      `${WORKER_API}/api/access/groups`,
+      this.getFetchOptions({
+        headers: this.getHeaders(),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to list group mappings: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to list group mappings");
+    }
+
+    const data = (await response.json()) as {
+      result: { mappings: AccessGroupMapping[] };
+      success: boolean;
+    };
+    return data.result.mappings;
+  }
+
+  async createAccessGroupMapping(
+    options: CreateAccessGroupMappingOptions,
+  ): Promise<AccessGroupMapping> {
+    const response = await fetch(
+      `${WORKER_API}/api/access/groups`,
+      this.getFetchOptions({
+        method: "POST",
+        headers: {
+          ...this.getHeaders(),
+          "Content-Type": "application/json",
+        },
+        body: JSON.stringify(options),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to save group mapping: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to save group mapping");
+    }
+
+    const data = (await response.json()) as {
+      result: AccessGroupMapping;
+      success: boolean;
+    };
+    return data.result;
+  }
+
+  async deleteAccessGroupMapping(mappingId: number): Promise<void> {
+    const response = await fetch(
+      `${WORKER_API}/api/access/groups/${mappingId}`,
+      this.getFetchOptions({
+        method: "DELETE",
+        headers: this.getHeaders(),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to remove group mapping: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to remove group mapping");
+    }
+  }
+
+  async listApiTokens(includeInactive = false): Promise<ApiToken[]> {
+    const response = await fetch(
+      `${WORKER_API}/api/tokens${includeInactive ? "?status=all" : ""}`,
+      this.getFetchOptions({
+        headers: this.getHeaders(),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to list API tokens: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to list API tokens");
+    }
+
+    const data = (await response.json()) as {
+      result: { tokens: ApiToken[] };
+      success: boolean;
+    };
+    return data.result.tokens;
+  }
+
+  async createApiToken(
+    options: CreateApiTokenOptions,
+  ): Promise<CreatedApiToken> {
+    const response = await fetch(
+      `${WORKER_API}/api/tokens`,
+      this.getFetchOptions({
+        method: "POST",
+        headers: {
+          ...this.getHeaders(),
+          "Content-Type": "application/json",
+        },
+        body: JSON.stringify(options),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to create API token: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to create API token");
+    }
+
+    const data = (await response.json()) as {
+      result: CreatedApiToken;
+      success: boolean;
+    };
+    return data.result;
+  }
+
+  async revokeApiToken(tokenId: string): Promise<void> {
+    const response = await fetch(
+      `${WORKER_API}/api/tokens/${encodeURIComponent(tokenId)}`,
+      this.getFetchOptions({
+        method: "DELETE",
+        headers: this.getHeaders(),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to revoke API token: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to revoke API token");
+    }
+  }
+
+  // Trash Methods
+
+  async getTrash(bucketName: string): Promise<TrashListing> {
+    const response = await fetch(
+      `${WORKER_API}/api/trash/${encodeURIComponent(bucketName)}`,
+      this.getFetchOptions({
+        headers: this.getHeaders(),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to load trash: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to load trash");
+    }
+
+    const data = (await response.json()) as {
+      result: TrashListing;
+      success: boolean;
+    };
+    return data.result;
+  }
+
+  async updateTrashSettings(
+    bucketName: string,
+    settings: TrashSettings,
+  ): Promise<TrashSettings> {
+    const response = await fetch(
+      `${WORKER_API}/api/trash/${encodeURIComponent(bucketName)}/settings`,
+      this.getFetchOptions({
+        method: "PUT",
+        headers: {
+          ...this.getHeaders(),
+          "Content-Type": "application/json",
+        },
+        body: JSON.stringify(settings),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to update trash settings: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to update trash settings");
+    }
+
+    const data = (await response.json()) as {
+      result: TrashSettings;
+      success: boolean;
+    };
+    return data.result;
+  }
+
+  /**
+   * Restore a trashed object to its original key. Returns "exists" without
+   * restoring when another object is now at that key and overwrite is not set.
+   */
+  async restoreTrashItem(
+    bucketName: string,
+    itemId: string,
+    overwrite = false,
+  ): Promise<"restored" | "exists"> {
+    const response = await fetch(
+      `${WORKER_API}/api/trash/${encodeURIComponent(bucketName)}/${encodeURIComponent(itemId)}/restore${overwrite ? "?overwrite=true" : ""}`,
+      this.getFetchOptions({
+        method: "POST",
+        headers: this.getHeaders(),
+      }),
+    );
+
+    if (response.status === 409) {
+      return "exists";
+    }
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to restore item: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to restore item");
+    }
+
+    invalidateFileListCache(bucketName);
+    return "restored";
+  }
+
+  async purgeTrashItem(bucketName: string, itemId: string): Promise<void> {
+    const response = await fetch(
+      `${WORKER_API}/api/trash/${encodeURIComponent(bucketName)}/${encodeURIComponent(itemId)}`,
+      this.getFetchOptions({
+        method: "DELETE",
+        headers: this.getHeaders(),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to purge item: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to purge item");
+    }
+  }
+
+  /**
+   * Empty a bucket's trash. Returns whether items remain (large trashes are
+   * emptied a page at a time).
+   */
+  async emptyTrash(
+    bucketName: string,
+  ): Promise<{ purged: number; remaining: boolean }> {
+    const response = await fetch(
+      `${WORKER_API}/api/trash/${encodeURIComponent(bucketName)}`,
+      this.getFetchOptions({
+        method: "DELETE",
+        headers: this.getHeaders(),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to empty trash: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to empty trash");
+    }
+
+    const data = (await response.json()) as {
+      result: { purged: number; remaining: boolean };
+      success: boolean;
+    };
+    return data.result;
+  }
+
+  // Folder Management Methods
+
   validateFolderName(name: string): ValidationResult {
     if (!name || name.trim().length === 0) {
       return { valid: false, error: "Folder name cannot be empty" };
@@ -1847,7 +2926,9 @@ class APIService {
     }
 
     const data = (await response.json()) as TransferResponse;
-    if (onProgress && data.copied !== undefined) {
+    if (data.queued && data.jobId) {
+      await this.waitForJob(data.jobId, onProgress);
+    } else if (onProgress && data.copied !== undefined) {
       onProgress(data.copied, data.copied + (data.failed ?? 0));
     }
 
@@ -1885,7 +2966,9 @@ class APIService {
     }
 
     const data = (await response.json()) as TransferResponse;
-    if (onProgress && data.copied !== undefined) {
+    if (data.queued && data.jobId) {
+      await this.waitForJob(data.jobId, onProgress);
+    } else if (onProgress && data.copied !== undefined) {
       onProgress(data.copied, data.copied + (data.failed ?? 0));
     }
 
@@ -1924,7 +3007,9 @@ class APIService {
     }
 
     const data = (await response.json()) as TransferResponse;
-    if (onProgress && data.moved !== undefined) {
+    if (data.queued && data.jobId) {
+      await this.waitForJob(data.jobId, onProgress);
+    } else if (onProgress && data.moved !== undefined) {
       onProgress(data.moved, data.moved + (data.failed ?? 0));
     }
 
@@ -1965,8 +3050,14 @@ class APIService {
       success: boolean;
       fileCount?: number;
       message?: string;
+      jobId?: string;
+      queued?: boolean;
     };
 
+    if (result.queued && result.jobId) {
+      await this.waitForJob(result.jobId);
+    }
+
     // Invalidate file list and bucket list cache
     if (result.success) {
       invalidateFileListCache(bucketName);
@@ -2285,6 +3376,76 @@ class APIService {
     return data.result;
   }
 
+  async getJobItems(
+    jobId: string,
+    filter: JobItemsFilter = {},
+  ): Promise<JobItemsResponse> {
+    const response = await fetch(
+      `${WORKER_API}/api/jobs/${encodeURIComponent(jobId)}/items?${this.buildJobItemsQuery(filter).toString()}`,
+      this.getFetchOptions({
+        headers: this.getHeaders(),
+      }),
+    );
+
+    if (!response.ok) {
+      const error = (await response
+        .json()
+        .catch(() => ({ error: "Unknown error" }))) as { error?: string };
+      throw new Error(
+        error.error ?? `Failed to get job items: ${response.status}`,
+      );
+    }
+
+    const data = (await response.json()) as {
+      result: JobItemsResponse;
+      success: boolean;
+    };
+    return data.result;
+  }
+
+  /**
+   * Download a job's item manifest as CSV or NDJSON
+   */
+  async exportJobItems(
+    jobId: string,
+    format: "csv" | "ndjson",
+    filter: JobItemsFilter = {},
+  ): Promise<void> {
+    const query = this.buildJobItemsQuery(filter);
+    query.set("format", format);
+
+    const response = await fetch(
+      `${WORKER_API}/api/jobs/${encodeURIComponent(jobId)}/items?${query.toString()}`,
+      this.getFetchOptions({
+        headers: this.getHeaders(),
+      }),
+    );
+
+    if (!response.ok) {
+      throw new Error(`Failed to export job items: ${response.status}`);
+    }
+
+    const blob = await response.blob();
+    const url = window.URL.createObjectURL(blob);
+    const link = document.createElement("a");
+    link.href = url;
+    link.download = `job-${jobId}-items.${format}`;
+    document.body.appendChild(link);
+    link.click();
+    document.body.removeChild(link);
+    window.URL.revokeObjectURL(url);
+  }
+
+  private buildJobItemsQuery(filter: JobItemsFilter): URLSearchParams {
+    const query = new URLSearchParams();
+    if (filter.status) query.set("status", filter.status);
+    if (filter.error_code) query.set("error_code", filter.error_code);
+    if (filter.prefix) query.set("prefix", filter.prefix);
+    if (filter.limit !== undefined) query.set("limit", String(filter.limit));
+    if (filter.offset !== undefined) query.set("offset", String(filter.offset));
+    return query;
+  }
+
   async getJobStatus(jobId: string): Promise<JobListItem> {
     const response = await fetch(
       `${WORKER_API}/api/jobs/${encodeURIComponent(jobId)}`,
@@ -2309,6 +3470,77 @@ class APIService {
     return data.result;
   }
 
+  async cancelJob(jobId: string): Promise<JobListItem> {
+    return this.controlJob(jobId, "cancel");
+  }
+
+  async pauseJob(jobId: string): Promise<JobListItem> {
+    return this.controlJob(jobId, "pause");
+  }
+
+  async resumeJob(jobId: string): Promise<JobListItem> {
+    return this.controlJob(jobId, "resume");
+  }
+
+  async retryFailedJob(jobId: string): Promise<JobListItem> {
+    return this.controlJob(jobId, "retry-failed");
+  }
+
+  private async controlJob(
+    jobId: string,
+    action: JobControlAction,
+  ): Promise<JobListItem> {
+    const response = await fetch(
+      `${WORKER_API}/api/jobs/${encodeURIComponent(jobId)}/${action}`,
+      this.getFetchOptions({
+        method: "POST",
+        headers: this.getHeaders(),
+      }),
+    );
+
+    if (!response.ok) {
+      const error = (await response
+        .json()
+        .catch(() => ({ error: "Unknown error" }))) as { error?: string };
+      throw new Error(
+        error.error ?? `Failed to ${action} job: ${response.status}`,
+      );
+    }
+
+    const data = (await response.json()) as {
+      result: JobListItem;
+      success: boolean;
+    };
+    return data.result;
+  }
+
+  /**
+   * Poll a background job until it finishes, reporting its progress. Paused
+   * jobs keep being polled until they are resumed or cancelled.
+   */
+  async waitForJob(
+    jobId: string,
+    onProgress?: (completed: number, total: number) => void,
+  ): Promise<JobListItem> {
+    for (;;) {
+      const job = await this.getJobStatus(jobId);
+      if (onProgress && job.total_items !== null) {
+        onProgress(job.processed_items ?? 0, job.total_items);
+      }
+
+      if (job.status === "completed") {
+        return job;
+      }
+      if (job.status === "failed" || job.status === "cancelled") {
+        throw new Error(
+          `Job ${job.status} after ${job.processed_items ?? 0} item(s) - see Job History for details`,
+        );
+      }
+
+      await delay(JOB_POLL_INTERVAL);
+    }
+  }
+
   // S3 Import Methods (Super Slurper)
   async createS3ImportJob(
     data: CreateS3ImportJobRequest,
@@ -2367,32 +3599,74 @@ class APIService {
       }),
     );
 
-    const result = (await response.json()) as S3ImportJobResponse;
-    return result;
+    const result = (await response.json()) as S3ImportJobResponse;
+    return result;
+  }
+
+  async abortS3ImportJob(
+    jobId: string,
+  ): Promise<{ success: boolean; error?: string }> {
+    const response = await fetchWithRetry(
+      `${WORKER_API}/api/s3-import/jobs/${encodeURIComponent(jobId)}/abort`,
+      this.getFetchOptions({
+        method: "POST",
+        headers: this.getHeaders(),
+      }),
+    );
+
+    const result = (await response.json()) as {
+      success: boolean;
+      error?: string;
+    };
+
+    // Invalidate cache on successful abort
+    if (result.success) {
+      invalidateS3ImportJobsCache();
+    }
+
+    return result;
+  }
+
+  async getSippyStatus(bucketName: string): Promise<SippyResponse> {
+    const response = await fetchWithRetry(
+      `${WORKER_API}/api/s3-import/sippy/${encodeURIComponent(bucketName)}`,
+      this.getFetchOptions({
+        headers: this.getHeaders(),
+      }),
+    );
+
+    return (await response.json()) as SippyResponse;
+  }
+
+  async enableSippy(
+    bucketName: string,
+    request: EnableSippyRequest,
+  ): Promise<SippyResponse> {
+    const response = await fetch(
+      `${WORKER_API}/api/s3-import/sippy/${encodeURIComponent(bucketName)}`,
+      this.getFetchOptions({
+        method: "PUT",
+        headers: {
+          ...this.getHeaders(),
+          "Content-Type": "application/json",
+        },
+        body: JSON.stringify(request),
+      }),
+    );
+
+    return (await response.json()) as SippyResponse;
   }
 
-  async abortS3ImportJob(
-    jobId: string,
-  ): Promise<{ success: boolean; error?: string }> {
-    const response = await fetchWithRetry(
-      `${WORKER_API}/api/s3-import/jobs/${encodeURIComponent(jobId)}/abort`,
+  async disableSippy(bucketName: string): Promise<SippyResponse> {
+    const response = await fetch(
+      `${WORKER_API}/api/s3-import/sippy/${encodeURIComponent(bucketName)}`,
       this.getFetchOptions({
-        method: "POST",
+        method: "DELETE",
         headers: this.getHeaders(),
       }),
     );
 
-    const result = (await response.json()) as {
-      success: boolean;
-      error?: string;
-    };
-
-    // Invalidate cache on successful abort
-    if (result.success) {
-      invalidateS3ImportJobsCache();
-    }
-
-    return result;
+    return (await response.json()) as SippyResponse;
   }
 
   getS3ImportDashboardUrl(): string {
@@ -2945,6 +4219,318 @@ class APIService {
 
     return { success: true, result: { enabled } };
   }
+
+  // ============================================
+  // Bucket Settings Methods
+  // ============================================
+
+  /**
+   * Get the CORS rules of a bucket (empty when no policy is set)
+   */
+  async getBucketCors(bucketName: string): Promise<BucketCorsRule[]> {
+    const response = await fetch(
+      `${WORKER_API}/api/bucket-settings/${encodeURIComponent(bucketName)}/cors`,
+      this.getFetchOptions({
+        headers: this.getHeaders(),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to load CORS rules: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to load CORS rules");
+    }
+
+    const data = (await response.json()) as {
+      result: { rules: BucketCorsRule[] };
+    };
+    return data.result.rules;
+  }
+
+  /**
+   * Replace the CORS rules of a bucket. An empty list removes the policy.
+   */
+  async setBucketCors(
+    bucketName: string,
+    rules: BucketCorsRule[],
+  ): Promise<void> {
+    const response = await fetchWithRetry(
+      `${WORKER_API}/api/bucket-settings/${encodeURIComponent(bucketName)}/cors`,
+      this.getFetchOptions({
+        method: "PUT",
+        headers: {
+          ...this.getHeaders(),
+          "Content-Type": "application/json",
+        },
+        body: JSON.stringify({ rules }),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to update CORS rules: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to update CORS rules");
+    }
+  }
+
+  /**
+   * Get the bucket lock rules of a bucket
+   */
+  async getBucketLock(bucketName: string): Promise<BucketLockRule[]> {
+    const response = await fetch(
+      `${WORKER_API}/api/bucket-settings/${encodeURIComponent(bucketName)}/lock`,
+      this.getFetchOptions({
+        headers: this.getHeaders(),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to load bucket lock rules: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to load bucket lock rules");
+    }
+
+    const data = (await response.json()) as {
+      result: { rules: BucketLockRule[] };
+    };
+    return data.result.rules;
+  }
+
+  /**
+   * Replace the bucket lock rules of a bucket. An empty list removes them.
+   */
+  async setBucketLock(
+    bucketName: string,
+    rules: BucketLockRule[],
+  ): Promise<void> {
+    const response = await fetchWithRetry(
+      `${WORKER_API}/api/bucket-settings/${encodeURIComponent(bucketName)}/lock`,
+      this.getFetchOptions({
+        method: "PUT",
+        headers: {
+          ...this.getHeaders(),
+          "Content-Type": "application/json",
+        },
+        body: JSON.stringify({ rules }),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to update bucket lock rules: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to update bucket lock rules");
+    }
+  }
+
+  /**
+   * Get the r2.dev public access and custom domains of a bucket
+   */
+  async getBucketDomains(bucketName: string): Promise<BucketDomains> {
+    const response = await fetch(
+      `${WORKER_API}/api/bucket-settings/${encodeURIComponent(bucketName)}/domains`,
+      this.getFetchOptions({
+        headers: this.getHeaders(),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to load domains: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to load domains");
+    }
+
+    const data = (await response.json()) as { result: BucketDomains };
+    return data.result;
+  }
+
+  /**
+   * Enable or disable public access through the bucket's r2.dev URL
+   */
+  async setManagedDomain(
+    bucketName: string,
+    enabled: boolean,
+  ): Promise<ManagedDomain> {
+    const response = await fetchWithRetry(
+      `${WORKER_API}/api/bucket-settings/${encodeURIComponent(bucketName)}/domains/managed`,
+      this.getFetchOptions({
+        method: "PUT",
+        headers: {
+          ...this.getHeaders(),
+          "Content-Type": "application/json",
+        },
+        body: JSON.stringify({ enabled }),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to update r2.dev access: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to update r2.dev access");
+    }
+
+    const data = (await response.json()) as { result: ManagedDomain };
+    return data.result;
+  }
+
+  /**
+   * Attach a custom domain. The domain's zone must be in the same account.
+   */
+  async attachCustomDomain(
+    bucketName: string,
+    domain: string,
+    minTLS?: string,
+  ): Promise<void> {
+    const response = await fetch(
+      `${WORKER_API}/api/bucket-settings/${encodeURIComponent(bucketName)}/domains/custom`,
+      this.getFetchOptions({
+        method: "POST",
+        headers: {
+          ...this.getHeaders(),
+          "Content-Type": "application/json",
+        },
+        body: JSON.stringify({
+          domain,
+          ...(minTLS !== undefined && { minTLS }),
+        }),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to attach domain: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to attach domain");
+    }
+  }
+
+  /**
+   * Enable or disable a custom domain without detaching it
+   */
+  async setCustomDomainEnabled(
+    bucketName: string,
+    domain: string,
+    enabled: boolean,
+  ): Promise<void> {
+    const response = await fetchWithRetry(
+      `${WORKER_API}/api/bucket-settings/${encodeURIComponent(bucketName)}/domains/custom/${encodeURIComponent(domain)}`,
+      this.getFetchOptions({
+        method: "PUT",
+        headers: {
+          ...this.getHeaders(),
+          "Content-Type": "application/json",
+        },
+        body: JSON.stringify({ enabled }),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to update domain: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to update domain");
+    }
+  }
+
+  async removeCustomDomain(bucketName: string, domain: string): Promise<void> {
+    const response = await fetch(
+      `${WORKER_API}/api/bucket-settings/${encodeURIComponent(bucketName)}/domains/custom/${encodeURIComponent(domain)}`,
+      this.getFetchOptions({
+        method: "DELETE",
+        headers: this.getHeaders(),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to remove domain: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to remove domain");
+    }
+  }
+
+  // ============================================
+  // Event Notification Methods
+  // ============================================
+
+  /**
+   * Get the event notification rules of a bucket and the available queues
+   */
+  async getEventNotifications(
+    bucketName: string,
+  ): Promise<EventNotificationsResponse> {
+    const response = await fetch(
+      `${WORKER_API}/api/event-notifications/${encodeURIComponent(bucketName)}`,
+      this.getFetchOptions({
+        headers: this.getHeaders(),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to load event notifications: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to load event notifications");
+    }
+
+    const data = (await response.json()) as {
+      result: EventNotificationsResponse;
+    };
+    return data.result;
+  }
+
+  /**
+   * Create an event notification rule. Fails if it overlaps an existing rule.
+   */
+  async createEventNotification(
+    bucketName: string,
+    options: CreateEventNotificationOptions,
+  ): Promise<void> {
+    const response = await fetch(
+      `${WORKER_API}/api/event-notifications/${encodeURIComponent(bucketName)}`,
+      this.getFetchOptions({
+        method: "POST",
+        headers: {
+          ...this.getHeaders(),
+          "Content-Type": "application/json",
+        },
+        body: JSON.stringify(options),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to create rule: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to create rule");
+    }
+  }
+
+  async deleteEventNotification(
+    bucketName: string,
+    queueId: string,
+    ruleId: string,
+  ): Promise<void> {
+    const response = await fetch(
+      `${WORKER_API}/api/event-notifications/${encodeURIComponent(bucketName)}/${encodeURIComponent(queueId)}/${encodeURIComponent(ruleId)}`,
+      this.getFetchOptions({
+        method: "DELETE",
+        headers: this.getHeaders(),
+      }),
+    );
+
+    if (!response.ok) {
+      const errorData = (await response.json().catch(() => ({
+        error: `Failed to delete rule: ${response.status}`,
+      }))) as ApiErrorResponse;
+      throw new Error(errorData.error ?? "Failed to delete rule");
+    }
+  }
 }
 
 export const api = new APIService();
@@ -2954,6 +4540,17 @@ export const api = new APIService();
 // ============================================
 import type { LifecycleRule, LifecycleRulesResponse } from "../types/lifecycle";
 import type { LocalUploadsResponse } from "../types/local-uploads";
+import type {
+  BucketCorsRule,
+  BucketDomains,
+  BucketLockRule,
+  CreateBucketOptions,
+  ManagedDomain,
+} from "../types/bucket-settings";
+import type {
+  CreateEventNotificationOptions,
+  EventNotificationsResponse,
+} from "../types/event-notifications";
 
 // ============================================
 // Tag Types (imported from types/tags.ts)
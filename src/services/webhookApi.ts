@@ -6,9 +6,14 @@
 
 import type {
   Webhook,
+  WebhookDeliveriesResponse,
+  WebhookDelivery,
+  WebhookDeliveryResponse,
   WebhookInput,
   WebhooksResponse,
   WebhookResponse,
+  WebhookRotateSecretResponse,
+  WebhookTestInput,
   WebhookTestResult,
 } from "../types/webhook";
 
@@ -101,11 +106,51 @@ export const webhookApi = {
   },
 
   /**
-   * Test a webhook by sending a test payload
+   * Test a webhook by sending a test payload, or preview the rendered
+   * payload without sending it with dryRun
    */
-  async test(id: string): Promise<WebhookTestResult> {
+  async test(
+    id: string,
+    input: WebhookTestInput = {},
+  ): Promise<WebhookTestResult> {
     return apiFetch<WebhookTestResult>(`/webhooks/${id}/test`, {
       method: "POST",
+      body: JSON.stringify(input),
     });
   },
+
+  /**
+   * Replace a webhook's secret with a generated one. The old secret keeps
+   * signing requests for gracePeriodHours.
+   */
+  async rotateSecret(
+    id: string,
+    gracePeriodHours: number,
+  ): Promise<WebhookRotateSecretResponse> {
+    return apiFetch<WebhookRotateSecretResponse>(
+      `/webhooks/${id}/rotate-secret`,
+      { method: "POST", body: JSON.stringify({ gracePeriodHours }) },
+    );
+  },
+
+  /**
+   * List the most recent delivery attempts of a webhook
+   */
+  async listDeliveries(id: string): Promise<WebhookDelivery[]> {
+    const data = await apiFetch<WebhookDeliveriesResponse>(
+      `/webhooks/${id}/deliveries`,
+    );
+    return data.deliveries;
+  },
+
+  /**
+   * Send a delivery again with its original payload
+   */
+  async redeliver(id: string, deliveryId: string): Promise<WebhookDelivery> {
+    const data = await apiFetch<WebhookDeliveryResponse>(
+      `/webhooks/${id}/deliveries/${deliveryId}/redeliver`,
+      { method: "POST" },
+    );
+    return data.delivery;
+  },
 };
@@ -0,0 +1,122 @@
+/**
+ * Bucket Settings Types for R2 Manager Frontend
+ *
+ * Types for bucket CORS policies, bucket lock rules, custom domains, managed
+ * r2.dev public access, and the options available when creating a bucket.
+ *
+ * Based on Cloudflare REST API format:
+ * https://developers.cloudflare.com/api/resources/r2/subresources/buckets/
+ */
+
+/**
+ * Default storage class of new objects in a bucket
+ */
+export type R2StorageClass = "Standard" | "InfrequentAccess";
+
+/**
+ * Location hint for where a new bucket's data is stored
+ */
+export type R2LocationHint = "apac" | "eeur" | "enam" | "weur" | "wnam" | "oc";
+
+export const LOCATION_HINT_LABELS: Record<R2LocationHint, string> = {
+  apac: "Asia-Pacific",
+  eeur: "Eastern Europe",
+  enam: "Eastern North America",
+  weur: "Western Europe",
+  wnam: "Western North America",
+  oc: "Oceania",
+};
+
+/**
+ * Options for creating a bucket. Cloudflare picks the location when no hint
+ * is given.
+ */
+export interface CreateBucketOptions {
+  locationHint?: R2LocationHint;
+  storageClass?: R2StorageClass;
+}
+
+export type CorsMethod = "GET" | "PUT" | "POST" | "DELETE" | "HEAD";
+
+export const CORS_METHODS: CorsMethod[] = [
+  "GET",
+  "PUT",
+  "POST",
+  "DELETE",
+  "HEAD",
+];
+
+/**
+ * Single bucket CORS rule (matches Cloudflare REST API format)
+ */
+export interface BucketCorsRule {
+  /** Optional rule identifier */
+  id?: string;
+  allowed: {
+    /** Origins allowed to make requests, e.g. https://example.com or * */
+    origins: string[];
+    methods: CorsMethod[];
+    /** Request headers allowed in preflight requests */
+    headers?: string[];
+  };
+  /** Response headers exposed to the browser */
+  exposeHeaders?: string[];
+  /** How long browsers may cache a preflight response */
+  maxAgeSeconds?: number;
+}
+
+/**
+ * How long a bucket lock rule keeps objects: for a number of seconds after
+ * upload, until a date, or indefinitely
+ */
+export type BucketLockCondition =
+  | { type: "Age"; maxAgeSeconds: number }
+  | { type: "Date"; date: string }
+  | { type: "Indefinite" };
+
+/**
+ * Bucket lock (WORM retention) rule (matches Cloudflare REST API format)
+ */
+export interface BucketLockRule {
+  id: string;
+  enabled: boolean;
+  /** Only objects whose keys start with this prefix; all objects if unset */
+  prefix?: string;
+  condition: BucketLockCondition;
+}
+
+/**
+ * Custom domain attached to a bucket
+ */
+export interface CustomDomain {
+  domain: string;
+  enabled: boolean;
+  status: {
+    /** Domain ownership verification: pending, active, deactivated, ... */
+    ownership: string;
+    /** Certificate status: initializing, pending, active, ... */
+    ssl: string;
+  };
+  minTLS?: string;
+  zoneId?: string;
+  zoneName?: string;
+}
+
+/**
+ * Managed r2.dev public access of a bucket
+ */
+export interface ManagedDomain {
+  bucketId: string;
+  /** The bucket's public r2.dev hostname */
+  domain: string;
+  enabled: boolean;
+}
+
+/**
+ * Public access of a bucket. managed is null for buckets without an r2.dev
+ * domain (e.g. buckets in a jurisdiction).
+ */
+export interface BucketDomains {
+  managed: ManagedDomain | null;
+  custom: CustomDomain[];
+}
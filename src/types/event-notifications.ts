@@ -0,0 +1,68 @@
+/**
+ * Event Notification Types for R2 Manager Frontend
+ *
+ * Types for R2 event notification rules, which send object events of a
+ * bucket to a Cloudflare Queue.
+ *
+ * Based on Cloudflare REST API format:
+ * https://developers.cloudflare.com/r2/buckets/event-notifications/
+ */
+
+/**
+ * Object actions that can trigger an event notification
+ */
+export type EventNotificationAction =
+  | "PutObject"
+  | "CopyObject"
+  | "DeleteObject"
+  | "CompleteMultipartUpload"
+  | "LifecycleDeletion";
+
+export const EVENT_ACTION_LABELS: Record<EventNotificationAction, string> = {
+  PutObject: "Upload",
+  CopyObject: "Copy",
+  CompleteMultipartUpload: "Multipart upload complete",
+  DeleteObject: "Delete",
+  LifecycleDeletion: "Lifecycle deletion",
+};
+
+/**
+ * Event notification rule and the queue it sends to
+ */
+export interface EventNotificationRule {
+  ruleId: string;
+  queueId: string;
+  queueName: string;
+  actions: EventNotificationAction[];
+  /** Only keys starting with this prefix */
+  prefix?: string;
+  /** Only keys ending with this suffix */
+  suffix?: string;
+  description?: string;
+  createdAt?: string;
+}
+
+/**
+ * Queue that rules can send to
+ */
+export interface EventNotificationQueue {
+  queueId: string;
+  queueName: string;
+}
+
+export interface EventNotificationsResponse {
+  rules: EventNotificationRule[];
+  /** Empty if the API token cannot list queues */
+  queues: EventNotificationQueue[];
+}
+
+/**
+ * Options for creating an event notification rule
+ */
+export interface CreateEventNotificationOptions {
+  queueId: string;
+  actions: EventNotificationAction[];
+  prefix?: string;
+  suffix?: string;
+  description?: string;
+}
@@ -17,10 +17,36 @@ export type WebhookEventType =
   | "bucket_rename"
   | "folder_create"
   | "folder_delete"
+  | "folder_move"
+  | "folder_copy"
+  | "folder_rename"
   | "bulk_download_complete"
   | "s3_import_complete"
+  | "lifecycle_updated"
+  | "bucket_tags_changed"
+  | "ai_search_sync_complete"
   | "job_failed"
-  | "job_completed";
+  | "job_completed"
+  | "job_cancelled"
+  | "rate_limit_exceeded";
+
+/**
+ * Body format sent to a webhook
+ */
+export type WebhookFormat = "raw" | "slack" | "discord" | "teams" | "custom";
+
+/**
+ * Conditions a webhook applies on top of its events. Bucket, key and user
+ * entries are globs (* and ?); sizes are in bytes.
+ */
+export interface WebhookFilters {
+  buckets?: string[];
+  bucketTags?: string[];
+  keyPatterns?: string[];
+  minSize?: number;
+  maxSize?: number;
+  userEmails?: string[];
+}
 
 /**
  * Webhook from API
@@ -30,8 +56,13 @@ export interface Webhook {
   name: string;
   url: string;
   secret: string | null;
+  previous_secret: string | null;
+  previous_secret_expires_at: string | null;
   events: string; // JSON array of WebhookEventType
   enabled: number;
+  format: WebhookFormat;
+  template: string | null;
+  filters: string | null; // JSON WebhookFilters
   created_at: string;
   updated_at: string;
 }
@@ -45,6 +76,19 @@ export interface WebhookInput {
   secret?: string | null;
   events: WebhookEventType[];
   enabled?: boolean;
+  format?: WebhookFormat;
+  template?: string | null;
+  filters?: WebhookFilters | null;
+}
+
+/**
+ * Webhook test request: format and template override the saved ones, and
+ * dryRun renders the body without sending it
+ */
+export interface WebhookTestInput {
+  format?: WebhookFormat;
+  template?: string | null;
+  dryRun?: boolean;
 }
 
 /**
@@ -55,6 +99,27 @@ export interface WebhookTestResult {
   message: string;
   statusCode?: number;
   error?: string;
+  body?: string;
+}
+
+/**
+ * A single delivery attempt of a webhook event
+ */
+export interface WebhookDelivery {
+  id: string;
+  delivery_id: string; // Shared by every attempt of the same event
+  webhook_id: string;
+  event: string;
+  payload: string; // JSON body that was sent
+  attempt: number;
+  reason: "event" | "retry" | "redeliver";
+  status: "succeeded" | "failed";
+  status_code: number | null;
+  latency_ms: number | null;
+  response_body: string | null;
+  error: string | null;
+  next_retry_at: string | null;
+  created_at: string;
 }
 
 /**
@@ -68,6 +133,41 @@ export interface WebhookResponse {
   webhook: Webhook;
 }
 
+export interface WebhookRotateSecretResponse {
+  webhook: Webhook;
+  secret: string;
+}
+
+export interface WebhookDeliveriesResponse {
+  deliveries: WebhookDelivery[];
+}
+
+export interface WebhookDeliveryResponse {
+  delivery: WebhookDelivery;
+}
+
+/**
+ * Format labels for UI display
+ */
+export const WEBHOOK_FORMAT_LABELS: Record<WebhookFormat, string> = {
+  raw: "Raw JSON",
+  slack: "Slack",
+  discord: "Discord",
+  teams: "Microsoft Teams",
+  custom: "Custom template",
+};
+
+/**
+ * All available webhook formats
+ */
+export const ALL_WEBHOOK_FORMATS: WebhookFormat[] = [
+  "raw",
+  "slack",
+  "discord",
+  "teams",
+  "custom",
+];
+
 /**
  * Event type labels for UI display
  */
@@ -83,10 +183,18 @@ export const WEBHOOK_EVENT_LABELS: Record<WebhookEventType, string> = {
   bucket_rename: "Bucket Renamed",
   folder_create: "Folder Created",
   folder_delete: "Folder Deleted",
+  folder_move: "Folder Moved",
+  folder_copy: "Folder Copied",
+  folder_rename: "Folder Renamed",
   bulk_download_complete: "Bulk Download Complete",
   s3_import_complete: "S3 Import Complete",
+  lifecycle_updated: "Lifecycle Rules Updated",
+  bucket_tags_changed: "Bucket Tags Changed",
+  ai_search_sync_complete: "AI Search Sync Complete",
   job_failed: "Job Failed",
   job_completed: "Job Completed",
+  job_cancelled: "Job Cancelled",
+  rate_limit_exceeded: "Rate Limit Exceeded",
 };
 
 /**
@@ -104,11 +212,22 @@ export const WEBHOOK_EVENT_DESCRIPTIONS: Record<WebhookEventType, string> = {
   bucket_rename: "Triggered when a bucket is renamed",
   folder_create: "Triggered when a new folder is created",
   folder_delete: "Triggered when a folder is deleted",
+  folder_move: "Triggered when a folder move completes",
+  folder_copy: "Triggered when a folder copy completes",
+  folder_rename: "Triggered when a folder rename completes",
   bulk_download_complete:
     "Triggered when a multi-file/bucket download completes",
   s3_import_complete: "Triggered when an S3 import job completes",
+  lifecycle_updated: "Triggered when a bucket's lifecycle rules are saved",
+  bucket_tags_changed:
+    "Triggered when tags are added to or removed from a bucket",
+  ai_search_sync_complete:
+    "Triggered when an AI Search indexing job finishes without errors",
   job_failed: "Triggered when any tracked operation fails",
   job_completed: "Triggered when a bulk operation completes successfully",
+  job_cancelled: "Triggered when a bulk operation is cancelled",
+  rate_limit_exceeded:
+    "Triggered when a user goes over an API rate limit (at most once per limit period)",
 };
 
 /**
@@ -126,8 +245,16 @@ export const ALL_WEBHOOK_EVENTS: WebhookEventType[] = [
   "bucket_rename",
   "folder_create",
   "folder_delete",
+  "folder_move",
+  "folder_copy",
+  "folder_rename",
   "bulk_download_complete",
   "s3_import_complete",
+  "lifecycle_updated",
+  "bucket_tags_changed",
+  "ai_search_sync_complete",
   "job_failed",
   "job_completed",
+  "job_cancelled",
+  "rate_limit_exceeded",
 ];
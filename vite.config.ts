@@ -22,7 +22,6 @@ export default defineConfig({
             return "vendor-icons";
           }
           if (
-            id.includes("node_modules/jszip/") ||
             id.includes("node_modules/jose/") ||
             id.includes("node_modules/spark-md5/") ||
             id.includes("node_modules/react-dropzone/")
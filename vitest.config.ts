@@ -0,0 +1,10 @@
+import { defineConfig } from "vitest/config";
+
+// Unit tests for the worker's pure helpers. They run on Node.js, which has
+// the Web Crypto and Streams APIs the helpers use.
+export default defineConfig({
+  test: {
+    include: ["worker/**/*.test.ts"],
+    environment: "node",
+  },
+});
@@ -1,21 +1,31 @@
-import type { Env } from "./types";
+import type { AccessIdentity, Env, JobQueueMessage } from "./types";
 import { logInfo, logWarning, logError } from "./utils/error-logger";
-import { CF_API } from "./types";
 import { validateAccessJWT } from "./utils/auth";
-import { validateSignature } from "./utils/signing";
+import { getBearerApiToken, validateApiToken } from "./utils/api-tokens";
+import { resolveDisposition, validateSignature } from "./utils/signing";
+import {
+  checkSignedLink,
+  consumeSignedLinkDownload,
+} from "./utils/signed-links";
+import { checkShare, consumeShareDownload } from "./utils/shares";
 import {
   getCorsHeaders,
   handleCorsPreflightRequest,
   isLocalDevelopment,
 } from "./utils/cors";
-import { getCloudflareHeaders } from "./utils/helpers";
+import { getObjectStorage } from "./utils/storage";
+import { handleJobQueue } from "./utils/job-runner";
+import { runScheduledMaintenance } from "./utils/maintenance";
 import {
   handleSiteWebmanifest,
   handleStaticAsset,
   serveFrontendAssets,
 } from "./utils/assets";
 import { checkRateLimit, createRateLimitResponse } from "./utils/ratelimit";
-import { SUPPORT_EMAIL } from "./utils/error-response";
+import { authorizeRequest } from "./utils/rbac";
+import { createErrorResponse, SUPPORT_EMAIL } from "./utils/error-response";
+import { handleAccessRoutes } from "./routes/access";
+import { handleApiTokenRoutes } from "./routes/api-tokens";
 import { handleBucketRoutes } from "./routes/buckets";
 import { handleFileRoutes } from "./routes/files";
 import { handleFolderRoutes } from "./routes/folders";
@@ -24,14 +34,45 @@ import { handleAISearchRoutes } from "./routes/ai-search";
 import { handleJobRoutes } from "./routes/jobs";
 import { handleAuditRoutes } from "./routes/audit";
 import { handleS3ImportRoutes } from "./routes/s3-import";
+import { handleS3Request } from "./routes/s3";
 import { handleMetricsRoutes } from "./routes/metrics";
 import { handleHealthRoutes } from "./routes/health";
 import { handleWebhookRoutes } from "./routes/webhooks";
+import { handleSignedLinkRoutes } from "./routes/signed-links";
+import { handlePublicShareRoutes, handleShareRoutes } from "./routes/shares";
 import { handleTagRoutes } from "./routes/tags";
+import { handleTrashRoutes } from "./routes/trash";
 import { handleMigrationRoutes } from "./routes/migrations";
 import { handleColorRoutes } from "./routes/colors";
 import { handleLifecycleRoutes } from "./routes/lifecycle";
 import { handleLocalUploadsRoutes } from "./routes/local-uploads";
+import { handleBucketSettingsRoutes } from "./routes/bucket-settings";
+import { handleEventNotificationRoutes } from "./routes/event-notifications";
+
+const SIGNED_LINK_REFUSALS = {
+  revoked: "Link has been revoked",
+  expired: "Link has expired",
+  exhausted: "Link has reached its download limit",
+};
+
+/**
+ * Response for a signed link (or share) that was revoked, expired or used up
+ */
+function createSignedLinkRefusal(
+  refusal: "revoked" | "expired" | "exhausted",
+  corsHeaders: HeadersInit,
+): Response {
+  return new Response(
+    JSON.stringify({
+      error: SIGNED_LINK_REFUSALS[refusal],
+      support: SUPPORT_EMAIL,
+    }),
+    {
+      status: refusal === "revoked" ? 403 : 410,
+      headers: { "Content-Type": "application/json", ...corsHeaders },
+    },
+  );
+}
 
 async function handleApiRequest(request: Request, env: Env): Promise<Response> {
   const url = new URL(request.url);
@@ -58,6 +99,12 @@ async function handleApiRequest(request: Request, env: Env): Promise<Response> {
     return handleSiteWebmanifest();
   }
 
+  // S3-compatible API - SigV4-signed with API token credentials, before
+  // static assets since object keys can look like asset paths
+  if (url.pathname === "/s3" || url.pathname.startsWith("/s3/")) {
+    return handleS3Request(request, env, url, isLocalDev);
+  }
+
   // Serve other static assets
   const staticAssetResponse = await handleStaticAsset(
     request,
@@ -79,7 +126,9 @@ async function handleApiRequest(request: Request, env: Env): Promise<Response> {
       operation: "download_check",
     });
 
-    if (await validateSignature(request, env)) {
+    const validation = await validateSignature(request, env);
+    if (validation.valid) {
+      const { params } = validation;
       const pathParts = url.pathname.split("/");
       const bucketName = pathParts[3];
       // Get everything after /download/ to support nested folders
@@ -96,35 +145,36 @@ async function handleApiRequest(request: Request, env: Env): Promise<Response> {
       });
 
       try {
-        const fetchUrl =
-          CF_API +
-          "/accounts/" +
-          env.ACCOUNT_ID +
-          "/r2/buckets/" +
-          bucketName +
-          "/objects/" +
-          encodeURIComponent(fileName);
-        logInfo(`Fetching from R2: ${fetchUrl}`, {
-          module: "worker",
-          operation: "download_check",
-          bucketName: bucketName ?? "unknown",
-        });
+        // Links with an ID are tracked in D1 for counters and revocation
+        if (params.linkId && env.METADATA) {
+          const refusal = await checkSignedLink(env.METADATA, params.linkId);
+          if (refusal !== null) {
+            return createSignedLinkRefusal(refusal, corsHeaders);
+          }
+        }
+        if (params.shareId && env.METADATA) {
+          const refusal = await checkShare(env.METADATA, params.shareId);
+          if (refusal !== null) {
+            return createSignedLinkRefusal(refusal, corsHeaders);
+          }
+        }
 
-        const response = await fetch(fetchUrl, {
-          headers: getCloudflareHeaders(env),
-        });
+        const storage = getObjectStorage(env, bucketName ?? "");
+        logInfo(
+          `Fetching from R2 (${storage.isNative ? "binding" : "REST API"})`,
+          {
+            module: "worker",
+            operation: "download_check",
+            bucketName: bucketName ?? "unknown",
+          },
+        );
 
-        logInfo(`R2 response status: ${response.status}`, {
-          module: "worker",
-          operation: "download_check",
-          bucketName: bucketName ?? "unknown",
-        });
+        const object = await storage.get(fileName);
 
-        if (!response.ok) {
-          const errorText = await response.text();
+        if (object === null) {
           void logError(
             env,
-            new Error(`R2 error: ${errorText}`),
+            new Error(`Object not found: ${fileName}`),
             {
               module: "worker",
               operation: "download_check",
@@ -132,15 +182,42 @@ async function handleApiRequest(request: Request, env: Env): Promise<Response> {
             },
             isLocalDev,
           );
-          throw new Error("Download failed: " + response.status);
+          throw new Error("Download failed: 404");
+        }
+
+        if (params.linkId && env.METADATA) {
+          const refusal = await consumeSignedLinkDownload(
+            env.METADATA,
+            params.linkId,
+          );
+          if (refusal !== null) {
+            await object.body.cancel();
+            return createSignedLinkRefusal(refusal, corsHeaders);
+          }
+        }
+        if (params.shareId && env.METADATA) {
+          const refusal = await consumeShareDownload(
+            env.METADATA,
+            params.shareId,
+          );
+          if (refusal !== null) {
+            await object.body.cancel();
+            return createSignedLinkRefusal(refusal, corsHeaders);
+          }
         }
 
-        return new Response(response.body, {
+        const downloadName = fileName.split("/").pop() ?? fileName;
+        const contentType =
+          object.httpMetadata.contentType ?? "application/octet-stream";
+        const disposition = resolveDisposition(params.disposition, contentType);
+        return new Response(object.body, {
           headers: {
-            "Content-Type":
-              response.headers.get("Content-Type") ??
-              "application/octet-stream",
-            "Content-Disposition": 'attachment; filename="' + fileName + '"',
+            "Content-Type": contentType,
+            "Content-Disposition": `${disposition}; filename="${downloadName.replace(/"/g, "")}"; filename*=UTF-8''${encodeURIComponent(downloadName)}`,
+            // Uploaded content is served from the app's origin, so it must
+            // not be sniffed into another type or run script if opened
+            "X-Content-Type-Options": "nosniff",
+            "Content-Security-Policy": "sandbox",
             "Cache-Control": "no-store, max-age=0, must-revalidate",
             Pragma: "no-cache",
             Expires: "0",
@@ -171,7 +248,7 @@ async function handleApiRequest(request: Request, env: Env): Promise<Response> {
     } else {
       void logError(
         env,
-        new Error("Invalid signature"),
+        new Error(validation.reason),
         {
           module: "worker",
           operation: "download_check",
@@ -181,11 +258,11 @@ async function handleApiRequest(request: Request, env: Env): Promise<Response> {
       );
       return new Response(
         JSON.stringify({
-          error: "Invalid signature",
+          error: validation.reason,
           support: SUPPORT_EMAIL,
         }),
         {
-          status: 403,
+          status: validation.status,
           headers: {
             "Content-Type": "application/json",
             ...corsHeaders,
@@ -195,23 +272,45 @@ async function handleApiRequest(request: Request, env: Env): Promise<Response> {
     }
   }
 
+  // Public share landing pages (password-protected shares unlock themselves)
+  if (url.pathname.startsWith("/share/")) {
+    const shareResponse = await handlePublicShareRoutes(
+      request,
+      env,
+      url,
+      isLocalDev,
+    );
+    if (shareResponse) {
+      return shareResponse;
+    }
+  }
+
   // Skip auth for localhost development
-  let userEmail: string | null;
+  let userEmail: string;
+  let identity: AccessIdentity;
   if (isLocalhost) {
     logInfo("Localhost detected, skipping JWT validation", {
       module: "worker",
       operation: "auth",
     });
     userEmail = "dev@localhost";
+    identity = { email: userEmail, groups: [] };
   } else {
-    // Require auth for production API endpoints
-    userEmail = await validateAccessJWT(request, env);
-    if (!userEmail) {
+    // Require auth for production API endpoints. API tokens act as the user
+    // who created them, so they share that user's audit trail and rate limits.
+    const apiToken = getBearerApiToken(request);
+    const validated =
+      apiToken !== null
+        ? await validateApiToken(apiToken, env)
+        : await validateAccessJWT(request, env);
+    if (!validated) {
       return new Response("Unauthorized", {
         status: 401,
         headers: corsHeaders,
       });
     }
+    identity = validated;
+    userEmail = identity.email;
   }
 
   // Check rate limits for API requests (skip for localhost and if rate limiters not configured)
@@ -258,7 +357,59 @@ async function handleApiRequest(request: Request, env: Env): Promise<Response> {
     }
   }
 
+  // Enforce role grants before routing (skip for localhost)
+  if (!isLocalhost && url.pathname.startsWith("/api/")) {
+    const authorization = await authorizeRequest(request, env, url, identity);
+    if (!authorization.allowed) {
+      logWarning("Request blocked by access control", {
+        module: "access",
+        operation: "authorize",
+        userId: userEmail,
+        metadata: {
+          method: request.method,
+          pathname: url.pathname,
+          reason: authorization.reason,
+        },
+      });
+      return createErrorResponse(
+        authorization.status === 400
+          ? authorization.reason
+          : `Forbidden: ${authorization.reason}`,
+        corsHeaders,
+        authorization.status,
+      );
+    }
+  }
+
   // Route API requests
+  if (url.pathname.startsWith("/api/tokens")) {
+    const tokenResponse = await handleApiTokenRoutes(
+      request,
+      env,
+      url,
+      corsHeaders,
+      isLocalDev,
+      userEmail,
+    );
+    if (tokenResponse) {
+      return tokenResponse;
+    }
+  }
+
+  if (url.pathname.startsWith("/api/access")) {
+    const accessResponse = await handleAccessRoutes(
+      request,
+      env,
+      url,
+      corsHeaders,
+      isLocalDev,
+      userEmail,
+    );
+    if (accessResponse) {
+      return accessResponse;
+    }
+  }
+
   if (url.pathname.startsWith("/api/metrics")) {
     const metricsResponse = await handleMetricsRoutes(
       request,
@@ -293,6 +444,7 @@ async function handleApiRequest(request: Request, env: Env): Promise<Response> {
       url,
       corsHeaders,
       isLocalDev,
+      userEmail,
     );
   }
 
@@ -423,6 +575,48 @@ async function handleApiRequest(request: Request, env: Env): Promise<Response> {
     }
   }
 
+  if (url.pathname.startsWith("/api/signed-links")) {
+    const signedLinkResponse = await handleSignedLinkRoutes(
+      request,
+      env,
+      url,
+      corsHeaders,
+      isLocalDev,
+      userEmail,
+    );
+    if (signedLinkResponse) {
+      return signedLinkResponse;
+    }
+  }
+
+  if (url.pathname.startsWith("/api/shares")) {
+    const shareResponse = await handleShareRoutes(
+      request,
+      env,
+      url,
+      corsHeaders,
+      isLocalDev,
+      userEmail,
+    );
+    if (shareResponse) {
+      return shareResponse;
+    }
+  }
+
+  if (url.pathname.startsWith("/api/trash/")) {
+    const trashResponse = await handleTrashRoutes(
+      request,
+      env,
+      url,
+      corsHeaders,
+      isLocalDev,
+      userEmail,
+    );
+    if (trashResponse) {
+      return trashResponse;
+    }
+  }
+
   // Handle migration routes
   if (url.pathname.startsWith("/api/migrations")) {
     const migrationResponse = await handleMigrationRoutes(
@@ -462,6 +656,30 @@ async function handleApiRequest(request: Request, env: Env): Promise<Response> {
     );
   }
 
+  // Handle bucket settings routes (CORS, custom domains, r2.dev access)
+  if (url.pathname.startsWith("/api/bucket-settings/")) {
+    return await handleBucketSettingsRoutes(
+      request,
+      env,
+      url,
+      corsHeaders,
+      isLocalDev,
+      userEmail,
+    );
+  }
+
+  // Handle event notification routes
+  if (url.pathname.startsWith("/api/event-notifications/")) {
+    return await handleEventNotificationRoutes(
+      request,
+      env,
+      url,
+      corsHeaders,
+      isLocalDev,
+      userEmail,
+    );
+  }
+
   // Serve frontend assets
   return await serveFrontendAssets(request, env, isLocalhost);
 }
@@ -495,4 +713,20 @@ export default {
       );
     }
   },
+
+  async queue(
+    batch: MessageBatch<JobQueueMessage>,
+    env: Env,
+    _ctx: ExecutionContext,
+  ): Promise<void> {
+    await handleJobQueue(batch, env);
+  },
+
+  scheduled(
+    controller: ScheduledController,
+    env: Env,
+    ctx: ExecutionContext,
+  ): void {
+    ctx.waitUntil(runScheduledMaintenance(env, controller.cron));
+  },
 };
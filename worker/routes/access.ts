@@ -0,0 +1,451 @@
+/**
+ * Access Control Routes
+ *
+ * Admin endpoints for managing role grants and identity provider group
+ * mappings, plus GET /api/access/me so the frontend can tell what the current
+ * user is allowed to do. Authorization for every other route happens
+ * centrally in handleApiRequest (see utils/rbac.ts).
+ */
+
+import type {
+  AccessGrant,
+  AccessGroupMapping,
+  AccessRole,
+  CreateAccessGrantBody,
+  CreateAccessGroupMappingBody,
+  Env,
+} from "../types";
+import { logError, logInfo } from "../utils/error-logger";
+import { createErrorResponse } from "../utils/error-response";
+import {
+  ACCESS_ROLES,
+  getHighestRole,
+  getRequestAccess,
+  loadUserAccess,
+  satisfiesCheck,
+} from "../utils/rbac";
+
+const EMAIL_PATTERN = /^[^\s@]+@[^\s@]+$/;
+
+const MOCK_GRANTS: AccessGrant[] = [
+  {
+    id: 1,
+    user_email: "intern@example.com",
+    role: "viewer",
+    bucket_name: "dev-bucket",
+    prefix: "assets/",
+    created_by: "dev@localhost",
+    created_at: new Date(Date.now() - 86400000).toISOString(),
+  },
+];
+
+const MOCK_GROUP_MAPPINGS: AccessGroupMapping[] = [
+  {
+    id: 1,
+    group_name: "r2-admins",
+    role: "admin",
+    bucket_name: null,
+    prefix: "",
+    created_by: "dev@localhost",
+    created_at: new Date(Date.now() - 86400000).toISOString(),
+  },
+];
+
+function jsonResponse(body: unknown, corsHeaders: HeadersInit): Response {
+  return new Response(JSON.stringify(body), {
+    headers: { "Content-Type": "application/json", ...corsHeaders },
+  });
+}
+
+/**
+ * Validate the role, bucket and prefix of a grant or group mapping, returning
+ * an error message if invalid
+ */
+function validateScope(
+  body: Partial<Pick<CreateAccessGrantBody, "role" | "bucketName" | "prefix">>,
+): string | null {
+  if (
+    typeof body.role !== "string" ||
+    !ACCESS_ROLES.includes(body.role as AccessRole)
+  ) {
+    return `role must be one of: ${ACCESS_ROLES.join(", ")}`;
+  }
+  if (
+    body.bucketName !== undefined &&
+    body.bucketName !== null &&
+    (typeof body.bucketName !== "string" || body.bucketName.trim() === "")
+  ) {
+    return "bucketName must be a bucket name, or null for all buckets";
+  }
+  if (body.prefix !== undefined && typeof body.prefix !== "string") {
+    return "prefix must be a string";
+  }
+  if (
+    body.prefix !== undefined &&
+    body.prefix !== "" &&
+    (body.bucketName === undefined || body.bucketName === null)
+  ) {
+    return "A prefix can only be granted within a specific bucket";
+  }
+  return null;
+}
+
+/**
+ * Validate a grant request body, returning an error message if invalid
+ */
+function validateGrantBody(
+  body: Partial<CreateAccessGrantBody>,
+): string | null {
+  if (
+    typeof body.userEmail !== "string" ||
+    !EMAIL_PATTERN.test(body.userEmail.trim())
+  ) {
+    return "A valid userEmail is required";
+  }
+  return validateScope(body);
+}
+
+/**
+ * Validate a group mapping request body, returning an error message if invalid
+ */
+function validateGroupMappingBody(
+  body: Partial<CreateAccessGroupMappingBody>,
+): string | null {
+  if (typeof body.groupName !== "string" || body.groupName.trim() === "") {
+    return "groupName is required";
+  }
+  return validateScope(body);
+}
+
+/**
+ * Handle access control routes
+ */
+export async function handleAccessRoutes(
+  request: Request,
+  env: Env,
+  url: URL,
+  corsHeaders: HeadersInit,
+  isLocalDev: boolean,
+  userEmail: string,
+): Promise<Response | null> {
+  const db = env.METADATA;
+
+  // GET /api/access/me - The current user's grants
+  if (url.pathname === "/api/access/me" && request.method === "GET") {
+    const access =
+      getRequestAccess(request) ??
+      (await loadUserAccess(env, { email: userEmail, groups: [] }));
+    return jsonResponse(
+      {
+        success: true,
+        result: {
+          email: access.email,
+          groups: access.groups,
+          enforced: access.enforced,
+          grants: access.grants,
+          role: getHighestRole(access),
+          isAdmin: satisfiesCheck(access, { role: "admin", scope: "account" }),
+        },
+      },
+      corsHeaders,
+    );
+  }
+
+  // GET /api/access/grants - List all grants
+  if (url.pathname === "/api/access/grants" && request.method === "GET") {
+    if (isLocalDev || !db) {
+      return jsonResponse(
+        { success: true, result: { grants: MOCK_GRANTS } },
+        corsHeaders,
+      );
+    }
+
+    try {
+      const result = await db
+        .prepare(
+          "SELECT * FROM access_grants ORDER BY user_email, bucket_name, prefix",
+        )
+        .all<AccessGrant>();
+      return jsonResponse(
+        { success: true, result: { grants: result.results } },
+        corsHeaders,
+      );
+    } catch (error) {
+      await logError(
+        env,
+        error instanceof Error ? error : String(error),
+        { module: "access", operation: "list", userId: userEmail },
+        isLocalDev,
+      );
+      return createErrorResponse("Failed to list access grants", corsHeaders);
+    }
+  }
+
+  // POST /api/access/grants - Grant a role (replaces the role of an existing
+  // grant with the same user, bucket and prefix)
+  if (url.pathname === "/api/access/grants" && request.method === "POST") {
+    let body: Partial<CreateAccessGrantBody>;
+    try {
+      body = (await request.json()) as Partial<CreateAccessGrantBody>;
+    } catch {
+      return createErrorResponse("Invalid JSON body", corsHeaders, 400);
+    }
+
+    const validationError = validateGrantBody(body);
+    if (validationError !== null) {
+      return createErrorResponse(validationError, corsHeaders, 400);
+    }
+
+    const grantEmail = (body.userEmail ?? "").trim().toLowerCase();
+    const role = body.role as AccessRole;
+    const bucketName = body.bucketName?.trim() ?? null;
+    const prefix = body.prefix ?? "";
+
+    if (isLocalDev || !db) {
+      const grant: AccessGrant = {
+        id: Date.now(),
+        user_email: grantEmail,
+        role,
+        bucket_name: bucketName,
+        prefix,
+        created_by: userEmail,
+        created_at: new Date().toISOString(),
+      };
+      return jsonResponse({ success: true, result: grant }, corsHeaders);
+    }
+
+    try {
+      const existing = await db
+        .prepare(
+          "SELECT id FROM access_grants WHERE user_email = ? AND IFNULL(bucket_name, '') = ? AND prefix = ?",
+        )
+        .bind(grantEmail, bucketName ?? "", prefix)
+        .first<{ id: number }>();
+
+      const grant =
+        existing !== null
+          ? await db
+              .prepare(
+                "UPDATE access_grants SET role = ?, created_by = ?, created_at = datetime('now') WHERE id = ? RETURNING *",
+              )
+              .bind(role, userEmail, existing.id)
+              .first<AccessGrant>()
+          : await db
+              .prepare(
+                "INSERT INTO access_grants (user_email, role, bucket_name, prefix, created_by) VALUES (?, ?, ?, ?, ?) RETURNING *",
+              )
+              .bind(grantEmail, role, bucketName, prefix, userEmail)
+              .first<AccessGrant>();
+
+      logInfo("Granted access", {
+        module: "access",
+        operation: "grant",
+        userId: userEmail,
+        metadata: { grantEmail, role, bucketName, prefix },
+      });
+
+      return jsonResponse({ success: true, result: grant }, corsHeaders);
+    } catch (error) {
+      await logError(
+        env,
+        error instanceof Error ? error : String(error),
+        { module: "access", operation: "grant", userId: userEmail },
+        isLocalDev,
+      );
+      return createErrorResponse("Failed to save access grant", corsHeaders);
+    }
+  }
+
+  // DELETE /api/access/grants/:id - Remove a grant
+  const deleteMatch = /^\/api\/access\/grants\/(\d+)$/.exec(url.pathname);
+  if (deleteMatch !== null && request.method === "DELETE") {
+    const grantId = Number(deleteMatch[1]);
+
+    if (isLocalDev || !db) {
+      return jsonResponse({ success: true }, corsHeaders);
+    }
+
+    try {
+      const removed = await db
+        .prepare("DELETE FROM access_grants WHERE id = ? RETURNING *")
+        .bind(grantId)
+        .first<AccessGrant>();
+
+      if (removed === null) {
+        return createErrorResponse("Access grant not found", corsHeaders, 404);
+      }
+
+      logInfo("Revoked access grant", {
+        module: "access",
+        operation: "revoke",
+        userId: userEmail,
+        metadata: {
+          grantEmail: removed.user_email,
+          role: removed.role,
+          bucketName: removed.bucket_name,
+          prefix: removed.prefix,
+        },
+      });
+
+      return jsonResponse({ success: true }, corsHeaders);
+    } catch (error) {
+      await logError(
+        env,
+        error instanceof Error ? error : String(error),
+        { module: "access", operation: "revoke", userId: userEmail },
+        isLocalDev,
+      );
+      return createErrorResponse("Failed to remove access grant", corsHeaders);
+    }
+  }
+
+  // GET /api/access/groups - List all group mappings
+  if (url.pathname === "/api/access/groups" && request.method === "GET") {
+    if (isLocalDev || !db) {
+      return jsonResponse(
+        { success: true, result: { mappings: MOCK_GROUP_MAPPINGS } },
+        corsHeaders,
+      );
+    }
+
+    try {
+      const result = await db
+        .prepare(
+          "SELECT * FROM access_group_mappings ORDER BY group_name, bucket_name, prefix",
+        )
+        .all<AccessGroupMapping>();
+      return jsonResponse(
+        { success: true, result: { mappings: result.results } },
+        corsHeaders,
+      );
+    } catch (error) {
+      await logError(
+        env,
+        error instanceof Error ? error : String(error),
+        { module: "access", operation: "list_groups", userId: userEmail },
+        isLocalDev,
+      );
+      return createErrorResponse("Failed to list group mappings", corsHeaders);
+    }
+  }
+
+  // POST /api/access/groups - Map an IdP group to a role (replaces the role
+  // of an existing mapping with the same group, bucket and prefix)
+  if (url.pathname === "/api/access/groups" && request.method === "POST") {
+    let body: Partial<CreateAccessGroupMappingBody>;
+    try {
+      body = (await request.json()) as Partial<CreateAccessGroupMappingBody>;
+    } catch {
+      return createErrorResponse("Invalid JSON body", corsHeaders, 400);
+    }
+
+    const validationError = validateGroupMappingBody(body);
+    if (validationError !== null) {
+      return createErrorResponse(validationError, corsHeaders, 400);
+    }
+
+    const groupName = (body.groupName ?? "").trim();
+    const role = body.role as AccessRole;
+    const bucketName = body.bucketName?.trim() ?? null;
+    const prefix = body.prefix ?? "";
+
+    if (isLocalDev || !db) {
+      const mapping: AccessGroupMapping = {
+        id: Date.now(),
+        group_name: groupName,
+        role,
+        bucket_name: bucketName,
+        prefix,
+        created_by: userEmail,
+        created_at: new Date().toISOString(),
+      };
+      return jsonResponse({ success: true, result: mapping }, corsHeaders);
+    }
+
+    try {
+      const existing = await db
+        .prepare(
+          "SELECT id FROM access_group_mappings WHERE lower(group_name) = lower(?) AND IFNULL(bucket_name, '') = ? AND prefix = ?",
+        )
+        .bind(groupName, bucketName ?? "", prefix)
+        .first<{ id: number }>();
+
+      const mapping =
+        existing !== null
+          ? await db
+              .prepare(
+                "UPDATE access_group_mappings SET group_name = ?, role = ?, created_by = ?, created_at = datetime('now') WHERE id = ? RETURNING *",
+              )
+              .bind(groupName, role, userEmail, existing.id)
+              .first<AccessGroupMapping>()
+          : await db
+              .prepare(
+                "INSERT INTO access_group_mappings (group_name, role, bucket_name, prefix, created_by) VALUES (?, ?, ?, ?, ?) RETURNING *",
+              )
+              .bind(groupName, role, bucketName, prefix, userEmail)
+              .first<AccessGroupMapping>();
+
+      logInfo("Mapped group to role", {
+        module: "access",
+        operation: "map_group",
+        userId: userEmail,
+        metadata: { groupName, role, bucketName, prefix },
+      });
+
+      return jsonResponse({ success: true, result: mapping }, corsHeaders);
+    } catch (error) {
+      await logError(
+        env,
+        error instanceof Error ? error : String(error),
+        { module: "access", operation: "map_group", userId: userEmail },
+        isLocalDev,
+      );
+      return createErrorResponse("Failed to save group mapping", corsHeaders);
+    }
+  }
+
+  // DELETE /api/access/groups/:id - Remove a group mapping
+  const deleteGroupMatch = /^\/api\/access\/groups\/(\d+)$/.exec(url.pathname);
+  if (deleteGroupMatch !== null && request.method === "DELETE") {
+    const mappingId = Number(deleteGroupMatch[1]);
+
+    if (isLocalDev || !db) {
+      return jsonResponse({ success: true }, corsHeaders);
+    }
+
+    try {
+      const removed = await db
+        .prepare("DELETE FROM access_group_mappings WHERE id = ? RETURNING *")
+        .bind(mappingId)
+        .first<AccessGroupMapping>();
+
+      if (removed === null) {
+        return createErrorResponse("Group mapping not found", corsHeaders, 404);
+      }
+
+      logInfo("Removed group mapping", {
+        module: "access",
+        operation: "unmap_group",
+        userId: userEmail,
+        metadata: {
+          groupName: removed.group_name,
+          role: removed.role,
+          bucketName: removed.bucket_name,
+          prefix: removed.prefix,
+        },
+      });
+
+      return jsonResponse({ success: true }, corsHeaders);
+    } catch (error) {
+      await logError(
+        env,
+        error instanceof Error ? error : String(error),
+        { module: "access", operation: "unmap_group", userId: userEmail },
+        isLocalDev,
+      );
+      return createErrorResponse("Failed to remove group mapping", corsHeaders);
+    }
+  }
+
+  return null;
+}
@@ -1,7 +1,7 @@
 import type {
   Env,
   CloudflareApiResponse,
-  R2ObjectInfo,
+  StorageObject,
   AISearchCompatibility,
   AISearchFileInfo,
   AISearchInstance,
@@ -18,7 +18,12 @@ import type {
 import { CF_API } from "../types";
 import { type CorsHeaders } from "../utils/cors";
 import { getCloudflareHeaders } from "../utils/helpers";
+import { getObjectStorage, listAllObjects } from "../utils/storage";
 import { logInfo, logError, logWarning } from "../utils/error-logger";
+import {
+  triggerWebhooks,
+  createAISearchSyncCompletePayload,
+} from "../utils/webhooks";
 
 // Cache for supported file types (5-minute TTL per Cloudflare Manager Rules)
 interface SupportedTypesCache {
@@ -121,6 +126,118 @@ function isFileIndexable(
   return { indexable: true };
 }
 
+/** Sync jobs that had ended this long before first seen are not announced */
+const AI_SEARCH_ANNOUNCE_WINDOW_MS = 24 * 60 * 60 * 1000;
+
+/**
+ * Send ai_search_sync_complete webhooks for indexing jobs that have ended
+ * without an error since the last run. Jobs are recorded in
+ * ai_search_sync_notifications so each is only announced once. Called by
+ * the scheduled maintenance handler; returns the number of newly ended jobs.
+ */
+export async function syncAISearchCompletions(env: Env): Promise<number> {
+  const db = env.METADATA;
+  if (!db) {
+    return 0;
+  }
+
+  const cfHeaders = getCloudflareHeaders(env);
+  const listResponse = await fetch(
+    `${CF_API}/accounts/${env.ACCOUNT_ID}/autorag/rags`,
+    { headers: cfHeaders },
+  );
+  if (!listResponse.ok) {
+    logWarning(`Failed to list instances: ${listResponse.status}`, {
+      module: "ai_search",
+      operation: "sync_completions",
+    });
+    return 0;
+  }
+
+  const listData = (await listResponse.json()) as CloudflareApiResponse<
+    AISearchInstance[] | AISearchInstancesListResult
+  >;
+  const instances = Array.isArray(listData.result)
+    ? listData.result
+    : (listData.result?.rags ?? []);
+
+  const notified = await db
+    .prepare("SELECT job_id FROM ai_search_sync_notifications")
+    .all<{ job_id: string }>();
+  const notifiedIds = new Set(notified.results.map((row) => row.job_id));
+  const announceAfter = Date.now() - AI_SEARCH_ANNOUNCE_WINDOW_MS;
+  let newlyEnded = 0;
+
+  for (const instance of instances) {
+    const instanceName = instance.id ?? instance.name;
+    if (!instanceName) continue;
+
+    const jobsResponse = await fetch(
+      `${CF_API}/accounts/${env.ACCOUNT_ID}/autorag/rags/${instanceName}/jobs`,
+      { headers: cfHeaders },
+    );
+    if (!jobsResponse.ok) {
+      logWarning(`Failed to list jobs: ${jobsResponse.status}`, {
+        module: "ai_search",
+        operation: "sync_completions",
+        metadata: { instanceName },
+      });
+      continue;
+    }
+
+    const jobsData = (await jobsResponse.json()) as CloudflareApiResponse<{
+      jobs: AISearchIndexingJob[];
+    }>;
+    // Handle both array format and { jobs: [...] } format
+    const jobs = Array.isArray(jobsData.result)
+      ? (jobsData.result as unknown as AISearchIndexingJob[])
+      : (jobsData.result?.jobs ?? []);
+
+    // A job that ended with an end_reason was stopped or failed
+    const ended = jobs.filter(
+      (job) =>
+        job.ended_at !== undefined &&
+        (job.end_reason === null || job.end_reason === undefined) &&
+        !notifiedIds.has(job.id),
+    );
+    if (ended.length === 0) continue;
+
+    const now = new Date().toISOString();
+    await db.batch(
+      ended.map((job) =>
+        db
+          .prepare(
+            "INSERT OR IGNORE INTO ai_search_sync_notifications (job_id, instance_name, notified_at) VALUES (?, ?, ?)",
+          )
+          .bind(job.id, instanceName, now),
+      ),
+    );
+    newlyEnded += ended.length;
+
+    for (const job of ended) {
+      const endedAt = job.ended_at ?? now;
+      if (Date.parse(endedAt) < announceAfter) {
+        continue;
+      }
+      await triggerWebhooks(
+        env,
+        "ai_search_sync_complete",
+        createAISearchSyncCompletePayload(
+          instanceName,
+          instance.data_source?.bucket_name ?? instance.source ?? null,
+          job.id,
+          job.source,
+          job.started_at,
+          endedAt,
+        ),
+        false,
+      );
+    }
+  }
+
+  return newlyEnded;
+}
+
 export async function handleAISearchRoutes(
   request: Request,
   env: Env,
@@ -178,36 +295,11 @@ export async function handleAISearchRoutes(
       }
 
       // Fetch all files from the bucket
-      const allFiles: R2ObjectInfo[] = [];
-      let cursor: string | undefined;
-      let hasMore = true;
-
-      while (hasMore) {
-        const listUrl = new URL(
-          `${CF_API}/accounts/${env.ACCOUNT_ID}/r2/buckets/${bucketName}/objects`,
-        );
-        if (cursor) {
-          listUrl.searchParams.set("cursor", cursor);
-        }
-        listUrl.searchParams.set("per_page", "100");
-
-        const listResponse = await fetch(listUrl.toString(), {
-          headers: cfHeaders,
-        });
-        if (!listResponse.ok) {
-          throw new Error(`Failed to list objects: ${listResponse.status}`);
-        }
-
-        const listData = (await listResponse.json()) as CloudflareApiResponse<
-          R2ObjectInfo[]
-        >;
-        const objects: R2ObjectInfo[] = Array.isArray(listData.result)
-          ? listData.result
-          : [];
+      const allFiles: StorageObject[] = [];
+      for await (const objects of listAllObjects(
+        getObjectStorage(env, bucketName),
+      )) {
         allFiles.push(...objects);
-
-        cursor = listData.result_info?.cursor;
-        hasMore = Boolean(cursor) && objects.length > 0;
       }
 
       // Analyze files for AI Search compatibility
@@ -0,0 +1,348 @@
+/**
+ * API Token Routes
+ *
+ * Lets users create, list and revoke their own API tokens. Tokens are
+ * accepted as "Authorization: Bearer" credentials by handleApiRequest (see
+ * utils/api-tokens.ts).
+ */
+
+import type {
+  ApiToken,
+  ApiTokenPermission,
+  ApiTokenStatus,
+  CreateApiTokenBody,
+  Env,
+} from "../types";
+import {
+  API_TOKEN_PERMISSIONS,
+  DEFAULT_API_TOKEN_TTL_SECONDS,
+  MAX_API_TOKEN_TTL_SECONDS,
+  generateApiToken,
+  getApiTokenStatus,
+  getS3SecretKey,
+  hashApiToken,
+} from "../utils/api-tokens";
+import { logError, logInfo } from "../utils/error-logger";
+import { createErrorResponse } from "../utils/error-response";
+
+const MIN_API_TOKEN_TTL_SECONDS = 60;
+const MAX_API_TOKEN_NAME_LENGTH = 100;
+
+const MOCK_API_TOKENS: ApiToken[] = [
+  {
+    token_id: "tok_dev0000000000001",
+    name: "CI artifact upload",
+    token_prefix: "r2m_3f9a1c2b",
+    token_hash: "",
+    created_by: "dev@localhost",
+    creator_groups: "[]",
+    created_at: new Date(Date.now() - 86400000).toISOString(),
+    expires_at: new Date(Date.now() + 30 * 86400000).toISOString(),
+    buckets: JSON.stringify(["dev-bucket"]),
+    permissions: "read,write",
+    last_used_at: new Date(Date.now() - 3600000).toISOString(),
+    revoked_at: null,
+  },
+];
+
+function jsonResponse(body: unknown, corsHeaders: HeadersInit): Response {
+  return new Response(JSON.stringify(body), {
+    headers: { "Content-Type": "application/json", ...corsHeaders },
+  });
+}
+
+interface ApiTokenResponse {
+  token_id: string;
+  name: string;
+  token_prefix: string;
+  created_by: string;
+  created_at: string;
+  expires_at: string;
+  buckets: string[] | null;
+  permissions: string[];
+  last_used_at: string | null;
+  revoked_at: string | null;
+  status: ApiTokenStatus;
+}
+
+/**
+ * Shape a stored token for API responses - the hash is never returned
+ */
+function toApiTokenResponse(
+  token: ApiToken,
+  now = Date.now(),
+): ApiTokenResponse {
+  return {
+    token_id: token.token_id,
+    name: token.name,
+    token_prefix: token.token_prefix,
+    created_by: token.created_by,
+    created_at: token.created_at,
+    expires_at: token.expires_at,
+    buckets:
+      token.buckets !== null ? (JSON.parse(token.buckets) as string[]) : null,
+    permissions: token.permissions.split(","),
+    last_used_at: token.last_used_at,
+    revoked_at: token.revoked_at,
+    status: getApiTokenStatus(token, now),
+  };
+}
+
+/**
+ * Validate the body of a create token request, returning an error message if
+ * invalid
+ */
+function validateCreateBody(body: Partial<CreateApiTokenBody>): string | null {
+  if (
+    typeof body.name !== "string" ||
+    body.name.trim() === "" ||
+    body.name.trim().length > MAX_API_TOKEN_NAME_LENGTH
+  ) {
+    return `name must be between 1 and ${MAX_API_TOKEN_NAME_LENGTH} characters`;
+  }
+  if (
+    !Array.isArray(body.permissions) ||
+    body.permissions.length === 0 ||
+    !body.permissions.every((permission) =>
+      API_TOKEN_PERMISSIONS.includes(permission),
+    )
+  ) {
+    return `permissions must be a non-empty list of: ${API_TOKEN_PERMISSIONS.join(", ")}`;
+  }
+  if (
+    body.buckets !== undefined &&
+    body.buckets !== null &&
+    (!Array.isArray(body.buckets) ||
+      body.buckets.length === 0 ||
+      !body.buckets.every(
+        (bucket) => typeof bucket === "string" && bucket.trim() !== "",
+      ))
+  ) {
+    return "buckets must be a non-empty list of bucket names, or null for all buckets";
+  }
+  if (
+    body.expiresIn !== undefined &&
+    (typeof body.expiresIn !== "number" ||
+      !Number.isInteger(body.expiresIn) ||
+      body.expiresIn < MIN_API_TOKEN_TTL_SECONDS ||
+      body.expiresIn > MAX_API_TOKEN_TTL_SECONDS)
+  ) {
+    return `expiresIn must be between ${MIN_API_TOKEN_TTL_SECONDS} and ${MAX_API_TOKEN_TTL_SECONDS} seconds`;
+  }
+  return null;
+}
+
+/**
+ * Handle API token routes
+ */
+export async function handleApiTokenRoutes(
+  request: Request,
+  env: Env,
+  url: URL,
+  corsHeaders: HeadersInit,
+  isLocalDev: boolean,
+  userEmail: string,
+): Promise<Response | null> {
+  const db = env.METADATA;
+
+  // GET /api/tokens - List tokens created by the current user
+  if (url.pathname === "/api/tokens" && request.method === "GET") {
+    const includeInactive = url.searchParams.get("status") === "all";
+
+    if (isLocalDev || !db) {
+      return jsonResponse(
+        {
+          success: true,
+          result: {
+            tokens: MOCK_API_TOKENS.map((token) => toApiTokenResponse(token)),
+          },
+        },
+        corsHeaders,
+      );
+    }
+
+    try {
+      const conditions = ["created_by = ?"];
+      const bindings: string[] = [userEmail];
+      if (!includeInactive) {
+        conditions.push("revoked_at IS NULL", "expires_at > ?");
+        bindings.push(new Date().toISOString());
+      }
+
+      const result = await db
+        .prepare(
+          `SELECT * FROM api_tokens WHERE ${conditions.join(" AND ")} ORDER BY created_at DESC LIMIT 500`,
+        )
+        .bind(...bindings)
+        .all<ApiToken>();
+
+      const now = Date.now();
+      const tokens = result.results.map((token) =>
+        toApiTokenResponse(token, now),
+      );
+
+      return jsonResponse({ success: true, result: { tokens } }, corsHeaders);
+    } catch (error) {
+      await logError(
+        env,
+        error instanceof Error ? error : String(error),
+        { module: "tokens", operation: "list", userId: userEmail },
+        isLocalDev,
+      );
+      return createErrorResponse("Failed to list API tokens", corsHeaders);
+    }
+  }
+
+  // POST /api/tokens - Create a token. The token and its S3 credentials are
+  // only returned here.
+  if (url.pathname === "/api/tokens" && request.method === "POST") {
+    let body: Partial<CreateApiTokenBody>;
+    try {
+      body = (await request.json()) as Partial<CreateApiTokenBody>;
+    } catch {
+      return createErrorResponse("Invalid JSON body", corsHeaders, 400);
+    }
+
+    const validationError = validateCreateBody(body);
+    if (validationError !== null) {
+      return createErrorResponse(validationError, corsHeaders, 400);
+    }
+
+    const name = (body.name ?? "").trim();
+    const permissions = API_TOKEN_PERMISSIONS.filter(
+      (permission: ApiTokenPermission) =>
+        body.permissions?.includes(permission) === true,
+    );
+    const buckets = body.buckets
+      ? [...new Set(body.buckets.map((bucket) => bucket.trim()))]
+      : null;
+    const createdAt = new Date();
+    const expiresAt = new Date(
+      createdAt.getTime() +
+        (body.expiresIn ?? DEFAULT_API_TOKEN_TTL_SECONDS) * 1000,
+    );
+    const { tokenId, token } = generateApiToken();
+
+    const record: ApiToken = {
+      token_id: tokenId,
+      name,
+      token_prefix: token.slice(0, 12),
+      token_hash: await hashApiToken(token),
+      created_by: userEmail,
+      creator_groups: "[]",
+      created_at: createdAt.toISOString(),
+      expires_at: expiresAt.toISOString(),
+      buckets: buckets !== null ? JSON.stringify(buckets) : null,
+      permissions: permissions.join(","),
+      last_used_at: null,
+      revoked_at: null,
+    };
+
+    if (!isLocalDev && db) {
+      try {
+        await db
+          .prepare(
+            "INSERT INTO api_tokens (token_id, name, token_prefix, token_hash, created_by, creator_groups, created_at, expires_at, buckets, permissions) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+          )
+          .bind(
+            record.token_id,
+            record.name,
+            record.token_prefix,
+            record.token_hash,
+            record.created_by,
+            record.creator_groups,
+            record.created_at,
+            record.expires_at,
+            record.buckets,
+            record.permissions,
+          )
+          .run();
+      } catch (error) {
+        await logError(
+          env,
+          error instanceof Error ? error : String(error),
+          { module: "tokens", operation: "create", userId: userEmail },
+          isLocalDev,
+        );
+        return createErrorResponse("Failed to create API token", corsHeaders);
+      }
+    }
+
+    logInfo("Created API token", {
+      module: "tokens",
+      operation: "create",
+      userId: userEmail,
+      metadata: { tokenId, name, buckets, permissions },
+    });
+
+    return jsonResponse(
+      {
+        success: true,
+        result: {
+          ...toApiTokenResponse(record),
+          token,
+          // SigV4 credentials for the /s3/ endpoint, derived from the token ID
+          s3: {
+            accessKeyId: tokenId,
+            secretAccessKey: await getS3SecretKey(tokenId, env),
+          },
+        },
+      },
+      corsHeaders,
+    );
+  }
+
+  // DELETE /api/tokens/:tokenId - Revoke a token
+  const revokeMatch = /^\/api\/tokens\/([^/]+)$/.exec(url.pathname);
+  if (revokeMatch !== null && request.method === "DELETE") {
+    const tokenId = revokeMatch[1];
+    if (!tokenId) {
+      return createErrorResponse("Invalid token ID", corsHeaders, 400);
+    }
+
+    if (isLocalDev || !db) {
+      return jsonResponse({ success: true }, corsHeaders);
+    }
+
+    try {
+      const revoked = await db
+        .prepare(
+          "UPDATE api_tokens SET revoked_at = ? WHERE token_id = ? AND created_by = ? AND revoked_at IS NULL RETURNING token_id",
+        )
+        .bind(new Date().toISOString(), tokenId, userEmail)
+        .first<{ token_id: string }>();
+
+      if (revoked === null) {
+        return createErrorResponse(
+          "API token not found or already revoked",
+          corsHeaders,
+          404,
+        );
+      }
+
+      logInfo("Revoked API token", {
+        module: "tokens",
+        operation: "revoke",
+        userId: userEmail,
+        metadata: { tokenId },
+      });
+
+      return jsonResponse({ success: true }, corsHeaders);
+    } catch (error) {
+      await logError(
+        env,
+        error instanceof Error ? error : String(error),
+        {
+          module: "tokens",
+          operation: "revoke",
+          userId: userEmail,
+          metadata: { tokenId },
+        },
+        isLocalDev,
+      );
+      return createErrorResponse("Failed to revoke API token", corsHeaders);
+    }
+  }
+
+  return null;
+}
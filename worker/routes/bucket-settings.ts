@@ -0,0 +1,821 @@
+/**
+ * Bucket Settings Routes
+ *
+ * CORS policies, bucket lock rules, custom domains and managed r2.dev public
+ * access of a bucket, proxied to the Cloudflare REST API.
+ */
+
+import type {
+  AttachCustomDomainBody,
+  BucketCorsPolicy,
+  BucketCorsRule,
+  BucketLockConfiguration,
+  BucketLockRule,
+  CloudflareApiResponse,
+  CorsMethod,
+  CustomDomain,
+  Env,
+  ManagedDomain,
+} from "../types";
+import { CF_API } from "../types";
+import { type CorsHeaders } from "../utils/cors";
+import { getCloudflareHeaders } from "../utils/helpers";
+import { invalidateBucketLockCache } from "../utils/bucket-lock";
+import { logError, logInfo, logWarning } from "../utils/error-logger";
+import { createErrorResponse } from "../utils/error-response";
+
+const CORS_METHODS: readonly CorsMethod[] = [
+  "GET",
+  "PUT",
+  "POST",
+  "DELETE",
+  "HEAD",
+];
+
+const MIN_TLS_VERSIONS = ["1.0", "1.1", "1.2", "1.3"];
+
+const DOMAIN_PATTERN =
+  /^(?=.{1,253}$)([a-z0-9]([a-z0-9-]{0,61}[a-z0-9])?\.)+[a-z]{2,63}$/;
+
+function jsonResponse(body: unknown, corsHeaders: CorsHeaders): Response {
+  return new Response(JSON.stringify(body), {
+    headers: { "Content-Type": "application/json", ...corsHeaders },
+  });
+}
+
+/**
+ * Error message of a failed Cloudflare API response
+ */
+async function readCloudflareError(
+  response: Response,
+  fallback: string,
+): Promise<string> {
+  const errorData = (await response.json().catch(() => ({}))) as {
+    errors?: { message: string }[];
+  };
+  return errorData.errors?.[0]?.message ?? fallback;
+}
+
+/**
+ * Validation error of a CORS policy, or null if it is valid
+ */
+function validateCorsRules(rules: unknown): string | null {
+  if (!Array.isArray(rules)) {
+    return "Invalid request body: rules array required";
+  }
+  const typedRules = rules as Partial<BucketCorsRule>[];
+  for (const [index, rule] of typedRules.entries()) {
+    const label = `Rule ${index + 1}`;
+    const allowed = rule.allowed;
+    if (
+      allowed === undefined ||
+      !Array.isArray(allowed.origins) ||
+      allowed.origins.length === 0
+    ) {
+      return `${label}: at least one allowed origin is required`;
+    }
+    if (!Array.isArray(allowed.methods) || allowed.methods.length === 0) {
+      return `${label}: at least one allowed method is required`;
+    }
+    const badMethod = allowed.methods.find((m) => !CORS_METHODS.includes(m));
+    if (badMethod !== undefined) {
+      return `${label}: unsupported method ${String(badMethod)}`;
+    }
+    if (
+      rule.maxAgeSeconds !== undefined &&
+      (!Number.isInteger(rule.maxAgeSeconds) || rule.maxAgeSeconds < 0)
+    ) {
+      return `${label}: maxAgeSeconds must be a non-negative integer`;
+    }
+  }
+  return null;
+}
+
+/**
+ * Validation error of a set of bucket lock rules, or null if they are valid
+ */
+function validateLockRules(rules: unknown): string | null {
+  if (!Array.isArray(rules)) {
+    return "Invalid request body: rules array required";
+  }
+  const typedRules = rules as Partial<BucketLockRule>[];
+  const ids = new Set<string>();
+  for (const [index, rule] of typedRules.entries()) {
+    const label = `Rule ${index + 1}`;
+    if (typeof rule.id !== "string" || rule.id.trim() === "") {
+      return `${label}: an ID is required`;
+    }
+    if (ids.has(rule.id)) {
+      return `${label}: duplicate ID ${rule.id}`;
+    }
+    ids.add(rule.id);
+    if (typeof rule.enabled !== "boolean") {
+      return `${label}: "enabled" must be a boolean`;
+    }
+    if (rule.prefix !== undefined && typeof rule.prefix !== "string") {
+      return `${label}: prefix must be a string`;
+    }
+
+    const condition = rule.condition;
+    if (condition?.type === "Age") {
+      if (
+        !Number.isInteger(condition.maxAgeSeconds) ||
+        condition.maxAgeSeconds <= 0
+      ) {
+        return `${label}: maxAgeSeconds must be a positive integer`;
+      }
+    } else if (condition?.type === "Date") {
+      if (Number.isNaN(Date.parse(condition.date))) {
+        return `${label}: date must be a valid date`;
+      }
+    } else if (condition?.type !== "Indefinite") {
+      return `${label}: condition type must be Age, Date or Indefinite`;
+    }
+  }
+  return null;
+}
+
+/**
+ * Find the ID of the account's zone that a domain belongs to, trying the
+ * domain itself and then each parent domain
+ */
+async function findZoneId(env: Env, domain: string): Promise<string | null> {
+  const labels = domain.split(".");
+  for (let i = 0; i < labels.length - 1; i++) {
+    const candidate = labels.slice(i).join(".");
+    const response = await fetch(
+      `${CF_API}/zones?name=${encodeURIComponent(candidate)}&account.id=${encodeURIComponent(env.ACCOUNT_ID)}`,
+      { headers: getCloudflareHeaders(env) },
+    );
+    if (!response.ok) {
+      continue;
+    }
+    const data = (await response.json()) as CloudflareApiResponse<
+      { id: string }[]
+    >;
+    const zoneId = data.result?.[0]?.id;
+    if (zoneId !== undefined) {
+      return zoneId;
+    }
+  }
+  return null;
+}
+
+/**
+ * Handle bucket settings routes
+ *
+ * Endpoints (under /api/bucket-settings/:bucketName):
+ * - GET    /cors                    - Get CORS rules
+ * - PUT    /cors                    - Replace CORS rules
+ * - DELETE /cors                    - Remove the CORS policy
+ * - GET    /lock                    - Get bucket lock rules
+ * - PUT    /lock                    - Replace bucket lock rules
+ * - GET    /domains                 - r2.dev access and custom domains
+ * - PUT    /domains/managed         - Enable/disable r2.dev access
+ * - POST   /domains/custom          - Attach a custom domain
+ * - GET    /domains/custom/:domain  - Custom domain status
+ * - PUT    /domains/custom/:domain  - Enable/disable, minimum TLS version
+ * - DELETE /domains/custom/:domain  - Detach a custom domain
+ */
+export async function handleBucketSettingsRoutes(
+  request: Request,
+  env: Env,
+  url: URL,
+  corsHeaders: CorsHeaders,
+  isLocalDev: boolean,
+  userEmail: string,
+): Promise<Response> {
+  const match =
+    /^\/api\/bucket-settings\/([^/]+)\/(cors|lock|domains)(?:\/(managed|custom)(?:\/([^/]+))?)?$/.exec(
+      url.pathname,
+    );
+  if (match === null) {
+    return new Response("Not Found", { status: 404, headers: corsHeaders });
+  }
+
+  const bucketName = decodeURIComponent(match[1] ?? "");
+  const section = match[2];
+  const domainType = match[3];
+  const domain =
+    match[4] !== undefined ? decodeURIComponent(match[4]).toLowerCase() : null;
+  const bucketApi = `${CF_API}/accounts/${env.ACCOUNT_ID}/r2/buckets/${encodeURIComponent(bucketName)}`;
+  const cfHeaders = getCloudflareHeaders(env);
+  const method = request.method;
+
+  try {
+    if (section === "cors" && domainType === undefined) {
+      return await handleCors(
+        request,
+        env,
+        bucketApi,
+        bucketName,
+        corsHeaders,
+        isLocalDev,
+        userEmail,
+      );
+    }
+
+    if (section === "lock" && domainType === undefined) {
+      return await handleLock(
+        request,
+        env,
+        bucketApi,
+        bucketName,
+        corsHeaders,
+        isLocalDev,
+        userEmail,
+      );
+    }
+
+    // GET /domains - managed r2.dev access and custom domains together
+    if (domainType === undefined && method === "GET") {
+      if (isLocalDev) {
+        return jsonResponse(
+          {
+            success: true,
+            result: {
+              managed: {
+                bucketId: "mock-bucket-id",
+                domain: "pub-mock.r2.dev",
+                enabled: false,
+              },
+              custom: [],
+            },
+          },
+          corsHeaders,
+        );
+      }
+
+      const [managedResponse, customResponse] = await Promise.all([
+        fetch(`${bucketApi}/domains/managed`, { headers: cfHeaders }),
+        fetch(`${bucketApi}/domains/custom`, { headers: cfHeaders }),
+      ]);
+      if (!customResponse.ok) {
+        const errorMessage = await readCloudflareError(
+          customResponse,
+          "Failed to get custom domains",
+        );
+        return createErrorResponse(
+          errorMessage,
+          corsHeaders,
+          customResponse.status,
+        );
+      }
+
+      const custom = (await customResponse.json()) as CloudflareApiResponse<{
+        domains: CustomDomain[];
+      }>;
+
+      // Buckets in a jurisdiction have no r2.dev domain
+      let managed: ManagedDomain | null = null;
+      if (managedResponse.ok) {
+        const data =
+          (await managedResponse.json()) as CloudflareApiResponse<ManagedDomain>;
+        managed = data.result ?? null;
+      }
+
+      return jsonResponse(
+        {
+          success: true,
+          result: {
+            managed,
+            custom: custom.result?.domains ?? [],
+          },
+        },
+        corsHeaders,
+      );
+    }
+
+    // PUT /domains/managed - Enable or disable the public r2.dev URL
+    if (domainType === "managed" && domain === null && method === "PUT") {
+      const body = (await request.json()) as { enabled?: unknown };
+      if (typeof body.enabled !== "boolean") {
+        return createErrorResponse(
+          'Invalid request body: "enabled" boolean field required',
+          corsHeaders,
+          400,
+        );
+      }
+
+      if (isLocalDev) {
+        return jsonResponse(
+          {
+            success: true,
+            result: {
+              bucketId: "mock-bucket-id",
+              domain: "pub-mock.r2.dev",
+              enabled: body.enabled,
+            },
+          },
+          corsHeaders,
+        );
+      }
+
+      const response = await fetch(`${bucketApi}/domains/managed`, {
+        method: "PUT",
+        headers: cfHeaders,
+        body: JSON.stringify({ enabled: body.enabled }),
+      });
+      if (!response.ok) {
+        const errorMessage = await readCloudflareError(
+          response,
+          "Failed to update r2.dev access",
+        );
+        logWarning(`Failed to update r2.dev access: ${errorMessage}`, {
+          module: "bucket_settings",
+          operation: "set_managed_domain",
+          bucketName,
+          metadata: { status: response.status },
+        });
+        return createErrorResponse(errorMessage, corsHeaders, response.status);
+      }
+
+      const data =
+        (await response.json()) as CloudflareApiResponse<ManagedDomain>;
+      logInfo(
+        `${body.enabled ? "Enabled" : "Disabled"} r2.dev access for bucket: ${bucketName}`,
+        {
+          module: "bucket_settings",
+          operation: "set_managed_domain",
+          bucketName,
+          userId: userEmail,
+        },
+      );
+      return jsonResponse({ success: true, result: data.result }, corsHeaders);
+    }
+
+    // POST /domains/custom - Attach a custom domain
+    if (domainType === "custom" && domain === null && method === "POST") {
+      const body = (await request.json()) as Partial<AttachCustomDomainBody>;
+      const newDomain = body.domain?.trim().toLowerCase() ?? "";
+      if (!DOMAIN_PATTERN.test(newDomain)) {
+        return createErrorResponse(
+          "A valid domain name is required",
+          corsHeaders,
+          400,
+        );
+      }
+      if (
+        body.minTLS !== undefined &&
+        !MIN_TLS_VERSIONS.includes(body.minTLS)
+      ) {
+        return createErrorResponse(
+          "minTLS must be one of 1.0, 1.1, 1.2 or 1.3",
+          corsHeaders,
+          400,
+        );
+      }
+
+      if (isLocalDev) {
+        return jsonResponse(
+          {
+            success: true,
+            result: {
+              domain: newDomain,
+              enabled: true,
+              status: { ownership: "pending", ssl: "initializing" },
+            },
+          },
+          corsHeaders,
+        );
+      }
+
+      const zoneId = body.zoneId ?? (await findZoneId(env, newDomain));
+      if (zoneId === null) {
+        return createErrorResponse(
+          `No Cloudflare zone in this account matches ${newDomain}. Add the zone to Cloudflare first, or pass its zoneId.`,
+          corsHeaders,
+          400,
+        );
+      }
+
+      const response = await fetch(`${bucketApi}/domains/custom`, {
+        method: "POST",
+        headers: cfHeaders,
+        body: JSON.stringify({
+          domain: newDomain,
+          zoneId,
+          enabled: true,
+          ...(body.minTLS !== undefined && { minTLS: body.minTLS }),
+        }),
+      });
+      if (!response.ok) {
+        const errorMessage = await readCloudflareError(
+          response,
+          "Failed to attach custom domain",
+        );
+        logWarning(`Failed to attach custom domain: ${errorMessage}`, {
+          module: "bucket_settings",
+          operation: "attach_domain",
+          bucketName,
+          metadata: { domain: newDomain, status: response.status },
+        });
+        return createErrorResponse(errorMessage, corsHeaders, response.status);
+      }
+
+      const data = (await response.json()) as CloudflareApiResponse;
+      logInfo(`Attached custom domain ${newDomain} to bucket: ${bucketName}`, {
+        module: "bucket_settings",
+        operation: "attach_domain",
+        bucketName,
+        userId: userEmail,
+        metadata: { domain: newDomain, zoneId },
+      });
+      return jsonResponse({ success: true, result: data.result }, corsHeaders);
+    }
+
+    // GET/PUT/DELETE /domains/custom/:domain
+    if (domainType === "custom" && domain !== null) {
+      const domainApi = `${bucketApi}/domains/custom/${encodeURIComponent(domain)}`;
+
+      if (method === "GET") {
+        if (isLocalDev) {
+          return jsonResponse(
+            {
+              success: true,
+              result: {
+                domain,
+                enabled: true,
+                status: { ownership: "active", ssl: "active" },
+              },
+            },
+            corsHeaders,
+          );
+        }
+
+        const response = await fetch(domainApi, { headers: cfHeaders });
+        if (!response.ok) {
+          const errorMessage = await readCloudflareError(
+            response,
+            "Failed to get custom domain",
+          );
+          return createErrorResponse(
+            errorMessage,
+            corsHeaders,
+            response.status,
+          );
+        }
+        const data =
+          (await response.json()) as CloudflareApiResponse<CustomDomain>;
+        return jsonResponse(
+          { success: true, result: data.result },
+          corsHeaders,
+        );
+      }
+
+      if (method === "PUT") {
+        const body = (await request.json()) as {
+          enabled?: unknown;
+          minTLS?: unknown;
+        };
+        if (body.enabled !== undefined && typeof body.enabled !== "boolean") {
+          return createErrorResponse(
+            '"enabled" must be a boolean',
+            corsHeaders,
+            400,
+          );
+        }
+        if (
+          body.minTLS !== undefined &&
+          !MIN_TLS_VERSIONS.includes(String(body.minTLS))
+        ) {
+          return createErrorResponse(
+            "minTLS must be one of 1.0, 1.1, 1.2 or 1.3",
+            corsHeaders,
+            400,
+          );
+        }
+        const update = {
+          ...(body.enabled !== undefined && { enabled: body.enabled }),
+          ...(body.minTLS !== undefined && { minTLS: body.minTLS }),
+        };
+
+        if (isLocalDev) {
+          return jsonResponse(
+            { success: true, result: { domain, ...update } },
+            corsHeaders,
+          );
+        }
+
+        const response = await fetch(domainApi, {
+          method: "PUT",
+          headers: cfHeaders,
+          body: JSON.stringify(update),
+        });
+        if (!response.ok) {
+          const errorMessage = await readCloudflareError(
+            response,
+            "Failed to update custom domain",
+          );
+          return createErrorResponse(
+            errorMessage,
+            corsHeaders,
+            response.status,
+          );
+        }
+
+        const data = (await response.json()) as CloudflareApiResponse;
+        logInfo(`Updated custom domain ${domain} of bucket: ${bucketName}`, {
+          module: "bucket_settings",
+          operation: "update_domain",
+          bucketName,
+          userId: userEmail,
+          metadata: { domain, ...update },
+        });
+        return jsonResponse(
+          { success: true, result: data.result },
+          corsHeaders,
+        );
+      }
+
+      if (method === "DELETE") {
+        if (isLocalDev) {
+          return jsonResponse({ success: true }, corsHeaders);
+        }
+
+        const response = await fetch(domainApi, {
+          method: "DELETE",
+          headers: cfHeaders,
+        });
+        if (!response.ok) {
+          const errorMessage = await readCloudflareError(
+            response,
+            "Failed to remove custom domain",
+          );
+          return createErrorResponse(
+            errorMessage,
+            corsHeaders,
+            response.status,
+          );
+        }
+
+        logInfo(`Removed custom domain ${domain} from bucket: ${bucketName}`, {
+          module: "bucket_settings",
+          operation: "remove_domain",
+          bucketName,
+          userId: userEmail,
+          metadata: { domain },
+        });
+        return jsonResponse({ success: true }, corsHeaders);
+      }
+    }
+
+    return new Response("Method Not Allowed", {
+      status: 405,
+      headers: corsHeaders,
+    });
+  } catch (err) {
+    void logError(
+      env,
+      err instanceof Error ? err : new Error(String(err)),
+      {
+        module: "bucket_settings",
+        operation: `${method.toLowerCase()}_${section ?? "settings"}`,
+        bucketName,
+      },
+      isLocalDev,
+    );
+    return createErrorResponse(
+      "Bucket settings operation failed",
+      corsHeaders,
+      500,
+    );
+  }
+}
+
+/**
+ * GET, PUT or DELETE the CORS policy of a bucket. Saving an empty rule list
+ * removes the policy.
+ */
+async function handleCors(
+  request: Request,
+  env: Env,
+  bucketApi: string,
+  bucketName: string,
+  corsHeaders: CorsHeaders,
+  isLocalDev: boolean,
+  userEmail: string,
+): Promise<Response> {
+  const cfHeaders = getCloudflareHeaders(env);
+
+  if (request.method === "GET") {
+    if (isLocalDev) {
+      return jsonResponse(
+        {
+          success: true,
+          result: {
+            rules: [
+              {
+                id: "mock-cors-rule",
+                allowed: {
+                  origins: ["http://localhost:5173"],
+                  methods: ["GET", "HEAD"],
+                },
+                maxAgeSeconds: 3600,
+              },
+            ],
+          },
+        },
+        corsHeaders,
+      );
+    }
+
+    const response = await fetch(`${bucketApi}/cors`, { headers: cfHeaders });
+    if (!response.ok) {
+      // 404 means no CORS policy is configured
+      if (response.status === 404) {
+        return jsonResponse(
+          { success: true, result: { rules: [] } },
+          corsHeaders,
+        );
+      }
+      const errorMessage = await readCloudflareError(
+        response,
+        "Failed to get CORS policy",
+      );
+      return createErrorResponse(errorMessage, corsHeaders, response.status);
+    }
+
+    const data =
+      (await response.json()) as CloudflareApiResponse<BucketCorsPolicy>;
+    return jsonResponse(
+      { success: true, result: { rules: data.result?.rules ?? [] } },
+      corsHeaders,
+    );
+  }
+
+  if (request.method !== "PUT" && request.method !== "DELETE") {
+    return new Response("Method Not Allowed", {
+      status: 405,
+      headers: corsHeaders,
+    });
+  }
+
+  let rules: BucketCorsRule[] = [];
+  if (request.method === "PUT") {
+    const body = (await request.json()) as { rules?: unknown };
+    const validationError = validateCorsRules(body.rules);
+    if (validationError !== null) {
+      return createErrorResponse(validationError, corsHeaders, 400);
+    }
+    rules = body.rules as BucketCorsRule[];
+  }
+
+  if (isLocalDev) {
+    return jsonResponse({ success: true, result: { rules } }, corsHeaders);
+  }
+
+  const response =
+    rules.length > 0
+      ? await fetch(`${bucketApi}/cors`, {
+          method: "PUT",
+          headers: cfHeaders,
+          body: JSON.stringify({ rules }),
+        })
+      : await fetch(`${bucketApi}/cors`, {
+          method: "DELETE",
+          headers: cfHeaders,
+        });
+
+  // Removing a policy that does not exist is not an error
+  if (!response.ok && !(rules.length === 0 && response.status === 404)) {
+    const errorMessage = await readCloudflareError(
+      response,
+      "Failed to update CORS policy",
+    );
+    void logError(
+      env,
+      new Error(errorMessage),
+      {
+        module: "bucket_settings",
+        operation: "set_cors",
+        bucketName,
+        metadata: { status: response.status },
+      },
+      isLocalDev,
+    );
+    return createErrorResponse(errorMessage, corsHeaders, response.status);
+  }
+
+  logInfo(`Updated CORS policy for bucket: ${bucketName}`, {
+    module: "bucket_settings",
+    operation: "set_cors",
+    bucketName,
+    userId: userEmail,
+    metadata: { ruleCount: rules.length },
+  });
+  return jsonResponse({ success: true, result: { rules } }, corsHeaders);
+}
+
+/**
+ * GET or PUT the bucket lock rules of a bucket. PUT replaces every rule;
+ * saving an empty list removes them all.
+ */
+async function handleLock(
+  request: Request,
+  env: Env,
+  bucketApi: string,
+  bucketName: string,
+  corsHeaders: CorsHeaders,
+  isLocalDev: boolean,
+  userEmail: string,
+): Promise<Response> {
+  const cfHeaders = getCloudflareHeaders(env);
+
+  if (request.method === "GET") {
+    if (isLocalDev) {
+      return jsonResponse(
+        {
+          success: true,
+          result: {
+            rules: [
+              {
+                id: "mock-lock-rule",
+                enabled: true,
+                prefix: "records/",
+                condition: { type: "Age", maxAgeSeconds: 30 * 86400 },
+              },
+            ],
+          },
+        },
+        corsHeaders,
+      );
+    }
+
+    const response = await fetch(`${bucketApi}/lock`, { headers: cfHeaders });
+    if (!response.ok) {
+      // 404 means no lock rules are configured
+      if (response.status === 404) {
+        return jsonResponse(
+          { success: true, result: { rules: [] } },
+          corsHeaders,
+        );
+      }
+      const errorMessage = await readCloudflareError(
+        response,
+        "Failed to get bucket lock rules",
+      );
+      return createErrorResponse(errorMessage, corsHeaders, response.status);
+    }
+
+    const data =
+      (await response.json()) as CloudflareApiResponse<BucketLockConfiguration>;
+    return jsonResponse(
+      { success: true, result: { rules: data.result?.rules ?? [] } },
+      corsHeaders,
+    );
+  }
+
+  if (request.method !== "PUT") {
+    return new Response("Method Not Allowed", {
+      status: 405,
+      headers: corsHeaders,
+    });
+  }
+
+  const body = (await request.json()) as { rules?: unknown };
+  const validationError = validateLockRules(body.rules);
+  if (validationError !== null) {
+    return createErrorResponse(validationError, corsHeaders, 400);
+  }
+  const rules = (body.rules as BucketLockRule[]).map((rule) => ({
+    id: rule.id.trim(),
+    enabled: rule.enabled,
+    ...(rule.prefix !== undefined &&
+      rule.prefix !== "" && { prefix: rule.prefix }),
+    condition: rule.condition,
+  }));
+
+  if (isLocalDev) {
+    return jsonResponse({ success: true, result: { rules } }, corsHeaders);
+  }
+
+  const response = await fetch(`${bucketApi}/lock`, {
+    method: "PUT",
+    headers: cfHeaders,
+    body: JSON.stringify({ rules }),
+  });
+  if (!response.ok) {
+    const errorMessage = await readCloudflareError(
+      response,
+      "Failed to update bucket lock rules",
+    );
+    logWarning(`Failed to update bucket lock rules: ${errorMessage}`, {
+      module: "bucket_settings",
+      operation: "set_lock",
+      bucketName,
+      metadata: { status: response.status },
+    });
+    return createErrorResponse(errorMessage, corsHeaders, response.status);
+  }
+
+  invalidateBucketLockCache(bucketName);
+  logInfo(`Updated bucket lock rules for bucket: ${bucketName}`, {
+    module: "bucket_settings",
+    operation: "set_lock",
+    bucketName,
+    userId: userEmail,
+    metadata: { ruleCount: rules.length },
+  });
+  return jsonResponse({ success: true, result: { rules } }, corsHeaders);
+}
@@ -2,15 +2,32 @@ import type {
   Env,
   CloudflareApiResponse,
   BucketsListResult,
-  R2ObjectInfo,
+  CreateBucketBody,
+  R2LocationHint,
+  R2StorageClass,
 } from "../types";
 import { CF_API } from "../types";
 import { type CorsHeaders } from "../utils/cors";
 import { getBucketStats, getCloudflareHeaders } from "../utils/helpers";
+import { createJobResponse, startJob } from "../utils/job-runner";
 import { logAuditEvent } from "./audit";
 import { logError, logInfo, logWarning } from "../utils/error-logger";
-import { triggerWebhooks, createBucketRenamePayload } from "../utils/webhooks";
 import { createErrorResponse } from "../utils/error-response";
+import { getRequestAccess, hasBucketAccess } from "../utils/rbac";
+
+const LOCATION_HINTS: readonly R2LocationHint[] = [
+  "apac",
+  "eeur",
+  "enam",
+  "weur",
+  "wnam",
+  "oc",
+];
+
+const STORAGE_CLASSES: readonly R2StorageClass[] = [
+  "Standard",
+  "InfrequentAccess",
+];
 
 export async function handleBucketRoutes(
   request: Request,
@@ -69,8 +86,8 @@ export async function handleBucketRoutes(
       const data =
         (await response.json()) as CloudflareApiResponse<BucketsListResult>;
 
-      // With Zero Trust auth, show all R2 buckets to authenticated users
-      // Exclude system/internal buckets
+      // Show the buckets the user has a grant on, excluding system/internal
+      // buckets
       const systemBuckets = [
         "r2-bucket",
         "sqlite-mcp-server-wiki",
@@ -83,8 +100,11 @@ export async function handleBucketRoutes(
         "worker-manager-backups",
       ];
       const buckets = data.result?.buckets ?? [];
+      const access = getRequestAccess(request);
       const filteredBuckets = buckets.filter(
-        (b) => !systemBuckets.includes(b.name),
+        (b) =>
+          !systemBuckets.includes(b.name) &&
+          (access === undefined || hasBucketAccess(access, b.name)),
       );
 
       // Add size and object count information to each bucket
@@ -114,11 +134,41 @@ export async function handleBucketRoutes(
 
     // Create bucket
     if (request.method === "POST" && url.pathname === "/api/buckets") {
-      const body = (await request.json()) as { name: string };
+      const body = (await request.json()) as CreateBucketBody;
+      if (
+        body.locationHint !== undefined &&
+        !LOCATION_HINTS.includes(body.locationHint)
+      ) {
+        return createErrorResponse(
+          `locationHint must be one of ${LOCATION_HINTS.join(", ")}`,
+          corsHeaders,
+          400,
+        );
+      }
+      if (
+        body.storageClass !== undefined &&
+        !STORAGE_CLASSES.includes(body.storageClass)
+      ) {
+        return createErrorResponse(
+          `storageClass must be one of ${STORAGE_CLASSES.join(", ")}`,
+          corsHeaders,
+          400,
+        );
+      }
+      const options = {
+        ...(body.locationHint !== undefined && {
+          locationHint: body.locationHint,
+        }),
+        ...(body.storageClass !== undefined && {
+          storageClass: body.storageClass,
+        }),
+      };
+
       logInfo(`Creating bucket: ${body.name}`, {
         module: "buckets",
         operation: "create",
         bucketName: body.name,
+        metadata: options,
       });
 
       // Mock response for local development
@@ -133,6 +183,8 @@ export async function handleBucketRoutes(
             result: {
               name: body.name,
               creation_date: new Date().toISOString(),
+              location: body.locationHint?.toUpperCase() ?? "ENAM",
+              storage_class: body.storageClass ?? "Standard",
             },
           }),
           {
@@ -149,7 +201,7 @@ export async function handleBucketRoutes(
         {
           method: "POST",
           headers: cfHeaders,
-          body: JSON.stringify({ name: body.name }),
+          body: JSON.stringify({ name: body.name, ...options }),
         },
       );
       const data = (await response.json()) as CloudflareApiResponse;
@@ -164,8 +216,8 @@ export async function handleBucketRoutes(
             userEmail,
             status: data.success ? "success" : "failed",
             metadata: data.success
-              ? undefined
-              : { error: data.errors?.[0]?.message },
+              ? options
+              : { ...options, error: data.errors?.[0]?.message },
           },
           isLocalDev,
         );
@@ -196,177 +248,34 @@ export async function handleBucketRoutes(
         metadata: { force },
       });
 
-      // Zero Trust: Owner check removed - all authenticated users can manage all buckets
+      // Requires an admin grant on the bucket (enforced in handleApiRequest)
 
-      // If force delete, first delete all objects in the bucket
+      // Force delete empties the bucket and then deletes it as a background job
       if (force) {
-        logInfo("Force delete enabled - deleting all objects first", {
+        logInfo("Force delete enabled - starting bucket delete job", {
           module: "buckets",
           operation: "delete",
           bucketName,
         });
-        try {
-          let cursor: string | undefined;
-          let totalDeleted = 0;
-          let totalAttempted = 0;
-          let hasMoreObjects = true;
-
-          while (hasMoreObjects) {
-            const listUrl = new URL(
-              CF_API +
-                "/accounts/" +
-                env.ACCOUNT_ID +
-                "/r2/buckets/" +
-                bucketName +
-                "/objects",
-            );
-            if (cursor !== undefined) {
-              listUrl.searchParams.set("cursor", cursor);
-            }
-            listUrl.searchParams.set("per_page", "100");
-
-            logInfo(`Listing objects with cursor: ${cursor ?? "none"}`, {
-              module: "buckets",
-              operation: "delete",
-              bucketName,
-              metadata: { cursor },
-            });
-            const listResponse = await fetch(listUrl.toString(), {
-              headers: cfHeaders,
-            });
-
-            if (!listResponse.ok) {
-              throw new Error(
-                "Failed to list objects: " + String(listResponse.status),
-              );
-            }
-
-            const listData =
-              (await listResponse.json()) as CloudflareApiResponse<
-                R2ObjectInfo[]
-              >;
-            const objects: R2ObjectInfo[] = Array.isArray(listData.result)
-              ? listData.result
-              : [];
-            logInfo(`Found ${String(objects.length)} objects to delete`, {
-              module: "buckets",
-              operation: "delete",
-              bucketName,
-              metadata: { count: objects.length },
-            });
-
-            if (objects.length === 0) {
-              hasMoreObjects = false;
-            }
-
-            // Delete each object
-            for (const obj of objects) {
-              try {
-                totalAttempted++;
-                const deleteUrl =
-                  CF_API +
-                  "/accounts/" +
-                  env.ACCOUNT_ID +
-                  "/r2/buckets/" +
-                  bucketName +
-                  "/objects/" +
-                  encodeURIComponent(obj.key);
-                logInfo(`Deleting object: ${obj.key}`, {
-                  module: "buckets",
-                  operation: "delete",
-                  bucketName,
-                  fileName: obj.key,
-                });
-                const deleteResponse = await fetch(deleteUrl, {
-                  method: "DELETE",
-                  headers: cfHeaders,
-                });
-
-                logInfo(
-                  `[BKT_DELETE] Delete response for ${obj.key}: ${String(deleteResponse.status)}`,
-                  {
-                    module: "buckets",
-                    operation: "delete",
-                    bucketName,
-                    fileName: obj.key,
-                  },
-                );
-
-                if (deleteResponse.ok) {
-                  totalDeleted++;
-                } else {
-                  logWarning(
-                    `Failed to delete object: ${obj.key}, status: ${String(deleteResponse.status)}`,
-                    {
-                      module: "buckets",
-                      operation: "delete",
-                      bucketName,
-                      fileName: obj.key,
-                      metadata: { status: deleteResponse.status },
-                    },
-                  );
-                }
-              } catch (objErr) {
-                void logError(
-                  env,
-                  objErr instanceof Error ? objErr : new Error(String(objErr)),
-                  {
-                    module: "buckets",
-                    operation: "delete_object",
-                    bucketName,
-                    fileName: obj.key,
-                  },
-                  isLocalDev,
-                );
-              }
-            }
-
-            // Get cursor for next page
-            cursor = listData.result_info?.cursor as string | undefined;
-
-            // Add small delay between batches to avoid rate limiting
-            if (objects.length > 0 && cursor !== undefined) {
-              logInfo("Waiting before next batch...", {
-                module: "buckets",
-                operation: "delete",
-                bucketName,
-              });
-              await new Promise((resolve) => setTimeout(resolve, 500));
-            } else {
-              hasMoreObjects = false;
-            }
-          }
+        const result = await startJob(env, {
+          taskType: "bucket_delete",
+          params: { sourceBucket: bucketName, sourcePrefix: "" },
+          userEmail,
+          isLocalDev,
+        });
 
-          logInfo(
-            `Object deletion complete - deleted ${String(totalDeleted)} of ${String(totalAttempted)} attempted`,
-            {
-              module: "buckets",
-              operation: "delete",
-              bucketName,
-              metadata: { deleted: totalDeleted, attempted: totalAttempted },
-            },
-          );
-        } catch (deleteErr) {
-          void logError(
-            env,
-            deleteErr instanceof Error
-              ? deleteErr
-              : new Error(String(deleteErr)),
-            { module: "buckets", operation: "delete", bucketName },
-            isLocalDev,
-          );
-          return createErrorResponse(
-            "Failed to delete objects from bucket",
+        if (result.queued || result.status === "completed") {
+          return createJobResponse(
+            result,
+            { deleted: result.processedItems },
             corsHeaders,
-            500,
-            {
-              details:
-                deleteErr instanceof Error
-                  ? deleteErr.message
-                  : "Unknown error",
-            },
           );
         }
+        return createErrorResponse(
+          result.errorMessage ?? "Failed to delete objects from bucket",
+          corsHeaders,
+          500,
+        );
       }
 
       const response = await fetch(
@@ -513,15 +422,19 @@ export async function handleBucketRoutes(
       );
       const body = (await request.json()) as { newName?: string };
       const newBucketName = body.newName?.trim();
-      logInfo(
-        `Rename request: ${oldBucketName} -> ${newBucketName ?? "undefined"}`,
-        {
-          module: "buckets",
-          operation: "rename",
-          bucketName: oldBucketName,
-          metadata: { newName: newBucketName },
-        },
-      );
+      if (newBucketName === undefined || newBucketName === "") {
+        return createErrorResponse(
+          "New bucket name is required",
+          corsHeaders,
+          400,
+        );
+      }
+      logInfo(`Rename request: ${oldBucketName} -> ${newBucketName}`, {
+        module: "buckets",
+        operation: "rename",
+        bucketName: oldBucketName,
+        metadata: { newName: newBucketName },
+      });
 
       // Mock response for local development
       if (isLocalDev) {
@@ -545,9 +458,9 @@ export async function handleBucketRoutes(
         );
       }
 
-      // Zero Trust: All authenticated users can manage all buckets
+      // Requires an account-wide admin grant (enforced in handleApiRequest)
       try {
-        logInfo(`Creating new bucket: ${newBucketName ?? "undefined"}`, {
+        logInfo(`Creating new bucket: ${newBucketName}`, {
           module: "buckets",
           operation: "rename",
           bucketName: oldBucketName,
@@ -570,187 +483,36 @@ export async function handleBucketRoutes(
             createResponse.status,
           );
         }
-        logInfo("New bucket created, copying objects...", {
+        logInfo("New bucket created, starting rename job", {
           module: "buckets",
           operation: "rename",
           bucketName: oldBucketName,
         });
-        let cursor: string | undefined;
-        let totalCopied = 0;
-        let totalFailed = 0;
-        let hasMoreObjects = true;
-        while (hasMoreObjects) {
-          const listUrl = new URL(
-            CF_API +
-              "/accounts/" +
-              env.ACCOUNT_ID +
-              "/r2/buckets/" +
-              oldBucketName +
-              "/objects",
-          );
-          if (cursor !== undefined) listUrl.searchParams.set("cursor", cursor);
-          listUrl.searchParams.set("per_page", "100");
-          const listResponse = await fetch(listUrl.toString(), {
-            headers: cfHeaders,
-          });
-          if (!listResponse.ok)
-            throw new Error(
-              "Failed to list objects: " + String(listResponse.status),
-            );
-          const listData = (await listResponse.json()) as CloudflareApiResponse<
-            R2ObjectInfo[]
-          >;
-          const objects: R2ObjectInfo[] = Array.isArray(listData.result)
-            ? listData.result
-            : [];
-          logInfo(`Copying ${String(objects.length)} objects`, {
-            module: "buckets",
-            operation: "rename",
-            bucketName: oldBucketName,
-            metadata: { count: objects.length },
-          });
-          if (objects.length === 0) hasMoreObjects = false;
-          for (const obj of objects) {
-            try {
-              const copyUrl =
-                CF_API +
-                "/accounts/" +
-                env.ACCOUNT_ID +
-                "/r2/buckets/" +
-                newBucketName +
-                "/objects/" +
-                encodeURIComponent(obj.key);
-              const copySource =
-                "/" + oldBucketName + "/" + encodeURIComponent(obj.key);
-              const copyResponse = await fetch(copyUrl, {
-                method: "PUT",
-                headers: { ...cfHeaders, "x-amz-copy-source": copySource },
-              });
-              if (copyResponse.ok) totalCopied++;
-              else totalFailed++;
-            } catch {
-              totalFailed++;
-            }
-          }
-          cursor = listData.result_info?.cursor as string | undefined;
-          if (objects.length > 0 && cursor !== undefined)
-            await new Promise((resolve) => setTimeout(resolve, 500));
-          else hasMoreObjects = false;
-        }
-        logInfo(
-          `Copied ${String(totalCopied)} objects, failed: ${String(totalFailed)}`,
-          {
-            module: "buckets",
-            operation: "rename",
-            bucketName: oldBucketName,
-            metadata: { copied: totalCopied, failed: totalFailed },
+
+        // Move every object into the new bucket, then delete the old one
+        const result = await startJob(env, {
+          taskType: "bucket_rename",
+          params: {
+            sourceBucket: oldBucketName,
+            sourcePrefix: "",
+            destBucket: newBucketName,
+            destPrefix: "",
           },
-        );
-        logInfo("Deleting old bucket", {
-          module: "buckets",
-          operation: "rename",
-          bucketName: oldBucketName,
-        });
-        let deleteCursor: string | undefined;
-        let deleteHasMore = true;
-        while (deleteHasMore) {
-          const listUrl = new URL(
-            CF_API +
-              "/accounts/" +
-              env.ACCOUNT_ID +
-              "/r2/buckets/" +
-              oldBucketName +
-              "/objects",
-          );
-          if (deleteCursor !== undefined)
-            listUrl.searchParams.set("cursor", deleteCursor);
-          listUrl.searchParams.set("per_page", "100");
-          const listResponse = await fetch(listUrl.toString(), {
-            headers: cfHeaders,
-          });
-          if (!listResponse.ok)
-            throw new Error("Failed to list objects for deletion");
-          const listData2 =
-            (await listResponse.json()) as CloudflareApiResponse<
-              R2ObjectInfo[]
-            >;
-          const deleteObjects: R2ObjectInfo[] = Array.isArray(listData2.result)
-            ? listData2.result
-            : [];
-          if (deleteObjects.length === 0) deleteHasMore = false;
-          for (const obj of deleteObjects) {
-            try {
-              const deleteUrl =
-                CF_API +
-                "/accounts/" +
-                env.ACCOUNT_ID +
-                "/r2/buckets/" +
-                oldBucketName +
-                "/objects/" +
-                encodeURIComponent(obj.key);
-              await fetch(deleteUrl, { method: "DELETE", headers: cfHeaders });
-            } catch {
-              // Silently continue on delete failure
-            }
-          }
-          deleteCursor = listData2.result_info?.cursor as string | undefined;
-          if (deleteObjects.length > 0 && deleteCursor !== undefined)
-            await new Promise((resolve) => setTimeout(resolve, 300));
-          else deleteHasMore = false;
-        }
-        const deleteResponse = await fetch(
-          CF_API +
-            "/accounts/" +
-            env.ACCOUNT_ID +
-            "/r2/buckets/" +
-            oldBucketName,
-          { method: "DELETE", headers: cfHeaders },
-        );
-        if (!deleteResponse.ok) throw new Error("Failed to delete old bucket");
-        logInfo("Rename completed", {
-          module: "buckets",
-          operation: "rename",
-          bucketName: oldBucketName,
-          metadata: { newName: newBucketName },
+          userEmail,
+          isLocalDev,
         });
 
-        // Log audit event for bucket rename success
-        if (db) {
-          await logAuditEvent(
-            env,
-            {
-              operationType: "bucket_rename",
-              bucketName: oldBucketName,
-              userEmail,
-              status: "success",
-              destinationBucket: newBucketName,
-              metadata: {
-                objectsCopied: totalCopied,
-                objectsFailed: totalFailed,
-              },
-            },
-            isLocalDev,
+        if (!result.queued && result.status !== "completed") {
+          return createErrorResponse(
+            result.errorMessage ?? "Rename failed",
+            corsHeaders,
+            500,
           );
         }
-
-        // Trigger webhook for bucket rename
-        void triggerWebhooks(
-          env,
-          "bucket_rename",
-          createBucketRenamePayload(
-            oldBucketName,
-            newBucketName ?? "",
-            userEmail,
-          ),
-          isLocalDev,
-        );
-
-        return new Response(
-          JSON.stringify({ success: true, newName: newBucketName }),
-          {
-            status: 200,
-            headers: { "Content-Type": "application/json", ...corsHeaders },
-          },
+        return createJobResponse(
+          result,
+          { newName: newBucketName, copied: result.processedItems },
+          corsHeaders,
         );
       } catch (err) {
         void logError(
@@ -0,0 +1,366 @@
+/**
+ * Event Notification Routes
+ *
+ * Rules that send R2 object events (create, copy, delete, lifecycle
+ * deletion) of a bucket to a Cloudflare Queue, proxied to the Cloudflare
+ * REST API.
+ */
+
+import type {
+  BucketEventNotificationRule,
+  CloudflareApiResponse,
+  CreateEventNotificationBody,
+  Env,
+  EventNotificationAction,
+  EventNotificationConfiguration,
+} from "../types";
+import { CF_API } from "../types";
+import { type CorsHeaders } from "../utils/cors";
+import { getCloudflareHeaders } from "../utils/helpers";
+import { logError, logInfo, logWarning } from "../utils/error-logger";
+import { createErrorResponse } from "../utils/error-response";
+
+const EVENT_ACTIONS: readonly EventNotificationAction[] = [
+  "PutObject",
+  "CopyObject",
+  "DeleteObject",
+  "CompleteMultipartUpload",
+  "LifecycleDeletion",
+];
+
+interface QueueSummary {
+  queueId: string;
+  queueName: string;
+}
+
+function jsonResponse(body: unknown, corsHeaders: CorsHeaders): Response {
+  return new Response(JSON.stringify(body), {
+    headers: { "Content-Type": "application/json", ...corsHeaders },
+  });
+}
+
+async function readCloudflareError(
+  response: Response,
+  fallback: string,
+): Promise<string> {
+  const errorData = (await response.json().catch(() => ({}))) as {
+    errors?: { message: string }[];
+  };
+  return errorData.errors?.[0]?.message ?? fallback;
+}
+
+/**
+ * Whether two prefixes (or suffixes, with endsWith) can match the same key.
+ * An empty filter matches every key.
+ */
+function filtersOverlap(
+  a: string,
+  b: string,
+  matches: (value: string, filter: string) => boolean,
+): boolean {
+  return a === "" || b === "" || matches(a, b) || matches(b, a);
+}
+
+/**
+ * The first existing rule that an object event could match together with
+ * the new rule: both handle one of the same actions, and one key can match
+ * both prefixes and both suffixes. R2 delivers each event to one rule, so
+ * such rules are rejected.
+ */
+function findOverlappingRule(
+  rule: Pick<CreateEventNotificationBody, "actions" | "prefix" | "suffix">,
+  existing: BucketEventNotificationRule[],
+): BucketEventNotificationRule | null {
+  const prefix = rule.prefix ?? "";
+  const suffix = rule.suffix ?? "";
+  return (
+    existing.find(
+      (other) =>
+        other.actions.some((action) => rule.actions.includes(action)) &&
+        filtersOverlap(prefix, other.prefix ?? "", (value, filter) =>
+          value.startsWith(filter),
+        ) &&
+        filtersOverlap(suffix, other.suffix ?? "", (value, filter) =>
+          value.endsWith(filter),
+        ),
+    ) ?? null
+  );
+}
+
+/**
+ * Flatten a bucket's per-queue configuration into a list of rules
+ */
+function flattenRules(
+  config: EventNotificationConfiguration | undefined,
+): BucketEventNotificationRule[] {
+  return (config?.queues ?? []).flatMap((queue) =>
+    queue.rules.map((rule) => ({
+      ...rule,
+      queueId: queue.queueId,
+      queueName: queue.queueName,
+    })),
+  );
+}
+
+/**
+ * Get the event notification rules of a bucket
+ */
+async function getBucketRules(
+  env: Env,
+  bucketName: string,
+): Promise<BucketEventNotificationRule[]> {
+  const response = await fetch(
+    `${CF_API}/accounts/${env.ACCOUNT_ID}/event_notifications/r2/${encodeURIComponent(bucketName)}/configuration`,
+    { headers: getCloudflareHeaders(env) },
+  );
+  // 404 means the bucket has no notification configuration
+  if (response.status === 404) {
+    return [];
+  }
+  if (!response.ok) {
+    throw new Error(
+      await readCloudflareError(
+        response,
+        "Failed to get event notification rules",
+      ),
+    );
+  }
+  const data =
+    (await response.json()) as CloudflareApiResponse<EventNotificationConfiguration>;
+  return flattenRules(data.result);
+}
+
+/**
+ * Queues in the account that rules can send to
+ */
+async function listQueues(env: Env): Promise<QueueSummary[]> {
+  const response = await fetch(
+    `${CF_API}/accounts/${env.ACCOUNT_ID}/queues?per_page=100`,
+    { headers: getCloudflareHeaders(env) },
+  );
+  if (!response.ok) {
+    // The API token may lack Queues permission; rules can still be listed
+    logWarning(`Failed to list queues: ${response.status}`, {
+      module: "event_notifications",
+      operation: "list_queues",
+    });
+    return [];
+  }
+  const data = (await response.json()) as CloudflareApiResponse<
+    { queue_id: string; queue_name: string }[]
+  >;
+  return (data.result ?? []).map((queue) => ({
+    queueId: queue.queue_id,
+    queueName: queue.queue_name,
+  }));
+}
+
+/**
+ * Handle event notification routes
+ *
+ * Endpoints (under /api/event-notifications/:bucketName):
+ * - GET    /                  - Rules and the queues they can send to
+ * - POST   /                  - Create a rule
+ * - DELETE /:queueId/:ruleId  - Delete a rule
+ */
+export async function handleEventNotificationRoutes(
+  request: Request,
+  env: Env,
+  url: URL,
+  corsHeaders: CorsHeaders,
+  isLocalDev: boolean,
+  userEmail: string,
+): Promise<Response> {
+  const match =
+    /^\/api\/event-notifications\/([^/]+)(?:\/([^/]+)\/([^/]+))?$/.exec(
+      url.pathname,
+    );
+  if (match === null) {
+    return new Response("Not Found", { status: 404, headers: corsHeaders });
+  }
+
+  const bucketName = decodeURIComponent(match[1] ?? "");
+  const queueId = match[2] !== undefined ? decodeURIComponent(match[2]) : null;
+  const ruleId = match[3] !== undefined ? decodeURIComponent(match[3]) : null;
+  const method = request.method;
+
+  try {
+    // GET - Rules of the bucket and the queues they can send to
+    if (method === "GET" && queueId === null) {
+      if (isLocalDev) {
+        return jsonResponse(
+          {
+            success: true,
+            result: {
+              rules: [
+                {
+                  ruleId: "mock-rule",
+                  queueId: "mock-queue-id",
+                  queueName: "r2-events",
+                  actions: ["PutObject", "CompleteMultipartUpload"],
+                  prefix: "uploads/",
+                  suffix: ".jpg",
+                  description: "New images",
+                  createdAt: new Date().toISOString(),
+                },
+              ],
+              queues: [{ queueId: "mock-queue-id", queueName: "r2-events" }],
+            },
+          },
+          corsHeaders,
+        );
+      }
+
+      const [rules, queues] = await Promise.all([
+        getBucketRules(env, bucketName),
+        listQueues(env),
+      ]);
+      return jsonResponse(
+        { success: true, result: { rules, queues } },
+        corsHeaders,
+      );
+    }
+
+    // POST - Create a rule
+    if (method === "POST" && queueId === null) {
+      const body =
+        (await request.json()) as Partial<CreateEventNotificationBody>;
+      const actions = body.actions;
+      if (typeof body.queueId !== "string" || body.queueId === "") {
+        return createErrorResponse("queueId is required", corsHeaders, 400);
+      }
+      if (!Array.isArray(actions) || actions.length === 0) {
+        return createErrorResponse(
+          "At least one action is required",
+          corsHeaders,
+          400,
+        );
+      }
+      const badAction = actions.find((a) => !EVENT_ACTIONS.includes(a));
+      if (badAction !== undefined) {
+        return createErrorResponse(
+          `Unsupported action: ${String(badAction)}`,
+          corsHeaders,
+          400,
+        );
+      }
+
+      const rule = {
+        actions: [...new Set(actions)],
+        ...(body.prefix !== undefined &&
+          body.prefix !== "" && { prefix: body.prefix }),
+        ...(body.suffix !== undefined &&
+          body.suffix !== "" && { suffix: body.suffix }),
+        ...(body.description !== undefined &&
+          body.description !== "" && { description: body.description }),
+      };
+
+      if (isLocalDev) {
+        return jsonResponse({ success: true, result: rule }, corsHeaders);
+      }
+
+      const existing = await getBucketRules(env, bucketName);
+      const overlap = findOverlappingRule(rule, existing);
+      if (overlap !== null) {
+        const filters = [
+          overlap.prefix !== undefined ? `prefix "${overlap.prefix}"` : null,
+          overlap.suffix !== undefined ? `suffix "${overlap.suffix}"` : null,
+        ].filter((f) => f !== null);
+        return createErrorResponse(
+          `This rule overlaps an existing rule for queue ${overlap.queueName} (${filters.length > 0 ? filters.join(", ") : "all objects"}; ${overlap.actions.join(", ")}). Rules that share an action must not match the same objects.`,
+          corsHeaders,
+          409,
+        );
+      }
+
+      const response = await fetch(
+        `${CF_API}/accounts/${env.ACCOUNT_ID}/event_notifications/r2/${encodeURIComponent(bucketName)}/configuration/queues/${encodeURIComponent(body.queueId)}`,
+        {
+          method: "PUT",
+          headers: getCloudflareHeaders(env),
+          body: JSON.stringify({ rules: [rule] }),
+        },
+      );
+      if (!response.ok) {
+        const errorMessage = await readCloudflareError(
+          response,
+          "Failed to create event notification rule",
+        );
+        logWarning(
+          `Failed to create event notification rule: ${errorMessage}`,
+          {
+            module: "event_notifications",
+            operation: "create",
+            bucketName,
+            metadata: { queueId: body.queueId, status: response.status },
+          },
+        );
+        return createErrorResponse(errorMessage, corsHeaders, response.status);
+      }
+
+      logInfo(`Created event notification rule for bucket: ${bucketName}`, {
+        module: "event_notifications",
+        operation: "create",
+        bucketName,
+        userId: userEmail,
+        metadata: { queueId: body.queueId, ...rule },
+      });
+      return jsonResponse({ success: true, result: rule }, corsHeaders);
+    }
+
+    // DELETE /:queueId/:ruleId - Delete a rule
+    if (method === "DELETE" && queueId !== null && ruleId !== null) {
+      if (isLocalDev) {
+        return jsonResponse({ success: true }, corsHeaders);
+      }
+
+      const response = await fetch(
+        `${CF_API}/accounts/${env.ACCOUNT_ID}/event_notifications/r2/${encodeURIComponent(bucketName)}/configuration/queues/${encodeURIComponent(queueId)}`,
+        {
+          method: "DELETE",
+          headers: getCloudflareHeaders(env),
+          body: JSON.stringify({ ruleIds: [ruleId] }),
+        },
+      );
+      if (!response.ok) {
+        const errorMessage = await readCloudflareError(
+          response,
+          "Failed to delete event notification rule",
+        );
+        return createErrorResponse(errorMessage, corsHeaders, response.status);
+      }
+
+      logInfo(`Deleted event notification rule for bucket: ${bucketName}`, {
+        module: "event_notifications",
+        operation: "delete",
+        bucketName,
+        userId: userEmail,
+        metadata: { queueId, ruleId },
+      });
+      return jsonResponse({ success: true }, corsHeaders);
+    }
+
+    return new Response("Method Not Allowed", {
+      status: 405,
+      headers: corsHeaders,
+    });
+  } catch (err) {
+    void logError(
+      env,
+      err instanceof Error ? err : new Error(String(err)),
+      {
+        module: "event_notifications",
+        operation: method.toLowerCase(),
+        bucketName,
+      },
+      isLocalDev,
+    );
+    return createErrorResponse(
+      err instanceof Error
+        ? err.message
+        : "Event notification operation failed",
+      corsHeaders,
+      500,
+    );
+  }
+}
@@ -1,14 +1,32 @@
-import JSZip from "jszip";
-import type { Env, CloudflareApiResponse, JobOperationType } from "../types";
-import { CF_API } from "../types";
-import { generateSignature } from "../utils/signing";
-import { getCloudflareHeaders } from "../utils/helpers";
+import type { Env, JobOperationType, StorageObjectBody } from "../types";
+import { createSignedPath, type SignedUrlDisposition } from "../utils/signing";
+import {
+  DEFAULT_SIGNED_LINK_TTL_SECONDS,
+  MAX_SIGNED_LINK_TTL_SECONDS,
+  createSignedLink,
+  generateLinkId,
+} from "../utils/signed-links";
+import {
+  copyObject,
+  getBucketBinding,
+  getObjectStorage,
+} from "../utils/storage";
+import { createZipStream, type ZipEntry } from "../utils/zip-stream";
+import {
+  createUploadSession,
+  recordUploadedPart,
+  getUploadedParts,
+  finishUploadSession,
+  getUploadSession,
+  listUploadSessions,
+} from "../utils/upload-sessions";
 import {
   generateJobId,
   createJob,
   updateJobProgress,
   completeJob,
-  logJobEvent,
+  getJobStatus,
+  recordJobItem,
 } from "./jobs";
 import { logAuditEvent } from "./audit";
 import { logError, logInfo, logWarning } from "../utils/error-logger";
@@ -19,6 +37,17 @@ import {
   createFileRenamePayload,
 } from "../utils/webhooks";
 import { createErrorResponse } from "../utils/error-response";
+import { canSeeKey, getRequestAccess, satisfiesCheck } from "../utils/rbac";
+import {
+  createTrashKeyResponse,
+  deleteOrTrashObject,
+  isTrashKey,
+  trashBeforeOverwrite,
+} from "../utils/trash";
+import {
+  checkObjectLock,
+  createObjectLockedResponse,
+} from "../utils/bucket-lock";
 
 interface MultiBucketDownloadBody {
   buckets: { bucketName: string; files: string[] }[];
@@ -28,6 +57,13 @@ interface ZipDownloadBody {
   files: string[];
 }
 
+interface ZipDownloadItem {
+  bucketName: string;
+  key: string;
+  /** Path of the object inside the archive */
+  entryName: string;
+}
+
 interface TransferBody {
   destinationBucket: string;
   destinationPath?: string;
@@ -37,19 +73,26 @@ interface RenameFileBody {
   newKey?: string;
 }
 
-interface ListFilesResponseResult {
-  objects: {
-    key: string;
-    size?: number;
-    uploaded?: string;
-    etag?: string;
-    httpEtag?: string;
-    version?: string;
-    checksums?: Record<string, string>;
-    httpMetadata?: Record<string, string>;
-    customMetadata?: Record<string, string>;
-  }[];
-  delimited?: string[];
+const MULTIPART_ACTIONS = ["create", "part", "complete", "abort"];
+
+interface CreateMultipartBody {
+  key?: string;
+  contentType?: string;
+  fileFingerprint?: string;
+  fileSize?: number;
+  partSize?: number;
+  totalParts?: number;
+}
+
+interface CompleteMultipartBody {
+  key?: string;
+  uploadId?: string;
+  parts?: { partNumber: number; etag: string }[];
+}
+
+interface AbortMultipartBody {
+  key?: string;
+  uploadId?: string;
 }
 
 export async function handleFileRoutes(
@@ -65,8 +108,6 @@ export async function handleFileRoutes(
   const bucketName = parts[3];
   const db = env.METADATA;
 
-  const cfHeaders = getCloudflareHeaders(env);
-
   // Handle multi-bucket ZIP download
   if (
     request.method === "POST" &&
@@ -74,9 +115,6 @@ export async function handleFileRoutes(
   ) {
     const jobId = generateJobId("bulk_download");
     const operationType: JobOperationType = "bulk_download";
-    let totalFiles: number;
-    let processedFiles = 0;
-    let errorCount = 0;
 
     try {
       logInfo("Processing multi-bucket ZIP download request", {
@@ -84,9 +122,19 @@ export async function handleFileRoutes(
         operation: "multi_download",
       });
       const { buckets } = (await request.json()) as MultiBucketDownloadBody;
+      if (
+        !Array.isArray(buckets) ||
+        !buckets.every((entry) => Array.isArray(entry.files))
+      ) {
+        return createErrorResponse(
+          "buckets must be a list of { bucketName, files }",
+          corsHeaders,
+          400,
+        );
+      }
 
       // Calculate total files
-      totalFiles = buckets.reduce((sum, b) => sum + b.files.length, 0);
+      const totalFiles = buckets.reduce((sum, b) => sum + b.files.length, 0);
       const bucketNames = buckets.map((b) => b.bucketName).join(", ");
 
       // Create job record
@@ -106,132 +154,35 @@ export async function handleFileRoutes(
         });
       }
 
-      const zip = new JSZip();
-
-      for (const bucket of buckets) {
-        logInfo(`Processing bucket: ${bucket.bucketName}`, {
-          module: "files",
-          operation: "multi_download",
+      // Each bucket becomes a top-level folder in the archive
+      const items: ZipDownloadItem[] = buckets.flatMap((bucket) =>
+        bucket.files.map((fileName) => ({
           bucketName: bucket.bucketName,
-        });
-        const bucketFolder = zip.folder(bucket.bucketName);
-
-        if (bucketFolder === null) {
-          throw new Error(
-            "Failed to create folder for bucket: " + bucket.bucketName,
-          );
-        }
-
-        for (const fileName of bucket.files) {
-          logInfo(`Fetching: ${fileName} from bucket: ${bucket.bucketName}`, {
-            module: "files",
-            operation: "multi_download",
-            bucketName: bucket.bucketName,
-            fileName,
-          });
-          try {
-            const response = await fetch(
-              CF_API +
-                "/accounts/" +
-                env.ACCOUNT_ID +
-                "/r2/buckets/" +
-                bucket.bucketName +
-                "/objects/" +
-                fileName,
-              { headers: cfHeaders },
-            );
-
-            if (!response.ok) {
-              errorCount++;
-              if (db) {
-                await logJobEvent(db, {
-                  jobId,
-                  eventType: "error",
-                  userEmail,
-                  details: {
-                    file: fileName,
-                    bucket: bucket.bucketName,
-                    error: "Failed to fetch file",
-                  },
-                });
-              }
-              continue;
-            }
-
-            const buffer = await response.arrayBuffer();
-            bucketFolder.file(fileName, buffer);
-            processedFiles++;
-
-            // Update progress every 5 files or on last file
-            if (
-              db &&
-              (processedFiles % 5 === 0 || processedFiles === totalFiles)
-            ) {
-              await updateJobProgress(db, {
-                jobId,
-                processedItems: processedFiles,
-                totalItems: totalFiles,
-                errorCount,
-              });
-            }
-          } catch (fileErr) {
-            errorCount++;
-            void logError(
-              env,
-              fileErr instanceof Error ? fileErr : new Error(String(fileErr)),
-              {
-                module: "files",
-                operation: "multi_download",
-                bucketName: bucket.bucketName,
-                fileName,
-              },
-              isLocalDev,
-            );
-            if (db) {
-              await logJobEvent(db, {
-                jobId,
-                eventType: "error",
-                userEmail,
-                details: {
-                  file: fileName,
-                  bucket: bucket.bucketName,
-                  error: String(fileErr),
-                },
-              });
-            }
-          }
-        }
-      }
-
-      logInfo("Creating multi-bucket ZIP", {
-        module: "files",
-        operation: "multi_download",
-      });
-      const zipContent = await zip.generateAsync({ type: "uint8array" });
-
-      // Complete the job
-      if (db) {
-        await completeJob(db, {
-          jobId,
-          status: errorCount > 0 ? "completed" : "completed",
-          processedItems: processedFiles,
-          errorCount,
-          userEmail,
-        });
-      }
+          key: fileName,
+          entryName: bucket.bucketName + "/" + fileName,
+        })),
+      );
 
       const timestamp = new Date()
         .toISOString()
         .replace(/[:.]/g, "-")
         .slice(0, -5);
-      return new Response(zipContent.buffer as ArrayBuffer, {
-        headers: {
-          "Content-Type": "application/zip",
-          "Content-Disposition":
-            'attachment; filename="buckets-' + timestamp + '.zip"',
-          ...corsHeaders,
+      return new Response(
+        createZipDownloadStream(env, items, {
+          jobId,
+          userEmail,
+          operation: "multi_download",
+          isLocalDev,
+        }),
+        {
+          headers: {
+            "Content-Type": "application/zip",
+            "Content-Disposition":
+              'attachment; filename="buckets-' + timestamp + '.zip"',
+            ...corsHeaders,
+          },
         },
-      });
+      );
     } catch (err) {
       void logError(
         env,
@@ -240,15 +191,16 @@ export async function handleFileRoutes(
         isLocalDev,
       );
 
-      // Mark job as failed
+      // Mark job as failed (a cancelled job keeps its status)
       if (db) {
         await completeJob(db, {
           jobId,
           status: "failed",
-          processedItems: processedFiles,
-          errorCount: errorCount + 1,
+          processedItems: 0,
+          errorCount: 1,
           userEmail,
           errorMessage: String(err),
+          fromStatuses: ["running"],
         });
       }
 
@@ -264,9 +216,6 @@ export async function handleFileRoutes(
   if (request.method === "POST" && parts[4] === "download-zip") {
     const jobId = generateJobId("bulk_download");
     const operationType: JobOperationType = "bulk_download";
-    let processedFiles = 0;
-    let errorCount = 0;
-    let totalFiles: number;
     const targetBucket = bucketName ?? "unknown";
 
     try {
@@ -277,7 +226,6 @@ export async function handleFileRoutes(
       });
       const body = (await request.json()) as ZipDownloadBody;
       const files = body.files;
-      totalFiles = files.length;
 
       // Create job record
       if (db) {
@@ -285,112 +233,34 @@ export async function handleFileRoutes(
           jobId,
           bucketName: targetBucket,
           operationType,
-          totalItems: totalFiles,
+          totalItems: files.length,
           userEmail,
           metadata: { files },
         });
       }
 
-      const zip = new JSZip();
+      const items: ZipDownloadItem[] = files.map((fileName) => ({
+        bucketName: bucketName ?? "",
+        key: fileName,
+        entryName: fileName,
+      }));
 
-      for (const fileName of files) {
-        logInfo(`Fetching: ${fileName}`, {
-          module: "files",
-          operation: "download_zip",
-          bucketName: bucketName ?? "unknown",
-          fileName,
-        });
-        try {
-          const response = await fetch(
-            CF_API +
-              "/accounts/" +
-              env.ACCOUNT_ID +
-              "/r2/buckets/" +
-              bucketName +
-              "/objects/" +
-              fileName,
-            { headers: cfHeaders },
-          );
-
-          if (!response.ok) {
-            errorCount++;
-            if (db) {
-              await logJobEvent(db, {
-                jobId,
-                eventType: "error",
-                userEmail,
-                details: { file: fileName, error: "Failed to fetch file" },
-              });
-            }
-            continue;
-          }
-
-          const buffer = await response.arrayBuffer();
-          zip.file(fileName, buffer);
-          processedFiles++;
-
-          // Update progress every 5 files or on last file
-          if (
-            db &&
-            (processedFiles % 5 === 0 || processedFiles === totalFiles)
-          ) {
-            await updateJobProgress(db, {
-              jobId,
-              processedItems: processedFiles,
-              totalItems: totalFiles,
-              errorCount,
-            });
-          }
-        } catch (fileErr) {
-          errorCount++;
-          void logError(
-            env,
-            fileErr instanceof Error ? fileErr : new Error(String(fileErr)),
-            {
-              module: "files",
-              operation: "download_zip",
-              bucketName: bucketName ?? "unknown",
-              fileName,
-            },
-            isLocalDev,
-          );
-          if (db) {
-            await logJobEvent(db, {
-              jobId,
-              eventType: "error",
-              userEmail,
-              details: { file: fileName, error: String(fileErr) },
-            });
-          }
-        }
-      }
-
-      logInfo("Creating ZIP", {
-        module: "files",
-        operation: "download_zip",
-        bucketName: bucketName ?? "unknown",
-      });
-      const zipContent = await zip.generateAsync({ type: "uint8array" });
-
-      // Complete the job
-      if (db) {
-        await completeJob(db, {
+      return new Response(
+        createZipDownloadStream(env, items, {
           jobId,
-          status: "completed",
-          processedItems: processedFiles,
-          errorCount,
           userEmail,
-        });
-      }
-
-      return new Response(zipContent.buffer as ArrayBuffer, {
-        headers: {
-          "Content-Type": "application/zip",
-          "Content-Disposition":
-            'attachment; filename="' + bucketName + '-files.zip"',
-          ...corsHeaders,
+          operation: "download_zip",
+          isLocalDev,
+        }),
+        {
+          headers: {
+            "Content-Type": "application/zip",
+            "Content-Disposition":
+              'attachment; filename="' + bucketName + '-files.zip"',
+            ...corsHeaders,
+          },
         },
-      });
+      );
     } catch (err) {
       void logError(
         env,
@@ -403,15 +273,16 @@ export async function handleFileRoutes(
         isLocalDev,
       );
 
-      // Mark job as failed
+      // Mark job as failed (a cancelled job keeps its status)
       if (db) {
         await completeJob(db, {
           jobId,
           status: "failed",
-          processedItems: processedFiles,
-          errorCount: errorCount + 1,
+          processedItems: 0,
+          errorCount: 1,
           userEmail,
           errorMessage: String(err),
+          fromStatuses: ["running"],
         });
       }
 
@@ -419,6 +290,74 @@ export async function handleFileRoutes(
     }
   }
 
+  // List in-progress (resumable) upload sessions
+  if (
+    request.method === "GET" &&
+    parts.length === 5 &&
+    parts[4] === "uploads"
+  ) {
+    try {
+      const objectKey = url.searchParams.get("key");
+      const fileFingerprint = url.searchParams.get("fingerprint");
+
+      if (isLocalDev || !db) {
+        return new Response(JSON.stringify({ success: true, sessions: [] }), {
+          headers: {
+            "Content-Type": "application/json",
+            "Cache-Control": "no-cache",
+            ...corsHeaders,
+          },
+        });
+      }
+
+      // Only bucket admins see other users' sessions
+      const access = getRequestAccess(request);
+      const isBucketAdmin =
+        access === undefined ||
+        satisfiesCheck(access, {
+          role: "admin",
+          scope: "bucket",
+          bucket: bucketName ?? "",
+        });
+
+      const sessions = await listUploadSessions(db, bucketName ?? "", {
+        objectKey,
+        fileFingerprint,
+        userEmail: isBucketAdmin ? null : userEmail,
+      });
+
+      logInfo(`Found ${sessions.length} in-progress upload session(s)`, {
+        module: "files",
+        operation: "list_uploads",
+        bucketName: bucketName ?? "unknown",
+      });
+
+      return new Response(JSON.stringify({ success: true, sessions }), {
+        headers: {
+          "Content-Type": "application/json",
+          "Cache-Control": "no-cache",
+          ...corsHeaders,
+        },
+      });
+    } catch (err) {
+      void logError(
+        env,
+        err instanceof Error ? err : new Error(String(err)),
+        {
+          module: "files",
+          operation: "list_uploads",
+          bucketName: bucketName ?? "unknown",
+        },
+        isLocalDev,
+      );
+      return createErrorResponse(
+        "Failed to list upload sessions",
+        corsHeaders,
+        500,
+      );
+    }
+  }
+
   // List files with pagination
   if (request.method === "GET" && parts.length === 4) {
     try {
@@ -451,34 +390,15 @@ export async function handleFileRoutes(
           {
             headers: {
               "Content-Type": "application/json",
-              "Cache-Control": skipCache ? "no-cache" : "public, max-age=60",
+              "Cache-Control": skipCache ? "no-cache" : "private, max-age=60",
               ...corsHeaders,
             },
           },
         );
       }
 
-      let apiUrl =
-        CF_API +
-        "/accounts/" +
-        env.ACCOUNT_ID +
-        "/r2/buckets/" +
-        bucketName +
-        "/objects" +
-        "?include=customMetadata,httpMetadata" +
-        "&per_page=" +
-        String(limit) +
-        "&delimiter=/" +
-        "&order=desc" +
-        "&sort_by=last_modified";
-
-      if (cursor !== null && cursor !== "") {
-        apiUrl += "&cursor=" + cursor;
-      }
-
-      // Add prefix parameter for folder navigation
+      // Add prefix filter for folder navigation
       if (prefix !== null && prefix !== "") {
-        apiUrl += "&prefix=" + encodeURIComponent(prefix);
         logInfo(`Using prefix filter: ${prefix}`, {
           module: "files",
           operation: "list",
@@ -487,70 +407,67 @@ export async function handleFileRoutes(
         });
       }
 
-      // Add cache-busting parameter if requested
-      if (skipCache) {
-        apiUrl += "&_t=" + String(Date.now());
-      }
-
-      logInfo(`List request URL: ${apiUrl}`, {
-        module: "files",
-        operation: "list",
-        bucketName: bucketName ?? "unknown",
-      });
-      const response = await fetch(apiUrl, {
-        headers: {
-          ...cfHeaders,
-          "Cache-Control": skipCache ? "no-cache" : "max-age=60",
-        },
+      const storage = getObjectStorage(env, bucketName ?? "");
+      const page = await storage.list({
+        prefix: prefix !== null && prefix !== "" ? prefix : undefined,
+        cursor: cursor !== null && cursor !== "" ? cursor : undefined,
+        limit,
+        delimiter: "/",
+        newestFirst: true,
       });
 
-      if (!response.ok) {
-        throw new Error("Failed to list files: " + String(response.status));
-      }
-
-      const data = (await response.json()) as CloudflareApiResponse<
-        ListFilesResponseResult["objects"]
-      >;
       logInfo("List response received", {
         module: "files",
         operation: "list",
         bucketName: bucketName ?? "unknown",
         metadata: {
-          url: apiUrl,
-          objectsSample: data.result?.slice(0, 3),
-          total: data.result?.length ?? 0,
-          pagination: data.result_info,
+          native: storage.isNative,
+          objectsSample: page.objects.slice(0, 3).map((obj) => obj.key),
+          total: page.objects.length,
+          truncated: page.truncated,
         },
       });
 
+      // Listing URLs (previews and downloads) expire on an hour boundary
+      // so they stay stable, and cacheable, between listings
+      const listingUrlExpiry = (Math.ceil(Date.now() / 3_600_000) + 1) * 3600;
+
+      // Only list what the user's grants cover
+      const access = getRequestAccess(request);
+      const listBucket = bucketName ?? "";
+
       // Filter out assets folder, .keep files, and process objects
-      const fileList: ListFilesResponseResult["objects"] = Array.isArray(
-        data.result,
-      )
-        ? data.result
-        : [];
-      const objectPromises = fileList
+      const objectPromises = page.objects
         .filter(
-          (obj: ListFilesResponseResult["objects"][0]) =>
+          (obj) =>
             !obj.key.startsWith("assets/") &&
+            !isTrashKey(obj.key) &&
             !obj.key.endsWith("/.keep") &&
-            obj.key !== ".keep",
+            obj.key !== ".keep" &&
+            (access === undefined ||
+              satisfiesCheck(access, {
+                role: "viewer",
+                scope: "key",
+                bucket: listBucket,
+                key: obj.key,
+              })),
         )
-        .map(async (obj: ListFilesResponseResult["objects"][0]) => {
+        .map(async (obj) => {
           const downloadPath =
             "/api/files/" + bucketName + "/download/" + obj.key;
-          const version =
-            obj.uploaded !== undefined
-              ? new Date(obj.uploaded).getTime()
-              : Date.now();
-          const versionedPath = downloadPath + "?ts=" + String(version);
-          const signature = await generateSignature(versionedPath, env);
-          const signedUrl = versionedPath + "&sig=" + signature;
+          const signedUrl = await createSignedPath(
+            downloadPath,
+            {
+              expires: listingUrlExpiry,
+              version: new Date(obj.uploaded).getTime(),
+            },
+            env,
+          );
 
           return {
             key: obj.key,
             size: obj.size,
-            uploaded: obj.uploaded ?? new Date().toISOString(),
+            uploaded: obj.uploaded,
             url: signedUrl,
           };
         });
@@ -563,11 +480,10 @@ export async function handleFileRoutes(
           new Date(b.uploaded).getTime() - new Date(a.uploaded).getTime(),
       );
 
-      // Extract folders from the API response
-      // The Cloudflare REST API returns folders in data.result_info.delimited
-      const rawPrefixes: string[] = data.result_info?.delimited ?? [];
+      // Extract folders from the delimited prefixes
+      const rawPrefixes: string[] = page.delimitedPrefixes;
       logInfo(
-        `Found folders in result_info.delimited: ${JSON.stringify(rawPrefixes)}`,
+        `Found folders in delimited prefixes: ${JSON.stringify(rawPrefixes)}`,
         {
           module: "files",
           operation: "list",
@@ -577,7 +493,12 @@ export async function handleFileRoutes(
       );
 
       const folders = rawPrefixes
-        .filter((prefix: string) => !prefix.startsWith("assets/"))
+        .filter(
+          (prefix: string) =>
+            !prefix.startsWith("assets/") &&
+            !isTrashKey(prefix) &&
+            (access === undefined || canSeeKey(access, listBucket, prefix)),
+        )
         .map((prefix: string) =>
           prefix.endsWith("/") ? prefix.slice(0, -1) : prefix,
         );
@@ -587,21 +508,16 @@ export async function handleFileRoutes(
         bucketName: bucketName ?? "unknown",
       });
 
-      // Determine if there are more results
-      // hasMore should be true only if the API indicates truncation AND we have a cursor for the next page
-      const apiHasMore = data.result_info?.is_truncated ?? false;
-      const hasValidCursor =
-        data.result_info?.cursor !== undefined &&
-        data.result_info.cursor !== "";
-      const hasMore = apiHasMore && hasValidCursor;
+      // hasMore is only true when the listing is truncated AND has a cursor
+      const hasMore = page.truncated && page.cursor !== undefined;
 
       logInfo("Pagination info", {
         module: "files",
         operation: "list",
         bucketName: bucketName ?? "unknown",
         metadata: {
-          apiTruncated: apiHasMore,
-          cursor: data.result_info?.cursor,
+          truncated: page.truncated,
+          cursor: page.cursor,
           hasMore,
           objectsReturned: objects.length,
           foldersReturned: folders.length,
@@ -614,14 +530,14 @@ export async function handleFileRoutes(
           objects,
           folders,
           pagination: {
-            cursor: data.result_info?.cursor,
+            cursor: page.cursor,
             hasMore: hasMore,
           },
         }),
         {
           headers: {
             "Content-Type": "application/json",
-            "Cache-Control": skipCache ? "no-cache" : "public, max-age=60",
+            "Cache-Control": skipCache ? "no-cache" : "private, max-age=60",
             ...corsHeaders,
           },
         },
@@ -685,8 +601,481 @@ export async function handleFileRoutes(
         return new Response(
           JSON.stringify({
             success: true,
-            key: decodeURIComponent(fileName),
-            timestamp: uploadTimestamp,
+            key: decodeURIComponent(fileName),
+            timestamp: uploadTimestamp,
+          }),
+          {
+            headers: {
+              "Content-Type": "application/json",
+              "Cache-Control": "no-cache",
+              ...corsHeaders,
+            },
+          },
+        );
+      }
+
+      const decodedFileName = decodeURIComponent(fileName);
+      if (isTrashKey(decodedFileName)) {
+        return createTrashKeyResponse(corsHeaders);
+      }
+      const uploadTimestamp = new Date().toISOString();
+
+      // Keep the version being replaced if the bucket has trash enabled
+      if (chunkIndex === 0) {
+        const lock = await checkObjectLock(
+          env,
+          bucketName ?? "",
+          decodedFileName,
+        );
+        if (lock !== null) {
+          return createObjectLockedResponse(lock, corsHeaders);
+        }
+        await trashBeforeOverwrite(
+          env,
+          bucketName ?? "",
+          decodedFileName,
+          userEmail,
+        );
+      }
+
+      // Upload through the bucket binding (or REST API fallback)
+      const stored = await getObjectStorage(env, bucketName ?? "").put(
+        decodedFileName,
+        file,
+        {
+          httpMetadata: {
+            contentType:
+              file.type !== "" ? file.type : "application/octet-stream",
+            cacheControl: "no-cache",
+          },
+          customMetadata: { uploadCreated: uploadTimestamp },
+        },
+      );
+      const etag = stored.etag;
+
+      if (totalChunks === 1) {
+        logInfo(`Upload completed: ${decodedFileName}, ETag: ${etag}`, {
+          module: "files",
+          operation: "upload",
+          bucketName: bucketName ?? "unknown",
+          fileName: decodedFileName,
+          metadata: { etag },
+        });
+      } else {
+        const chunkId = decodedFileName + "-" + String(chunkIndex);
+        logInfo(`Chunk uploaded: ${chunkId}, ETag: ${etag}`, {
+          module: "files",
+          operation: "upload_chunk",
+          bucketName: bucketName ?? "unknown",
+          fileName: decodedFileName,
+          metadata: { chunkId, etag },
+        });
+      }
+
+      // Log audit event for file upload (only for final chunk or single chunk uploads)
+      if (db && (totalChunks === 1 || chunkIndex === totalChunks - 1)) {
+        await logAuditEvent(
+          env,
+          {
+            operationType: "file_upload",
+            bucketName: bucketName ?? undefined,
+            objectKey: decodedFileName,
+            userEmail,
+            status: "success",
+            sizeBytes: file.size,
+            metadata: { etag, totalChunks },
+          },
+          isLocalDev,
+        );
+      }
+
+      return new Response(
+        JSON.stringify({
+          success: true,
+          timestamp: uploadTimestamp,
+          etag: etag,
+          chunkIndex: chunkIndex,
+        }),
+        {
+          headers: {
+            "Content-Type": "application/json",
+            "Cache-Control": "no-cache",
+            ...corsHeaders,
+          },
+        },
+      );
+    } catch (err) {
+      void logError(
+        env,
+        err instanceof Error ? err : new Error(String(err)),
+        {
+          module: "files",
+          operation: "upload",
+          bucketName: bucketName ?? "unknown",
+          fileName: fileName ? decodeURIComponent(fileName) : "unknown",
+        },
+        isLocalDev,
+      );
+
+      // Log failed upload
+      if (db) {
+        await logAuditEvent(
+          env,
+          {
+            operationType: "file_upload",
+            bucketName: bucketName ?? undefined,
+            objectKey: decodeURIComponent(fileName),
+            userEmail,
+            status: "failed",
+            metadata: { error: String(err) },
+          },
+          isLocalDev,
+        );
+      }
+
+      return createErrorResponse("Upload failed", corsHeaders, 500);
+    }
+  }
+
+  // Multipart upload lifecycle (create, part, complete, abort)
+  const multipartAction = parts[5];
+  if (
+    request.method === "POST" &&
+    parts.length === 6 &&
+    parts[4] === "multipart" &&
+    multipartAction !== undefined &&
+    MULTIPART_ACTIONS.includes(multipartAction)
+  ) {
+    const action = multipartAction;
+    const targetBucket = bucketName ?? "";
+
+    // Mock responses for local development
+    if (isLocalDev) {
+      logInfo(`Simulating multipart ${action} for local development`, {
+        module: "files",
+        operation: "multipart",
+        bucketName: targetBucket,
+      });
+      const partNumber = parseInt(url.searchParams.get("partNumber") ?? "1");
+      const mockResult =
+        action === "create"
+          ? { uploadId: "mock-upload-" + String(Date.now()) }
+          : action === "part"
+            ? { partNumber, etag: "mock-etag-" + String(partNumber) }
+            : {};
+      return new Response(JSON.stringify({ success: true, ...mockResult }), {
+        headers: {
+          "Content-Type": "application/json",
+          "Cache-Control": "no-cache",
+          ...corsHeaders,
+        },
+      });
+    }
+
+    const bucket = getBucketBinding(env, targetBucket);
+    if (bucket === null) {
+      return createErrorResponse(
+        `Multipart uploads require an R2 binding for bucket "${targetBucket}". Add it to R2_BUCKET_BINDINGS.`,
+        corsHeaders,
+        501,
+        { code: "MULTIPART_BINDING_MISSING" },
+      );
+    }
+
+    // Refuse trash keys, and an upload ID tracked for another bucket, key or
+    // user. Bucket admins may finish or abort other users' uploads.
+    const checkUploadSession = async (
+      key: string,
+      uploadId: string,
+    ): Promise<Response | null> => {
+      if (isTrashKey(key)) {
+        return createTrashKeyResponse(corsHeaders);
+      }
+      const session = db ? await getUploadSession(db, uploadId) : null;
+      if (session === null) {
+        return null;
+      }
+      if (session.bucket_name !== targetBucket || session.object_key !== key) {
+        return createErrorResponse(
+          "Upload ID does not belong to this bucket and key",
+          corsHeaders,
+          400,
+        );
+      }
+      const access = getRequestAccess(request);
+      if (
+        session.user_email !== userEmail &&
+        access !== undefined &&
+        !satisfiesCheck(access, {
+          role: "admin",
+          scope: "bucket",
+          bucket: targetBucket,
+        })
+      ) {
+        return createErrorResponse(
+          "Forbidden: this upload belongs to another user",
+          corsHeaders,
+          403,
+        );
+      }
+      return null;
+    };
+
+    // POST /api/files/:bucketName/multipart/create
+    if (action === "create") {
+      try {
+        const body = (await request.json()) as CreateMultipartBody;
+        const key = body.key?.trim();
+        if (key === undefined || key === "") {
+          return createErrorResponse("Missing object key", corsHeaders, 400);
+        }
+        if (isTrashKey(key)) {
+          return createTrashKeyResponse(corsHeaders);
+        }
+
+        // Refuse before any parts are uploaded if the upload would replace
+        // a locked object
+        const lock = await checkObjectLock(env, targetBucket, key);
+        if (lock !== null) {
+          return createObjectLockedResponse(lock, corsHeaders);
+        }
+
+        const multipartUpload = await bucket.createMultipartUpload(key, {
+          httpMetadata: {
+            contentType:
+              body.contentType !== undefined && body.contentType !== ""
+                ? body.contentType
+                : "application/octet-stream",
+          },
+          customMetadata: { uploadCreated: new Date().toISOString() },
+        });
+
+        if (db) {
+          await createUploadSession(db, {
+            uploadId: multipartUpload.uploadId,
+            bucketName: targetBucket,
+            objectKey: key,
+            fileFingerprint: body.fileFingerprint,
+            fileSize: body.fileSize,
+            partSize: body.partSize,
+            totalParts: body.totalParts,
+            contentType: body.contentType,
+            userEmail,
+          });
+        }
+
+        logInfo(`Multipart upload created: ${key}`, {
+          module: "files",
+          operation: "multipart_create",
+          bucketName: targetBucket,
+          fileName: key,
+          metadata: { uploadId: multipartUpload.uploadId },
+        });
+
+        return new Response(
+          JSON.stringify({
+            success: true,
+            key,
+            uploadId: multipartUpload.uploadId,
+          }),
+          {
+            headers: {
+              "Content-Type": "application/json",
+              "Cache-Control": "no-cache",
+              ...corsHeaders,
+            },
+          },
+        );
+      } catch (err) {
+        void logError(
+          env,
+          err instanceof Error ? err : new Error(String(err)),
+          {
+            module: "files",
+            operation: "multipart_create",
+            bucketName: targetBucket,
+          },
+          isLocalDev,
+        );
+        return createErrorResponse(
+          "Failed to create multipart upload",
+          corsHeaders,
+          500,
+        );
+      }
+    }
+
+    // POST /api/files/:bucketName/multipart/part?key=&uploadId=&partNumber=
+    if (action === "part") {
+      const key = url.searchParams.get("key");
+      const uploadId = url.searchParams.get("uploadId");
+      const partNumber = parseInt(url.searchParams.get("partNumber") ?? "");
+
+      if (
+        key === null ||
+        key === "" ||
+        uploadId === null ||
+        uploadId === "" ||
+        isNaN(partNumber) ||
+        partNumber < 1 ||
+        partNumber > 10000
+      ) {
+        return createErrorResponse(
+          "Missing or invalid key, uploadId or partNumber",
+          corsHeaders,
+          400,
+        );
+      }
+      if (request.body === null) {
+        return createErrorResponse("Missing part body", corsHeaders, 400);
+      }
+
+      try {
+        const sessionError = await checkUploadSession(key, uploadId);
+        if (sessionError !== null) {
+          return sessionError;
+        }
+
+        const multipartUpload = bucket.resumeMultipartUpload(key, uploadId);
+        const uploadedPart = await multipartUpload.uploadPart(
+          partNumber,
+          request.body,
+        );
+
+        if (db) {
+          await recordUploadedPart(db, uploadId, {
+            partNumber: uploadedPart.partNumber,
+            etag: uploadedPart.etag,
+            md5: request.headers.get("X-Chunk-MD5"),
+          });
+        }
+
+        logInfo(`Part ${partNumber} uploaded: ${key}`, {
+          module: "files",
+          operation: "multipart_part",
+          bucketName: targetBucket,
+          fileName: key,
+          metadata: { uploadId, partNumber, etag: uploadedPart.etag },
+        });
+
+        return new Response(
+          JSON.stringify({
+            success: true,
+            partNumber: uploadedPart.partNumber,
+            etag: uploadedPart.etag,
+          }),
+          {
+            headers: {
+              "Content-Type": "application/json",
+              "Cache-Control": "no-cache",
+              ...corsHeaders,
+            },
+          },
+        );
+      } catch (err) {
+        void logError(
+          env,
+          err instanceof Error ? err : new Error(String(err)),
+          {
+            module: "files",
+            operation: "multipart_part",
+            bucketName: targetBucket,
+            fileName: key,
+            metadata: { uploadId, partNumber },
+          },
+          isLocalDev,
+        );
+        return createErrorResponse("Failed to upload part", corsHeaders, 500);
+      }
+    }
+
+    // POST /api/files/:bucketName/multipart/complete
+    if (action === "complete") {
+      let key = "";
+      try {
+        const body = (await request.json()) as CompleteMultipartBody;
+        key = body.key ?? "";
+        const uploadId = body.uploadId ?? "";
+        let uploadedParts = body.parts ?? [];
+
+        const sessionError = await checkUploadSession(key, uploadId);
+        if (sessionError !== null) {
+          return sessionError;
+        }
+
+        // Fall back to the parts recorded by the worker for this session
+        if (uploadedParts.length === 0 && db && uploadId !== "") {
+          uploadedParts = (await getUploadedParts(db, uploadId)).map(
+            (part) => ({ partNumber: part.part_number, etag: part.etag }),
+          );
+        }
+
+        if (key === "" || uploadId === "" || uploadedParts.length === 0) {
+          return createErrorResponse(
+            "Missing key, uploadId or parts",
+            corsHeaders,
+            400,
+          );
+        }
+
+        // R2 requires parts in ascending order
+        const sortedParts = [...uploadedParts].sort(
+          (a, b) => a.partNumber - b.partNumber,
+        );
+
+        // A lock may have been added since the upload was created
+        const lock = await checkObjectLock(env, targetBucket, key);
+        if (lock !== null) {
+          return createObjectLockedResponse(lock, corsHeaders);
+        }
+
+        await trashBeforeOverwrite(env, targetBucket, key, userEmail);
+
+        const multipartUpload = bucket.resumeMultipartUpload(key, uploadId);
+        const object = await multipartUpload.complete(sortedParts);
+
+        if (db) {
+          await finishUploadSession(db, uploadId, "completed");
+        }
+
+        logInfo(`Multipart upload completed: ${key}, ETag: ${object.etag}`, {
+          module: "files",
+          operation: "multipart_complete",
+          bucketName: targetBucket,
+          fileName: key,
+          metadata: {
+            uploadId,
+            etag: object.etag,
+            partCount: sortedParts.length,
+          },
+        });
+
+        if (db) {
+          await logAuditEvent(
+            env,
+            {
+              operationType: "file_upload",
+              bucketName: targetBucket,
+              objectKey: key,
+              userEmail,
+              status: "success",
+              sizeBytes: object.size,
+              metadata: {
+                etag: object.etag,
+                multipart: true,
+                partCount: sortedParts.length,
+              },
+            },
+            isLocalDev,
+          );
+        }
+
+        return new Response(
+          JSON.stringify({
+            success: true,
+            key,
+            etag: object.etag,
+            size: object.size,
           }),
           {
             headers: {
@@ -696,178 +1085,99 @@ export async function handleFileRoutes(
             },
           },
         );
-      }
-
-      const decodedFileName = decodeURIComponent(fileName);
-      const uploadUrl =
-        CF_API +
-        "/accounts/" +
-        env.ACCOUNT_ID +
-        "/r2/buckets/" +
-        bucketName +
-        "/objects/" +
-        decodedFileName;
-      const uploadTimestamp = new Date().toISOString();
-      let etag = "";
+      } catch (err) {
+        void logError(
+          env,
+          err instanceof Error ? err : new Error(String(err)),
+          {
+            module: "files",
+            operation: "multipart_complete",
+            bucketName: targetBucket,
+            fileName: key,
+          },
+          isLocalDev,
+        );
 
-      // Upload file using REST API
-      const uploadResponse = await fetch(uploadUrl, {
-        method: "PUT",
-        headers: {
-          ...cfHeaders,
-          "Content-Type":
-            file.type !== "" ? file.type : "application/octet-stream",
-          "X-Upload-Created": uploadTimestamp,
-          "Cache-Control": "no-cache",
-        },
-        body: file,
-      });
+        if (db && key !== "") {
+          await logAuditEvent(
+            env,
+            {
+              operationType: "file_upload",
+              bucketName: targetBucket,
+              objectKey: key,
+              userEmail,
+              status: "failed",
+              metadata: { error: String(err), multipart: true },
+            },
+            isLocalDev,
+          );
+        }
 
-      if (!uploadResponse.ok) {
-        throw new Error("Upload failed: " + String(uploadResponse.status));
+        return createErrorResponse(
+          "Failed to complete multipart upload",
+          corsHeaders,
+          500,
+        );
       }
+    }
 
-      // Try to get ETag from PUT response headers first
-      etag =
-        uploadResponse.headers.get("etag") ??
-        uploadResponse.headers.get("ETag") ??
-        "";
-
-      // If not in headers, try parsing from response body (R2 REST API returns JSON)
-      if (!etag) {
-        try {
-          const uploadResult = (await uploadResponse.json()) as {
-            result?: { etag?: string; httpEtag?: string };
-          };
-          etag =
-            uploadResult.result?.etag ?? uploadResult.result?.httpEtag ?? "";
-        } catch {
-          // Response might not be JSON, continue without ETag from body
+    // POST /api/files/:bucketName/multipart/abort
+    if (action === "abort") {
+      try {
+        const body = (await request.json()) as AbortMultipartBody;
+        const key = body.key ?? "";
+        const uploadId = body.uploadId ?? "";
+        if (key === "" || uploadId === "") {
+          return createErrorResponse(
+            "Missing key or uploadId",
+            corsHeaders,
+            400,
+          );
         }
-      }
 
-      // If still no ETag, do a GET request to list objects and find the file
-      if (!etag) {
-        const listUrl =
-          CF_API +
-          "/accounts/" +
-          env.ACCOUNT_ID +
-          "/r2/buckets/" +
-          bucketName +
-          "/objects?prefix=" +
-          encodeURIComponent(decodedFileName);
-        const listResponse = await fetch(listUrl, { headers: cfHeaders });
-
-        if (listResponse.ok) {
-          const listData = (await listResponse.json()) as CloudflareApiResponse<
-            { key: string; etag?: string; httpEtag?: string }[]
-          >;
-          const fileObj = listData.result?.find(
-            (obj) => obj.key === decodedFileName,
-          );
-          if (fileObj) {
-            etag = fileObj.etag ?? fileObj.httpEtag ?? "";
-          }
+        const sessionError = await checkUploadSession(key, uploadId);
+        if (sessionError !== null) {
+          return sessionError;
         }
-      }
 
-      if (totalChunks === 1) {
-        logInfo(`Upload completed: ${decodedFileName}, ETag: ${etag}`, {
-          module: "files",
-          operation: "upload",
-          bucketName: bucketName ?? "unknown",
-          fileName: decodedFileName,
-          metadata: { etag },
-        });
-      } else {
-        const chunkId = decodedFileName + "-" + String(chunkIndex);
-        logInfo(`Chunk uploaded: ${chunkId}, ETag: ${etag}`, {
-          module: "files",
-          operation: "upload_chunk",
-          bucketName: bucketName ?? "unknown",
-          fileName: decodedFileName,
-          metadata: { chunkId, etag },
-        });
-      }
+        const multipartUpload = bucket.resumeMultipartUpload(key, uploadId);
+        await multipartUpload.abort();
 
-      // Force a cache refresh of the file listing
-      await fetch(
-        CF_API +
-          "/accounts/" +
-          env.ACCOUNT_ID +
-          "/r2/buckets/" +
-          bucketName +
-          "/objects?skipCache=true",
-        {
-          headers: {
-            ...cfHeaders,
-            "Cache-Control": "no-cache",
-          },
-        },
-      );
+        if (db) {
+          await finishUploadSession(db, uploadId, "aborted");
+        }
 
-      // Log audit event for file upload (only for final chunk or single chunk uploads)
-      if (db && (totalChunks === 1 || chunkIndex === totalChunks - 1)) {
-        await logAuditEvent(
-          env,
-          {
-            operationType: "file_upload",
-            bucketName: bucketName ?? undefined,
-            objectKey: decodedFileName,
-            userEmail,
-            status: "success",
-            sizeBytes: file.size,
-            metadata: { etag, totalChunks },
-          },
-          isLocalDev,
-        );
-      }
+        logInfo(`Multipart upload aborted: ${key}`, {
+          module: "files",
+          operation: "multipart_abort",
+          bucketName: targetBucket,
+          fileName: key,
+          metadata: { uploadId },
+        });
 
-      return new Response(
-        JSON.stringify({
-          success: true,
-          timestamp: uploadTimestamp,
-          etag: etag,
-          chunkIndex: chunkIndex,
-        }),
-        {
+        return new Response(JSON.stringify({ success: true }), {
           headers: {
             "Content-Type": "application/json",
-            "Cache-Control": "no-cache",
             ...corsHeaders,
           },
-        },
-      );
-    } catch (err) {
-      void logError(
-        env,
-        err instanceof Error ? err : new Error(String(err)),
-        {
-          module: "files",
-          operation: "upload",
-          bucketName: bucketName ?? "unknown",
-          fileName: fileName ? decodeURIComponent(fileName) : "unknown",
-        },
-        isLocalDev,
-      );
-
-      // Log failed upload
-      if (db) {
-        await logAuditEvent(
+        });
+      } catch (err) {
+        void logError(
           env,
+          err instanceof Error ? err : new Error(String(err)),
           {
-            operationType: "file_upload",
-            bucketName: bucketName ?? undefined,
-            objectKey: decodeURIComponent(fileName),
-            userEmail,
-            status: "failed",
-            metadata: { error: String(err) },
+            module: "files",
+            operation: "multipart_abort",
+            bucketName: targetBucket,
           },
           isLocalDev,
         );
+        return createErrorResponse(
+          "Failed to abort multipart upload",
+          corsHeaders,
+          500,
+        );
       }
-
-      return createErrorResponse("Upload failed", corsHeaders, 500);
     }
   }
 
@@ -886,12 +1196,46 @@ export async function handleFileRoutes(
         fileName: key,
       });
 
+      const options = parseSignedUrlOptions(url, request);
+      if (typeof options === "string") {
+        return createErrorResponse(options, corsHeaders, 400);
+      }
+      if (options.maxDownloads !== undefined && !db) {
+        return createErrorResponse(
+          "Download limits require the METADATA database",
+          corsHeaders,
+          400,
+        );
+      }
+
+      const expiresAt = new Date(Date.now() + options.expiresIn * 1000);
+      const linkId = db ? generateLinkId() : undefined;
+      if (db && linkId) {
+        await createSignedLink(db, {
+          linkId,
+          bucketName: bucketName ?? "",
+          objectKey: key,
+          createdBy: userEmail,
+          expiresAt,
+          ip: options.ip,
+          maxDownloads: options.maxDownloads,
+          disposition: options.disposition,
+        });
+      }
+
       // Create the download path
       const downloadPath = "/api/files/" + bucketName + "/download/" + key;
-      const version = Date.now();
-      const versionedPath = downloadPath + "?ts=" + String(version);
-      const signature = await generateSignature(versionedPath, env);
-      const signedUrl = versionedPath + "&sig=" + signature;
+      const signedUrl = await createSignedPath(
+        downloadPath,
+        {
+          expires: Math.floor(expiresAt.getTime() / 1000),
+          ip: options.ip,
+          maxDownloads: options.maxDownloads,
+          disposition: options.disposition,
+          linkId,
+        },
+        env,
+      );
 
       // Return the full URL
       const fullUrl = new URL(signedUrl, request.url).toString();
@@ -915,6 +1259,8 @@ export async function handleFileRoutes(
         JSON.stringify({
           success: true,
           url: fullUrl,
+          expiresAt: expiresAt.toISOString(),
+          ...(linkId && { linkId }),
         }),
         {
           headers: {
@@ -951,6 +1297,9 @@ export async function handleFileRoutes(
         return createErrorResponse("Missing file key", corsHeaders, 400);
       }
       fileKey = decodeURIComponent(keyPart);
+      if (isTrashKey(fileKey)) {
+        return createTrashKeyResponse(corsHeaders);
+      }
       logInfo(`Deleting file: ${fileKey}`, {
         module: "files",
         operation: "delete",
@@ -958,24 +1307,19 @@ export async function handleFileRoutes(
         fileName: fileKey,
       });
 
-      const response = await fetch(
-        CF_API +
-          "/accounts/" +
-          env.ACCOUNT_ID +
-          "/r2/buckets/" +
-          bucketName +
-          "/objects/" +
-          fileKey,
-        {
-          method: "DELETE",
-          headers: cfHeaders,
-        },
-      );
-
-      if (!response.ok) {
-        throw new Error("Delete failed: " + String(response.status));
+      const lock = await checkObjectLock(env, bucketName ?? "", fileKey);
+      if (lock !== null) {
+        return createObjectLockedResponse(lock, corsHeaders);
       }
 
+      // Kept in the trash first if the bucket has trash enabled
+      const trashed = await deleteOrTrashObject(
+        env,
+        bucketName ?? "",
+        fileKey,
+        userEmail,
+      );
+
       // Log audit event for file delete
       if (db) {
         await logAuditEvent(
@@ -986,17 +1330,21 @@ export async function handleFileRoutes(
             objectKey: fileKey,
             userEmail,
             status: "success",
+            metadata: trashed ? { trashItemId: trashed.item_id } : undefined,
           },
           isLocalDev,
         );
       }
 
-      return new Response(JSON.stringify({ success: true }), {
-        headers: {
-          "Content-Type": "application/json",
-          ...corsHeaders,
+      return new Response(
+        JSON.stringify({ success: true, trashed: trashed !== null }),
+        {
+          headers: {
+            "Content-Type": "application/json",
+            ...corsHeaders,
+          },
         },
-      });
+      );
     } catch (err) {
       void logError(
         env,
@@ -1060,6 +1408,9 @@ export async function handleFileRoutes(
           400,
         );
       }
+      if (isTrashKey(sourceKey) || isTrashKey(destKey)) {
+        return createTrashKeyResponse(corsHeaders);
+      }
 
       logInfo(`Moving file: ${sourceKey} to ${destBucket}/${destKey}`, {
         module: "files",
@@ -1069,70 +1420,35 @@ export async function handleFileRoutes(
         metadata: { destination: `${destBucket}/${destKey}` },
       });
 
-      // 1. Fetch file from source bucket
-      const getUrl =
-        CF_API +
-        "/accounts/" +
-        env.ACCOUNT_ID +
-        "/r2/buckets/" +
-        bucketName +
-        "/objects/" +
-        sourceKey;
-      const getResponse = await fetch(getUrl, { headers: cfHeaders });
-
-      if (!getResponse.ok) {
-        if (getResponse.status === 404) {
-          return createErrorResponse("Source file not found", corsHeaders, 404);
-        }
-        throw new Error("Failed to fetch file: " + String(getResponse.status));
+      // The source is deleted and the destination may be overwritten
+      const lock =
+        (await checkObjectLock(env, bucketName ?? "", sourceKey)) ??
+        (await checkObjectLock(env, destBucket, destKey));
+      if (lock !== null) {
+        return createObjectLockedResponse(lock, corsHeaders);
       }
 
-      // 2. Preserve metadata from source
-      const contentType =
-        getResponse.headers.get("Content-Type") ?? "application/octet-stream";
-      const fileBuffer = await getResponse.arrayBuffer();
-
-      // 3. Upload to destination bucket with destination path
-      const putUrl =
-        CF_API +
-        "/accounts/" +
-        env.ACCOUNT_ID +
-        "/r2/buckets/" +
-        destBucket +
-        "/objects/" +
-        encodeURIComponent(destKey);
-      const putResponse = await fetch(putUrl, {
-        method: "PUT",
-        headers: {
-          ...cfHeaders,
-          "Content-Type": contentType,
-        },
-        body: fileBuffer,
-      });
+      await trashBeforeOverwrite(env, destBucket, destKey, userEmail);
 
-      if (!putResponse.ok) {
-        throw new Error(
-          "Failed to upload to destination: " + String(putResponse.status),
-        );
-      }
+      // 1. Copy to destination (streamed, metadata preserved)
+      const sourceObject = await copyObject(
+        env,
+        bucketName ?? "",
+        sourceKey,
+        destBucket,
+        destKey,
+      );
 
-      // 4. Delete from source bucket
-      const deleteUrl =
-        CF_API +
-        "/accounts/" +
-        env.ACCOUNT_ID +
-        "/r2/buckets/" +
-        bucketName +
-        "/objects/" +
-        sourceKey;
-      const deleteResponse = await fetch(deleteUrl, {
-        method: "DELETE",
-        headers: cfHeaders,
-      });
+      if (sourceObject === null) {
+        return createErrorResponse("Source file not found", corsHeaders, 404);
+      }
 
-      if (!deleteResponse.ok) {
+      // 2. Delete from source bucket
+      try {
+        await getObjectStorage(env, bucketName ?? "").delete(sourceKey);
+      } catch (deleteErr) {
         logWarning(
-          `Failed to delete source file after successful copy: ${deleteResponse.status}`,
+          `Failed to delete source file after successful copy: ${String(deleteErr)}`,
           {
             module: "files",
             operation: "move",
@@ -1160,7 +1476,7 @@ export async function handleFileRoutes(
             objectKey: sourceKey,
             userEmail,
             status: "success",
-            sizeBytes: fileBuffer.byteLength,
+            sizeBytes: sourceObject.size,
             destinationBucket: destBucket,
             destinationKey: destKey,
           },
@@ -1253,6 +1569,9 @@ export async function handleFileRoutes(
           400,
         );
       }
+      if (isTrashKey(sourceKey) || isTrashKey(destKey)) {
+        return createTrashKeyResponse(corsHeaders);
+      }
 
       logInfo(`Copying file: ${sourceKey} to ${destBucket}/${destKey}`, {
         module: "files",
@@ -1262,51 +1581,24 @@ export async function handleFileRoutes(
         metadata: { destination: `${destBucket}/${destKey}` },
       });
 
-      // 1. Fetch file from source bucket (same as move)
-      const getUrl =
-        CF_API +
-        "/accounts/" +
-        env.ACCOUNT_ID +
-        "/r2/buckets/" +
-        bucketName +
-        "/objects/" +
-        sourceKey;
-      const getResponse = await fetch(getUrl, { headers: cfHeaders });
-
-      if (!getResponse.ok) {
-        if (getResponse.status === 404) {
-          return createErrorResponse("Source file not found", corsHeaders, 404);
-        }
-        throw new Error("Failed to fetch file: " + String(getResponse.status));
+      const lock = await checkObjectLock(env, destBucket, destKey);
+      if (lock !== null) {
+        return createObjectLockedResponse(lock, corsHeaders);
       }
 
-      // 2. Preserve metadata from source (same as move)
-      const contentType =
-        getResponse.headers.get("Content-Type") ?? "application/octet-stream";
-      const fileBuffer = await getResponse.arrayBuffer();
-
-      // 3. Upload to destination bucket with destination path
-      const putUrl =
-        CF_API +
-        "/accounts/" +
-        env.ACCOUNT_ID +
-        "/r2/buckets/" +
-        destBucket +
-        "/objects/" +
-        encodeURIComponent(destKey);
-      const putResponse = await fetch(putUrl, {
-        method: "PUT",
-        headers: {
-          ...cfHeaders,
-          "Content-Type": contentType,
-        },
-        body: fileBuffer,
-      });
+      await trashBeforeOverwrite(env, destBucket, destKey, userEmail);
 
-      if (!putResponse.ok) {
-        throw new Error(
-          "Failed to upload to destination: " + String(putResponse.status),
-        );
+      // Copy to destination (streamed, metadata preserved)
+      const sourceObject = await copyObject(
+        env,
+        bucketName ?? "",
+        sourceKey,
+        destBucket,
+        destKey,
+      );
+
+      if (sourceObject === null) {
+        return createErrorResponse("Source file not found", corsHeaders, 404);
       }
 
       logInfo("Copy completed successfully", {
@@ -1326,7 +1618,7 @@ export async function handleFileRoutes(
             objectKey: sourceKey,
             userEmail,
             status: "success",
-            sizeBytes: fileBuffer.byteLength,
+            sizeBytes: sourceObject.size,
             destinationBucket: destBucket,
             destinationKey: destKey,
           },
@@ -1399,22 +1691,14 @@ export async function handleFileRoutes(
       if (newKey === undefined || newKey === "") {
         return createErrorResponse("New key is required", corsHeaders, 400);
       }
+      if (isTrashKey(sourceKey) || isTrashKey(newKey)) {
+        return createTrashKeyResponse(corsHeaders);
+      }
 
-      // Prevent overwriting existing files
-      const checkUrl =
-        CF_API +
-        "/accounts/" +
-        env.ACCOUNT_ID +
-        "/r2/buckets/" +
-        bucketName +
-        "/objects/" +
-        encodeURIComponent(newKey);
-      const checkResponse = await fetch(checkUrl, {
-        method: "HEAD",
-        headers: cfHeaders,
-      });
+      const storage = getObjectStorage(env, bucketName ?? "");
 
-      if (checkResponse.ok) {
+      // Prevent overwriting existing files
+      if ((await storage.head(newKey)) !== null) {
         return createErrorResponse(
           "File with that name already exists",
           corsHeaders,
@@ -1430,70 +1714,34 @@ export async function handleFileRoutes(
         metadata: { newKey },
       });
 
-      // 1. Get source file
-      const getUrl =
-        CF_API +
-        "/accounts/" +
-        env.ACCOUNT_ID +
-        "/r2/buckets/" +
-        bucketName +
-        "/objects/" +
-        encodeURIComponent(sourceKey);
-      const getResponse = await fetch(getUrl, { headers: cfHeaders });
-
-      if (!getResponse.ok) {
-        if (getResponse.status === 404) {
-          return createErrorResponse("Source file not found", corsHeaders, 404);
-        }
-        throw new Error("Failed to fetch file: " + String(getResponse.status));
+      const lock =
+        (await checkObjectLock(env, bucketName ?? "", sourceKey)) ??
+        (await checkObjectLock(env, bucketName ?? "", newKey));
+      if (lock !== null) {
+        return createObjectLockedResponse(lock, corsHeaders);
       }
 
-      // 2. Preserve metadata
-      const contentType =
-        getResponse.headers.get("Content-Type") ?? "application/octet-stream";
-      const fileBuffer = await getResponse.arrayBuffer();
-
-      // 3. Create file with new key
-      const putUrl =
-        CF_API +
-        "/accounts/" +
-        env.ACCOUNT_ID +
-        "/r2/buckets/" +
-        bucketName +
-        "/objects/" +
-        encodeURIComponent(newKey);
-      const putResponse = await fetch(putUrl, {
-        method: "PUT",
-        headers: {
-          ...cfHeaders,
-          "Content-Type": contentType,
-        },
-        body: fileBuffer,
-      });
+      await trashBeforeOverwrite(env, bucketName ?? "", newKey, userEmail);
 
-      if (!putResponse.ok) {
-        throw new Error(
-          "Failed to create renamed file: " + String(putResponse.status),
-        );
-      }
+      // 1. Copy to the new key (streamed, metadata preserved)
+      const sourceObject = await copyObject(
+        env,
+        bucketName ?? "",
+        sourceKey,
+        bucketName ?? "",
+        newKey,
+      );
 
-      // 4. Delete original file
-      const deleteUrl =
-        CF_API +
-        "/accounts/" +
-        env.ACCOUNT_ID +
-        "/r2/buckets/" +
-        bucketName +
-        "/objects/" +
-        encodeURIComponent(sourceKey);
-      const deleteResponse = await fetch(deleteUrl, {
-        method: "DELETE",
-        headers: cfHeaders,
-      });
+      if (sourceObject === null) {
+        return createErrorResponse("Source file not found", corsHeaders, 404);
+      }
 
-      if (!deleteResponse.ok) {
+      // 2. Delete original file
+      try {
+        await storage.delete(sourceKey);
+      } catch (deleteErr) {
         logWarning(
-          `Failed to delete original file after rename: ${deleteResponse.status}`,
+          `Failed to delete original file after rename: ${String(deleteErr)}`,
           {
             module: "files",
             operation: "rename",
@@ -1521,7 +1769,7 @@ export async function handleFileRoutes(
             objectKey: sourceKey,
             userEmail,
             status: "success",
-            sizeBytes: fileBuffer.byteLength,
+            sizeBytes: sourceObject.size,
             destinationBucket: bucketName ?? undefined,
             destinationKey: newKey,
           },
@@ -1583,3 +1831,197 @@ export async function handleFileRoutes(
     headers: corsHeaders,
   });
 }
+
+interface SignedUrlOptions {
+  expiresIn: number;
+  ip?: string | undefined;
+  maxDownloads?: number | undefined;
+  disposition: SignedUrlDisposition;
+}
+
+/**
+ * Read ?expires_in, ?ip, ?max_downloads and ?disposition for a signed URL.
+ * Returns an error message if a value is invalid.
+ */
+function parseSignedUrlOptions(
+  url: URL,
+  request: Request,
+): SignedUrlOptions | string {
+  const expiresInParam = url.searchParams.get("expires_in");
+  const expiresIn =
+    expiresInParam === null
+      ? DEFAULT_SIGNED_LINK_TTL_SECONDS
+      : Number(expiresInParam);
+  if (
+    !Number.isInteger(expiresIn) ||
+    expiresIn < 60 ||
+    expiresIn > MAX_SIGNED_LINK_TTL_SECONDS
+  ) {
+    return `expires_in must be between 60 and ${String(MAX_SIGNED_LINK_TTL_SECONDS)} seconds`;
+  }
+
+  // "auto" restricts the link to the IP address requesting it
+  let ip = url.searchParams.get("ip") ?? undefined;
+  if (ip === "auto") {
+    ip = request.headers.get("CF-Connecting-IP") ?? undefined;
+    if (ip === undefined) {
+      return "Could not determine the client IP address";
+    }
+  } else if (ip !== undefined && !/^[0-9a-fA-F:.]+$/.test(ip)) {
+    return "ip must be an IPv4 or IPv6 address";
+  }
+
+  const maxDownloadsParam = url.searchParams.get("max_downloads");
+  const maxDownloads =
+    maxDownloadsParam === null ? undefined : Number(maxDownloadsParam);
+  if (
+    maxDownloads !== undefined &&
+    (!Number.isInteger(maxDownloads) || maxDownloads < 1)
+  ) {
+    return "max_downloads must be a positive integer";
+  }
+
+  const disposition = url.searchParams.get("disposition") ?? "attachment";
+  if (disposition !== "attachment" && disposition !== "inline") {
+    return "disposition must be attachment or inline";
+  }
+
+  return { expiresIn, ip, maxDownloads, disposition };
+}
+
+/**
+ * Stream a ZIP archive of the given objects. Objects are fetched one at a
+ * time and piped straight into the archive; missing or unreadable objects are
+ * skipped and recorded as job errors. The job is completed (or failed) once
+ * the archive has finished streaming, and cancelling the job aborts the
+ * archive at the next progress update.
+ */
+function createZipDownloadStream(
+  env: Env,
+  items: ZipDownloadItem[],
+  job: {
+    jobId: string;
+    userEmail: string;
+    operation: "download_zip" | "multi_download";
+    isLocalDev: boolean;
+  },
+): ReadableStream<Uint8Array> {
+  const db = env.METADATA;
+  const totalFiles = items.length;
+  let processedFiles = 0;
+  let errorCount = 0;
+
+  const logSkippedFile = async (
+    item: ZipDownloadItem,
+    error: string,
+  ): Promise<void> => {
+    errorCount++;
+    if (db) {
+      await recordJobItem(db, {
+        jobId: job.jobId,
+        objectKey: item.entryName,
+        status: "failed",
+        error,
+      });
+    }
+  };
+
+  async function* entries(): AsyncGenerator<ZipEntry> {
+    for (const item of items) {
+      logInfo(`Fetching: ${item.key} from bucket: ${item.bucketName}`, {
+        module: "files",
+        operation: job.operation,
+        bucketName: item.bucketName,
+        fileName: item.key,
+      });
+
+      let object: StorageObjectBody | null;
+      try {
+        object = await getObjectStorage(env, item.bucketName).get(item.key);
+      } catch (fileErr) {
+        void logError(
+          env,
+          fileErr instanceof Error ? fileErr : new Error(String(fileErr)),
+          {
+            module: "files",
+            operation: job.operation,
+            bucketName: item.bucketName,
+            fileName: item.key,
+          },
+          job.isLocalDev,
+        );
+        await logSkippedFile(item, String(fileErr));
+        continue;
+      }
+
+      if (object === null) {
+        await logSkippedFile(item, "Failed to fetch file");
+        continue;
+      }
+
+      // Resumes once the entry has been fully written to the archive
+      yield {
+        name: item.entryName,
+        size: object.size,
+        lastModified: new Date(object.uploaded),
+        body: object.body as ReadableStream<Uint8Array>,
+      };
+      processedFiles++;
+
+      // Update progress every 5 files or on last file
+      if (db && (processedFiles % 5 === 0 || processedFiles === totalFiles)) {
+        if ((await getJobStatus(db, job.jobId)) === "cancelled") {
+          throw new Error("Download was cancelled");
+        }
+        await updateJobProgress(db, {
+          jobId: job.jobId,
+          processedItems: processedFiles,
+          totalItems: totalFiles,
+          errorCount,
+        });
+      }
+    }
+
+    logInfo("ZIP stream complete", {
+      module: "files",
+      operation: job.operation,
+      metadata: { processedFiles, errorCount },
+    });
+
+    // Complete the job
+    if (db) {
+      await completeJob(db, {
+        jobId: job.jobId,
+        status: "completed",
+        processedItems: processedFiles,
+        errorCount,
+        userEmail: job.userEmail,
+        fromStatuses: ["running"],
+      });
+    }
+  }
+
+  return createZipStream(entries(), {
+    onError: async (err) => {
+      void logError(
+        env,
+        err instanceof Error ? err : new Error(String(err)),
+        { module: "files", operation: job.operation },
+        job.isLocalDev,
+      );
+
+      // Mark job as failed
+      if (db) {
+        await completeJob(db, {
+          jobId: job.jobId,
+          status: "failed",
+          processedItems: processedFiles,
+          errorCount: errorCount + 1,
+          userEmail: job.userEmail,
+          errorMessage: String(err),
+          fromStatuses: ["running"],
+        });
+      }
+    },
+  });
+}
@@ -1,20 +1,11 @@
-import type { Env, CloudflareApiResponse } from "../types";
-import { CF_API } from "../types";
-import { getCloudflareHeaders } from "../utils/helpers";
+import type { Env } from "../types";
+import { getObjectStorage } from "../utils/storage";
+import { createJobResponse, startJob } from "../utils/job-runner";
 import { logAuditEvent } from "./audit";
 import { logInfo, logError } from "../utils/error-logger";
-import {
-  triggerWebhooks,
-  createFolderCreatePayload,
-  createFolderDeletePayload,
-} from "../utils/webhooks";
+import { triggerWebhooks, createFolderCreatePayload } from "../utils/webhooks";
 import { createErrorResponse } from "../utils/error-response";
-
-interface R2ObjectInfo {
-  key: string;
-  size: number;
-  uploaded?: string;
-}
+import { createTrashKeyResponse, isTrashKey } from "../utils/trash";
 
 interface CreateFolderBody {
   folderName?: string;
@@ -46,7 +37,7 @@ export async function handleFolderRoutes(
   const bucketName = parts[3];
   const db = env.METADATA;
 
-  const cfHeaders = getCloudflareHeaders(env);
+  const storage = getObjectStorage(env, bucketName ?? "");
 
   // Create folder
   if (request.method === "POST" && parts[4] === "create") {
@@ -72,6 +63,9 @@ export async function handleFolderRoutes(
       const folderPath = folderName.endsWith("/")
         ? folderName
         : folderName + "/";
+      if (isTrashKey(folderPath)) {
+        return createTrashKeyResponse(corsHeaders);
+      }
 
       // Mock response for local development
       if (isLocalDev) {
@@ -96,28 +90,10 @@ export async function handleFolderRoutes(
 
       // Create a placeholder .keep file
       const keepFilePath = folderPath + ".keep";
-      const putUrl =
-        CF_API +
-        "/accounts/" +
-        env.ACCOUNT_ID +
-        "/r2/buckets/" +
-        bucketName +
-        "/objects/" +
-        encodeURIComponent(keepFilePath);
-
-      const response = await fetch(putUrl, {
-        method: "PUT",
-        headers: {
-          ...cfHeaders,
-          "Content-Type": "text/plain",
-        },
-        body: "",
+      await storage.put(keepFilePath, "", {
+        httpMetadata: { contentType: "text/plain" },
       });
 
-      if (!response.ok) {
-        throw new Error("Failed to create folder: " + String(response.status));
-      }
-
       logInfo("Created folder", {
         module: "folders",
         operation: "create_folder",
@@ -203,6 +179,9 @@ export async function handleFolderRoutes(
       // Ensure paths end with /
       const oldFolderPath = oldPath.endsWith("/") ? oldPath : oldPath + "/";
       const newFolderPath = newPath.endsWith("/") ? newPath : newPath + "/";
+      if (isTrashKey(oldFolderPath) || isTrashKey(newFolderPath)) {
+        return createTrashKeyResponse(corsHeaders);
+      }
 
       logInfo("Renaming folder", {
         module: "folders",
@@ -210,202 +189,31 @@ export async function handleFolderRoutes(
         metadata: { oldFolderPath, newFolderPath },
       });
 
-      // List all objects with the old prefix
-      let cursor: string | undefined;
-      let totalCopied = 0;
-      let totalFailed = 0;
-      let hasMore = true;
-
-      while (hasMore) {
-        const listUrl = new URL(
-          CF_API +
-            "/accounts/" +
-            env.ACCOUNT_ID +
-            "/r2/buckets/" +
-            bucketName +
-            "/objects",
-        );
-        listUrl.searchParams.set("prefix", oldFolderPath);
-        listUrl.searchParams.set("per_page", "100");
-        if (cursor !== undefined) {
-          listUrl.searchParams.set("cursor", cursor);
-        }
-
-        const listResponse = await fetch(listUrl.toString(), {
-          headers: cfHeaders,
-        });
-        if (!listResponse.ok) {
-          throw new Error(
-            "Failed to list objects: " + String(listResponse.status),
-          );
-        }
-
-        const listData = (await listResponse.json()) as CloudflareApiResponse<
-          R2ObjectInfo[]
-        >;
-        const objects: R2ObjectInfo[] = Array.isArray(listData.result)
-          ? listData.result
-          : [];
-
-        if (objects.length === 0) {
-          break;
-        }
-
-        // Copy each object to new location
-        for (const obj of objects) {
-          try {
-            const newKey = obj.key.replace(oldFolderPath, newFolderPath);
-
-            // Get the object
-            const getUrl =
-              CF_API +
-              "/accounts/" +
-              env.ACCOUNT_ID +
-              "/r2/buckets/" +
-              bucketName +
-              "/objects/" +
-              encodeURIComponent(obj.key);
-            const getResponse = await fetch(getUrl, { headers: cfHeaders });
-
-            if (!getResponse.ok) {
-              totalFailed++;
-              continue;
-            }
-
-            const fileBuffer = await getResponse.arrayBuffer();
-            const contentType =
-              getResponse.headers.get("Content-Type") ??
-              "application/octet-stream";
-
-            // Put to new location
-            const putUrl =
-              CF_API +
-              "/accounts/" +
-              env.ACCOUNT_ID +
-              "/r2/buckets/" +
-              bucketName +
-              "/objects/" +
-              encodeURIComponent(newKey);
-            const putResponse = await fetch(putUrl, {
-              method: "PUT",
-              headers: {
-                ...cfHeaders,
-                "Content-Type": contentType,
-              },
-              body: fileBuffer,
-            });
-
-            if (putResponse.ok) {
-              totalCopied++;
-            } else {
-              totalFailed++;
-            }
-          } catch {
-            totalFailed++;
-          }
-        }
-
-        cursor = listData.result_info?.cursor as string | undefined;
-        hasMore = cursor !== undefined && objects.length > 0;
-
-        if (hasMore) {
-          await new Promise((resolve) => setTimeout(resolve, 300));
-        }
-      }
-
-      // Delete old folder objects
-      cursor = undefined;
-      hasMore = true;
-
-      while (hasMore) {
-        const listUrl = new URL(
-          CF_API +
-            "/accounts/" +
-            env.ACCOUNT_ID +
-            "/r2/buckets/" +
-            bucketName +
-            "/objects",
+      if (newFolderPath.startsWith(oldFolderPath)) {
+        return createErrorResponse(
+          "Cannot rename a folder into itself",
+          corsHeaders,
+          400,
         );
-        listUrl.searchParams.set("prefix", oldFolderPath);
-        listUrl.searchParams.set("per_page", "100");
-        if (cursor !== undefined) {
-          listUrl.searchParams.set("cursor", cursor);
-        }
-
-        const listResponse = await fetch(listUrl.toString(), {
-          headers: cfHeaders,
-        });
-        if (!listResponse.ok) break;
-
-        const listData = (await listResponse.json()) as CloudflareApiResponse<
-          R2ObjectInfo[]
-        >;
-        const objects: R2ObjectInfo[] = Array.isArray(listData.result)
-          ? listData.result
-          : [];
-
-        if (objects.length === 0) break;
-
-        for (const obj of objects) {
-          try {
-            const deleteUrl =
-              CF_API +
-              "/accounts/" +
-              env.ACCOUNT_ID +
-              "/r2/buckets/" +
-              bucketName +
-              "/objects/" +
-              encodeURIComponent(obj.key);
-            await fetch(deleteUrl, { method: "DELETE", headers: cfHeaders });
-          } catch {
-            // Continue on delete failure
-          }
-        }
-
-        cursor = listData.result_info?.cursor as string | undefined;
-        hasMore = cursor !== undefined && objects.length > 0;
-
-        if (hasMore) {
-          await new Promise((resolve) => setTimeout(resolve, 300));
-        }
       }
 
-      logInfo("Renamed folder", {
-        module: "folders",
-        operation: "rename_folder",
-        metadata: { totalCopied, totalFailed },
+      // Copy every object to the new prefix, then delete the original
+      const result = await startJob(env, {
+        taskType: "folder_rename",
+        params: {
+          sourceBucket: bucketName ?? "",
+          sourcePrefix: oldFolderPath,
+          destBucket: bucketName ?? "",
+          destPrefix: newFolderPath,
+        },
+        userEmail,
+        isLocalDev,
       });
 
-      // Log audit event for folder rename
-      if (db) {
-        await logAuditEvent(
-          env,
-          {
-            operationType: "folder_rename",
-            bucketName: bucketName ?? undefined,
-            objectKey: oldFolderPath,
-            userEmail,
-            status: "success",
-            destinationBucket: bucketName ?? undefined,
-            destinationKey: newFolderPath,
-            metadata: { filesCopied: totalCopied, filesFailed: totalFailed },
-          },
-          isLocalDev,
-        );
-      }
-
-      return new Response(
-        JSON.stringify({
-          success: true,
-          copied: totalCopied,
-          failed: totalFailed,
-        }),
-        {
-          headers: {
-            "Content-Type": "application/json",
-            ...corsHeaders,
-          },
-        },
+      return createJobResponse(
+        result,
+        { copied: result.processedItems, failed: result.errorCount },
+        corsHeaders,
       );
     } catch (err) {
       await logError(
@@ -455,6 +263,9 @@ export async function handleFolderRoutes(
         ? folderPath
         : folderPath + "/";
       const destFolderPath = destPath.endsWith("/") ? destPath : destPath + "/";
+      if (isTrashKey(sourceFolderPath) || isTrashKey(destFolderPath)) {
+        return createTrashKeyResponse(corsHeaders);
+      }
 
       logInfo("Copying folder", {
         module: "folders",
@@ -465,144 +276,33 @@ export async function handleFolderRoutes(
         },
       });
 
-      let cursor: string | undefined;
-      let totalCopied = 0;
-      let totalFailed = 0;
-      let hasMore = true;
-
-      while (hasMore) {
-        const listUrl = new URL(
-          CF_API +
-            "/accounts/" +
-            env.ACCOUNT_ID +
-            "/r2/buckets/" +
-            bucketName +
-            "/objects",
+      if (
+        destBucket === bucketName &&
+        destFolderPath.startsWith(sourceFolderPath)
+      ) {
+        return createErrorResponse(
+          "Cannot copy a folder into itself",
+          corsHeaders,
+          400,
         );
-        listUrl.searchParams.set("prefix", sourceFolderPath);
-        listUrl.searchParams.set("per_page", "100");
-        if (cursor !== undefined) {
-          listUrl.searchParams.set("cursor", cursor);
-        }
-
-        const listResponse = await fetch(listUrl.toString(), {
-          headers: cfHeaders,
-        });
-        if (!listResponse.ok) {
-          throw new Error(
-            "Failed to list objects: " + String(listResponse.status),
-          );
-        }
-
-        const listData = (await listResponse.json()) as CloudflareApiResponse<
-          R2ObjectInfo[]
-        >;
-        const objects: R2ObjectInfo[] = Array.isArray(listData.result)
-          ? listData.result
-          : [];
-
-        if (objects.length === 0) {
-          break;
-        }
-
-        for (const obj of objects) {
-          try {
-            const relativePath = obj.key.substring(sourceFolderPath.length);
-            const destKey = destFolderPath + relativePath;
-
-            // Get the object
-            const getUrl =
-              CF_API +
-              "/accounts/" +
-              env.ACCOUNT_ID +
-              "/r2/buckets/" +
-              bucketName +
-              "/objects/" +
-              encodeURIComponent(obj.key);
-            const getResponse = await fetch(getUrl, { headers: cfHeaders });
-
-            if (!getResponse.ok) {
-              totalFailed++;
-              continue;
-            }
-
-            const fileBuffer = await getResponse.arrayBuffer();
-            const contentType =
-              getResponse.headers.get("Content-Type") ??
-              "application/octet-stream";
-
-            // Put to destination
-            const putUrl =
-              CF_API +
-              "/accounts/" +
-              env.ACCOUNT_ID +
-              "/r2/buckets/" +
-              destBucket +
-              "/objects/" +
-              encodeURIComponent(destKey);
-            const putResponse = await fetch(putUrl, {
-              method: "PUT",
-              headers: {
-                ...cfHeaders,
-                "Content-Type": contentType,
-              },
-              body: fileBuffer,
-            });
-
-            if (putResponse.ok) {
-              totalCopied++;
-            } else {
-              totalFailed++;
-            }
-          } catch {
-            totalFailed++;
-          }
-        }
-
-        cursor = listData.result_info?.cursor as string | undefined;
-        hasMore = cursor !== undefined && objects.length > 0;
-
-        if (hasMore) {
-          await new Promise((resolve) => setTimeout(resolve, 300));
-        }
       }
 
-      logInfo("Copied folder", {
-        module: "folders",
-        operation: "copy_folder",
-        metadata: { totalCopied, totalFailed },
+      const result = await startJob(env, {
+        taskType: "folder_copy",
+        params: {
+          sourceBucket: bucketName ?? "",
+          sourcePrefix: sourceFolderPath,
+          destBucket,
+          destPrefix: destFolderPath,
+        },
+        userEmail,
+        isLocalDev,
       });
 
-      // Log audit event for folder copy
-      if (db) {
-        await logAuditEvent(
-          env,
-          {
-            operationType: "folder_copy",
-            bucketName: bucketName ?? undefined,
-            objectKey: sourceFolderPath,
-            userEmail,
-            status: "success",
-            destinationBucket: destBucket,
-            destinationKey: destFolderPath,
-            metadata: { filesCopied: totalCopied, filesFailed: totalFailed },
-          },
-          isLocalDev,
-        );
-      }
-
-      return new Response(
-        JSON.stringify({
-          success: true,
-          copied: totalCopied,
-          failed: totalFailed,
-        }),
-        {
-          headers: {
-            "Content-Type": "application/json",
-            ...corsHeaders,
-          },
-        },
+      return createJobResponse(
+        result,
+        { copied: result.processedItems, failed: result.errorCount },
+        corsHeaders,
       );
     } catch (err) {
       await logError(
@@ -656,6 +356,9 @@ export async function handleFolderRoutes(
         ? folderPath
         : folderPath + "/";
       const destFolderPath = destPath.endsWith("/") ? destPath : destPath + "/";
+      if (isTrashKey(sourceFolderPath) || isTrashKey(destFolderPath)) {
+        return createTrashKeyResponse(corsHeaders);
+      }
 
       logInfo("Moving folder", {
         module: "folders",
@@ -666,202 +369,34 @@ export async function handleFolderRoutes(
         },
       });
 
-      // First copy all files
-      let cursor: string | undefined;
-      let totalMoved = 0;
-      let totalFailed = 0;
-      let hasMore = true;
-
-      while (hasMore) {
-        const listUrl = new URL(
-          CF_API +
-            "/accounts/" +
-            env.ACCOUNT_ID +
-            "/r2/buckets/" +
-            bucketName +
-            "/objects",
-        );
-        listUrl.searchParams.set("prefix", sourceFolderPath);
-        listUrl.searchParams.set("per_page", "100");
-        if (cursor !== undefined) {
-          listUrl.searchParams.set("cursor", cursor);
-        }
-
-        const listResponse = await fetch(listUrl.toString(), {
-          headers: cfHeaders,
-        });
-        if (!listResponse.ok) {
-          throw new Error(
-            "Failed to list objects: " + String(listResponse.status),
-          );
-        }
-
-        const listData = (await listResponse.json()) as CloudflareApiResponse<
-          R2ObjectInfo[]
-        >;
-        const objects: R2ObjectInfo[] = Array.isArray(listData.result)
-          ? listData.result
-          : [];
-
-        if (objects.length === 0) {
-          break;
-        }
-
-        for (const obj of objects) {
-          try {
-            const relativePath = obj.key.substring(sourceFolderPath.length);
-            const destKey = destFolderPath + relativePath;
-
-            // Get the object
-            const getUrl =
-              CF_API +
-              "/accounts/" +
-              env.ACCOUNT_ID +
-              "/r2/buckets/" +
-              bucketName +
-              "/objects/" +
-              encodeURIComponent(obj.key);
-            const getResponse = await fetch(getUrl, { headers: cfHeaders });
-
-            if (!getResponse.ok) {
-              totalFailed++;
-              continue;
-            }
-
-            const fileBuffer = await getResponse.arrayBuffer();
-            const contentType =
-              getResponse.headers.get("Content-Type") ??
-              "application/octet-stream";
-
-            // Put to destination
-            const putUrl =
-              CF_API +
-              "/accounts/" +
-              env.ACCOUNT_ID +
-              "/r2/buckets/" +
-              destBucket +
-              "/objects/" +
-              encodeURIComponent(destKey);
-            const putResponse = await fetch(putUrl, {
-              method: "PUT",
-              headers: {
-                ...cfHeaders,
-                "Content-Type": contentType,
-              },
-              body: fileBuffer,
-            });
-
-            if (putResponse.ok) {
-              totalMoved++;
-            } else {
-              totalFailed++;
-            }
-          } catch {
-            totalFailed++;
-          }
-        }
-
-        cursor = listData.result_info?.cursor as string | undefined;
-        hasMore = cursor !== undefined && objects.length > 0;
-
-        if (hasMore) {
-          await new Promise((resolve) => setTimeout(resolve, 300));
-        }
-      }
-
-      // Delete source folder objects
-      cursor = undefined;
-      hasMore = true;
-
-      while (hasMore) {
-        const listUrl = new URL(
-          CF_API +
-            "/accounts/" +
-            env.ACCOUNT_ID +
-            "/r2/buckets/" +
-            bucketName +
-            "/objects",
+      if (
+        destBucket === bucketName &&
+        destFolderPath.startsWith(sourceFolderPath)
+      ) {
+        return createErrorResponse(
+          "Cannot move a folder into itself",
+          corsHeaders,
+          400,
         );
-        listUrl.searchParams.set("prefix", sourceFolderPath);
-        listUrl.searchParams.set("per_page", "100");
-        if (cursor !== undefined) {
-          listUrl.searchParams.set("cursor", cursor);
-        }
-
-        const listResponse = await fetch(listUrl.toString(), {
-          headers: cfHeaders,
-        });
-        if (!listResponse.ok) break;
-
-        const listData = (await listResponse.json()) as CloudflareApiResponse<
-          R2ObjectInfo[]
-        >;
-        const objects: R2ObjectInfo[] = Array.isArray(listData.result)
-          ? listData.result
-          : [];
-
-        if (objects.length === 0) break;
-
-        for (const obj of objects) {
-          try {
-            const deleteUrl =
-              CF_API +
-              "/accounts/" +
-              env.ACCOUNT_ID +
-              "/r2/buckets/" +
-              bucketName +
-              "/objects/" +
-              encodeURIComponent(obj.key);
-            await fetch(deleteUrl, { method: "DELETE", headers: cfHeaders });
-          } catch {
-            // Continue on delete failure
-          }
-        }
-
-        cursor = listData.result_info?.cursor as string | undefined;
-        hasMore = cursor !== undefined && objects.length > 0;
-
-        if (hasMore) {
-          await new Promise((resolve) => setTimeout(resolve, 300));
-        }
       }
 
-      logInfo("Moved folder", {
-        module: "folders",
-        operation: "move_folder",
-        metadata: { totalMoved, totalFailed },
+      // Copy every object to the destination, then delete the original
+      const result = await startJob(env, {
+        taskType: "folder_move",
+        params: {
+          sourceBucket: bucketName ?? "",
+          sourcePrefix: sourceFolderPath,
+          destBucket,
+          destPrefix: destFolderPath,
+        },
+        userEmail,
+        isLocalDev,
       });
 
-      // Log audit event for folder move
-      if (db) {
-        await logAuditEvent(
-          env,
-          {
-            operationType: "folder_move",
-            bucketName: bucketName ?? undefined,
-            objectKey: sourceFolderPath,
-            userEmail,
-            status: "success",
-            destinationBucket: destBucket,
-            destinationKey: destFolderPath,
-            metadata: { filesMoved: totalMoved, filesFailed: totalFailed },
-          },
-          isLocalDev,
-        );
-      }
-
-      return new Response(
-        JSON.stringify({
-          success: true,
-          moved: totalMoved,
-          failed: totalFailed,
-        }),
-        {
-          headers: {
-            "Content-Type": "application/json",
-            ...corsHeaders,
-          },
-        },
+      return createJobResponse(
+        result,
+        { moved: result.processedItems, failed: result.errorCount },
+        corsHeaders,
       );
     } catch (err) {
       await logError(
@@ -904,6 +439,9 @@ export async function handleFolderRoutes(
       const folderPathWithSlash = folderPath.endsWith("/")
         ? folderPath
         : folderPath + "/";
+      if (isTrashKey(folderPathWithSlash)) {
+        return createTrashKeyResponse(corsHeaders);
+      }
 
       logInfo("Deleting folder", {
         module: "folders",
@@ -912,32 +450,11 @@ export async function handleFolderRoutes(
       });
 
       // List objects in folder
-      const listUrl = new URL(
-        CF_API +
-          "/accounts/" +
-          env.ACCOUNT_ID +
-          "/r2/buckets/" +
-          bucketName +
-          "/objects",
-      );
-      listUrl.searchParams.set("prefix", folderPathWithSlash);
-      listUrl.searchParams.set("per_page", "100");
-
-      const listResponse = await fetch(listUrl.toString(), {
-        headers: cfHeaders,
+      const listData = await storage.list({
+        prefix: folderPathWithSlash,
+        limit: 100,
       });
-      if (!listResponse.ok) {
-        throw new Error(
-          "Failed to list objects: " + String(listResponse.status),
-        );
-      }
-
-      const listData = (await listResponse.json()) as CloudflareApiResponse<
-        R2ObjectInfo[]
-      >;
-      const countObjects: R2ObjectInfo[] = Array.isArray(listData.result)
-        ? listData.result
-        : [];
+      const countObjects = listData.objects;
       const fileCount = countObjects.length;
 
       // If not force mode, return count for confirmation
@@ -959,119 +476,20 @@ export async function handleFolderRoutes(
       }
 
       // Delete all objects
-      let cursor: string | undefined;
-      let totalDeleted = 0;
-      let hasMore = true;
-
-      while (hasMore) {
-        const deleteListUrl = new URL(
-          CF_API +
-            "/accounts/" +
-            env.ACCOUNT_ID +
-            "/r2/buckets/" +
-            bucketName +
-            "/objects",
-        );
-        deleteListUrl.searchParams.set("prefix", folderPathWithSlash);
-        deleteListUrl.searchParams.set("per_page", "100");
-        if (cursor !== undefined) {
-          deleteListUrl.searchParams.set("cursor", cursor);
-        }
-
-        const deleteListResponse = await fetch(deleteListUrl.toString(), {
-          headers: cfHeaders,
-        });
-        if (!deleteListResponse.ok) break;
-
-        const deleteListData =
-          (await deleteListResponse.json()) as CloudflareApiResponse<
-            R2ObjectInfo[]
-          >;
-        const deleteObjects: R2ObjectInfo[] = Array.isArray(
-          deleteListData.result,
-        )
-          ? deleteListData.result
-          : [];
-
-        if (deleteObjects.length === 0) break;
-
-        for (const obj of deleteObjects) {
-          try {
-            const deleteUrl =
-              CF_API +
-              "/accounts/" +
-              env.ACCOUNT_ID +
-              "/r2/buckets/" +
-              bucketName +
-              "/objects/" +
-              encodeURIComponent(obj.key);
-            const deleteResponse = await fetch(deleteUrl, {
-              method: "DELETE",
-              headers: cfHeaders,
-            });
-
-            if (deleteResponse.ok) {
-              totalDeleted++;
-            }
-          } catch {
-            // Continue on delete failure
-          }
-        }
-
-        cursor = deleteListData.result_info?.cursor as string | undefined;
-        hasMore = cursor !== undefined && deleteObjects.length > 0;
-
-        if (hasMore) {
-          await new Promise((resolve) => setTimeout(resolve, 300));
-        }
-      }
-
-      logInfo("Deleted folder", {
-        module: "folders",
-        operation: "delete_folder",
-        metadata: { totalDeleted },
-      });
-
-      // Log audit event for folder delete
-      if (db) {
-        await logAuditEvent(
-          env,
-          {
-            operationType: "folder_delete",
-            bucketName: bucketName ?? undefined,
-            objectKey: folderPathWithSlash,
-            userEmail,
-            status: "success",
-            metadata: { filesDeleted: totalDeleted, force },
-          },
-          isLocalDev,
-        );
-      }
-
-      // Trigger webhook for folder deletion
-      void triggerWebhooks(
-        env,
-        "folder_delete",
-        createFolderDeletePayload(
-          bucketName ?? "",
-          folderPathWithSlash,
-          totalDeleted,
-          userEmail,
-        ),
+      const result = await startJob(env, {
+        taskType: "folder_delete",
+        params: {
+          sourceBucket: bucketName ?? "",
+          sourcePrefix: folderPathWithSlash,
+        },
+        userEmail,
         isLocalDev,
-      );
+      });
 
-      return new Response(
-        JSON.stringify({
-          success: true,
-          deleted: totalDeleted,
-        }),
-        {
-          headers: {
-            "Content-Type": "application/json",
-            ...corsHeaders,
-          },
-        },
+      return createJobResponse(
+        result,
+        { deleted: result.processedItems },
+        corsHeaders,
       );
     } catch (err) {
       await logError(
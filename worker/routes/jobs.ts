@@ -1,6 +1,7 @@
 import type {
   Env,
   BulkJob,
+  JobStatus,
   JobAuditEvent,
   JobOperationType,
   CreateJobParams,
@@ -8,9 +9,17 @@ import type {
   CompleteJobParams,
   LogJobEventParams,
   AuditLogEntry,
+  JobControlAction,
+  JobItem,
+  JobItemErrorCode,
+  JobItemStatus,
+  RecordJobItemParams,
 } from "../types";
 import { logInfo, logError } from "../utils/error-logger";
-import { SUPPORT_EMAIL } from "../utils/error-response";
+import { SUPPORT_EMAIL, createErrorResponse } from "../utils/error-response";
+import { continueJob } from "../utils/job-runner";
+import { getRequestAccess, satisfiesCheck } from "../utils/rbac";
+import { triggerWebhooks, createJobCancelledPayload } from "../utils/webhooks";
 
 interface APIResponse {
   success: boolean;
@@ -24,10 +33,13 @@ const BULK_JOB_OPERATIONS = [
   "bulk_download",
   "bulk_delete",
   "bucket_delete",
+  "bucket_rename",
   "file_move",
   "file_copy",
   "folder_move",
   "folder_copy",
+  "folder_rename",
+  "folder_delete",
   "ai_search_sync",
 ] as const;
 
@@ -38,10 +50,7 @@ const AUDIT_ONLY_OPERATIONS = [
   "file_delete",
   "file_rename",
   "bucket_create",
-  "bucket_rename",
   "folder_create",
-  "folder_delete",
-  "folder_rename",
 ] as const;
 
 /**
@@ -96,7 +105,7 @@ export async function createJob(
       params.jobId,
       params.bucketName,
       params.operationType,
-      "running",
+      params.status ?? "running",
       params.totalItems ?? null,
       0,
       0,
@@ -107,10 +116,10 @@ export async function createJob(
     )
     .run();
 
-  // Log the started event
+  // Log the started (or queued) event
   await logJobEvent(db, {
     jobId: params.jobId,
-    eventType: "started",
+    eventType: params.status === "queued" ? "queued" : "started",
     userEmail: params.userEmail,
     details: { total: params.totalItems },
   });
@@ -135,6 +144,7 @@ export async function updateJobProgress(
       `
     UPDATE bulk_jobs SET
       processed_items = ?,
+      total_items = COALESCE(?, total_items),
       error_count = COALESCE(?, error_count),
       percentage = ?
     WHERE job_id = ?
@@ -142,6 +152,7 @@ export async function updateJobProgress(
     )
     .bind(
       params.processedItems,
+      params.totalItems ?? null,
       params.errorCount ?? null,
       percentage,
       params.jobId,
@@ -150,15 +161,21 @@ export async function updateJobProgress(
 }
 
 /**
- * Complete a job
+ * Complete a job. Returns false, without logging an event, if the job was
+ * not in one of `fromStatuses` (for example, it was cancelled meanwhile).
  */
 export async function completeJob(
   db: D1Database,
   params: CompleteJobParams,
-): Promise<void> {
+): Promise<boolean> {
   const now = new Date().toISOString();
+  const fromStatuses = params.fromStatuses ?? [];
+  const statusCondition =
+    fromStatuses.length > 0
+      ? ` AND status IN (${fromStatuses.map(() => "?").join(", ")})`
+      : "";
 
-  await db
+  const result = await db
     .prepare(
       `
     UPDATE bulk_jobs SET
@@ -167,7 +184,7 @@ export async function completeJob(
       processed_items = COALESCE(?, processed_items),
       error_count = COALESCE(?, error_count),
       percentage = CASE WHEN ? = 'completed' THEN 100 ELSE percentage END
-    WHERE job_id = ?
+    WHERE job_id = ?${statusCondition}
   `,
     )
     .bind(
@@ -177,8 +194,12 @@ export async function completeJob(
       params.errorCount ?? null,
       params.status,
       params.jobId,
+      ...fromStatuses,
     )
     .run();
+  if (result.meta.changes === 0) {
+    return false;
+  }
 
   // Log the completion event
   await logJobEvent(db, {
@@ -191,6 +212,89 @@ export async function completeJob(
       error_message: params.errorMessage,
     },
   });
+  return true;
+}
+
+/**
+ * Get a job's current status, or null if the job does not exist
+ */
+export async function getJobStatus(
+  db: D1Database,
+  jobId: string,
+): Promise<JobStatus | null> {
+  const job = await db
+    .prepare("SELECT status FROM bulk_jobs WHERE job_id = ?")
+    .bind(jobId)
+    .first<{ status: JobStatus }>();
+  return job?.status ?? null;
+}
+
+/**
+ * Classify a failed item's error into a stable code for the failure manifest
+ */
+export function getJobItemErrorCode(error: Error | string): JobItemErrorCode {
+  const message = (
+    error instanceof Error ? error.message : error
+  ).toLowerCase();
+
+  if (/retention-locked/.test(message)) return "object_locked";
+  if (/\b404\b|not found|nosuchkey/.test(message)) return "not_found";
+  if (/\b(401|403)\b|access denied|forbidden|unauthorized/.test(message)) {
+    return "access_denied";
+  }
+  if (/\b429\b|rate limit|too many requests/.test(message)) {
+    return "rate_limited";
+  }
+  if (/timed? ?out|timeout/.test(message)) return "timeout";
+  if (/^failed to |\b5\d\d\b|during copy/.test(message)) {
+    return "storage_error";
+  }
+  return "unknown";
+}
+
+/**
+ * Record the outcome of a single job item. Failures are upserted so the
+ * attempt count grows each time the item is retried.
+ */
+export async function recordJobItem(
+  db: D1Database,
+  params: RecordJobItemParams,
+): Promise<void> {
+  const errorMessage =
+    params.error === undefined
+      ? null
+      : params.error instanceof Error
+        ? params.error.message
+        : params.error;
+  const errorCode =
+    params.status === "failed" && params.error !== undefined
+      ? getJobItemErrorCode(params.error)
+      : null;
+
+  await db
+    .prepare(
+      `
+    INSERT INTO job_items (
+      job_id, object_key, status, error_code, error, attempts, updated_at
+    )
+    VALUES (?, ?, ?, ?, ?, 1, ?)
+    ON CONFLICT (job_id, object_key) DO UPDATE SET
+      status = excluded.status,
+      error_code = excluded.error_code,
+      error = excluded.error,
+      attempts = job_items.attempts + 1,
+      updated_at = excluded.updated_at
+  `,
+    )
+    .bind(
+      params.jobId,
+      params.objectKey,
+      params.status,
+      errorCode,
+      errorMessage,
+      new Date().toISOString(),
+    )
+    .run();
 }
 
 /**
@@ -920,6 +1024,573 @@ export async function handleJobRoutes(
     }
   }
 
+  // GET /api/jobs/:jobId/items - Per-item failure manifest
+  const itemsRegex = /^\/api\/jobs\/([^/]+)\/items$/;
+  const itemsMatch = itemsRegex.exec(url.pathname);
+  if (itemsMatch !== null && request.method === "GET") {
+    const requestedJobId = itemsMatch[1];
+    if (!requestedJobId) {
+      return createErrorResponse("Invalid job ID", corsHeaders, 400);
+    }
+
+    const format = url.searchParams.get("format") ?? "json";
+    if (format !== "json" && format !== "csv" && format !== "ndjson") {
+      return createErrorResponse(
+        "Invalid format. Use json, csv or ndjson",
+        corsHeaders,
+        400,
+      );
+    }
+
+    const filter = parseJobItemFilter(url, requestedJobId);
+
+    logInfo("Getting items for job", {
+      module: "jobs",
+      operation: "get_job_items",
+      metadata: { jobId: requestedJobId, format },
+    });
+
+    if (isLocalDev || !db) {
+      const mockItems: JobItem[] = [
+        {
+          job_id: requestedJobId,
+          object_key: "photos/missing.jpg",
+          status: "failed",
+          error_code: "not_found",
+          error: "Source object not found",
+          attempts: 1,
+          updated_at: new Date().toISOString(),
+        },
+        {
+          job_id: requestedJobId,
+          object_key: "photos/large.mov",
+          status: "failed",
+          error_code: "storage_error",
+          error: "Failed to copy object: 500",
+          attempts: 2,
+          updated_at: new Date().toISOString(),
+        },
+      ];
+
+      if (format !== "json") {
+        return createJobItemsExport(
+          requestedJobId,
+          format,
+          [mockItems].values(),
+          corsHeaders,
+        );
+      }
+
+      const response: APIResponse = {
+        success: true,
+        result: {
+          job_id: requestedJobId,
+          items: mockItems,
+          total: mockItems.length,
+          summary: {
+            failed: 2,
+            succeeded: 0,
+            by_error_code: { not_found: 1, storage_error: 1 },
+          },
+        },
+      };
+      return new Response(JSON.stringify(response), {
+        headers: { "Content-Type": "application/json", ...corsHeaders },
+      });
+    }
+
+    try {
+      const job = await db
+        .prepare("SELECT bucket_name, user_email FROM bulk_jobs WHERE job_id = ?")
+        .bind(requestedJobId)
+        .first<JobOwnership>();
+      if (!job) {
+        return createErrorResponse("Job not found", corsHeaders, 404);
+      }
+      if (!canAccessJob(request, userEmail, job, "viewer")) {
+        return createErrorResponse(
+          "Forbidden: you cannot view items of this job",
+          corsHeaders,
+          403,
+        );
+      }
+
+      if (format !== "json") {
+        return createJobItemsExport(
+          requestedJobId,
+          format,
+          iterateJobItems(db, filter),
+          corsHeaders,
+        );
+      }
+
+      const limit = Math.min(
+        Math.max(parseInt(url.searchParams.get("limit") ?? "100") || 100, 1),
+        1000,
+      );
+      const offset = Math.max(
+        parseInt(url.searchParams.get("offset") ?? "0") || 0,
+        0,
+      );
+
+      const [items, count, summary] = await Promise.all([
+        db
+          .prepare(
+            `SELECT * FROM job_items WHERE ${filter.where} ORDER BY object_key LIMIT ? OFFSET ?`,
+          )
+          .bind(...filter.bindings, limit, offset)
+          .all<JobItem>(),
+        db
+          .prepare(
+            `SELECT COUNT(*) AS count FROM job_items WHERE ${filter.where}`,
+          )
+          .bind(...filter.bindings)
+          .first<{ count: number }>(),
+        db
+          .prepare(
+            "SELECT status, error_code, COUNT(*) AS count FROM job_items WHERE job_id = ? GROUP BY status, error_code",
+          )
+          .bind(requestedJobId)
+          .all<{
+            status: JobItemStatus;
+            error_code: JobItemErrorCode | null;
+            count: number;
+          }>(),
+      ]);
+
+      const byErrorCode: Partial<Record<JobItemErrorCode, number>> = {};
+      let failed = 0;
+      let succeeded = 0;
+      for (const row of summary.results) {
+        if (row.status === "succeeded") {
+          succeeded += row.count;
+          continue;
+        }
+        failed += row.count;
+        const code = row.error_code ?? "unknown";
+        byErrorCode[code] = (byErrorCode[code] ?? 0) + row.count;
+      }
+
+      const response: APIResponse = {
+        success: true,
+        result: {
+          job_id: requestedJobId,
+          items: items.results,
+          total: count?.count ?? 0,
+          summary: { failed, succeeded, by_error_code: byErrorCode },
+        },
+      };
+      return new Response(JSON.stringify(response), {
+        headers: { "Content-Type": "application/json", ...corsHeaders },
+      });
+    } catch (error) {
+      await logError(
+        env,
+        error instanceof Error ? error : String(error),
+        {
+          module: "jobs",
+          operation: "get_job_items",
+          metadata: { jobId: requestedJobId },
+        },
+        isLocalDev,
+      );
+      return createErrorResponse("Failed to get job items", corsHeaders, 500);
+    }
+  }
+
+  // POST /api/jobs/:jobId/(cancel|pause|resume|retry-failed) - Control a job
+  const controlRegex =
+    /^\/api\/jobs\/([^/]+)\/(cancel|pause|resume|retry-failed)$/;
+  const controlMatch = controlRegex.exec(url.pathname);
+  if (controlMatch !== null && request.method === "POST") {
+    const requestedJobId = controlMatch[1];
+    const action = controlMatch[2] as JobControlAction | undefined;
+    if (!requestedJobId || !action) {
+      return createErrorResponse("Invalid job ID", corsHeaders, 400);
+    }
+
+    logInfo(`Job control: ${action}`, {
+      module: "jobs",
+      operation: "control_job",
+      userId: userEmail,
+      metadata: { jobId: requestedJobId, action },
+    });
+
+    if (isLocalDev || !db) {
+      const response: APIResponse = {
+        success: true,
+        result: {
+          job_id: requestedJobId,
+          status:
+            action === "cancel"
+              ? "cancelled"
+              : action === "pause"
+                ? "paused"
+                : "queued",
+        },
+      };
+      return new Response(JSON.stringify(response), {
+        headers: { "Content-Type": "application/json", ...corsHeaders },
+      });
+    }
+
+    try {
+      return await controlJob(
+        request,
+        env,
+        db,
+        requestedJobId,
+        action,
+        corsHeaders,
+        isLocalDev,
+        userEmail,
+      );
+    } catch (error) {
+      await logError(
+        env,
+        error instanceof Error ? error : String(error),
+        {
+          module: "jobs",
+          operation: "control_job",
+          metadata: { jobId: requestedJobId, action },
+        },
+        isLocalDev,
+      );
+      return createErrorResponse(`Failed to ${action} job`, corsHeaders, 500);
+    }
+  }
+
   // Not a job route
   return null;
 }
+
+interface JobItemFilter {
+  where: string;
+  bindings: (string | number)[];
+}
+
+/**
+ * Build the WHERE clause for ?status, ?error_code and ?prefix item filters
+ */
+function parseJobItemFilter(url: URL, jobId: string): JobItemFilter {
+  const conditions = ["job_id = ?"];
+  const bindings: (string | number)[] = [jobId];
+
+  const status = url.searchParams.get("status");
+  if (status === "failed" || status === "succeeded") {
+    conditions.push("status = ?");
+    bindings.push(status);
+  }
+
+  const errorCode = url.searchParams.get("error_code");
+  if (errorCode) {
+    conditions.push("error_code = ?");
+    bindings.push(errorCode);
+  }
+
+  const prefix = url.searchParams.get("prefix");
+  if (prefix) {
+    conditions.push("substr(object_key, 1, ?) = ?");
+    bindings.push(prefix.length, prefix);
+  }
+
+  return { where: conditions.join(" AND "), bindings };
+}
+
+/**
+ * Page through all matching items by key, 1000 rows per query
+ */
+async function* iterateJobItems(
+  db: D1Database,
+  filter: JobItemFilter,
+): AsyncGenerator<JobItem[]> {
+  let after = "";
+  for (;;) {
+    const page = await db
+      .prepare(
+        `SELECT * FROM job_items WHERE ${filter.where} AND object_key > ? ORDER BY object_key LIMIT 1000`,
+      )
+      .bind(...filter.bindings, after)
+      .all<JobItem>();
+
+    if (page.results.length > 0) {
+      yield page.results;
+    }
+    const last = page.results[page.results.length - 1];
+    if (!last || page.results.length < 1000) return;
+    after = last.object_key;
+  }
+}
+
+const JOB_ITEM_CSV_COLUMNS = [
+  "object_key",
+  "status",
+  "error_code",
+  "error",
+  "attempts",
+  "updated_at",
+] as const;
+
+/**
+ * Quote a CSV field. Keys and error messages come from uploaders, so text
+ * that a spreadsheet would run as a formula is prefixed with "'".
+ */
+function escapeCsvValue(value: string | number | null): string {
+  if (value === null) return "";
+  if (typeof value === "string" && /^[=+\-@\t\r]/.test(value)) {
+    return `"'${value.replace(/"/g, '""')}"`;
+  }
+  const text = String(value);
+  return /[",\r\n]/.test(text) ? `"${text.replace(/"/g, '""')}"` : text;
+}
+
+/**
+ * Stream job items as a CSV or NDJSON attachment
+ */
+function createJobItemsExport(
+  jobId: string,
+  format: "csv" | "ndjson",
+  pages: Iterator<JobItem[]> | AsyncIterator<JobItem[]>,
+  corsHeaders: HeadersInit,
+): Response {
+  const encoder = new TextEncoder();
+  const stream = new ReadableStream<Uint8Array>({
+    start(controller) {
+      if (format === "csv") {
+        controller.enqueue(
+          encoder.encode(JOB_ITEM_CSV_COLUMNS.join(",") + "\n"),
+        );
+      }
+    },
+    async pull(controller) {
+      const { done, value } = await pages.next();
+      if (done) {
+        controller.close();
+        return;
+      }
+      const lines = value.map((item) =>
+        format === "csv"
+          ? JOB_ITEM_CSV_COLUMNS.map((column) =>
+              escapeCsvValue(item[column]),
+            ).join(",")
+          : JSON.stringify(item),
+      );
+      controller.enqueue(encoder.encode(lines.join("\n") + "\n"));
+    },
+  });
+
+  const safeJobId = jobId.replace(/[^\w.-]/g, "_");
+  return new Response(stream, {
+    headers: {
+      "Content-Type":
+        format === "csv" ? "text/csv; charset=utf-8" : "application/x-ndjson",
+      "Content-Disposition": `attachment; filename="job-${safeJobId}-items.${format}"`,
+      ...corsHeaders,
+    },
+  });
+}
+
+interface JobOwnership {
+  bucket_name: string;
+  user_email: string;
+}
+
+/**
+ * Whether the caller may see or control a job: they started it, or they
+ * hold the role on every bucket it covers. Multi-bucket downloads record
+ * their buckets as a comma-separated list.
+ */
+function canAccessJob(
+  request: Request,
+  userEmail: string,
+  job: JobOwnership,
+  role: "viewer" | "editor",
+): boolean {
+  if (job.user_email === userEmail) {
+    return true;
+  }
+  const access = getRequestAccess(request);
+  if (access === undefined) {
+    return true;
+  }
+  return job.bucket_name
+    .split(",")
+    .map((bucket) => bucket.trim())
+    .every((bucket) => satisfiesCheck(access, { role, scope: "any", bucket }));
+}
+
+/**
+ * Apply a cancel, pause, resume or retry-failed action to a job. Running
+ * jobs observe cancel and pause before their next batch.
+ */
+async function controlJob(
+  request: Request,
+  env: Env,
+  db: D1Database,
+  jobId: string,
+  action: JobControlAction,
+  corsHeaders: HeadersInit,
+  isLocalDev: boolean,
+  userEmail: string,
+): Promise<Response> {
+  const job = await db
+    .prepare(
+      `
+    SELECT j.status, j.operation_type, j.bucket_name, j.user_email,
+      j.processed_items, j.error_count, t.job_id AS task_job_id
+    FROM bulk_jobs j LEFT JOIN job_tasks t ON t.job_id = j.job_id
+    WHERE j.job_id = ?
+  `,
+    )
+    .bind(jobId)
+    .first<
+      JobOwnership & {
+        status: JobStatus;
+        operation_type: JobOperationType;
+        processed_items: number | null;
+        error_count: number | null;
+        task_job_id: string | null;
+      }
+    >();
+
+  if (!job) {
+    return createErrorResponse("Job not found", corsHeaders, 404);
+  }
+  if (!canAccessJob(request, userEmail, job, "editor")) {
+    return createErrorResponse(
+      "Forbidden: you cannot control this job",
+      corsHeaders,
+      403,
+    );
+  }
+
+  // Only jobs run by the job runner have a task row to pause or replay
+  const resumable = job.task_job_id !== null;
+  const conflict = (message: string): Response =>
+    createErrorResponse(message, corsHeaders, 409);
+
+  switch (action) {
+    case "cancel": {
+      if (
+        job.status !== "queued" &&
+        job.status !== "running" &&
+        job.status !== "paused"
+      ) {
+        return conflict(`Cannot cancel a ${job.status} job`);
+      }
+      const cancelled = await completeJob(db, {
+        jobId,
+        status: "cancelled",
+        userEmail,
+        fromStatuses: ["queued", "running", "paused"],
+      });
+      if (!cancelled) {
+        return conflict("The job finished before it could be cancelled");
+      }
+      void triggerWebhooks(
+        env,
+        "job_cancelled",
+        createJobCancelledPayload(
+          jobId,
+          job.operation_type,
+          job.processed_items ?? 0,
+          job.bucket_name,
+          userEmail,
+        ),
+        isLocalDev,
+      );
+      break;
+    }
+
+    case "pause": {
+      if (job.status !== "queued" && job.status !== "running") {
+        return conflict(`Cannot pause a ${job.status} job`);
+      }
+      if (!resumable) {
+        return conflict("This job cannot be paused");
+      }
+      const paused = await db
+        .prepare(
+          "UPDATE bulk_jobs SET status = 'paused' WHERE job_id = ? AND status IN ('queued', 'running')",
+        )
+        .bind(jobId)
+        .run();
+      if (paused.meta.changes === 0) {
+        return conflict("The job finished before it could be paused");
+      }
+      await logJobEvent(db, { jobId, eventType: "paused", userEmail });
+      break;
+    }
+
+    case "resume": {
+      if (job.status !== "paused") {
+        return conflict(`Cannot resume a ${job.status} job`);
+      }
+      const resumed = await db
+        .prepare(
+          "UPDATE bulk_jobs SET status = 'queued' WHERE job_id = ? AND status = 'paused'",
+        )
+        .bind(jobId)
+        .run();
+      if (resumed.meta.changes === 0) {
+        return conflict("The job is no longer paused");
+      }
+      await logJobEvent(db, { jobId, eventType: "resumed", userEmail });
+      await continueJob(env, jobId, isLocalDev);
+      break;
+    }
+
+    case "retry-failed": {
+      if (job.status !== "completed" && job.status !== "failed") {
+        return conflict(`Cannot retry a ${job.status} job`);
+      }
+      if (!resumable) {
+        return conflict("This job cannot be retried");
+      }
+      const failed = await db
+        .prepare(
+          "SELECT COUNT(*) AS count FROM job_items WHERE job_id = ? AND status = 'failed'",
+        )
+        .bind(jobId)
+        .first<{ count: number }>();
+      const failedItems = failed?.count ?? 0;
+      if (failedItems === 0) {
+        return conflict("Job has no failed items to retry");
+      }
+
+      await db.batch([
+        db
+          .prepare(
+            "UPDATE bulk_jobs SET status = 'queued', completed_at = NULL WHERE job_id = ?",
+          )
+          .bind(jobId),
+        db
+          .prepare(
+            "UPDATE job_tasks SET phase = 'retry', cursor = NULL, attempts = 0, updated_at = ? WHERE job_id = ?",
+          )
+          .bind(new Date().toISOString(), jobId),
+      ]);
+      await logJobEvent(db, {
+        jobId,
+        eventType: "retry",
+        userEmail,
+        details: { items: failedItems },
+      });
+      await continueJob(env, jobId, isLocalDev);
+      break;
+    }
+  }
+
+  const updated = await db
+    .prepare("SELECT * FROM bulk_jobs WHERE job_id = ?")
+    .bind(jobId)
+    .first();
+
+  const response: APIResponse = {
+    success: true,
+    result: updated,
+  };
+  return new Response(JSON.stringify(response), {
+    headers: { "Content-Type": "application/json", ...corsHeaders },
+  });
+}
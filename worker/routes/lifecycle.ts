@@ -9,6 +9,10 @@ import { type CorsHeaders } from "../utils/cors";
 import { getCloudflareHeaders } from "../utils/helpers";
 import { logError, logInfo, logWarning } from "../utils/error-logger";
 import { createErrorResponse } from "../utils/error-response";
+import {
+  triggerWebhooks,
+  createLifecycleUpdatedPayload,
+} from "../utils/webhooks";
 
 /**
  * Handle lifecycle routes for R2 bucket lifecycle management
@@ -23,7 +27,7 @@ export async function handleLifecycleRoutes(
   url: URL,
   corsHeaders: CorsHeaders,
   isLocalDev: boolean,
-  _userEmail: string,
+  userEmail: string,
 ): Promise<Response> {
   logInfo("Handling lifecycle operation", {
     module: "lifecycle",
@@ -268,6 +272,13 @@ export async function handleLifecycleRoutes(
         },
       );
 
+      void triggerWebhooks(
+        env,
+        "lifecycle_updated",
+        createLifecycleUpdatedPayload(bucketName, body.rules.length, userEmail),
+        isLocalDev,
+      );
+
       return new Response(
         JSON.stringify({
           success: true,
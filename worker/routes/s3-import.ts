@@ -5,12 +5,19 @@ import type {
   S3ImportJobsListResponse,
   CreateS3ImportJobBody,
   S3ImportJobResponse,
+  EnableSippyBody,
+  SippyConfig,
 } from "../types";
 import { CF_API } from "../types";
 import { type CorsHeaders } from "../utils/cors";
 import { getCloudflareHeaders } from "../utils/helpers";
 import { logInfo, logError, logWarning } from "../utils/error-logger";
 import { SUPPORT_EMAIL } from "../utils/error-response";
+import {
+  triggerWebhooks,
+  createJobFailedPayload,
+  createS3ImportCompletePayload,
+} from "../utils/webhooks";
 
 // Cloudflare Super Slurper API response types
 interface SlurperJobApiResponse {
@@ -91,12 +98,79 @@ function mapApiJobToS3ImportJob(apiJob: SlurperJobApiResponse): S3ImportJob {
   };
 }
 
+// Cloudflare Sippy API response type
+interface SippyApiResponse {
+  enabled: boolean;
+  source?: {
+    provider: string;
+    bucket?: string;
+    region?: string;
+  };
+}
+
+/**
+ * Sippy status of a bucket, or null if it could not be read
+ */
+export async function getSippyConfig(
+  env: Env,
+  bucketName: string,
+): Promise<SippyConfig | null> {
+  const response = await fetch(
+    `${CF_API}/accounts/${env.ACCOUNT_ID}/r2/buckets/${encodeURIComponent(bucketName)}/sippy`,
+    { headers: getCloudflareHeaders(env) },
+  );
+  if (!response.ok) {
+    return null;
+  }
+
+  const data =
+    (await response.json()) as CloudflareApiResponse<SippyApiResponse>;
+  const result = data.result;
+  if (result?.enabled !== true || result.source === undefined) {
+    return { enabled: false };
+  }
+  return {
+    enabled: true,
+    source: {
+      provider: result.source.provider === "gcs" ? "gcs" : "aws",
+      bucket: result.source.bucket ?? "",
+      region: result.source.region,
+    },
+  };
+}
+
+/**
+ * Validation error of a Sippy enable request, or null if it is valid
+ */
+function validateSippyBody(body: Partial<EnableSippyBody>): string | null {
+  if (body.provider !== "aws" && body.provider !== "gcs") {
+    return "provider must be aws or gcs";
+  }
+  if (!body.sourceBucket) {
+    return "sourceBucket is required";
+  }
+  if (
+    body.provider === "aws" &&
+    (!body.accessKeyId || !body.secretAccessKey || !body.region)
+  ) {
+    return "AWS sources need accessKeyId, secretAccessKey and region";
+  }
+  if (body.provider === "gcs" && (!body.clientEmail || !body.privateKey)) {
+    return "GCS sources need clientEmail and privateKey";
+  }
+  if (!body.r2AccessKeyId || !body.r2SecretAccessKey) {
+    return "r2AccessKeyId and r2SecretAccessKey are required";
+  }
+  return null;
+}
+
 // S3 Import Error Codes
 const S3_ERROR_CODES = {
   LIST_FAILED: "S3_LIST_JOBS_FAILED",
   CREATE_FAILED: "S3_CREATE_JOB_FAILED",
   GET_STATUS_FAILED: "S3_GET_STATUS_FAILED",
   ABORT_FAILED: "S3_ABORT_JOB_FAILED",
+  SIPPY_FAILED: "S3_SIPPY_FAILED",
   RATE_LIMITED: "S3_RATE_LIMITED",
 } as const;
 
@@ -194,12 +268,116 @@ async function fetchWithRetryBackend(
   throw lastError ?? new Error("Request failed after retries");
 }
 
+/** Finished jobs older than this when first seen are not announced */
+const S3_IMPORT_ANNOUNCE_WINDOW_MS = 24 * 60 * 60 * 1000;
+
+/**
+ * Send webhooks for Super Slurper jobs that finished since the last sync:
+ * s3_import_complete for completed jobs and job_failed for failed ones.
+ * Returns the number of newly finished jobs.
+ */
+export async function syncS3ImportCompletions(env: Env): Promise<number> {
+  const db = env.METADATA;
+  if (!db) {
+    return 0;
+  }
+
+  const response = await fetchWithRetryBackend(
+    `${CF_API}/accounts/${env.ACCOUNT_ID}/slurper/jobs`,
+    { headers: getCloudflareHeaders(env) },
+    env,
+    false,
+    { operation: "sync_completions", code: S3_ERROR_CODES.LIST_FAILED },
+  );
+  if (!response.ok) {
+    logWarning(`Failed to list jobs: ${response.status}`, {
+      module: "s3-import",
+      operation: "sync_completions",
+    });
+    return 0;
+  }
+
+  const data =
+    (await response.json()) as CloudflareApiResponse<SlurperJobsListApiResponse>;
+  const finished = (data.result?.jobs ?? [])
+    .map(mapApiJobToS3ImportJob)
+    .filter(
+      (job) =>
+        job.status === "complete" ||
+        job.status === "error" ||
+        job.status === "aborted",
+    );
+  if (finished.length === 0) {
+    return 0;
+  }
+
+  const notified = await db
+    .prepare("SELECT job_id FROM s3_import_notifications")
+    .all<{ job_id: string }>();
+  const notifiedIds = new Set(notified.results.map((row) => row.job_id));
+  const newlyFinished = finished.filter((job) => !notifiedIds.has(job.id));
+  if (newlyFinished.length === 0) {
+    return 0;
+  }
+
+  const now = new Date().toISOString();
+  await db.batch(
+    newlyFinished.map((job) =>
+      db
+        .prepare(
+          "INSERT OR IGNORE INTO s3_import_notifications (job_id, status, notified_at) VALUES (?, ?, ?)",
+        )
+        .bind(job.id, job.status, now),
+    ),
+  );
+
+  // Jobs that finished long before they were first seen are only recorded,
+  // so that imports from before the cron was set up are not announced
+  const announceAfter = Date.now() - S3_IMPORT_ANNOUNCE_WINDOW_MS;
+  for (const job of newlyFinished) {
+    if (Date.parse(job.completed_at ?? job.created_at) < announceAfter) {
+      continue;
+    }
+    if (job.status === "complete") {
+      await triggerWebhooks(
+        env,
+        "s3_import_complete",
+        createS3ImportCompletePayload(
+          job.id,
+          job.source.bucket,
+          job.destination.bucket,
+          job.progress?.objects_copied ?? 0,
+          job.progress?.objects_failed ?? 0,
+          job.progress?.bytes_copied ?? 0,
+          null,
+        ),
+        false,
+      );
+    } else if (job.status === "error") {
+      await triggerWebhooks(
+        env,
+        "job_failed",
+        createJobFailedPayload(
+          job.id,
+          "s3_import",
+          job.error ?? "S3 import failed",
+          job.destination.bucket,
+          null,
+        ),
+        false,
+      );
+    }
+  }
+  return newlyFinished.length;
+}
+
 export async function handleS3ImportRoutes(
   request: Request,
   env: Env,
   url: URL,
   corsHeaders: CorsHeaders,
   isLocalDev: boolean,
+  userEmail: string,
 ): Promise<Response> {
   logInfo("Handling S3 Import operation", {
     module: "s3-import",
@@ -672,6 +850,200 @@ export async function handleS3ImportRoutes(
       }
     }
 
+    // GET/PUT/DELETE /api/s3-import/sippy/:bucketName - Sippy incremental
+    // migration of a bucket
+    const sippyMatch = /^\/api\/s3-import\/sippy\/([^/]+)$/.exec(url.pathname);
+    if (sippyMatch?.[1] !== undefined) {
+      const bucketName = decodeURIComponent(sippyMatch[1]);
+      const sippyApi = `${CF_API}/accounts/${env.ACCOUNT_ID}/r2/buckets/${encodeURIComponent(bucketName)}/sippy`;
+
+      if (request.method === "GET") {
+        if (isLocalDev) {
+          const mockConfig: SippyConfig = {
+            enabled: true,
+            source: {
+              provider: "aws",
+              bucket: "legacy-s3-bucket",
+              region: "us-east-1",
+            },
+          };
+          return new Response(
+            JSON.stringify({ success: true, result: mockConfig }),
+            {
+              headers: { "Content-Type": "application/json", ...corsHeaders },
+            },
+          );
+        }
+
+        const config = await getSippyConfig(env, bucketName);
+        if (config === null) {
+          return new Response(
+            JSON.stringify({
+              success: false,
+              error: "Failed to get Sippy status",
+              support: SUPPORT_EMAIL,
+            }),
+            {
+              status: 502,
+              headers: { "Content-Type": "application/json", ...corsHeaders },
+            },
+          );
+        }
+        return new Response(JSON.stringify({ success: true, result: config }), {
+          headers: { "Content-Type": "application/json", ...corsHeaders },
+        });
+      }
+
+      if (request.method === "PUT") {
+        const body = (await request.json()) as Partial<EnableSippyBody>;
+        const validationError = validateSippyBody(body);
+        if (validationError !== null) {
+          return new Response(
+            JSON.stringify({
+              success: false,
+              error: validationError,
+              support: SUPPORT_EMAIL,
+            }),
+            {
+              status: 400,
+              headers: { "Content-Type": "application/json", ...corsHeaders },
+            },
+          );
+        }
+
+        const config: SippyConfig = {
+          enabled: true,
+          source: {
+            provider: body.provider ?? "aws",
+            bucket: body.sourceBucket ?? "",
+            region: body.region,
+          },
+        };
+        if (isLocalDev) {
+          return new Response(
+            JSON.stringify({ success: true, result: config }),
+            {
+              headers: { "Content-Type": "application/json", ...corsHeaders },
+            },
+          );
+        }
+
+        const source =
+          body.provider === "gcs"
+            ? {
+                provider: "gcs",
+                bucket: body.sourceBucket,
+                clientEmail: body.clientEmail,
+                privateKey: body.privateKey,
+              }
+            : {
+                provider: "aws",
+                bucket: body.sourceBucket,
+                region: body.region,
+                accessKeyId: body.accessKeyId,
+                secretAccessKey: body.secretAccessKey,
+              };
+        const response = await fetchWithRetryBackend(
+          sippyApi,
+          {
+            method: "PUT",
+            headers: cfHeaders,
+            body: JSON.stringify({
+              source,
+              destination: {
+                provider: "r2",
+                accessKeyId: body.r2AccessKeyId,
+                secretAccessKey: body.r2SecretAccessKey,
+              },
+            }),
+          },
+          env,
+          isLocalDev,
+          { operation: "enable_sippy", code: S3_ERROR_CODES.SIPPY_FAILED },
+        );
+
+        if (!response.ok) {
+          const errorData = (await response.json().catch(() => ({}))) as {
+            errors?: { message: string }[];
+          };
+          const errorMessage =
+            errorData.errors?.[0]?.message ?? "Failed to enable Sippy";
+          logWarning(
+            `[${S3_ERROR_CODES.SIPPY_FAILED}] Failed to enable Sippy: ${errorMessage}`,
+            {
+              module: "s3-import",
+              operation: "enable_sippy",
+              bucketName,
+              metadata: { status: response.status },
+            },
+          );
+          return new Response(
+            JSON.stringify({
+              success: false,
+              error: errorMessage,
+              support: SUPPORT_EMAIL,
+            }),
+            {
+              status: response.status,
+              headers: { "Content-Type": "application/json", ...corsHeaders },
+            },
+          );
+        }
+
+        logInfo(`Enabled Sippy for bucket: ${bucketName}`, {
+          module: "s3-import",
+          operation: "enable_sippy",
+          bucketName,
+          userId: userEmail,
+          metadata: {
+            provider: body.provider,
+            sourceBucket: body.sourceBucket,
+          },
+        });
+        return new Response(JSON.stringify({ success: true, result: config }), {
+          headers: { "Content-Type": "application/json", ...corsHeaders },
+        });
+      }
+
+      if (request.method === "DELETE") {
+        if (!isLocalDev) {
+          const response = await fetchWithRetryBackend(
+            sippyApi,
+            { method: "DELETE", headers: cfHeaders },
+            env,
+            isLocalDev,
+            { operation: "disable_sippy", code: S3_ERROR_CODES.SIPPY_FAILED },
+          );
+          if (!response.ok) {
+            return new Response(
+              JSON.stringify({
+                success: false,
+                error: "Failed to disable Sippy",
+                support: SUPPORT_EMAIL,
+              }),
+              {
+                status: response.status,
+                headers: { "Content-Type": "application/json", ...corsHeaders },
+              },
+            );
+          }
+        }
+
+        logInfo(`Disabled Sippy for bucket: ${bucketName}`, {
+          module: "s3-import",
+          operation: "disable_sippy",
+          bucketName,
+          userId: userEmail,
+        });
+        return new Response(
+          JSON.stringify({ success: true, result: { enabled: false } }),
+          {
+            headers: { "Content-Type": "application/json", ...corsHeaders },
+          },
+        );
+      }
+    }
+
     // GET /api/s3-import/dashboard-url - Get the Cloudflare dashboard URL
     if (
       request.method === "GET" &&
@@ -0,0 +1,1411 @@
+/**
+ * S3-Compatible API
+ *
+ * A path-style subset of the S3 REST API under /s3/, so tools such as the
+ * AWS CLI and rclone can work through R2 Manager: ListBuckets, ListObjects
+ * (V1 and V2), HeadBucket, GetObject, HeadObject, PutObject, CopyObject,
+ * DeleteObject, DeleteObjects and multipart uploads.
+ *
+ * Requests are signed with SigV4 using an API token's S3 credentials (see
+ * utils/api-tokens.ts). They act as the token's creator and go through the
+ * same role grants, token scope, rate limits, audit log and webhooks as the
+ * rest of the API.
+ */
+
+import type {
+  AccessIdentity,
+  ApiTokenPermission,
+  AuditOperationType,
+  BucketsListResult,
+  CloudflareApiResponse,
+  Env,
+  StorageHttpMetadata,
+  StorageListResult,
+  StorageObject,
+  UserAccess,
+} from "../types";
+import { CF_API } from "../types";
+import { getS3SecretKey, validateApiTokenId } from "../utils/api-tokens";
+import { logAuditEvent } from "./audit";
+import { logError, logInfo, logWarning } from "../utils/error-logger";
+import { getCloudflareHeaders } from "../utils/helpers";
+import { checkObjectLock, describeObjectLock } from "../utils/bucket-lock";
+import { checkRateLimit } from "../utils/ratelimit";
+import { isActiveContentType, resolveDisposition } from "../utils/signing";
+import {
+  canSeeKey,
+  hasBucketAccess,
+  loadUserAccess,
+  satisfiesCheck,
+  type AccessCheck,
+} from "../utils/rbac";
+import {
+  parseCompleteMultipartUpload,
+  parseDeleteRequest,
+  renderCompleteMultipartUpload,
+  renderCopyObjectResult,
+  renderDeleteResult,
+  renderInitiateMultipartUpload,
+  renderListBuckets,
+  renderListObjects,
+  renderLocationConstraint,
+  renderS3Error,
+} from "../utils/s3-xml";
+import {
+  STREAMING_UNSIGNED_PAYLOAD_TRAILER,
+  UNSIGNED_PAYLOAD,
+  createAwsChunkedDecoder,
+  isSignedPayloadHash,
+  parseSigV4Request,
+  sha256Hex,
+  verifyPayloadHash,
+  verifySigV4,
+} from "../utils/sigv4";
+import {
+  copyObject,
+  getBucketBinding,
+  getObjectStorage,
+} from "../utils/storage";
+import {
+  deleteOrTrashObject,
+  isTrashKey,
+  TRASH_PREFIX,
+  trashBeforeOverwrite,
+} from "../utils/trash";
+import {
+  createFileCopyPayload,
+  createFileDeletePayload,
+  createFileDownloadPayload,
+  createFileUploadPayload,
+  triggerWebhooks,
+} from "../utils/webhooks";
+
+const MAX_KEYS = 1000;
+const MAX_PART_NUMBER = 10000;
+const DELETE_BATCH_SIZE = 25;
+/** Signed bodies up to this size are hashed in memory before being stored */
+const MAX_BUFFERED_PUT_BYTES = 32 * 1024 * 1024;
+/** Larger signed bodies wait here, hidden from listings, until verified */
+const PENDING_PUT_PREFIX = `${TRASH_PREFIX}pending/`;
+
+/** Bucket and object configuration sub-resources the endpoint does not serve */
+const UNSUPPORTED_SUBRESOURCES = [
+  "accelerate",
+  "acl",
+  "analytics",
+  "cors",
+  "encryption",
+  "intelligent-tiering",
+  "inventory",
+  "legal-hold",
+  "lifecycle",
+  "logging",
+  "metrics",
+  "notification",
+  "object-lock",
+  "ownershipControls",
+  "policy",
+  "policyStatus",
+  "publicAccessBlock",
+  "replication",
+  "requestPayment",
+  "retention",
+  "tagging",
+  "torrent",
+  "versioning",
+  "versions",
+  "website",
+];
+
+const HTTP_METADATA_HEADERS: [keyof StorageHttpMetadata, string][] = [
+  ["contentType", "Content-Type"],
+  ["contentDisposition", "Content-Disposition"],
+  ["contentEncoding", "Content-Encoding"],
+  ["contentLanguage", "Content-Language"],
+  ["cacheControl", "Cache-Control"],
+];
+
+interface S3Context {
+  request: Request;
+  env: Env;
+  url: URL;
+  isLocalDev: boolean;
+  access: UserAccess;
+  userEmail: string;
+  tokenId: string;
+  payloadHash: string;
+  requestId: string;
+}
+
+function s3Error(
+  request: Request,
+  url: URL,
+  requestId: string,
+  code: string,
+  message: string,
+  status: number,
+  headers: Record<string, string> = {},
+): Response {
+  return new Response(
+    request.method === "HEAD"
+      ? null
+      : renderS3Error(code, message, url.pathname, requestId),
+    {
+      status,
+      headers: {
+        "Content-Type": "application/xml",
+        "x-amz-request-id": requestId,
+        ...headers,
+      },
+    },
+  );
+}
+
+function fail(
+  ctx: S3Context,
+  code: string,
+  message: string,
+  status: number,
+  headers?: Record<string, string>,
+): Response {
+  return s3Error(
+    ctx.request,
+    ctx.url,
+    ctx.requestId,
+    code,
+    message,
+    status,
+    headers,
+  );
+}
+
+function accessDenied(ctx: S3Context): Response {
+  return fail(ctx, "AccessDenied", "Access Denied", 403);
+}
+
+function payloadMismatch(ctx: S3Context): Response {
+  return fail(
+    ctx,
+    "XAmzContentSHA256Mismatch",
+    "The provided x-amz-content-sha256 header does not match what was computed",
+    400,
+  );
+}
+
+/**
+ * AccessDenied for a key a bucket lock rule keeps from being deleted or
+ * overwritten, or null if it is not locked
+ */
+async function checkLocked(
+  ctx: S3Context,
+  bucket: string,
+  key: string,
+): Promise<Response | null> {
+  const lock = await checkObjectLock(ctx.env, bucket, key);
+  return lock === null
+    ? null
+    : fail(ctx, "AccessDenied", describeObjectLock(lock), 403);
+}
+
+function xmlResponse(ctx: S3Context, body: string): Response {
+  return new Response(body, {
+    headers: {
+      "Content-Type": "application/xml",
+      "x-amz-request-id": ctx.requestId,
+    },
+  });
+}
+
+/**
+ * Whether the request's role grants and API token allow an operation
+ */
+function isAllowed(
+  ctx: S3Context,
+  check: AccessCheck,
+  permission: ApiTokenPermission,
+): boolean {
+  return (
+    satisfiesCheck(ctx.access, check) &&
+    ctx.access.token?.permissions.includes(permission) !== false
+  );
+}
+
+function auditMetadata(
+  ctx: S3Context,
+  extra: Record<string, unknown> = {},
+): Record<string, unknown> {
+  return { source: "s3", tokenId: ctx.tokenId, ...extra };
+}
+
+async function audit(
+  ctx: S3Context,
+  operationType: AuditOperationType,
+  bucketName: string,
+  objectKey: string,
+  extra: {
+    sizeBytes?: number;
+    destinationBucket?: string;
+    destinationKey?: string;
+    metadata?: Record<string, unknown>;
+  } = {},
+): Promise<void> {
+  await logAuditEvent(
+    ctx.env,
+    {
+      operationType,
+      bucketName,
+      objectKey,
+      userEmail: ctx.userEmail,
+      status: "success",
+      sizeBytes: extra.sizeBytes,
+      destinationBucket: extra.destinationBucket,
+      destinationKey: extra.destinationKey,
+      metadata: auditMetadata(ctx, extra.metadata),
+    },
+    ctx.isLocalDev,
+  );
+}
+
+/**
+ * HTTP and x-amz-meta-* metadata sent with an upload
+ */
+function readUploadMetadata(request: Request): {
+  httpMetadata: StorageHttpMetadata;
+  customMetadata: Record<string, string>;
+} {
+  const httpMetadata: StorageHttpMetadata = {};
+  for (const [field, header] of HTTP_METADATA_HEADERS) {
+    const value = request.headers.get(header);
+    if (value !== null && value !== "") {
+      httpMetadata[field] = value;
+    }
+  }
+
+  const customMetadata: Record<string, string> = {};
+  request.headers.forEach((value, name) => {
+    if (name.startsWith("x-amz-meta-")) {
+      customMetadata[name.slice("x-amz-meta-".length)] = value;
+    }
+  });
+  return { httpMetadata, customMetadata };
+}
+
+function objectHeaders(ctx: S3Context, object: StorageObject): Headers {
+  const headers = new Headers({
+    ETag: `"${object.etag}"`,
+    "Last-Modified": new Date(object.uploaded).toUTCString(),
+    "Content-Type":
+      object.httpMetadata.contentType ?? "application/octet-stream",
+    "Accept-Ranges": "bytes",
+    "x-amz-request-id": ctx.requestId,
+  });
+  for (const [field, header] of HTTP_METADATA_HEADERS) {
+    const value = object.httpMetadata[field];
+    if (value !== undefined && field !== "contentType") {
+      headers.set(header, value);
+    }
+  }
+  for (const [name, value] of Object.entries(object.customMetadata)) {
+    headers.set(`x-amz-meta-${name}`, value);
+  }
+
+  // Presigned URLs can override response headers
+  const overrides: [string, string][] = [
+    ["response-content-type", "Content-Type"],
+    ["response-content-disposition", "Content-Disposition"],
+    ["response-content-encoding", "Content-Encoding"],
+    ["response-content-language", "Content-Language"],
+    ["response-cache-control", "Cache-Control"],
+    ["response-expires", "Expires"],
+  ];
+  for (const [param, header] of overrides) {
+    const value = ctx.url.searchParams.get(param);
+    if (value !== null) {
+      headers.set(header, value);
+    }
+  }
+
+  // Objects are served from the app's origin, so active content is always
+  // downloaded, and nothing is sniffed into another type or runs script
+  const disposition = headers.get("Content-Disposition") ?? "inline";
+  const contentType = headers.get("Content-Type") ?? "";
+  if (
+    /^\s*inline\b/i.test(disposition) &&
+    resolveDisposition("inline", contentType) === "attachment"
+  ) {
+    headers.set(
+      "Content-Disposition",
+      disposition.replace(/^\s*inline/i, "attachment"),
+    );
+  }
+  headers.set("X-Content-Type-Options", "nosniff");
+  headers.set("Content-Security-Policy", "sandbox");
+  return headers;
+}
+
+/**
+ * The body of a PutObject or UploadPart request, decoded from aws-chunked
+ * if needed and sized for the storage layer. `verified` settles once the
+ * body has been read.
+ */
+function readUploadBody(
+  ctx: S3Context,
+):
+  | { body: ReadableStream; length: number; verified: Promise<boolean> }
+  | Response {
+  const { request, payloadHash } = ctx;
+  const chunked = payloadHash === STREAMING_UNSIGNED_PAYLOAD_TRAILER;
+  if (payloadHash.startsWith("STREAMING-") && !chunked) {
+    return fail(
+      ctx,
+      "NotImplemented",
+      "Signed aws-chunked uploads are not supported, send UNSIGNED-PAYLOAD or a payload hash",
+      501,
+    );
+  }
+  if (
+    !chunked &&
+    payloadHash !== UNSIGNED_PAYLOAD &&
+    !isSignedPayloadHash(payloadHash)
+  ) {
+    return fail(
+      ctx,
+      "InvalidArgument",
+      "x-amz-content-sha256 must be UNSIGNED-PAYLOAD or a SHA-256 hash",
+      400,
+    );
+  }
+
+  const lengthHeader = request.headers.get(
+    chunked ? "x-amz-decoded-content-length" : "Content-Length",
+  );
+  const length = lengthHeader !== null ? Number(lengthHeader) : NaN;
+  if (!Number.isSafeInteger(length) || length < 0) {
+    return fail(
+      ctx,
+      "MissingContentLength",
+      "You must provide the Content-Length HTTP header",
+      411,
+    );
+  }
+
+  let body: ReadableStream<Uint8Array> = request.body ?? new Blob().stream();
+  if (chunked) {
+    body = body.pipeThrough(createAwsChunkedDecoder());
+  }
+
+  let verified = Promise.resolve(true);
+  if (isSignedPayloadHash(payloadHash)) {
+    ({ body, verified } = verifyPayloadHash(body, payloadHash));
+  }
+  return {
+    body: body.pipeThrough(new FixedLengthStream(length)),
+    length,
+    verified,
+  };
+}
+
+/**
+ * Read a small XML request body, checking it against the payload hash
+ */
+async function readXmlBody(ctx: S3Context): Promise<string | Response> {
+  const bytes = new Uint8Array(await ctx.request.arrayBuffer());
+  if (
+    isSignedPayloadHash(ctx.payloadHash) &&
+    (await sha256Hex(bytes)) !== ctx.payloadHash.toLowerCase()
+  ) {
+    return payloadMismatch(ctx);
+  }
+  return new TextDecoder().decode(bytes);
+}
+
+async function bucketExists(env: Env, bucket: string): Promise<boolean> {
+  if (getBucketBinding(env, bucket) !== null) {
+    return true;
+  }
+  const response = await fetch(
+    CF_API +
+      "/accounts/" +
+      env.ACCOUNT_ID +
+      "/r2/buckets/" +
+      encodeURIComponent(bucket),
+    { headers: getCloudflareHeaders(env) },
+  );
+  return response.ok;
+}
+
+function noSuchBucket(ctx: S3Context): Response {
+  return fail(ctx, "NoSuchBucket", "The specified bucket does not exist", 404);
+}
+
+function noSuchKey(ctx: S3Context): Response {
+  return fail(ctx, "NoSuchKey", "The specified key does not exist", 404);
+}
+
+function noSuchUpload(ctx: S3Context): Response {
+  return fail(
+    ctx,
+    "NoSuchUpload",
+    "The specified multipart upload does not exist",
+    404,
+  );
+}
+
+function isMissingUploadError(error: unknown): boolean {
+  return (
+    error instanceof Error &&
+    /NoSuchUpload|upload does not exist/i.test(error.message)
+  );
+}
+
+// ============================================
+// Service and bucket operations
+// ============================================
+
+async function listBuckets(ctx: S3Context): Promise<Response> {
+  if (!isAllowed(ctx, { role: "viewer", scope: "any" }, "read")) {
+    return accessDenied(ctx);
+  }
+
+  const response = await fetch(
+    CF_API + "/accounts/" + ctx.env.ACCOUNT_ID + "/r2/buckets",
+    { headers: getCloudflareHeaders(ctx.env) },
+  );
+  if (!response.ok) {
+    throw new Error(`Failed to list buckets: ${String(response.status)}`);
+  }
+  const data =
+    (await response.json()) as CloudflareApiResponse<BucketsListResult>;
+
+  const buckets = (data.result?.buckets ?? [])
+    .filter((bucket) => hasBucketAccess(ctx.access, bucket.name))
+    .map((bucket) => ({
+      name: bucket.name,
+      creationDate: new Date(bucket.creation_date ?? 0).toISOString(),
+    }));
+  return xmlResponse(ctx, renderListBuckets(buckets, ctx.userEmail));
+}
+
+async function headBucket(ctx: S3Context, bucket: string): Promise<Response> {
+  if (!isAllowed(ctx, { role: "viewer", scope: "any", bucket }, "read")) {
+    return accessDenied(ctx);
+  }
+  if (!(await bucketExists(ctx.env, bucket))) {
+    return noSuchBucket(ctx);
+  }
+  return new Response(null, {
+    headers: { "x-amz-request-id": ctx.requestId },
+  });
+}
+
+/**
+ * Last key or common prefix of a listing page
+ */
+function lastEntry(page: StorageListResult): string {
+  const lastKey = page.objects[page.objects.length - 1]?.key ?? "";
+  const lastPrefix = page.delimitedPrefixes[page.delimitedPrefixes.length - 1];
+  return lastPrefix !== undefined && lastPrefix > lastKey
+    ? lastPrefix
+    : lastKey;
+}
+
+async function listObjects(ctx: S3Context, bucket: string): Promise<Response> {
+  const params = ctx.url.searchParams;
+  const version = params.get("list-type") === "2" ? 2 : 1;
+  const prefix = params.get("prefix") ?? "";
+  const delimiterParam = params.get("delimiter");
+  const delimiter =
+    delimiterParam !== null && delimiterParam !== ""
+      ? delimiterParam
+      : undefined;
+  const continuationToken =
+    version === 2 ? (params.get("continuation-token") ?? undefined) : undefined;
+  const startAfter =
+    (version === 2 ? params.get("start-after") : params.get("marker")) ??
+    undefined;
+  const maxKeys = Math.min(
+    Number(params.get("max-keys") ?? MAX_KEYS),
+    MAX_KEYS,
+  );
+  if (!Number.isInteger(maxKeys) || maxKeys < 0) {
+    return fail(ctx, "InvalidArgument", "max-keys must be an integer", 400);
+  }
+
+  if (!isAllowed(ctx, { role: "viewer", scope: "any", bucket }, "read")) {
+    return accessDenied(ctx);
+  }
+
+  const storage = getObjectStorage(ctx.env, bucket);
+  const resumeAfter =
+    continuationToken === undefined && startAfter !== undefined
+      ? startAfter
+      : undefined;
+
+  let page: StorageListResult;
+  try {
+    page = await storage.list({
+      prefix,
+      delimiter,
+      cursor: continuationToken,
+      limit: Math.max(maxKeys, 1),
+      startAfter: storage.isNative ? resumeAfter : undefined,
+    });
+    // REST listings cannot start after a key, so skip the pages before it
+    if (resumeAfter !== undefined && !storage.isNative) {
+      while (page.truncated && lastEntry(page) <= resumeAfter) {
+        page = await storage.list({
+          prefix,
+          delimiter,
+          cursor: page.cursor,
+          limit: Math.max(maxKeys, 1),
+        });
+      }
+    }
+  } catch (error) {
+    if (!(await bucketExists(ctx.env, bucket))) {
+      return noSuchBucket(ctx);
+    }
+    throw error;
+  }
+
+  const after = (entry: string): boolean =>
+    resumeAfter === undefined || entry > resumeAfter;
+  const objects =
+    maxKeys === 0
+      ? []
+      : page.objects.filter(
+          (object) =>
+            after(object.key) &&
+            !isTrashKey(object.key) &&
+            satisfiesCheck(ctx.access, {
+              role: "viewer",
+              scope: "key",
+              bucket,
+              key: object.key,
+            }),
+        );
+  const commonPrefixes =
+    maxKeys === 0
+      ? []
+      : page.delimitedPrefixes.filter(
+          (folder) =>
+            after(folder) &&
+            !isTrashKey(folder) &&
+            canSeeKey(ctx.access, bucket, folder),
+        );
+  const truncated = maxKeys > 0 && page.truncated;
+
+  return xmlResponse(
+    ctx,
+    renderListObjects({
+      version,
+      bucket,
+      prefix,
+      delimiter,
+      maxKeys,
+      urlEncode: params.get("encoding-type") === "url",
+      objects,
+      commonPrefixes,
+      truncated,
+      continuationToken,
+      startAfter: version === 2 ? startAfter : undefined,
+      nextContinuationToken:
+        version === 2 && truncated ? page.cursor : undefined,
+      marker: version === 1 ? startAfter : undefined,
+      nextMarker: version === 1 && truncated ? lastEntry(page) : undefined,
+    }),
+  );
+}
+
+async function deleteObjects(
+  ctx: S3Context,
+  bucket: string,
+): Promise<Response> {
+  const xml = await readXmlBody(ctx);
+  if (xml instanceof Response) {
+    return xml;
+  }
+
+  const { keys, quiet } = parseDeleteRequest(xml);
+  if (keys.length === 0 || keys.length > MAX_KEYS) {
+    return fail(
+      ctx,
+      "MalformedXML",
+      `The request must list between 1 and ${MAX_KEYS} keys`,
+      400,
+    );
+  }
+
+  const deleted: string[] = [];
+  const errors: { key: string; code: string; message: string }[] = [];
+
+  for (let i = 0; i < keys.length; i += DELETE_BATCH_SIZE) {
+    await Promise.all(
+      keys.slice(i, i + DELETE_BATCH_SIZE).map(async (key) => {
+        if (
+          isTrashKey(key) ||
+          !isAllowed(
+            ctx,
+            { role: "editor", scope: "key", bucket, key },
+            "delete",
+          )
+        ) {
+          errors.push({ key, code: "AccessDenied", message: "Access Denied" });
+          return;
+        }
+        try {
+          const lock = await checkObjectLock(ctx.env, bucket, key);
+          if (lock !== null) {
+            errors.push({
+              key,
+              code: "AccessDenied",
+              message: describeObjectLock(lock),
+            });
+            return;
+          }
+          await deleteOrTrashObject(ctx.env, bucket, key, ctx.userEmail);
+          deleted.push(key);
+        } catch (error) {
+          errors.push({
+            key,
+            code: "InternalError",
+            message: error instanceof Error ? error.message : String(error),
+          });
+        }
+      }),
+    );
+  }
+
+  for (const key of deleted) {
+    await audit(ctx, "file_delete", bucket, key);
+    void triggerWebhooks(
+      ctx.env,
+      "file_delete",
+      createFileDeletePayload(bucket, key, ctx.userEmail),
+      ctx.isLocalDev,
+    );
+  }
+
+  logInfo(`S3 DeleteObjects removed ${deleted.length} object(s)`, {
+    module: "s3_api",
+    operation: "delete_objects",
+    bucketName: bucket,
+    userId: ctx.userEmail,
+    metadata: { deleted: deleted.length, errors: errors.length },
+  });
+
+  return xmlResponse(ctx, renderDeleteResult(deleted, errors, quiet));
+}
+
+// ============================================
+// Object operations
+// ============================================
+
+/**
+ * Parse a single-range Range header against an object size
+ */
+function parseRange(
+  header: string,
+  size: number,
+): { offset: number; length: number } | null {
+  const match = /^bytes=(\d*)-(\d*)$/.exec(header.trim());
+  if (match === null || (match[1] === "" && match[2] === "")) {
+    return null;
+  }
+  const start = match[1] ?? "";
+  const end = match[2] ?? "";
+
+  if (start === "") {
+    const suffix = Math.min(Number(end), size);
+    return suffix > 0 ? { offset: size - suffix, length: suffix } : null;
+  }
+  const offset = Number(start);
+  const last = end === "" ? size - 1 : Math.min(Number(end), size - 1);
+  return offset <= last ? { offset, length: last - offset + 1 } : null;
+}
+
+async function getObject(
+  ctx: S3Context,
+  bucket: string,
+  key: string,
+): Promise<Response> {
+  if (!isAllowed(ctx, { role: "viewer", scope: "key", bucket, key }, "read")) {
+    return accessDenied(ctx);
+  }
+
+  const typeOverride = ctx.url.searchParams.get("response-content-type");
+  if (typeOverride !== null && isActiveContentType(typeOverride)) {
+    return fail(
+      ctx,
+      "InvalidArgument",
+      "response-content-type cannot be HTML, SVG, XML or JavaScript",
+      400,
+    );
+  }
+
+  const storage = getObjectStorage(ctx.env, bucket);
+
+  if (ctx.request.method === "HEAD") {
+    const object = await storage.head(key);
+    if (object === null) {
+      return noSuchKey(ctx);
+    }
+    const headers = objectHeaders(ctx, object);
+    headers.set("Content-Length", String(object.size));
+    return new Response(null, { headers });
+  }
+
+  const rangeHeader = ctx.request.headers.get("Range");
+  if (rangeHeader !== null) {
+    const head = await storage.head(key);
+    if (head === null) {
+      return noSuchKey(ctx);
+    }
+    const range = parseRange(rangeHeader, head.size);
+    if (range === null) {
+      return fail(
+        ctx,
+        "InvalidRange",
+        "The requested range is not satisfiable",
+        416,
+        { "Content-Range": `bytes */${head.size}` },
+      );
+    }
+
+    const object = await storage.get(key, { range });
+    if (object === null) {
+      return noSuchKey(ctx);
+    }
+    const headers = objectHeaders(ctx, object);
+    headers.set("Content-Length", String(range.length));
+    headers.set(
+      "Content-Range",
+      `bytes ${range.offset}-${range.offset + range.length - 1}/${object.size}`,
+    );
+    return new Response(object.body, { status: 206, headers });
+  }
+
+  const object = await storage.get(key);
+  if (object === null) {
+    return noSuchKey(ctx);
+  }
+
+  await audit(ctx, "file_download", bucket, key, { sizeBytes: object.size });
+  void triggerWebhooks(
+    ctx.env,
+    "file_download",
+    createFileDownloadPayload(bucket, key, ctx.userEmail),
+    ctx.isLocalDev,
+  );
+
+  const headers = objectHeaders(ctx, object);
+  headers.set("Content-Length", String(object.size));
+  return new Response(object.body, { headers });
+}
+
+async function putObject(
+  ctx: S3Context,
+  bucket: string,
+  key: string,
+): Promise<Response> {
+  if (!isAllowed(ctx, { role: "editor", scope: "key", bucket, key }, "write")) {
+    return accessDenied(ctx);
+  }
+  const locked = await checkLocked(ctx, bucket, key);
+  if (locked !== null) {
+    return locked;
+  }
+
+  const upload = readUploadBody(ctx);
+  if (upload instanceof Response) {
+    return upload;
+  }
+
+  const storage = getObjectStorage(ctx.env, bucket);
+  const metadata = readUploadMetadata(ctx.request);
+
+  // A signed body is only known to be intact once it has been fully read, so
+  // it is verified before the key is written: in memory when small, and
+  // otherwise under a hidden staging key that is copied over once it matches
+  let object: StorageObject | null;
+  if (!isSignedPayloadHash(ctx.payloadHash)) {
+    await trashBeforeOverwrite(ctx.env, bucket, key, ctx.userEmail);
+    object = await storage.put(key, upload.body, metadata);
+  } else if (upload.length <= MAX_BUFFERED_PUT_BYTES) {
+    const bytes = await new Response(upload.body).arrayBuffer();
+    if (!(await upload.verified)) {
+      return payloadMismatch(ctx);
+    }
+    await trashBeforeOverwrite(ctx.env, bucket, key, ctx.userEmail);
+    object = await storage.put(key, bytes, metadata);
+  } else {
+    const stagingKey = `${PENDING_PUT_PREFIX}${crypto.randomUUID()}`;
+    try {
+      await storage.put(stagingKey, upload.body, metadata);
+      if (!(await upload.verified)) {
+        return payloadMismatch(ctx);
+      }
+      await trashBeforeOverwrite(ctx.env, bucket, key, ctx.userEmail);
+      object = await copyObject(ctx.env, bucket, stagingKey, bucket, key);
+    } finally {
+      await storage.delete(stagingKey);
+    }
+  }
+  if (object === null) {
+    return fail(ctx, "InternalError", "The object could not be stored", 500);
+  }
+
+  await audit(ctx, "file_upload", bucket, key, { sizeBytes: object.size });
+  void triggerWebhooks(
+    ctx.env,
+    "file_upload",
+    createFileUploadPayload(bucket, key, object.size, ctx.userEmail),
+    ctx.isLocalDev,
+  );
+
+  return new Response(null, {
+    headers: { ETag: `"${object.etag}"`, "x-amz-request-id": ctx.requestId },
+  });
+}
+
+async function copyObjectRequest(
+  ctx: S3Context,
+  bucket: string,
+  key: string,
+  copySource: string,
+): Promise<Response> {
+  if (ctx.request.headers.get("x-amz-metadata-directive") === "REPLACE") {
+    return fail(
+      ctx,
+      "NotImplemented",
+      "Copying with x-amz-metadata-directive REPLACE is not supported",
+      501,
+    );
+  }
+
+  // "/bucket/key" or "bucket/key", URL-encoded, optionally with ?versionId
+  let source: string;
+  try {
+    source = decodeURIComponent(copySource.split("?")[0] ?? "");
+  } catch {
+    return fail(ctx, "InvalidArgument", "Invalid x-amz-copy-source", 400);
+  }
+  source = source.replace(/^\//, "");
+  const separator = source.indexOf("/");
+  const sourceBucket = separator === -1 ? "" : source.slice(0, separator);
+  const sourceKey = separator === -1 ? "" : source.slice(separator + 1);
+  if (sourceBucket === "" || sourceKey === "") {
+    return fail(ctx, "InvalidArgument", "Invalid x-amz-copy-source", 400);
+  }
+  if (isTrashKey(sourceKey)) {
+    return accessDenied(ctx);
+  }
+
+  if (
+    !isAllowed(
+      ctx,
+      { role: "viewer", scope: "key", bucket: sourceBucket, key: sourceKey },
+      "read",
+    ) ||
+    !isAllowed(ctx, { role: "editor", scope: "key", bucket, key }, "write")
+  ) {
+    return accessDenied(ctx);
+  }
+  const locked = await checkLocked(ctx, bucket, key);
+  if (locked !== null) {
+    return locked;
+  }
+
+  await trashBeforeOverwrite(ctx.env, bucket, key, ctx.userEmail);
+
+  const copied = await copyObject(
+    ctx.env,
+    sourceBucket,
+    sourceKey,
+    bucket,
+    key,
+  );
+  if (copied === null) {
+    return noSuchKey(ctx);
+  }
+  const object = (await getObjectStorage(ctx.env, bucket).head(key)) ?? copied;
+
+  await audit(ctx, "file_copy", sourceBucket, sourceKey, {
+    sizeBytes: copied.size,
+    destinationBucket: bucket,
+    destinationKey: key,
+  });
+  void triggerWebhooks(
+    ctx.env,
+    "file_copy",
+    createFileCopyPayload(sourceBucket, sourceKey, bucket, key, ctx.userEmail),
+    ctx.isLocalDev,
+  );
+
+  return xmlResponse(
+    ctx,
+    renderCopyObjectResult(object.etag, object.uploaded),
+  );
+}
+
+async function deleteObject(
+  ctx: S3Context,
+  bucket: string,
+  key: string,
+): Promise<Response> {
+  if (
+    !isAllowed(ctx, { role: "editor", scope: "key", bucket, key }, "delete")
+  ) {
+    return accessDenied(ctx);
+  }
+  const locked = await checkLocked(ctx, bucket, key);
+  if (locked !== null) {
+    return locked;
+  }
+
+  await deleteOrTrashObject(ctx.env, bucket, key, ctx.userEmail);
+
+  await audit(ctx, "file_delete", bucket, key);
+  void triggerWebhooks(
+    ctx.env,
+    "file_delete",
+    createFileDeletePayload(bucket, key, ctx.userEmail),
+    ctx.isLocalDev,
+  );
+
+  return new Response(null, {
+    status: 204,
+    headers: { "x-amz-request-id": ctx.requestId },
+  });
+}
+
+// ============================================
+// Multipart uploads (buckets with an R2 binding only)
+// ============================================
+
+async function handleMultipart(
+  ctx: S3Context,
+  bucket: string,
+  key: string,
+): Promise<Response> {
+  const { request, url } = ctx;
+  if (!isAllowed(ctx, { role: "editor", scope: "key", bucket, key }, "write")) {
+    return accessDenied(ctx);
+  }
+
+  const binding = getBucketBinding(ctx.env, bucket);
+  if (binding === null) {
+    return fail(
+      ctx,
+      "NotImplemented",
+      `Multipart uploads need an R2 binding for bucket "${bucket}" in R2_BUCKET_BINDINGS`,
+      501,
+    );
+  }
+
+  // CreateMultipartUpload
+  if (request.method === "POST" && url.searchParams.has("uploads")) {
+    const locked = await checkLocked(ctx, bucket, key);
+    if (locked !== null) {
+      return locked;
+    }
+    const upload = await binding.createMultipartUpload(
+      key,
+      readUploadMetadata(request),
+    );
+    return xmlResponse(
+      ctx,
+      renderInitiateMultipartUpload(bucket, key, upload.uploadId),
+    );
+  }
+
+  const uploadId = url.searchParams.get("uploadId") ?? "";
+  const upload = binding.resumeMultipartUpload(key, uploadId);
+
+  try {
+    // UploadPart
+    if (request.method === "PUT") {
+      if (request.headers.has("x-amz-copy-source")) {
+        return fail(
+          ctx,
+          "NotImplemented",
+          "UploadPartCopy is not supported",
+          501,
+        );
+      }
+      const partNumber = Number(url.searchParams.get("partNumber"));
+      if (
+        !Number.isInteger(partNumber) ||
+        partNumber < 1 ||
+        partNumber > MAX_PART_NUMBER
+      ) {
+        return fail(
+          ctx,
+          "InvalidArgument",
+          `Part number must be an integer between 1 and ${MAX_PART_NUMBER}`,
+          400,
+        );
+      }
+
+      const body = readUploadBody(ctx);
+      if (body instanceof Response) {
+        return body;
+      }
+      // As with PutObject, a signed part is verified before it is added
+      let part: R2UploadedPart;
+      if (!isSignedPayloadHash(ctx.payloadHash)) {
+        part = await upload.uploadPart(partNumber, body.body);
+      } else if (body.length <= MAX_BUFFERED_PUT_BYTES) {
+        const bytes = await new Response(body.body).arrayBuffer();
+        if (!(await body.verified)) {
+          return payloadMismatch(ctx);
+        }
+        part = await upload.uploadPart(partNumber, bytes);
+      } else {
+        const stagingKey = `${PENDING_PUT_PREFIX}${crypto.randomUUID()}`;
+        try {
+          await binding.put(stagingKey, body.body);
+          if (!(await body.verified)) {
+            return payloadMismatch(ctx);
+          }
+          const staged = await binding.get(stagingKey);
+          if (staged === null) {
+            return fail(
+              ctx,
+              "InternalError",
+              "The part could not be stored",
+              500,
+            );
+          }
+          part = await upload.uploadPart(
+            partNumber,
+            staged.body.pipeThrough(new FixedLengthStream(staged.size)),
+          );
+        } finally {
+          await binding.delete(stagingKey);
+        }
+      }
+      return new Response(null, {
+        headers: { ETag: `"${part.etag}"`, "x-amz-request-id": ctx.requestId },
+      });
+    }
+
+    // CompleteMultipartUpload
+    if (request.method === "POST") {
+      const xml = await readXmlBody(ctx);
+      if (xml instanceof Response) {
+        return xml;
+      }
+      const parts = parseCompleteMultipartUpload(xml);
+      if (
+        parts.length === 0 ||
+        parts.some((part) => !Number.isInteger(part.partNumber))
+      ) {
+        return fail(
+          ctx,
+          "MalformedXML",
+          "The request must list the uploaded parts",
+          400,
+        );
+      }
+
+      // A lock may have been added since the upload was created
+      const locked = await checkLocked(ctx, bucket, key);
+      if (locked !== null) {
+        return locked;
+      }
+      await trashBeforeOverwrite(ctx.env, bucket, key, ctx.userEmail);
+      const object = await upload.complete(parts);
+
+      await audit(ctx, "file_upload", bucket, key, {
+        sizeBytes: object.size,
+        metadata: { multipart: true, parts: parts.length },
+      });
+      void triggerWebhooks(
+        ctx.env,
+        "file_upload",
+        createFileUploadPayload(bucket, key, object.size, ctx.userEmail),
+        ctx.isLocalDev,
+      );
+
+      return xmlResponse(
+        ctx,
+        renderCompleteMultipartUpload(
+          `${url.origin}/s3/${bucket}/${key}`,
+          bucket,
+          key,
+          object.etag,
+        ),
+      );
+    }
+
+    // AbortMultipartUpload
+    if (request.method === "DELETE") {
+      await upload.abort();
+      return new Response(null, {
+        status: 204,
+        headers: { "x-amz-request-id": ctx.requestId },
+      });
+    }
+  } catch (error) {
+    if (isMissingUploadError(error)) {
+      return noSuchUpload(ctx);
+    }
+    throw error;
+  }
+
+  return fail(
+    ctx,
+    "NotImplemented",
+    "Listing multipart uploads and parts is not supported",
+    501,
+  );
+}
+
+// ============================================
+// Request handling
+// ============================================
+
+/**
+ * Verify a request's SigV4 signature and resolve the API token it was signed
+ * with
+ */
+async function authenticate(
+  request: Request,
+  env: Env,
+  url: URL,
+  requestId: string,
+): Promise<
+  { identity: AccessIdentity; tokenId: string; payloadHash: string } | Response
+> {
+  const parsed = parseSigV4Request(request, url);
+  if (!parsed.ok) {
+    return s3Error(
+      request,
+      url,
+      requestId,
+      parsed.code,
+      parsed.message,
+      parsed.status,
+    );
+  }
+
+  const { auth } = parsed;
+  const secretKey = await getS3SecretKey(auth.accessKeyId, env);
+  if (!(await verifySigV4(request, url, auth, secretKey))) {
+    logWarning("S3 request signature does not match", {
+      module: "s3_api",
+      operation: "authenticate",
+      metadata: { accessKeyId: auth.accessKeyId },
+    });
+    return s3Error(
+      request,
+      url,
+      requestId,
+      "SignatureDoesNotMatch",
+      "The request signature we calculated does not match the signature you provided",
+      403,
+    );
+  }
+
+  const identity = await validateApiTokenId(auth.accessKeyId, env);
+  if (identity === null) {
+    return s3Error(
+      request,
+      url,
+      requestId,
+      "InvalidAccessKeyId",
+      "The access key ID does not exist, has expired or was revoked",
+      403,
+    );
+  }
+
+  return { identity, tokenId: auth.accessKeyId, payloadHash: auth.payloadHash };
+}
+
+/**
+ * Handle a request to the S3-compatible endpoint (/s3/...)
+ */
+export async function handleS3Request(
+  request: Request,
+  env: Env,
+  url: URL,
+  isLocalDev: boolean,
+): Promise<Response> {
+  const requestId = crypto.randomUUID();
+
+  if (isLocalDev || !env.METADATA) {
+    return s3Error(
+      request,
+      url,
+      requestId,
+      "NotImplemented",
+      "The S3 endpoint needs Cloudflare credentials and the METADATA database",
+      501,
+    );
+  }
+
+  // /s3, /s3/:bucket or /s3/:bucket/:key (path-style addressing)
+  const match = /^\/s3(?:\/([^/]*)(?:\/(.*))?)?$/.exec(url.pathname);
+  let bucket: string;
+  let key: string;
+  try {
+    bucket = decodeURIComponent(match?.[1] ?? "");
+    key = decodeURIComponent(match?.[2] ?? "");
+  } catch {
+    return s3Error(
+      request,
+      url,
+      requestId,
+      "InvalidURI",
+      "Could not parse the specified URI",
+      400,
+    );
+  }
+
+  const authenticated = await authenticate(request, env, url, requestId);
+  if (authenticated instanceof Response) {
+    return authenticated;
+  }
+  const { identity, tokenId, payloadHash } = authenticated;
+
+  // Same per-user rate limits as the rest of the API
+  if (env.RATE_LIMITER_READ !== undefined) {
+    try {
+      const rateLimit = await checkRateLimit(
+        env,
+        request.method,
+        url.pathname,
+        identity.email,
+      );
+      if (!rateLimit.success) {
+        logWarning("S3 request blocked by rate limit", {
+          module: "s3_api",
+          operation: "rate_limit",
+          userId: identity.email,
+          metadata: { method: request.method, tier: rateLimit.tier },
+        });
+        return s3Error(
+          request,
+          url,
+          requestId,
+          "SlowDown",
+          "Please reduce your request rate",
+          503,
+          { "Retry-After": String(rateLimit.period) },
+        );
+      }
+    } catch (error) {
+      void logError(
+        env,
+        error instanceof Error ? error : new Error(String(error)),
+        { module: "s3_api", operation: "rate_limit" },
+        isLocalDev,
+      );
+    }
+  }
+
+  const ctx: S3Context = {
+    request,
+    env,
+    url,
+    isLocalDev,
+    access: await loadUserAccess(env, identity),
+    userEmail: identity.email,
+    tokenId,
+    payloadHash,
+    requestId,
+  };
+
+  const params = url.searchParams;
+  const method = request.method;
+  const unsupported = UNSUPPORTED_SUBRESOURCES.find((name) => params.has(name));
+  if (unsupported !== undefined) {
+    return fail(
+      ctx,
+      "NotImplemented",
+      `The ${unsupported} sub-resource is not supported`,
+      501,
+    );
+  }
+
+  try {
+    if (bucket === "") {
+      return method === "GET"
+        ? await listBuckets(ctx)
+        : fail(ctx, "MethodNotAllowed", "The method is not allowed", 405);
+    }
+
+    if (key === "") {
+      if (method === "HEAD") {
+        return await headBucket(ctx, bucket);
+      }
+      if (method === "GET" && params.has("location")) {
+        return isAllowed(ctx, { role: "viewer", scope: "any", bucket }, "read")
+          ? xmlResponse(ctx, renderLocationConstraint())
+          : accessDenied(ctx);
+      }
+      if (method === "GET" && !params.has("uploads")) {
+        return await listObjects(ctx, bucket);
+      }
+      if (method === "POST" && params.has("delete")) {
+        return await deleteObjects(ctx, bucket);
+      }
+      return fail(
+        ctx,
+        "NotImplemented",
+        method === "PUT" || method === "DELETE"
+          ? "Create and delete buckets from R2 Manager"
+          : "This bucket operation is not supported",
+        501,
+      );
+    }
+
+    // Trash copies and staged uploads are only managed by R2 Manager
+    if (isTrashKey(key)) {
+      return accessDenied(ctx);
+    }
+
+    if (
+      params.has("uploadId") ||
+      (method === "POST" && params.has("uploads"))
+    ) {
+      return await handleMultipart(ctx, bucket, key);
+    }
+
+    switch (method) {
+      case "GET":
+      case "HEAD":
+        return await getObject(ctx, bucket, key);
+      case "PUT": {
+        const copySource = request.headers.get("x-amz-copy-source");
+        return copySource !== null
+          ? await copyObjectRequest(ctx, bucket, key, copySource)
+          : await putObject(ctx, bucket, key);
+      }
+      case "DELETE":
+        return await deleteObject(ctx, bucket, key);
+      default:
+        return fail(ctx, "MethodNotAllowed", "The method is not allowed", 405);
+    }
+  } catch (error) {
+    await logError(
+      env,
+      error instanceof Error ? error : String(error),
+      {
+        module: "s3_api",
+        operation: method.toLowerCase(),
+        bucketName: bucket,
+        fileName: key,
+        userId: identity.email,
+      },
+      isLocalDev,
+    );
+    return fail(
+      ctx,
+      "InternalError",
+      "We encountered an internal error. Please try again.",
+      500,
+    );
+  }
+}
@@ -1,13 +1,14 @@
 import type { Env, CloudflareApiResponse, BucketsListResult } from "../types";
 import { CF_API } from "../types";
 import { getCloudflareHeaders } from "../utils/helpers";
+import { getObjectStorage } from "../utils/storage";
 import { logInfo, logError } from "../utils/error-logger";
-
-interface R2ObjectResult {
-  key: string;
-  size: number;
-  uploaded?: string;
-}
+import {
+  getRequestAccess,
+  hasBucketAccess,
+  satisfiesCheck,
+} from "../utils/rbac";
+import { isTrashKey } from "../utils/trash";
 
 interface SearchResult {
   key: string;
@@ -118,8 +119,14 @@ export async function handleSearchRoutes(
       "container-manager-snapshots",
       "worker-manager-backups",
     ];
+    // Only search what the user's grants cover
+    const access = getRequestAccess(request);
     const buckets = (bucketsData.result?.buckets ?? [])
       .filter((b: { name: string }) => !systemBuckets.includes(b.name))
+      .filter(
+        (b: { name: string }) =>
+          access === undefined || hasBucketAccess(access, b.name),
+      )
       .map((b: { name: string }) => b.name);
 
     logInfo(`Searching across ${buckets.length} buckets`, {
@@ -131,41 +138,28 @@ export async function handleSearchRoutes(
     // Fetch files from all buckets in parallel
     const bucketFilePromises = buckets.map(async (bucketName: string) => {
       try {
-        const listUrl =
-          CF_API +
-          "/accounts/" +
-          env.ACCOUNT_ID +
-          "/r2/buckets/" +
-          bucketName +
-          "/objects" +
-          "?include=customMetadata,httpMetadata" +
-          "&per_page=1000"; // Get up to 1000 files per bucket
-
-        const response = await fetch(listUrl, { headers: cfHeaders });
+        // Get up to 1000 files per bucket
+        const { objects } = await getObjectStorage(env, bucketName).list({
+          limit: 1000,
+        });
 
-        if (!response.ok) {
-          await logError(
-            env,
-            new Error(`Failed to list files in bucket: ${bucketName}`),
-            { module: "search", operation: "search", bucketName },
-            isLocalDev,
-          );
-          return [];
-        }
-
-        const data = (await response.json()) as CloudflareApiResponse<
-          R2ObjectResult[]
-        >;
-        const objects: R2ObjectResult[] = Array.isArray(data.result)
-          ? data.result
-          : [];
-
-        // Map files with bucket name
-        return objects.map((obj: R2ObjectResult) => ({
+        // Map files with bucket name, leaving out trashed objects
+        const visible = objects.filter(
+          (obj) =>
+            !isTrashKey(obj.key) &&
+            (access === undefined ||
+              satisfiesCheck(access, {
+                role: "viewer",
+                scope: "key",
+                bucket: bucketName,
+                key: obj.key,
+              })),
+        );
+        return visible.map((obj) => ({
           key: obj.key,
           bucket: bucketName,
           size: obj.size,
-          uploaded: obj.uploaded ?? new Date().toISOString(),
+          uploaded: obj.uploaded,
           url: `https://${env.ACCOUNT_ID}.r2.cloudflarestorage.com/${bucketName}/${obj.key}`,
         }));
       } catch (err) {
@@ -0,0 +1,685 @@
+/**
+ * Share Routes
+ *
+ * /api/shares creates, lists and revokes public share links for files and
+ * folders. /share/:id serves the public landing page for a share (no
+ * Cloudflare Access login): an optional password prompt, the file or a
+ * browsable folder listing with signed download links, and an optional
+ * ZIP download of the whole folder.
+ */
+
+import type { CreateShareBody, Env, Share, ShareStatus } from "../types";
+import { logError, logInfo, logWarning } from "../utils/error-logger";
+import { createErrorResponse } from "../utils/error-response";
+import { checkRateLimit } from "../utils/ratelimit";
+import {
+  renderFileShare,
+  renderFolderShare,
+  renderSharePasswordForm,
+  renderShareUnavailable,
+  type ShareBreadcrumb,
+} from "../utils/share-page";
+import {
+  DEFAULT_SHARE_TTL_SECONDS,
+  MAX_SHARE_TTL_SECONDS,
+  MIN_SHARE_PASSWORD_LENGTH,
+  SHARE_DOWNLOAD_URL_TTL_SECONDS,
+  consumeShareDownload,
+  createShare,
+  createShareSessionCookie,
+  generateShareId,
+  getShare,
+  getShareStatus,
+  hasShareSession,
+  hashSharePassword,
+  touchShare,
+  verifySharePassword,
+} from "../utils/shares";
+import { createSignedPath } from "../utils/signing";
+import { getObjectStorage, listAllObjects } from "../utils/storage";
+import { createZipStream, type ZipEntry } from "../utils/zip-stream";
+
+/** Share as returned by the API (password hash replaced by a flag) */
+type ShareResponse = Omit<Share, "password_hash" | "allow_zip"> & {
+  has_password: boolean;
+  allow_zip: boolean;
+  status: ShareStatus;
+  url: string;
+};
+
+const FOLDER_PAGE_SIZE = 200;
+
+const MOCK_SHARES: Share[] = [
+  {
+    share_id: "shr_dev0000000000000000000000000001",
+    bucket_name: "dev-bucket",
+    object_key: "reports/",
+    share_type: "folder",
+    created_by: "dev@localhost",
+    created_at: new Date(Date.now() - 3600000).toISOString(),
+    expires_at: new Date(Date.now() + 6 * 86400000).toISOString(),
+    password_hash: "mock",
+    max_downloads: 20,
+    download_count: 3,
+    allow_zip: 1,
+    last_accessed_at: new Date(Date.now() - 600000).toISOString(),
+    revoked_at: null,
+  },
+];
+
+const UNAVAILABLE_MESSAGES: Record<Exclude<ShareStatus, "active">, string> = {
+  revoked: "This share link does not exist or has been revoked.",
+  expired: "This share link has expired.",
+  exhausted: "This share link has reached its download limit.",
+};
+
+function jsonResponse(body: unknown, corsHeaders: HeadersInit): Response {
+  return new Response(JSON.stringify(body), {
+    headers: { "Content-Type": "application/json", ...corsHeaders },
+  });
+}
+
+function toShareResponse(share: Share, origin: string): ShareResponse {
+  const { password_hash, allow_zip, ...rest } = share;
+  return {
+    ...rest,
+    has_password: password_hash !== null,
+    allow_zip: allow_zip === 1,
+    status: getShareStatus(share),
+    url: `${origin}/share/${share.share_id}`,
+  };
+}
+
+function shareUnavailable(status: Exclude<ShareStatus, "active">): Response {
+  return renderShareUnavailable(
+    UNAVAILABLE_MESSAGES[status],
+    status === "revoked" ? 404 : 410,
+  );
+}
+
+/**
+ * Validate a create-share request body. Returns an error message if invalid.
+ */
+function validateCreateShareBody(body: CreateShareBody): string | null {
+  if (typeof body.bucket !== "string" || body.bucket === "") {
+    return "bucket is required";
+  }
+  if (typeof body.key !== "string" || body.key === "") {
+    return "key is required";
+  }
+  if (body.type !== "file" && body.type !== "folder") {
+    return "type must be file or folder";
+  }
+  if (
+    body.expiresIn !== undefined &&
+    (!Number.isInteger(body.expiresIn) ||
+      body.expiresIn < 60 ||
+      body.expiresIn > MAX_SHARE_TTL_SECONDS)
+  ) {
+    return `expiresIn must be between 60 and ${String(MAX_SHARE_TTL_SECONDS)} seconds`;
+  }
+  if (
+    body.maxDownloads !== undefined &&
+    (!Number.isInteger(body.maxDownloads) || body.maxDownloads < 1)
+  ) {
+    return "maxDownloads must be a positive integer";
+  }
+  if (
+    body.password !== undefined &&
+    body.password !== "" &&
+    body.password.length < MIN_SHARE_PASSWORD_LENGTH
+  ) {
+    return `password must be at least ${String(MIN_SHARE_PASSWORD_LENGTH)} characters`;
+  }
+  if (body.allowZip === true && body.type !== "folder") {
+    return "allowZip is only supported for folder shares";
+  }
+  return null;
+}
+
+/**
+ * Handle authenticated share management routes
+ */
+export async function handleShareRoutes(
+  request: Request,
+  env: Env,
+  url: URL,
+  corsHeaders: HeadersInit,
+  isLocalDev: boolean,
+  userEmail: string,
+): Promise<Response | null> {
+  const db = env.METADATA;
+
+  // GET /api/shares - List shares created by the current user
+  if (url.pathname === "/api/shares" && request.method === "GET") {
+    const includeInactive = url.searchParams.get("status") === "all";
+
+    if (isLocalDev || !db) {
+      return jsonResponse(
+        {
+          success: true,
+          result: {
+            shares: MOCK_SHARES.map((share) =>
+              toShareResponse(share, url.origin),
+            ),
+          },
+        },
+        corsHeaders,
+      );
+    }
+
+    try {
+      const conditions = ["created_by = ?"];
+      const bindings: string[] = [userEmail];
+      if (!includeInactive) {
+        conditions.push("revoked_at IS NULL", "expires_at > ?");
+        bindings.push(new Date().toISOString());
+      }
+
+      const result = await db
+        .prepare(
+          `SELECT * FROM shares WHERE ${conditions.join(" AND ")} ORDER BY created_at DESC LIMIT 500`,
+        )
+        .bind(...bindings)
+        .all<Share>();
+
+      const shares = result.results
+        .map((share) => toShareResponse(share, url.origin))
+        .filter((share) => includeInactive || share.status === "active");
+
+      return jsonResponse({ success: true, result: { shares } }, corsHeaders);
+    } catch (error) {
+      await logError(
+        env,
+        error instanceof Error ? error : String(error),
+        { module: "shares", operation: "list", userId: userEmail },
+        isLocalDev,
+      );
+      return createErrorResponse("Failed to list shares", corsHeaders);
+    }
+  }
+
+  // POST /api/shares - Create a share for a file or folder
+  if (url.pathname === "/api/shares" && request.method === "POST") {
+    let body: CreateShareBody;
+    try {
+      body = (await request.json()) as CreateShareBody;
+    } catch {
+      return createErrorResponse("Invalid JSON body", corsHeaders, 400);
+    }
+
+    const validationError = validateCreateShareBody(body);
+    if (validationError !== null) {
+      return createErrorResponse(validationError, corsHeaders, 400);
+    }
+
+    // Folder shares cover everything under the prefix
+    const objectKey =
+      body.type === "folder" && !body.key.endsWith("/")
+        ? body.key + "/"
+        : body.key;
+    const expiresAt = new Date(
+      Date.now() + (body.expiresIn ?? DEFAULT_SHARE_TTL_SECONDS) * 1000,
+    );
+
+    if (isLocalDev) {
+      const mockShare: Share = {
+        share_id: generateShareId(),
+        bucket_name: body.bucket,
+        object_key: objectKey,
+        share_type: body.type,
+        created_by: userEmail,
+        created_at: new Date().toISOString(),
+        expires_at: expiresAt.toISOString(),
+        password_hash: body.password ? "mock" : null,
+        max_downloads: body.maxDownloads ?? null,
+        download_count: 0,
+        allow_zip: body.allowZip === true ? 1 : 0,
+        last_accessed_at: null,
+        revoked_at: null,
+      };
+      return jsonResponse(
+        {
+          success: true,
+          result: { share: toShareResponse(mockShare, url.origin) },
+        },
+        corsHeaders,
+      );
+    }
+
+    if (!db) {
+      return createErrorResponse(
+        "Share links require the METADATA database",
+        corsHeaders,
+        400,
+      );
+    }
+
+    try {
+      const storage = getObjectStorage(env, body.bucket);
+      const exists =
+        body.type === "file"
+          ? (await storage.head(objectKey)) !== null
+          : (await storage.list({ prefix: objectKey, limit: 1 })).objects
+              .length > 0;
+      if (!exists) {
+        return createErrorResponse(
+          body.type === "file" ? "File not found" : "Folder is empty",
+          corsHeaders,
+          404,
+        );
+      }
+
+      const shareId = generateShareId();
+      await createShare(db, {
+        shareId,
+        bucketName: body.bucket,
+        objectKey,
+        shareType: body.type,
+        createdBy: userEmail,
+        expiresAt,
+        passwordHash: body.password
+          ? await hashSharePassword(body.password)
+          : undefined,
+        maxDownloads: body.maxDownloads,
+        allowZip: body.allowZip === true,
+      });
+
+      logInfo("Created share link", {
+        module: "shares",
+        operation: "create",
+        bucketName: body.bucket,
+        fileName: objectKey,
+        userId: userEmail,
+        metadata: { shareId, type: body.type },
+      });
+
+      const share = await getShare(db, shareId);
+      if (share === null) {
+        throw new Error(`Share ${shareId} was not saved`);
+      }
+      return jsonResponse(
+        {
+          success: true,
+          result: { share: toShareResponse(share, url.origin) },
+        },
+        corsHeaders,
+      );
+    } catch (error) {
+      await logError(
+        env,
+        error instanceof Error ? error : String(error),
+        {
+          module: "shares",
+          operation: "create",
+          bucketName: body.bucket,
+          userId: userEmail,
+        },
+        isLocalDev,
+      );
+      return createErrorResponse("Failed to create share", corsHeaders);
+    }
+  }
+
+  // DELETE /api/shares/:shareId - Revoke a share
+  const revokeMatch = /^\/api\/shares\/([^/]+)$/.exec(url.pathname);
+  if (revokeMatch !== null && request.method === "DELETE") {
+    const shareId = revokeMatch[1];
+    if (!shareId) {
+      return createErrorResponse("Invalid share ID", corsHeaders, 400);
+    }
+
+    if (isLocalDev || !db) {
+      return jsonResponse({ success: true }, corsHeaders);
+    }
+
+    try {
+      const revoked = await db
+        .prepare(
+          "UPDATE shares SET revoked_at = ? WHERE share_id = ? AND created_by = ? AND revoked_at IS NULL RETURNING share_id",
+        )
+        .bind(new Date().toISOString(), shareId, userEmail)
+        .first<{ share_id: string }>();
+
+      if (revoked === null) {
+        return createErrorResponse(
+          "Share not found or already revoked",
+          corsHeaders,
+          404,
+        );
+      }
+
+      logInfo("Revoked share link", {
+        module: "shares",
+        operation: "revoke",
+        userId: userEmail,
+        metadata: { shareId },
+      });
+
+      return jsonResponse({ success: true }, corsHeaders);
+    } catch (error) {
+      await logError(
+        env,
+        error instanceof Error ? error : String(error),
+        {
+          module: "shares",
+          operation: "revoke",
+          userId: userEmail,
+          metadata: { shareId },
+        },
+        isLocalDev,
+      );
+      return createErrorResponse("Failed to revoke share", corsHeaders);
+    }
+  }
+
+  return null;
+}
+
+/**
+ * Handle public share landing page routes (no Access authentication)
+ */
+export async function handlePublicShareRoutes(
+  request: Request,
+  env: Env,
+  url: URL,
+  isLocalDev: boolean,
+): Promise<Response | null> {
+  const match = /^\/share\/([A-Za-z0-9_]+)(\/zip)?\/?$/.exec(url.pathname);
+  if (match === null) {
+    return null;
+  }
+  const shareId = match[1] ?? "";
+  const isZip = match[2] !== undefined;
+
+  const db = env.METADATA;
+  if (!db) {
+    return renderShareUnavailable("Sharing is not configured.", 404);
+  }
+
+  try {
+    const share = await getShare(db, shareId);
+    if (share === null) {
+      return shareUnavailable("revoked");
+    }
+    const status = getShareStatus(share);
+    if (status !== "active") {
+      return shareUnavailable(status);
+    }
+
+    // Password-protected shares: unlock with a signed session cookie
+    if (
+      share.password_hash !== null &&
+      !(await hasShareSession(request, shareId, env))
+    ) {
+      if (request.method !== "POST" || isZip) {
+        return renderSharePasswordForm(shareId);
+      }
+      return await unlockShare(request, env, url, share.password_hash, shareId);
+    }
+
+    if (request.method !== "GET" && request.method !== "POST") {
+      return renderShareUnavailable("Method not allowed.", 405);
+    }
+    if (request.method === "POST") {
+      // Already unlocked (or no password) - back to the landing page
+      return new Response(null, {
+        status: 303,
+        headers: { Location: `/share/${shareId}` },
+      });
+    }
+
+    if (isZip) {
+      return await createShareZipResponse(db, env, share, isLocalDev);
+    }
+
+    await touchShare(db, shareId);
+    return share.share_type === "file"
+      ? await renderFileSharePage(env, share)
+      : await renderFolderSharePage(env, url, share);
+  } catch (error) {
+    await logError(
+      env,
+      error instanceof Error ? error : String(error),
+      { module: "shares", operation: "view", metadata: { shareId } },
+      isLocalDev,
+    );
+    return renderShareUnavailable(
+      "Something went wrong loading this share. Please try again later.",
+      500,
+    );
+  }
+}
+
+/**
+ * Check a submitted password and set the share session cookie
+ */
+async function unlockShare(
+  request: Request,
+  env: Env,
+  url: URL,
+  passwordHash: string,
+  shareId: string,
+): Promise<Response> {
+  // Throttle password guesses per client IP
+  const clientIp = request.headers.get("CF-Connecting-IP") ?? "unknown";
+  if (env.RATE_LIMITER_WRITE !== undefined) {
+    const rateLimit = await checkRateLimit(
+      env,
+      "POST",
+      url.pathname,
+      `share:${clientIp}`,
+    );
+    if (!rateLimit.success) {
+      return renderShareUnavailable(
+        "Too many attempts. Please wait a minute and try again.",
+        429,
+      );
+    }
+  }
+
+  const form = await request.formData();
+  const password = form.get("password");
+  if (
+    typeof password !== "string" ||
+    !(await verifySharePassword(password, passwordHash))
+  ) {
+    logWarning("Incorrect share password", {
+      module: "shares",
+      operation: "unlock",
+      metadata: { shareId, clientIp },
+    });
+    return renderSharePasswordForm(shareId, "Incorrect password.");
+  }
+
+  return new Response(null, {
+    status: 303,
+    headers: {
+      Location: `/share/${shareId}`,
+      "Set-Cookie": await createShareSessionCookie(shareId, env),
+    },
+  });
+}
+
+/**
+ * Signed /download/ URL for an object in a share. Downloads are counted
+ * against the share, and the URL never outlives the share.
+ */
+async function createShareDownloadUrl(
+  env: Env,
+  share: Share,
+  key: string,
+): Promise<string> {
+  const expires = Math.min(
+    Math.floor(new Date(share.expires_at).getTime() / 1000),
+    Math.floor(Date.now() / 1000) + SHARE_DOWNLOAD_URL_TTL_SECONDS,
+  );
+  return createSignedPath(
+    "/api/files/" + share.bucket_name + "/download/" + key,
+    { expires, disposition: "attachment", shareId: share.share_id },
+    env,
+  );
+}
+
+async function renderFileSharePage(env: Env, share: Share): Promise<Response> {
+  const object = await getObjectStorage(env, share.bucket_name).head(
+    share.object_key,
+  );
+  if (object === null) {
+    return renderShareUnavailable("The shared file no longer exists.", 404);
+  }
+
+  return renderFileShare(
+    {
+      name: share.object_key.split("/").pop() ?? share.object_key,
+      size: object.size,
+      downloadUrl: await createShareDownloadUrl(env, share, share.object_key),
+    },
+    share.expires_at,
+  );
+}
+
+async function renderFolderSharePage(
+  env: Env,
+  url: URL,
+  share: Share,
+): Promise<Response> {
+  const basePath = `/share/${share.share_id}`;
+  const folderName =
+    share.object_key.split("/").filter(Boolean).pop() ?? share.object_key;
+
+  // ?path= is relative to the shared folder and must stay inside it
+  let path = url.searchParams.get("path") ?? "";
+  if (path.split("/").some((part) => part === "..") || path.startsWith("/")) {
+    return renderShareUnavailable("Folder not found.", 404);
+  }
+  if (path !== "" && !path.endsWith("/")) {
+    path += "/";
+  }
+
+  const storage = getObjectStorage(env, share.bucket_name);
+  const page = await storage.list({
+    prefix: share.object_key + path,
+    delimiter: "/",
+    limit: FOLDER_PAGE_SIZE,
+    cursor: url.searchParams.get("cursor") ?? undefined,
+  });
+
+  const browseUrl = (subPath: string): string =>
+    subPath === ""
+      ? basePath
+      : `${basePath}?${new URLSearchParams({ path: subPath }).toString()}`;
+
+  const breadcrumbs: ShareBreadcrumb[] = [{ name: folderName, url: basePath }];
+  let crumbPath = "";
+  for (const part of path.split("/").filter(Boolean)) {
+    crumbPath += part + "/";
+    breadcrumbs.push({ name: part, url: browseUrl(crumbPath) });
+  }
+
+  const files = await Promise.all(
+    page.objects
+      .filter((obj) => !obj.key.endsWith("/") && !obj.key.endsWith("/.keep"))
+      .map(async (obj) => ({
+        name: obj.key.substring(share.object_key.length + path.length),
+        size: obj.size,
+        downloadUrl: await createShareDownloadUrl(env, share, obj.key),
+      })),
+  );
+
+  const folders = page.delimitedPrefixes.map((prefix) => {
+    const subPath = prefix.substring(share.object_key.length);
+    return {
+      name: subPath.substring(path.length).replace(/\/$/, ""),
+      browseUrl: browseUrl(subPath),
+    };
+  });
+
+  let nextPageUrl: string | undefined;
+  if (page.truncated && page.cursor !== undefined) {
+    const query = new URLSearchParams({ cursor: page.cursor });
+    if (path !== "") query.set("path", path);
+    nextPageUrl = `${basePath}?${query.toString()}`;
+  }
+
+  return renderFolderShare({
+    title: folderName,
+    expiresAt: share.expires_at,
+    breadcrumbs,
+    folders,
+    files,
+    zipUrl: share.allow_zip === 1 ? `${basePath}/zip` : undefined,
+    nextPageUrl,
+  });
+}
+
+/**
+ * Stream the whole shared folder as a ZIP (counts as one download)
+ */
+async function createShareZipResponse(
+  db: D1Database,
+  env: Env,
+  share: Share,
+  isLocalDev: boolean,
+): Promise<Response> {
+  if (share.share_type !== "folder" || share.allow_zip !== 1) {
+    return renderShareUnavailable("ZIP download is not enabled.", 404);
+  }
+
+  const refusal = await consumeShareDownload(db, share.share_id);
+  if (refusal !== null) {
+    return shareUnavailable(refusal);
+  }
+
+  const storage = getObjectStorage(env, share.bucket_name);
+  async function* entries(): AsyncGenerator<ZipEntry> {
+    for await (const objects of listAllObjects(storage, share.object_key)) {
+      for (const obj of objects) {
+        if (obj.key.endsWith("/") || obj.key.endsWith("/.keep")) continue;
+        const object = await storage.get(obj.key);
+        if (object === null) continue;
+        yield {
+          name: obj.key.substring(share.object_key.length),
+          size: object.size,
+          lastModified: new Date(object.uploaded),
+          body: object.body as ReadableStream<Uint8Array>,
+        };
+      }
+    }
+  }
+
+  const folderName =
+    share.object_key.split("/").filter(Boolean).pop() ?? "share";
+  logInfo("Streaming share ZIP", {
+    module: "shares",
+    operation: "zip",
+    bucketName: share.bucket_name,
+    fileName: share.object_key,
+    metadata: { shareId: share.share_id },
+  });
+
+  return new Response(
+    createZipStream(entries(), {
+      onError: async (error) => {
+        await logError(
+          env,
+          error instanceof Error ? error : String(error),
+          {
+            module: "shares",
+            operation: "zip",
+            bucketName: share.bucket_name,
+            metadata: { shareId: share.share_id },
+          },
+          isLocalDev,
+        );
+      },
+    }),
+    {
+      headers: {
+        "Content-Type": "application/zip",
+        "Content-Disposition": `attachment; filename="${folderName.replace(/"/g, "")}.zip"; filename*=UTF-8''${encodeURIComponent(folderName)}.zip`,
+        "Cache-Control": "no-store",
+      },
+    },
+  );
+}
@@ -0,0 +1,158 @@
+/**
+ * Signed Link Routes
+ *
+ * Lists the signed download links a user has issued and revokes them.
+ * Links themselves are created by GET /api/files/:bucket/signed-url/:key.
+ */
+
+import type { Env, SignedLink } from "../types";
+import { logError, logInfo } from "../utils/error-logger";
+import { createErrorResponse } from "../utils/error-response";
+import { getSignedLinkStatus } from "../utils/signed-links";
+
+const MOCK_SIGNED_LINKS: SignedLink[] = [
+  {
+    link_id: "lnk_dev000000000000000000001",
+    bucket_name: "dev-bucket",
+    object_key: "reports/q1.pdf",
+    created_by: "dev@localhost",
+    created_at: new Date(Date.now() - 3600000).toISOString(),
+    expires_at: new Date(Date.now() + 82800000).toISOString(),
+    ip: null,
+    max_downloads: 5,
+    disposition: "attachment",
+    download_count: 2,
+    last_download_at: new Date(Date.now() - 600000).toISOString(),
+    revoked_at: null,
+  },
+];
+
+function jsonResponse(body: unknown, corsHeaders: HeadersInit): Response {
+  return new Response(JSON.stringify(body), {
+    headers: { "Content-Type": "application/json", ...corsHeaders },
+  });
+}
+
+/**
+ * Handle signed link routes
+ */
+export async function handleSignedLinkRoutes(
+  request: Request,
+  env: Env,
+  url: URL,
+  corsHeaders: HeadersInit,
+  isLocalDev: boolean,
+  userEmail: string,
+): Promise<Response | null> {
+  const db = env.METADATA;
+
+  // GET /api/signed-links - List links issued by the current user
+  if (url.pathname === "/api/signed-links" && request.method === "GET") {
+    const includeInactive = url.searchParams.get("status") === "all";
+    const bucketName = url.searchParams.get("bucket");
+
+    if (isLocalDev || !db) {
+      return jsonResponse(
+        {
+          success: true,
+          result: {
+            links: MOCK_SIGNED_LINKS.map((link) => ({
+              ...link,
+              status: getSignedLinkStatus(link),
+            })),
+          },
+        },
+        corsHeaders,
+      );
+    }
+
+    try {
+      const conditions = ["created_by = ?"];
+      const bindings: string[] = [userEmail];
+      if (!includeInactive) {
+        conditions.push("revoked_at IS NULL", "expires_at > ?");
+        bindings.push(new Date().toISOString());
+      }
+      if (bucketName) {
+        conditions.push("bucket_name = ?");
+        bindings.push(bucketName);
+      }
+
+      const result = await db
+        .prepare(
+          `SELECT * FROM signed_links WHERE ${conditions.join(" AND ")} ORDER BY created_at DESC LIMIT 500`,
+        )
+        .bind(...bindings)
+        .all<SignedLink>();
+
+      const now = Date.now();
+      const links = result.results
+        .map((link) => ({ ...link, status: getSignedLinkStatus(link, now) }))
+        .filter((link) => includeInactive || link.status === "active");
+
+      return jsonResponse({ success: true, result: { links } }, corsHeaders);
+    } catch (error) {
+      await logError(
+        env,
+        error instanceof Error ? error : String(error),
+        { module: "signing", operation: "list", userId: userEmail },
+        isLocalDev,
+      );
+      return createErrorResponse("Failed to list signed links", corsHeaders);
+    }
+  }
+
+  // DELETE /api/signed-links/:linkId - Revoke a link
+  const revokeMatch = /^\/api\/signed-links\/([^/]+)$/.exec(url.pathname);
+  if (revokeMatch !== null && request.method === "DELETE") {
+    const linkId = revokeMatch[1];
+    if (!linkId) {
+      return createErrorResponse("Invalid link ID", corsHeaders, 400);
+    }
+
+    if (isLocalDev || !db) {
+      return jsonResponse({ success: true }, corsHeaders);
+    }
+
+    try {
+      const revoked = await db
+        .prepare(
+          "UPDATE signed_links SET revoked_at = ? WHERE link_id = ? AND created_by = ? AND revoked_at IS NULL RETURNING link_id",
+        )
+        .bind(new Date().toISOString(), linkId, userEmail)
+        .first<{ link_id: string }>();
+
+      if (revoked === null) {
+        return createErrorResponse(
+          "Signed link not found or already revoked",
+          corsHeaders,
+          404,
+        );
+      }
+
+      logInfo("Revoked signed link", {
+        module: "signing",
+        operation: "revoke",
+        userId: userEmail,
+        metadata: { linkId },
+      });
+
+      return jsonResponse({ success: true }, corsHeaders);
+    } catch (error) {
+      await logError(
+        env,
+        error instanceof Error ? error : String(error),
+        {
+          module: "signing",
+          operation: "revoke",
+          userId: userEmail,
+          metadata: { linkId },
+        },
+        isLocalDev,
+      );
+      return createErrorResponse("Failed to revoke signed link", corsHeaders);
+    }
+  }
+
+  return null;
+}
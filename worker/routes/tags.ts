@@ -9,6 +9,10 @@
 import type { Env } from "../types";
 import type { CorsHeaders } from "../utils/cors";
 import { logInfo, logError } from "../utils/error-logger";
+import {
+  triggerWebhooks,
+  createBucketTagsChangedPayload,
+} from "../utils/webhooks";
 
 // ============================================
 // Types
@@ -126,6 +130,53 @@ function normalizeTag(tag: string): string {
   return tag.trim().toLowerCase();
 }
 
+async function listBucketTags(
+  db: D1Database,
+  bucketName: string,
+): Promise<string[]> {
+  const result = await db
+    .prepare("SELECT tag FROM bucket_tags WHERE bucket_name = ? ORDER BY tag")
+    .bind(bucketName)
+    .all<{ tag: string }>();
+  return result.results.map((row) => row.tag);
+}
+
+// Send the bucket_tags_changed webhook with the bucket's tags after a change
+async function notifyTagsChanged(
+  env: Env,
+  bucketName: string,
+  addedTags: string[],
+  removedTags: string[],
+  userEmail: string | null,
+  isLocalDev: boolean,
+): Promise<void> {
+  if (!env.METADATA || (addedTags.length === 0 && removedTags.length === 0)) {
+    return;
+  }
+  try {
+    const tags = await listBucketTags(env.METADATA, bucketName);
+    await triggerWebhooks(
+      env,
+      "bucket_tags_changed",
+      createBucketTagsChangedPayload(
+        bucketName,
+        tags,
+        addedTags,
+        removedTags,
+        userEmail,
+      ),
+      isLocalDev,
+    );
+  } catch (error) {
+    await logError(
+      env,
+      error instanceof Error ? error : String(error),
+      { module: "tags", operation: "notify_tags_changed", bucketName },
+      isLocalDev,
+    );
+  }
+}
+
 // ============================================
 // Mock Data
 // ============================================
@@ -253,7 +304,14 @@ export async function handleTagRoutes(
     const bucketName = decodeURIComponent(deleteTagMatch[1] ?? "");
     const tag = decodeURIComponent(deleteTagMatch[2] ?? "");
     if (bucketName && tag)
-      return removeBucketTag(bucketName, tag, env, corsHeaders, isLocalDev);
+      return removeBucketTag(
+        bucketName,
+        tag,
+        env,
+        corsHeaders,
+        isLocalDev,
+        userEmail,
+      );
   }
 
   return null;
@@ -469,6 +527,8 @@ async function setBucketTags(
   }
 
   try {
+    const previousTags = await listBucketTags(env.METADATA, bucketName);
+
     // Delete all existing tags for bucket
     await env.METADATA.prepare("DELETE FROM bucket_tags WHERE bucket_name = ?")
       .bind(bucketName)
@@ -499,6 +559,14 @@ async function setBucketTags(
       operation: "set_bucket_tags",
       bucketName,
     });
+    void notifyTagsChanged(
+      env,
+      bucketName,
+      uniqueTags.filter((tag) => !previousTags.includes(tag)),
+      previousTags.filter((tag) => !uniqueTags.includes(tag)),
+      userEmail,
+      isLocalDev,
+    );
     return jsonResponse(
       { bucket_name: bucketName, tags: uniqueTags },
       corsHeaders,
@@ -569,7 +637,7 @@ async function addBucketTag(
   }
 
   try {
-    await env.METADATA.prepare(
+    const result = await env.METADATA.prepare(
       "INSERT OR IGNORE INTO bucket_tags (bucket_name, tag, created_by) VALUES (?, ?, ?)",
     )
       .bind(bucketName, normalizedTag, userEmail)
@@ -584,6 +652,16 @@ async function addBucketTag(
       operation: "add_bucket_tag",
       bucketName,
     });
+    if (result.meta.changes > 0) {
+      void notifyTagsChanged(
+        env,
+        bucketName,
+        [normalizedTag],
+        [],
+        userEmail,
+        isLocalDev,
+      );
+    }
     return jsonResponse(
       { bucket_name: bucketName, tag: normalizedTag, success: true },
       corsHeaders,
@@ -612,6 +690,7 @@ async function removeBucketTag(
   env: Env,
   corsHeaders: CorsHeaders,
   isLocalDev: boolean,
+  userEmail: string | null,
 ): Promise<Response> {
   const normalizedTag = normalizeTag(tag);
 
@@ -648,6 +727,14 @@ async function removeBucketTag(
       operation: "remove_bucket_tag",
       bucketName,
     });
+    void notifyTagsChanged(
+      env,
+      bucketName,
+      [],
+      [normalizedTag],
+      userEmail,
+      isLocalDev,
+    );
     return jsonResponse({ success: true }, corsHeaders);
   } catch (error) {
     await logError(
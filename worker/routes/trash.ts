@@ -0,0 +1,391 @@
+/**
+ * Trash Routes
+ *
+ * Lists a bucket's trash, restores and purges trashed objects, and turns
+ * trash on or off per bucket (see utils/trash.ts).
+ */
+
+import type {
+  BucketTrashSettings,
+  Env,
+  TrashItem,
+  UpdateTrashSettingsBody,
+} from "../types";
+import { logAuditEvent } from "./audit";
+import { logError, logInfo } from "../utils/error-logger";
+import { createErrorResponse } from "../utils/error-response";
+import { getRequestAccess, satisfiesCheck } from "../utils/rbac";
+import {
+  DEFAULT_TRASH_RETENTION_DAYS,
+  MAX_TRASH_RETENTION_DAYS,
+  invalidateTrashSettingsCache,
+  purgeExpiredTrash,
+  purgeTrashItem,
+  restoreTrashItem,
+} from "../utils/trash";
+
+/** Items returned per listing, and purged per "empty trash" request */
+const TRASH_PAGE_SIZE = 500;
+
+const MOCK_TRASH_ITEMS: TrashItem[] = [
+  {
+    item_id: "trs_dev000000000000000001",
+    bucket_name: "dev-bucket",
+    original_key: "reports/q3-summary.pdf",
+    trash_key: ".r2m-trash/trs_dev000000000000000001/reports/q3-summary.pdf",
+    size: 248_000,
+    etag: null,
+    reason: "delete",
+    deleted_by: "dev@localhost",
+    deleted_at: new Date(Date.now() - 2 * 86400000).toISOString(),
+    expires_at: new Date(Date.now() + 28 * 86400000).toISOString(),
+  },
+];
+
+function jsonResponse(body: unknown, corsHeaders: HeadersInit): Response {
+  return new Response(JSON.stringify(body), {
+    headers: { "Content-Type": "application/json", ...corsHeaders },
+  });
+}
+
+function toSettingsResponse(settings: BucketTrashSettings | null): {
+  enabled: boolean;
+  retentionDays: number;
+} {
+  return {
+    enabled: settings?.enabled === 1,
+    retentionDays: settings?.retention_days ?? DEFAULT_TRASH_RETENTION_DAYS,
+  };
+}
+
+/**
+ * Whether the current user may restore or purge an item - the same as
+ * deleting the original object
+ */
+function canManageItem(request: Request, item: TrashItem): boolean {
+  const access = getRequestAccess(request);
+  return (
+    access === undefined ||
+    satisfiesCheck(access, {
+      role: "editor",
+      scope: "key",
+      bucket: item.bucket_name,
+      key: item.original_key,
+    })
+  );
+}
+
+/**
+ * Handle trash routes
+ *
+ * Endpoints:
+ * - GET    /api/trash/:bucketName                 - Settings and trashed items
+ * - PUT    /api/trash/:bucketName/settings        - Enable/disable, retention
+ * - DELETE /api/trash/:bucketName                 - Empty the trash
+ * - POST   /api/trash/:bucketName/:itemId/restore - Restore an item
+ * - DELETE /api/trash/:bucketName/:itemId         - Purge an item
+ */
+export async function handleTrashRoutes(
+  request: Request,
+  env: Env,
+  url: URL,
+  corsHeaders: HeadersInit,
+  isLocalDev: boolean,
+  userEmail: string,
+): Promise<Response | null> {
+  const match = /^\/api\/trash\/([^/]+)(?:\/([^/]+))?(?:\/(restore))?$/.exec(
+    url.pathname,
+  );
+  if (match === null) {
+    return null;
+  }
+
+  const bucketName = decodeURIComponent(match[1] ?? "");
+  const segment = match[2] !== undefined ? decodeURIComponent(match[2]) : null;
+  const isRestore = match[3] !== undefined;
+  const db = env.METADATA;
+
+  // GET /api/trash/:bucketName - Settings and the items the user may manage
+  if (segment === null && request.method === "GET") {
+    if (isLocalDev || !db) {
+      return jsonResponse(
+        {
+          success: true,
+          result: {
+            settings: { enabled: true, retentionDays: 30 },
+            items: MOCK_TRASH_ITEMS,
+          },
+        },
+        corsHeaders,
+      );
+    }
+
+    try {
+      await purgeExpiredTrash(env, bucketName);
+
+      const settings = await db
+        .prepare("SELECT * FROM bucket_trash_settings WHERE bucket_name = ?")
+        .bind(bucketName)
+        .first<BucketTrashSettings>();
+
+      const prefix = url.searchParams.get("prefix") ?? "";
+      const result = await db
+        .prepare(
+          `SELECT * FROM trash_items WHERE bucket_name = ? AND substr(original_key, 1, ?) = ? ORDER BY deleted_at DESC LIMIT ${TRASH_PAGE_SIZE}`,
+        )
+        .bind(bucketName, prefix.length, prefix)
+        .all<TrashItem>();
+
+      const items = result.results.filter((item) =>
+        canManageItem(request, item),
+      );
+
+      return jsonResponse(
+        {
+          success: true,
+          result: {
+            settings: toSettingsResponse(settings),
+            items,
+          },
+        },
+        corsHeaders,
+      );
+    } catch (error) {
+      await logError(
+        env,
+        error instanceof Error ? error : String(error),
+        { module: "trash", operation: "list", bucketName },
+        isLocalDev,
+      );
+      return createErrorResponse("Failed to list trash", corsHeaders);
+    }
+  }
+
+  // PUT /api/trash/:bucketName/settings - Enable or disable trash
+  if (segment === "settings" && !isRestore && request.method === "PUT") {
+    let body: Partial<UpdateTrashSettingsBody>;
+    try {
+      body = (await request.json()) as Partial<UpdateTrashSettingsBody>;
+    } catch {
+      return createErrorResponse("Invalid JSON body", corsHeaders, 400);
+    }
+
+    if (typeof body.enabled !== "boolean") {
+      return createErrorResponse("enabled must be a boolean", corsHeaders, 400);
+    }
+    const retentionDays = body.retentionDays ?? DEFAULT_TRASH_RETENTION_DAYS;
+    if (
+      !Number.isInteger(retentionDays) ||
+      retentionDays < 1 ||
+      retentionDays > MAX_TRASH_RETENTION_DAYS
+    ) {
+      return createErrorResponse(
+        `retentionDays must be between 1 and ${MAX_TRASH_RETENTION_DAYS}`,
+        corsHeaders,
+        400,
+      );
+    }
+
+    const settings = { enabled: body.enabled, retentionDays };
+    if (isLocalDev || !db) {
+      return jsonResponse({ success: true, result: settings }, corsHeaders);
+    }
+
+    try {
+      await db
+        .prepare(
+          `INSERT INTO bucket_trash_settings (bucket_name, enabled, retention_days, updated_by, updated_at)
+           VALUES (?, ?, ?, ?, ?)
+           ON CONFLICT(bucket_name) DO UPDATE SET enabled = excluded.enabled, retention_days = excluded.retention_days, updated_by = excluded.updated_by, updated_at = excluded.updated_at`,
+        )
+        .bind(
+          bucketName,
+          body.enabled ? 1 : 0,
+          retentionDays,
+          userEmail,
+          new Date().toISOString(),
+        )
+        .run();
+      invalidateTrashSettingsCache(bucketName);
+
+      logInfo(`Trash ${body.enabled ? "enabled" : "disabled"}`, {
+        module: "trash",
+        operation: "update_settings",
+        bucketName,
+        userId: userEmail,
+        metadata: settings,
+      });
+
+      return jsonResponse({ success: true, result: settings }, corsHeaders);
+    } catch (error) {
+      await logError(
+        env,
+        error instanceof Error ? error : String(error),
+        { module: "trash", operation: "update_settings", bucketName },
+        isLocalDev,
+      );
+      return createErrorResponse(
+        "Failed to update trash settings",
+        corsHeaders,
+      );
+    }
+  }
+
+  // DELETE /api/trash/:bucketName - Empty the trash (one page per request)
+  if (segment === null && request.method === "DELETE") {
+    if (isLocalDev || !db) {
+      return jsonResponse(
+        { success: true, result: { purged: 0, remaining: false } },
+        corsHeaders,
+      );
+    }
+
+    try {
+      const result = await db
+        .prepare(
+          `SELECT * FROM trash_items WHERE bucket_name = ? ORDER BY deleted_at LIMIT ${TRASH_PAGE_SIZE}`,
+        )
+        .bind(bucketName)
+        .all<TrashItem>();
+
+      for (const item of result.results) {
+        await purgeTrashItem(env, item);
+      }
+
+      logInfo(`Emptied trash (${result.results.length} item(s))`, {
+        module: "trash",
+        operation: "empty",
+        bucketName,
+        userId: userEmail,
+      });
+
+      return jsonResponse(
+        {
+          success: true,
+          result: {
+            purged: result.results.length,
+            remaining: result.results.length === TRASH_PAGE_SIZE,
+          },
+        },
+        corsHeaders,
+      );
+    } catch (error) {
+      await logError(
+        env,
+        error instanceof Error ? error : String(error),
+        { module: "trash", operation: "empty", bucketName },
+        isLocalDev,
+      );
+      return createErrorResponse("Failed to empty trash", corsHeaders);
+    }
+  }
+
+  const itemId = segment;
+  if (
+    itemId === null ||
+    itemId === "settings" ||
+    !(
+      (isRestore && request.method === "POST") ||
+      (!isRestore && request.method === "DELETE")
+    )
+  ) {
+    return null;
+  }
+
+  if (isLocalDev || !db) {
+    return jsonResponse({ success: true }, corsHeaders);
+  }
+
+  const operation = isRestore ? "restore" : "purge";
+  try {
+    const item = await db
+      .prepare(
+        "SELECT * FROM trash_items WHERE item_id = ? AND bucket_name = ?",
+      )
+      .bind(itemId, bucketName)
+      .first<TrashItem>();
+
+    if (item === null) {
+      return createErrorResponse("Trash item not found", corsHeaders, 404);
+    }
+    if (!canManageItem(request, item)) {
+      return createErrorResponse(
+        "You do not have permission to manage this item",
+        corsHeaders,
+        403,
+      );
+    }
+
+    // POST /api/trash/:bucketName/:itemId/restore - Restore to the original key
+    if (isRestore) {
+      const overwrite = url.searchParams.get("overwrite") === "true";
+      const restored = await restoreTrashItem(env, item, userEmail, overwrite);
+
+      if (restored === "exists") {
+        return createErrorResponse(
+          `An object already exists at ${item.original_key}. Restore with overwrite to replace it.`,
+          corsHeaders,
+          409,
+        );
+      }
+      if (restored === "missing") {
+        return createErrorResponse(
+          "The trashed object no longer exists",
+          corsHeaders,
+          410,
+        );
+      }
+
+      await logAuditEvent(
+        env,
+        {
+          operationType: "file_move",
+          bucketName,
+          objectKey: item.trash_key,
+          userEmail,
+          status: "success",
+          sizeBytes: item.size,
+          destinationBucket: bucketName,
+          destinationKey: item.original_key,
+          metadata: { action: "trash_restore", itemId, overwrite },
+        },
+        isLocalDev,
+      );
+
+      return jsonResponse(
+        { success: true, result: { key: item.original_key } },
+        corsHeaders,
+      );
+    }
+
+    // DELETE /api/trash/:bucketName/:itemId - Permanently delete an item
+    await purgeTrashItem(env, item);
+
+    await logAuditEvent(
+      env,
+      {
+        operationType: "file_delete",
+        bucketName,
+        objectKey: item.original_key,
+        userEmail,
+        status: "success",
+        sizeBytes: item.size,
+        metadata: { action: "trash_purge", itemId },
+      },
+      isLocalDev,
+    );
+
+    return jsonResponse({ success: true }, corsHeaders);
+  } catch (error) {
+    await logError(
+      env,
+      error instanceof Error ? error : String(error),
+      { module: "trash", operation, bucketName, metadata: { itemId } },
+      isLocalDev,
+    );
+    return createErrorResponse(
+      `Failed to ${operation} trash item`,
+      corsHeaders,
+    );
+  }
+}
@@ -2,12 +2,35 @@
  * Webhook Routes
  *
  * CRUD API for managing webhook configurations.
- * Supports creating, updating, deleting, and testing webhooks.
+ * Supports creating, updating, deleting, and testing webhooks, rotating
+ * their secrets, and listing and redelivering their delivery attempts.
  */
 
-import type { Env, Webhook, WebhookInput, WebhookTestResult } from "../types";
+import type {
+  Env,
+  Webhook,
+  WebhookDelivery,
+  WebhookFilters,
+  WebhookInput,
+  WebhookRotateSecretInput,
+  WebhookTestInput,
+  WebhookTestResult,
+} from "../types";
 import type { CorsHeaders } from "../utils/cors";
-import { sendWebhook } from "../utils/webhooks";
+import {
+  createWebhookBody,
+  redeliverWebhook,
+  sendWebhook,
+} from "../utils/webhooks";
+import {
+  hasWebhookFilters,
+  validateWebhookFilters,
+} from "../utils/webhook-filters";
+import {
+  isWebhookEvent,
+  isWebhookFormat,
+  validateWebhookTemplate,
+} from "../utils/webhook-formats";
 import { logError } from "../utils/error-logger";
 
 // Helper to create response headers with CORS
@@ -26,6 +49,15 @@ function generateId(): string {
   return `whk_${timestamp}${randomStr}`;
 }
 
+// Generate a random signing secret
+function generateSecret(): string {
+  const bytes = crypto.getRandomValues(new Uint8Array(32));
+  const hex = Array.from(bytes)
+    .map((b) => b.toString(16).padStart(2, "0"))
+    .join("");
+  return `whsec_${hex}`;
+}
+
 // Get current ISO timestamp
 function nowISO(): string {
   return new Date().toISOString();
@@ -70,8 +102,16 @@ const MOCK_WEBHOOKS: Webhook[] = [
     name: "Slack Notifications",
     url: "https://hooks.slack.com/services/xxx/yyy/zzz",
     secret: "mock-secret-123",
+    previous_secret: null,
+    previous_secret_expires_at: null,
     events: JSON.stringify(["file_upload", "bucket_create", "job_failed"]),
     enabled: 1,
+    format: "slack",
+    template: null,
+    filters: JSON.stringify({
+      buckets: ["media"],
+      keyPatterns: ["incoming/*"],
+    }),
     created_at: "2024-03-01T12:00:00Z",
     updated_at: "2024-03-01T12:00:00Z",
   },
@@ -80,13 +120,129 @@ const MOCK_WEBHOOKS: Webhook[] = [
     name: "Discord Alerts",
     url: "https://discord.com/api/webhooks/xxx/yyy",
     secret: null,
+    previous_secret: null,
+    previous_secret_expires_at: null,
     events: JSON.stringify(["job_completed", "bucket_delete"]),
     enabled: 0,
+    format: "discord",
+    template: null,
+    filters: null,
     created_at: "2024-03-02T14:30:00Z",
     updated_at: "2024-03-02T14:30:00Z",
   },
 ];
 
+/**
+ * Mock delivery attempts for local development
+ */
+const MOCK_DELIVERIES: WebhookDelivery[] = [
+  {
+    id: "whd_mock2",
+    delivery_id: "whd_mockdelivery1",
+    webhook_id: "webhook-1",
+    event: "file_upload",
+    payload: JSON.stringify({
+      event: "file_upload",
+      timestamp: "2024-03-05T10:00:00Z",
+      data: { bucket_name: "media", file_name: "photo.jpg" },
+    }),
+    attempt: 2,
+    reason: "retry",
+    status: "succeeded",
+    status_code: 200,
+    latency_ms: 182,
+    response_body: "ok",
+    error: null,
+    next_retry_at: null,
+    created_at: "2024-03-05T10:00:31Z",
+  },
+  {
+    id: "whd_mock1",
+    delivery_id: "whd_mockdelivery1",
+    webhook_id: "webhook-1",
+    event: "file_upload",
+    payload: JSON.stringify({
+      event: "file_upload",
+      timestamp: "2024-03-05T10:00:00Z",
+      data: { bucket_name: "media", file_name: "photo.jpg" },
+    }),
+    attempt: 1,
+    reason: "event",
+    status: "failed",
+    status_code: 503,
+    latency_ms: 2040,
+    response_body: "Service Unavailable",
+    error: "Service Unavailable",
+    next_retry_at: null,
+    created_at: "2024-03-05T10:00:01Z",
+  },
+];
+
+/**
+ * Check the format and template a webhook would have after a create, update
+ * or test request, returning an error message if they are invalid
+ */
+function validateFormat(
+  input: Pick<WebhookInput, "format" | "template">,
+  existing?: Pick<Webhook, "format" | "template">,
+): string | null {
+  if (input.format !== undefined && !isWebhookFormat(input.format)) {
+    return "Invalid format: expected raw, slack, discord, teams or custom";
+  }
+  const format = input.format ?? existing?.format ?? "raw";
+  const template =
+    input.template !== undefined ? input.template : existing?.template;
+  return format === "custom" ? validateWebhookTemplate(template) : null;
+}
+
+// Check that events, if given, is a non-empty list of known event types.
+// Names a webhook was saved with before events were checked may be kept;
+// they never match an event.
+function validateEvents(
+  events: unknown,
+  existing?: Pick<Webhook, "events">,
+): string | null {
+  if (events === undefined) return null;
+  if (!Array.isArray(events) || events.length === 0) {
+    return "events must be a non-empty list of event types";
+  }
+  let saved: unknown = [];
+  try {
+    saved = existing ? JSON.parse(existing.events) : [];
+  } catch {
+    // Stored events that are not valid JSON allow no extra names
+  }
+  const kept = Array.isArray(saved) ? saved : [];
+  const invalid = events.filter(
+    (event) => !isWebhookEvent(event) && !kept.includes(event),
+  );
+  if (invalid.length > 0) {
+    return `Unknown event types: ${JSON.stringify(invalid)}`;
+  }
+  return null;
+}
+
+// Serialize request filters for the filters column, null if none are set
+function serializeFilters(
+  filters: WebhookFilters | null | undefined,
+): string | null {
+  return filters && hasWebhookFilters(filters) ? JSON.stringify(filters) : null;
+}
+
+/** Hours a replaced secret keeps signing requests, by default and at most */
+const DEFAULT_ROTATION_GRACE_HOURS = 24;
+const MAX_ROTATION_GRACE_HOURS = 168;
+const MIN_SECRET_LENGTH = 16;
+
+type WebhookSecretFields = Pick<
+  Webhook,
+  "secret" | "previous_secret" | "previous_secret_expires_at"
+>;
+
+/** Delivery attempts returned per request by default and at most */
+const DEFAULT_DELIVERIES_LIMIT = 50;
+const MAX_DELIVERIES_LIMIT = 200;
+
 // Handle webhook routes
 export async function handleWebhookRoutes(
   request: Request,
@@ -129,11 +285,36 @@ export async function handleWebhookRoutes(
     if (id) return deleteWebhook(id, env, corsHeaders, isLocalDev);
   }
 
+  // Rotate secret
+  const rotateMatch = /^\/api\/webhooks\/([^/]+)\/rotate-secret$/.exec(path);
+  if (rotateMatch && request.method === "POST") {
+    const id = rotateMatch[1];
+    if (id) return rotateSecret(id, request, env, corsHeaders, isLocalDev);
+  }
+
+  // List delivery attempts
+  const deliveriesMatch = /^\/api\/webhooks\/([^/]+)\/deliveries$/.exec(path);
+  if (deliveriesMatch && request.method === "GET") {
+    const id = deliveriesMatch[1];
+    if (id) return listDeliveries(id, url, env, corsHeaders, isLocalDev);
+  }
+
+  // Redeliver a delivery
+  const redeliverMatch =
+    /^\/api\/webhooks\/([^/]+)\/deliveries\/([^/]+)\/redeliver$/.exec(path);
+  if (redeliverMatch && request.method === "POST") {
+    const id = redeliverMatch[1];
+    const deliveryId = redeliverMatch[2];
+    if (id && deliveryId) {
+      return redeliver(id, deliveryId, env, corsHeaders, isLocalDev);
+    }
+  }
+
   // Test webhook
   const testMatch = /^\/api\/webhooks\/([^/]+)\/test$/.exec(path);
   if (testMatch && request.method === "POST") {
     const id = testMatch[1];
-    if (id) return testWebhook(id, env, corsHeaders, isLocalDev);
+    if (id) return testWebhook(id, request, env, corsHeaders, isLocalDev);
   }
 
   return null;
@@ -237,14 +418,27 @@ async function createWebhook(
     );
   }
 
+  const validationError =
+    validateEvents(body.events) ??
+    validateFormat(body) ??
+    validateWebhookFilters(body.filters ?? null);
+  if (validationError) {
+    return errorResponse(validationError, corsHeaders, 400);
+  }
+
   if (isLocalDev) {
     const newWebhook: Webhook = {
       id: generateId(),
       name: body.name,
       url: body.url,
       secret: body.secret ?? null,
+      previous_secret: null,
+      previous_secret_expires_at: null,
       events: JSON.stringify(body.events),
       enabled: body.enabled !== false ? 1 : 0,
+      format: body.format ?? "raw",
+      template: body.template ?? null,
+      filters: serializeFilters(body.filters),
       created_at: nowISO(),
       updated_at: nowISO(),
     };
@@ -261,10 +455,20 @@ async function createWebhook(
     const enabled = body.enabled !== false ? 1 : 0;
 
     await env.METADATA.prepare(
-      `INSERT INTO webhooks (id, name, url, secret, events, enabled)
-       VALUES (?, ?, ?, ?, ?, ?)`,
+      `INSERT INTO webhooks (id, name, url, secret, events, enabled, format, template, filters)
+       VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)`,
     )
-      .bind(id, body.name, body.url, body.secret ?? null, eventsJson, enabled)
+      .bind(
+        id,
+        body.name,
+        body.url,
+        body.secret ?? null,
+        eventsJson,
+        enabled,
+        body.format ?? "raw",
+        body.template ?? null,
+        serializeFilters(body.filters),
+      )
       .run();
 
     const webhook = await env.METADATA.prepare(
@@ -309,6 +513,14 @@ async function updateWebhook(
       return errorResponse("Webhook not found", corsHeaders, 404);
     }
 
+    const validationError =
+      validateEvents(body.events, webhook) ??
+      validateFormat(body, webhook) ??
+      validateWebhookFilters(body.filters ?? null);
+    if (validationError) {
+      return errorResponse(validationError, corsHeaders, 400);
+    }
+
     const updated: Webhook = {
       ...webhook,
       name: body.name ?? webhook.name,
@@ -317,6 +529,12 @@ async function updateWebhook(
       events: body.events ? JSON.stringify(body.events) : webhook.events,
       enabled:
         body.enabled !== undefined ? (body.enabled ? 1 : 0) : webhook.enabled,
+      format: body.format ?? webhook.format,
+      template: body.template !== undefined ? body.template : webhook.template,
+      filters:
+        body.filters !== undefined
+          ? serializeFilters(body.filters)
+          : webhook.filters,
       updated_at: nowISO(),
     };
 
@@ -339,6 +557,14 @@ async function updateWebhook(
       return errorResponse("Webhook not found", corsHeaders, 404);
     }
 
+    const validationError =
+      validateEvents(body.events, existing) ??
+      validateFormat(body, existing) ??
+      validateWebhookFilters(body.filters ?? null);
+    if (validationError) {
+      return errorResponse(validationError, corsHeaders, 400);
+    }
+
     // Build update query dynamically
     const updates: string[] = [];
     const values: (string | number | null)[] = [];
@@ -351,9 +577,12 @@ async function updateWebhook(
       updates.push("url = ?");
       values.push(body.url);
     }
-    if (body.secret !== undefined) {
+    if (body.secret !== undefined && body.secret !== existing.secret) {
+      // Replacing the secret directly ends any rotation grace period
       updates.push("secret = ?");
       values.push(body.secret);
+      updates.push("previous_secret = NULL");
+      updates.push("previous_secret_expires_at = NULL");
     }
     if (body.events !== undefined) {
       updates.push("events = ?");
@@ -363,6 +592,18 @@ async function updateWebhook(
       updates.push("enabled = ?");
       values.push(body.enabled ? 1 : 0);
     }
+    if (body.format !== undefined) {
+      updates.push("format = ?");
+      values.push(body.format);
+    }
+    if (body.template !== undefined) {
+      updates.push("template = ?");
+      values.push(body.template);
+    }
+    if (body.filters !== undefined) {
+      updates.push("filters = ?");
+      values.push(serializeFilters(body.filters));
+    }
 
     if (updates.length === 0) {
       return errorResponse("No fields to update", corsHeaders, 400);
@@ -453,24 +694,169 @@ async function deleteWebhook(
   }
 }
 
-// Test a webhook by sending a test payload
+// Replace a webhook's secret, keeping the old one active for a grace period
+async function rotateSecret(
+  webhookId: string,
+  request: Request,
+  env: Env,
+  corsHeaders: CorsHeaders,
+  isLocalDev: boolean,
+): Promise<Response> {
+  const input = (await parseJsonBody<WebhookRotateSecretInput>(request)) ?? {};
+  const graceHours = input.gracePeriodHours ?? DEFAULT_ROTATION_GRACE_HOURS;
+
+  if (
+    !Number.isFinite(graceHours) ||
+    graceHours < 0 ||
+    graceHours > MAX_ROTATION_GRACE_HOURS
+  ) {
+    return errorResponse(
+      `gracePeriodHours must be between 0 and ${String(MAX_ROTATION_GRACE_HOURS)}`,
+      corsHeaders,
+      400,
+    );
+  }
+  if (
+    input.secret !== undefined &&
+    input.secret.trim().length < MIN_SECRET_LENGTH
+  ) {
+    return errorResponse(
+      `secret must be at least ${String(MIN_SECRET_LENGTH)} characters`,
+      corsHeaders,
+      400,
+    );
+  }
+
+  const secret = input.secret?.trim() ?? generateSecret();
+
+  // Without a grace period, or a secret to keep, the old secret stops at once
+  const rotate = (webhook: Webhook): WebhookSecretFields => {
+    const keepPrevious = webhook.secret !== null && graceHours > 0;
+    return {
+      secret,
+      previous_secret: keepPrevious ? webhook.secret : null,
+      previous_secret_expires_at: keepPrevious
+        ? new Date(Date.now() + graceHours * 60 * 60 * 1000).toISOString()
+        : null,
+    };
+  };
+
+  if (isLocalDev) {
+    const webhook = MOCK_WEBHOOKS.find((w) => w.id === webhookId);
+    if (!webhook) {
+      return errorResponse("Webhook not found", corsHeaders, 404);
+    }
+    const updated: Webhook = {
+      ...webhook,
+      ...rotate(webhook),
+      updated_at: nowISO(),
+    };
+    return jsonResponse({ webhook: updated, secret }, corsHeaders);
+  }
+
+  if (!env.METADATA) {
+    return errorResponse("Database not configured", corsHeaders, 500);
+  }
+
+  try {
+    const existing = await env.METADATA.prepare(
+      "SELECT * FROM webhooks WHERE id = ?",
+    )
+      .bind(webhookId)
+      .first<Webhook>();
+
+    if (!existing) {
+      return errorResponse("Webhook not found", corsHeaders, 404);
+    }
+
+    const rotated = rotate(existing);
+    await env.METADATA.prepare(
+      `UPDATE webhooks
+       SET secret = ?, previous_secret = ?, previous_secret_expires_at = ?, updated_at = datetime("now")
+       WHERE id = ?`,
+    )
+      .bind(
+        rotated.secret,
+        rotated.previous_secret,
+        rotated.previous_secret_expires_at,
+        webhookId,
+      )
+      .run();
+
+    const webhook = await env.METADATA.prepare(
+      "SELECT * FROM webhooks WHERE id = ?",
+    )
+      .bind(webhookId)
+      .first<Webhook>();
+
+    return jsonResponse({ webhook, secret }, corsHeaders);
+  } catch (error) {
+    await logError(
+      env,
+      error instanceof Error ? error : String(error),
+      {
+        module: "webhooks",
+        operation: "rotate_secret",
+        metadata: { webhookId },
+      },
+      isLocalDev,
+    );
+    return errorResponse(
+      error instanceof Error ? error.message : "Failed to rotate secret",
+      corsHeaders,
+      500,
+    );
+  }
+}
+
+// Build the test payload body for a webhook, applying any format override
+function createTestBody(
+  webhook: Webhook,
+  input: WebhookTestInput,
+): { target: Webhook; body: string } {
+  const target: Webhook = {
+    ...webhook,
+    format: input.format ?? webhook.format,
+    template: input.template !== undefined ? input.template : webhook.template,
+  };
+  const body = createWebhookBody(target, "file_upload", {
+    test: true,
+    message: "This is a test webhook from R2 Manager",
+    timestamp: nowISO(),
+  });
+  return { target, body };
+}
+
+// Test a webhook by sending a test payload, or preview it with dryRun
 async function testWebhook(
   webhookId: string,
+  request: Request,
   env: Env,
   corsHeaders: CorsHeaders,
   isLocalDev: boolean,
 ): Promise<Response> {
+  const input = (await parseJsonBody<WebhookTestInput>(request)) ?? {};
+
   if (isLocalDev) {
     const webhook = MOCK_WEBHOOKS.find((w) => w.id === webhookId);
     if (!webhook) {
       return errorResponse("Webhook not found", corsHeaders, 404);
     }
 
-    const result: WebhookTestResult = {
-      success: true,
-      message: "Test webhook sent successfully (mock mode)",
-      statusCode: 200,
-    };
+    const formatError = validateFormat(input, webhook);
+    if (formatError) {
+      return errorResponse(formatError, corsHeaders, 400);
+    }
+
+    const { body } = createTestBody(webhook, input);
+    const result: WebhookTestResult = input.dryRun
+      ? { success: true, message: "Preview rendered (not sent)", body }
+      : {
+          success: true,
+          message: "Test webhook sent successfully (mock mode)",
+          statusCode: 200,
+          body,
+        };
 
     return jsonResponse(result, corsHeaders);
   }
@@ -490,14 +876,24 @@ async function testWebhook(
       return errorResponse("Webhook not found", corsHeaders, 404);
     }
 
-    // Send test payload
-    const testData = {
-      test: true,
-      message: "This is a test webhook from R2 Manager",
-      timestamp: nowISO(),
-    };
+    const formatError = validateFormat(input, webhook);
+    if (formatError) {
+      return errorResponse(formatError, corsHeaders, 400);
+    }
+
+    const { target, body } = createTestBody(webhook, input);
 
-    const sendResult = await sendWebhook(webhook, "file_upload", testData);
+    if (input.dryRun) {
+      const preview: WebhookTestResult = {
+        success: true,
+        message: "Preview rendered (not sent)",
+        body,
+      };
+      return jsonResponse(preview, corsHeaders);
+    }
+
+    // Send test payload
+    const sendResult = await sendWebhook(target, "file_upload", body);
 
     const result: WebhookTestResult = {
       success: sendResult.success,
@@ -508,6 +904,7 @@ async function testWebhook(
         statusCode: sendResult.statusCode,
       }),
       ...(sendResult.error !== undefined && { error: sendResult.error }),
+      body,
     };
 
     return jsonResponse(result, corsHeaders);
@@ -530,3 +927,134 @@ async function testWebhook(
     return jsonResponse(result, corsHeaders, 500);
   }
 }
+
+// List the most recent delivery attempts of a webhook
+async function listDeliveries(
+  webhookId: string,
+  url: URL,
+  env: Env,
+  corsHeaders: CorsHeaders,
+  isLocalDev: boolean,
+): Promise<Response> {
+  const requestedLimit = Number.parseInt(
+    url.searchParams.get("limit") ?? "",
+    10,
+  );
+  const limit = Number.isNaN(requestedLimit)
+    ? DEFAULT_DELIVERIES_LIMIT
+    : Math.min(Math.max(requestedLimit, 1), MAX_DELIVERIES_LIMIT);
+
+  if (isLocalDev) {
+    return jsonResponse(
+      {
+        deliveries: MOCK_DELIVERIES.filter((d) => d.webhook_id === webhookId),
+      },
+      corsHeaders,
+    );
+  }
+
+  if (!env.METADATA) {
+    return errorResponse("Database not configured", corsHeaders, 500);
+  }
+
+  try {
+    const result = await env.METADATA.prepare(
+      "SELECT * FROM webhook_deliveries WHERE webhook_id = ? ORDER BY created_at DESC LIMIT ?",
+    )
+      .bind(webhookId, limit)
+      .all<WebhookDelivery>();
+
+    return jsonResponse({ deliveries: result.results }, corsHeaders);
+  } catch (error) {
+    await logError(
+      env,
+      error instanceof Error ? error : String(error),
+      {
+        module: "webhooks",
+        operation: "list_deliveries",
+        metadata: { webhookId },
+      },
+      isLocalDev,
+    );
+    return errorResponse(
+      error instanceof Error ? error.message : "Failed to list deliveries",
+      corsHeaders,
+      500,
+    );
+  }
+}
+
+// Send a delivery again with its original payload
+async function redeliver(
+  webhookId: string,
+  deliveryId: string,
+  env: Env,
+  corsHeaders: CorsHeaders,
+  isLocalDev: boolean,
+): Promise<Response> {
+  if (isLocalDev) {
+    const latest = MOCK_DELIVERIES.find(
+      (d) => d.webhook_id === webhookId && d.delivery_id === deliveryId,
+    );
+    if (!latest) {
+      return errorResponse("Delivery not found", corsHeaders, 404);
+    }
+    const delivery: WebhookDelivery = {
+      ...latest,
+      id: `whd_mock${String(Date.now())}`,
+      attempt: latest.attempt + 1,
+      reason: "redeliver",
+      status: "succeeded",
+      status_code: 200,
+      latency_ms: 95,
+      response_body: "ok",
+      error: null,
+      created_at: nowISO(),
+    };
+    return jsonResponse({ delivery }, corsHeaders);
+  }
+
+  if (!env.METADATA) {
+    return errorResponse("Database not configured", corsHeaders, 500);
+  }
+
+  try {
+    const webhook = await env.METADATA.prepare(
+      "SELECT * FROM webhooks WHERE id = ?",
+    )
+      .bind(webhookId)
+      .first<Webhook>();
+
+    if (!webhook) {
+      return errorResponse("Webhook not found", corsHeaders, 404);
+    }
+
+    const delivery = await redeliverWebhook(
+      env,
+      env.METADATA,
+      webhook,
+      deliveryId,
+    );
+    if (!delivery) {
+      return errorResponse("Delivery not found", corsHeaders, 404);
+    }
+
+    return jsonResponse({ delivery }, corsHeaders);
+  } catch (error) {
+    await logError(
+      env,
+      error instanceof Error ? error : String(error),
+      {
+        module: "webhooks",
+        operation: "redeliver",
+        metadata: { webhookId, deliveryId },
+      },
+      isLocalDev,
+    );
+    return errorResponse(
+      error instanceof Error ? error.message : "Failed to redeliver webhook",
+      corsHeaders,
+      500,
+    );
+  }
+}
@@ -11,10 +11,13 @@ CREATE TABLE IF NOT EXISTS bulk_jobs (
         'bulk_download', 
         'bulk_delete',
         'bucket_delete',
+        'bucket_rename',
         'file_move',
         'file_copy',
         'folder_move',
         'folder_copy',
+        'folder_rename',
+        'folder_delete',
         'ai_search_sync'
     )),
     status TEXT NOT NULL DEFAULT 'queued' CHECK (status IN (
@@ -22,7 +25,8 @@ CREATE TABLE IF NOT EXISTS bulk_jobs (
         'running',
         'completed',
         'failed',
-        'cancelled'
+        'cancelled',
+        'paused'
     )),
     total_items INTEGER,
     processed_items INTEGER DEFAULT 0,
@@ -107,8 +111,13 @@ CREATE TABLE IF NOT EXISTS webhooks (
   name TEXT NOT NULL,
   url TEXT NOT NULL,
   secret TEXT,
+  previous_secret TEXT, -- Replaced secret, still signing until previous_secret_expires_at
+  previous_secret_expires_at TEXT,
   events TEXT NOT NULL, -- JSON array of event types
   enabled INTEGER DEFAULT 1,
+  format TEXT NOT NULL DEFAULT 'raw' CHECK (format IN ('raw', 'slack', 'discord', 'teams', 'custom')),
+  template TEXT, -- JSON template with {{placeholders}} for the custom format
+  filters TEXT, -- JSON bucket, tag, key, size and user filters; NULL if unfiltered
   created_at TEXT DEFAULT (datetime('now')),
   updated_at TEXT DEFAULT (datetime('now'))
 );
@@ -149,3 +158,273 @@ CREATE TABLE IF NOT EXISTS bucket_colors (
 -- Index for efficient color queries
 CREATE INDEX IF NOT EXISTS idx_bucket_colors_updated ON bucket_colors(updated_at DESC);
 
+-- ============================================
+-- Upload Sessions Tables
+-- ============================================
+
+-- Multipart upload sessions for resumable uploads
+CREATE TABLE IF NOT EXISTS upload_sessions (
+  upload_id TEXT PRIMARY KEY,
+  bucket_name TEXT NOT NULL,
+  object_key TEXT NOT NULL,
+  file_fingerprint TEXT, -- Client-computed identity of the source file
+  file_size INTEGER,
+  part_size INTEGER,
+  total_parts INTEGER,
+  content_type TEXT,
+  status TEXT NOT NULL DEFAULT 'in_progress' CHECK (status IN (
+    'in_progress',
+    'completed',
+    'aborted'
+  )),
+  user_email TEXT NOT NULL,
+  created_at TEXT NOT NULL,
+  updated_at TEXT NOT NULL
+);
+
+-- Indexes for finding resumable sessions
+CREATE INDEX IF NOT EXISTS idx_upload_sessions_bucket ON upload_sessions(bucket_name, status);
+CREATE INDEX IF NOT EXISTS idx_upload_sessions_fingerprint ON upload_sessions(file_fingerprint);
+
+-- Completed parts of each upload session
+CREATE TABLE IF NOT EXISTS upload_session_parts (
+  upload_id TEXT NOT NULL,
+  part_number INTEGER NOT NULL,
+  etag TEXT NOT NULL,
+  md5 TEXT,
+  uploaded_at TEXT NOT NULL,
+  PRIMARY KEY (upload_id, part_number),
+  FOREIGN KEY (upload_id) REFERENCES upload_sessions(upload_id) ON DELETE CASCADE
+);
+
+-- ============================================
+-- Background Job Tables
+-- ============================================
+
+-- Resumable state for bulk jobs executed by the queue consumer
+CREATE TABLE IF NOT EXISTS job_tasks (
+  job_id TEXT PRIMARY KEY,
+  task_type TEXT NOT NULL,
+  params TEXT NOT NULL, -- JSON: source/destination bucket and prefix
+  phase TEXT NOT NULL DEFAULT 'count', -- count, transfer, delete, retry, finalize
+  cursor TEXT, -- R2 list cursor within the current phase
+  attempts INTEGER DEFAULT 0,
+  updated_at TEXT NOT NULL,
+  FOREIGN KEY (job_id) REFERENCES bulk_jobs(job_id) ON DELETE CASCADE
+);
+
+-- Per-item failures of a job, replayed by POST /api/jobs/:id/retry-failed
+-- and listed by GET /api/jobs/:id/items
+CREATE TABLE IF NOT EXISTS job_items (
+  job_id TEXT NOT NULL,
+  object_key TEXT NOT NULL,
+  status TEXT NOT NULL DEFAULT 'failed' CHECK (status IN (
+    'failed',
+    'succeeded'
+  )),
+  error_code TEXT,
+  error TEXT,
+  attempts INTEGER DEFAULT 1,
+  updated_at TEXT NOT NULL,
+  PRIMARY KEY (job_id, object_key),
+  FOREIGN KEY (job_id) REFERENCES bulk_jobs(job_id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_job_items_status ON job_items(job_id, status);
+
+-- ============================================
+-- Signed Links Table
+-- ============================================
+
+-- Signed download links with download counters and revocation
+CREATE TABLE IF NOT EXISTS signed_links (
+  link_id TEXT PRIMARY KEY,
+  bucket_name TEXT NOT NULL,
+  object_key TEXT NOT NULL,
+  created_by TEXT NOT NULL,
+  created_at TEXT NOT NULL,
+  expires_at TEXT NOT NULL,
+  ip TEXT,
+  max_downloads INTEGER,
+  disposition TEXT NOT NULL DEFAULT 'attachment' CHECK (disposition IN (
+    'attachment',
+    'inline'
+  )),
+  download_count INTEGER NOT NULL DEFAULT 0,
+  last_download_at TEXT,
+  revoked_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_signed_links_creator ON signed_links(created_by, expires_at);
+CREATE INDEX IF NOT EXISTS idx_signed_links_bucket ON signed_links(bucket_name, object_key);
+
+-- ============================================
+-- Shares Table
+-- ============================================
+
+-- Public share links for files and folders, served on a landing page
+CREATE TABLE IF NOT EXISTS shares (
+  share_id TEXT PRIMARY KEY,
+  bucket_name TEXT NOT NULL,
+  object_key TEXT NOT NULL,
+  share_type TEXT NOT NULL CHECK (share_type IN ('file', 'folder')),
+  created_by TEXT NOT NULL,
+  created_at TEXT NOT NULL,
+  expires_at TEXT NOT NULL,
+  password_hash TEXT,
+  max_downloads INTEGER,
+  download_count INTEGER NOT NULL DEFAULT 0,
+  allow_zip INTEGER NOT NULL DEFAULT 0,
+  last_accessed_at TEXT,
+  revoked_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_shares_creator ON shares(created_by, expires_at);
+CREATE INDEX IF NOT EXISTS idx_shares_bucket ON shares(bucket_name, object_key);
+
+-- ============================================
+-- Access Grants Table
+-- ============================================
+
+-- Role grants (viewer, editor, admin) per user, optionally limited to a
+-- bucket (NULL = all buckets) and a key prefix ('' = whole bucket)
+CREATE TABLE IF NOT EXISTS access_grants (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  user_email TEXT NOT NULL,
+  role TEXT NOT NULL CHECK (role IN ('viewer', 'editor', 'admin')),
+  bucket_name TEXT,
+  prefix TEXT NOT NULL DEFAULT '',
+  created_by TEXT NOT NULL,
+  created_at TEXT DEFAULT (datetime('now'))
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_access_grants_scope ON access_grants(user_email, IFNULL(bucket_name, ''), prefix);
+
+-- ============================================
+-- Access Group Mappings Table
+-- ============================================
+
+-- Maps identity provider groups (from Access JWT claims or get-identity) to
+-- roles. Members of a group get its role as if granted individually.
+CREATE TABLE IF NOT EXISTS access_group_mappings (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  group_name TEXT NOT NULL,
+  role TEXT NOT NULL CHECK (role IN ('viewer', 'editor', 'admin')),
+  bucket_name TEXT,
+  prefix TEXT NOT NULL DEFAULT '',
+  created_by TEXT NOT NULL,
+  created_at TEXT DEFAULT (datetime('now'))
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_access_group_mappings_scope ON access_group_mappings(lower(group_name), IFNULL(bucket_name, ''), prefix);
+
+-- ============================================
+-- API Tokens Table
+-- ============================================
+
+-- Personal/service API tokens, sent as "Authorization: Bearer r2m_...".
+-- Only the SHA-256 hash of each token is stored. buckets is a JSON array
+-- (NULL = all buckets) and permissions a comma-separated list of read,
+-- write and delete.
+CREATE TABLE IF NOT EXISTS api_tokens (
+  token_id TEXT PRIMARY KEY,
+  name TEXT NOT NULL,
+  token_prefix TEXT NOT NULL,
+  token_hash TEXT NOT NULL UNIQUE,
+  created_by TEXT NOT NULL,
+  creator_groups TEXT NOT NULL DEFAULT '[]',
+  created_at TEXT NOT NULL,
+  expires_at TEXT NOT NULL,
+  buckets TEXT,
+  permissions TEXT NOT NULL,
+  last_used_at TEXT,
+  revoked_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_api_tokens_creator ON api_tokens(created_by, expires_at);
+
+-- ============================================
+-- Trash Tables
+-- ============================================
+
+-- Buckets with trash enabled. Deleted and overwritten objects are copied
+-- under the hidden .r2m-trash/ prefix and kept for retention_days.
+CREATE TABLE IF NOT EXISTS bucket_trash_settings (
+  bucket_name TEXT PRIMARY KEY,
+  enabled INTEGER NOT NULL DEFAULT 1,
+  retention_days INTEGER NOT NULL DEFAULT 30,
+  updated_by TEXT NOT NULL,
+  updated_at TEXT NOT NULL
+);
+
+-- Trashed objects, restorable until expires_at
+CREATE TABLE IF NOT EXISTS trash_items (
+  item_id TEXT PRIMARY KEY,
+  bucket_name TEXT NOT NULL,
+  original_key TEXT NOT NULL,
+  trash_key TEXT NOT NULL,
+  size INTEGER NOT NULL DEFAULT 0,
+  etag TEXT,
+  reason TEXT NOT NULL CHECK (reason IN ('delete', 'overwrite')),
+  deleted_by TEXT NOT NULL,
+  deleted_at TEXT NOT NULL,
+  expires_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_trash_items_bucket ON trash_items(bucket_name, deleted_at DESC);
+CREATE INDEX IF NOT EXISTS idx_trash_items_expires ON trash_items(expires_at);
+
+-- ============================================
+-- Scheduled Maintenance Tables
+-- ============================================
+
+-- Daily storage size and object count per bucket, written by the cron handler
+CREATE TABLE IF NOT EXISTS storage_snapshots (
+  snapshot_date TEXT NOT NULL,
+  bucket_name TEXT NOT NULL,
+  storage_bytes INTEGER NOT NULL DEFAULT 0,
+  object_count INTEGER NOT NULL DEFAULT 0,
+  captured_at TEXT NOT NULL,
+  PRIMARY KEY (snapshot_date, bucket_name)
+);
+
+-- Finished S3 import jobs whose completion has been sent to webhooks
+CREATE TABLE IF NOT EXISTS s3_import_notifications (
+  job_id TEXT PRIMARY KEY,
+  status TEXT NOT NULL,
+  notified_at TEXT NOT NULL
+);
+
+-- Ended AI Search sync jobs whose completion has been sent to webhooks
+CREATE TABLE IF NOT EXISTS ai_search_sync_notifications (
+  job_id TEXT PRIMARY KEY,
+  instance_name TEXT NOT NULL,
+  notified_at TEXT NOT NULL
+);
+
+-- ============================================
+-- Webhook Deliveries Table
+-- ============================================
+
+-- Every webhook delivery attempt. Attempts of one event share delivery_id;
+-- a failed attempt with next_retry_at set is retried once that time passes.
+CREATE TABLE IF NOT EXISTS webhook_deliveries (
+  id TEXT PRIMARY KEY,
+  delivery_id TEXT NOT NULL,
+  webhook_id TEXT NOT NULL,
+  event TEXT NOT NULL,
+  payload TEXT NOT NULL, -- JSON body as sent
+  attempt INTEGER NOT NULL DEFAULT 1,
+  reason TEXT NOT NULL DEFAULT 'event' CHECK (reason IN ('event', 'retry', 'redeliver')),
+  status TEXT NOT NULL CHECK (status IN ('succeeded', 'failed')),
+  status_code INTEGER,
+  latency_ms INTEGER,
+  response_body TEXT, -- first 1000 characters
+  error TEXT,
+  next_retry_at TEXT,
+  created_at TEXT NOT NULL,
+  FOREIGN KEY (webhook_id) REFERENCES webhooks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_webhook ON webhook_deliveries(webhook_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_retry ON webhook_deliveries(next_retry_at);
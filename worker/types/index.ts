@@ -15,6 +15,15 @@ export interface Env {
   RATE_LIMITER_DELETE: RateLimit;
   AI?: Ai;
   METADATA?: D1Database;
+  R2_BUCKET_BINDINGS?: string; // Optional - JSON map of bucket name to R2 binding name
+  JOB_QUEUE?: Queue<JobQueueMessage>; // Optional - runs bulk jobs in the background
+  ADMIN_EMAILS?: string; // Optional - comma-separated emails that are always admins
+  ACCESS_GROUP_CLAIMS?: string; // Optional - comma-separated claims holding IdP groups (default "groups")
+  ACCESS_IDENTITY_GROUPS?: string; // Optional - "true" to also read groups from Access get-identity
+  AUDIT_LOG_RETENTION_DAYS?: string; // Optional - days of audit_log kept by the cron (default 90, 0 keeps all)
+  JOB_EVENT_RETENTION_DAYS?: string; // Optional - days of job_audit_events kept by the cron (default 90, 0 keeps all)
+  WEBHOOK_DELIVERY_RETENTION_DAYS?: string; // Optional - days of webhook_deliveries kept by the cron (default 30, 0 keeps all)
+  STALE_JOB_MINUTES?: string; // Optional - minutes without progress before a running job is failed (default 60, 0 disables)
 }
 
 export const CF_API = "https://api.cloudflare.com/client/v4";
@@ -54,6 +63,97 @@ export interface R2ObjectInfo {
   customMetadata?: Record<string, string>;
 }
 
+// ============================================
+// Object Storage Types (binding / REST abstraction)
+// ============================================
+
+/**
+ * HTTP metadata stored with an object
+ */
+export interface StorageHttpMetadata {
+  contentType?: string;
+  contentLanguage?: string;
+  contentDisposition?: string;
+  contentEncoding?: string;
+  cacheControl?: string;
+}
+
+/**
+ * Object metadata returned by head/list/put
+ */
+export interface StorageObject {
+  key: string;
+  size: number;
+  uploaded: string;
+  etag: string;
+  httpMetadata: StorageHttpMetadata;
+  customMetadata: Record<string, string>;
+}
+
+/**
+ * Object metadata plus a streaming body, returned by get
+ */
+export interface StorageObjectBody extends StorageObject {
+  body: ReadableStream;
+}
+
+export interface StorageListOptions {
+  prefix?: string | undefined;
+  cursor?: string | undefined;
+  limit?: number | undefined;
+  delimiter?: string | undefined;
+  /** List keys after this key; honoured by native bindings only */
+  startAfter?: string | undefined;
+  /** Newest-first ordering; honoured by the REST API only */
+  newestFirst?: boolean | undefined;
+}
+
+export interface StorageListResult {
+  objects: StorageObject[];
+  delimitedPrefixes: string[];
+  truncated: boolean;
+  cursor?: string | undefined;
+}
+
+export interface StorageGetOptions {
+  /** Byte range to read (the returned size is still the full object size) */
+  range?: { offset: number; length: number } | undefined;
+}
+
+export interface StoragePutOptions {
+  httpMetadata?: StorageHttpMetadata | undefined;
+  customMetadata?: Record<string, string> | undefined;
+}
+
+export type StoragePutBody =
+  | ReadableStream
+  | ArrayBuffer
+  | ArrayBufferView
+  | string
+  | Blob
+  | null;
+
+/**
+ * Object I/O for a single bucket, backed by a native R2 binding when one is
+ * configured and by the Cloudflare REST API otherwise
+ */
+export interface ObjectStorage {
+  readonly bucketName: string;
+  readonly isNative: boolean;
+  get(
+    key: string,
+    options?: StorageGetOptions,
+  ): Promise<StorageObjectBody | null>;
+  head(key: string): Promise<StorageObject | null>;
+  put(
+    key: string,
+    body: StoragePutBody,
+    options?: StoragePutOptions,
+  ): Promise<StorageObject>;
+  delete(key: string): Promise<void>;
+  list(options?: StorageListOptions): Promise<StorageListResult>;
+}
+
 export interface CreateBucketBody {
   name: string;
 }
@@ -204,6 +304,8 @@ export interface BucketMetricsSummary {
   classAOperations: number;
   classBOperations: number;
   currentStorageBytes?: number | undefined;
+  /** Objects Sippy copied in during the range (Sippy-enabled buckets only) */
+  sippyObjectsMigrated?: number | undefined;
 }
 
 /**
@@ -219,6 +321,7 @@ export interface MetricsSummary {
   totalBytesDownloaded: number;
   totalStorageBytes: number;
   totalObjectCount?: number;
+  totalSippyObjectsMigrated?: number;
   bucketCount: number;
 }
 
@@ -345,17 +448,21 @@ export type JobStatus =
   | "running"
   | "completed"
   | "failed"
-  | "cancelled";
+  | "cancelled"
+  | "paused";
 
 export type JobOperationType =
   | "bulk_upload"
   | "bulk_download"
   | "bulk_delete"
   | "bucket_delete"
+  | "bucket_rename"
   | "file_move"
   | "file_copy"
   | "folder_move"
   | "folder_copy"
+  | "folder_rename"
+  | "folder_delete"
   | "ai_search_sync";
 
 export interface BulkJob {
@@ -398,6 +505,7 @@ export interface CreateJobParams {
   jobId: string;
   bucketName: string;
   operationType: JobOperationType;
+  status?: "queued" | "running";
   totalItems?: number;
   userEmail: string;
   metadata?: Record<string, unknown>;
@@ -417,8 +525,88 @@ export interface CompleteJobParams {
   errorCount?: number;
   userEmail: string;
   errorMessage?: string;
+  /** Only finish the job if it is still in one of these statuses */
+  fromStatuses?: JobStatus[];
+}
+
+// Background job execution types
+
+export type JobTaskType =
+  | "folder_copy"
+  | "folder_move"
+  | "folder_rename"
+  | "folder_delete"
+  | "bucket_delete"
+  | "bucket_rename";
+
+/**
+ * Phases a background job moves through: count the source objects, copy
+ * (and for moves, delete) them, delete them, then run the final step such
+ * as deleting the emptied bucket. "retry" replays only the failed items.
+ */
+export type JobTaskPhase =
+  | "count"
+  | "transfer"
+  | "delete"
+  | "retry"
+  | "finalize";
+
+export interface JobTaskParams {
+  sourceBucket: string;
+  sourcePrefix: string; // Empty string for the whole bucket
+  destBucket?: string | undefined;
+  destPrefix?: string | undefined;
+}
+
+export interface JobTask {
+  job_id: string;
+  task_type: JobTaskType;
+  params: string; // JSON-encoded JobTaskParams
+  phase: JobTaskPhase;
+  cursor: string | null;
+  attempts: number;
+  updated_at: string;
 }
 
+/**
+ * JOB_QUEUE message: a background job to run, or a failed webhook delivery
+ * attempt to retry
+ */
+export type JobQueueMessage = { jobId: string } | { webhookDeliveryId: string };
+
+export type JobItemStatus = "failed" | "succeeded";
+
+/**
+ * Classified cause of a failed job item
+ */
+export type JobItemErrorCode =
+  | "not_found"
+  | "access_denied"
+  | "rate_limited"
+  | "timeout"
+  | "storage_error"
+  | "object_locked"
+  | "unknown";
+
+export interface JobItem {
+  job_id: string;
+  object_key: string;
+  status: JobItemStatus;
+  error_code: JobItemErrorCode | null;
+  error: string | null;
+  attempts: number;
+  updated_at: string;
+}
+
+export interface RecordJobItemParams {
+  jobId: string;
+  objectKey: string;
+  status: JobItemStatus;
+  error?: Error | string | undefined;
+}
+
+export type JobControlAction = "cancel" | "pause" | "resume" | "retry-failed";
+
 export interface LogJobEventParams {
   jobId: string;
   eventType: string;
@@ -426,6 +614,259 @@ export interface LogJobEventParams {
   details?: Record<string, unknown>;
 }
 
+// Upload Session Types - for resumable multipart uploads
+export type UploadSessionStatus = "in_progress" | "completed" | "aborted";
+
+export interface UploadSessionPart {
+  part_number: number;
+  etag: string;
+  md5: string | null;
+  uploaded_at: string;
+}
+
+export interface UploadSession {
+  upload_id: string;
+  bucket_name: string;
+  object_key: string;
+  file_fingerprint: string | null;
+  file_size: number | null;
+  part_size: number | null;
+  total_parts: number | null;
+  content_type: string | null;
+  status: UploadSessionStatus;
+  user_email: string;
+  created_at: string;
+  updated_at: string;
+  parts: UploadSessionPart[];
+}
+
+export interface CreateUploadSessionParams {
+  uploadId: string;
+  bucketName: string;
+  objectKey: string;
+  fileFingerprint?: string | undefined;
+  fileSize?: number | undefined;
+  partSize?: number | undefined;
+  totalParts?: number | undefined;
+  contentType?: string | undefined;
+  userEmail: string;
+}
+
+// Signed Link Types - for expiring, scoped download links
+export type SignedLinkStatus = "active" | "expired" | "exhausted" | "revoked";
+
+export interface SignedLink {
+  link_id: string;
+  bucket_name: string;
+  object_key: string;
+  created_by: string;
+  created_at: string;
+  expires_at: string;
+  ip: string | null;
+  max_downloads: number | null;
+  disposition: "attachment" | "inline";
+  download_count: number;
+  last_download_at: string | null;
+  revoked_at: string | null;
+}
+
+export interface CreateSignedLinkParams {
+  linkId: string;
+  bucketName: string;
+  objectKey: string;
+  createdBy: string;
+  expiresAt: Date;
+  ip?: string | undefined;
+  maxDownloads?: number | undefined;
+  disposition: "attachment" | "inline";
+}
+
+// Share Types - for public share links with a landing page
+export type ShareType = "file" | "folder";
+
+export type ShareStatus = "active" | "expired" | "exhausted" | "revoked";
+
+export interface Share {
+  share_id: string;
+  bucket_name: string;
+  /** File key, or folder prefix (ending in "/") for folder shares */
+  object_key: string;
+  share_type: ShareType;
+  created_by: string;
+  created_at: string;
+  expires_at: string;
+  /** PBKDF2 hash ("pbkdf2$iterations$salt$hash"), null if no password */
+  password_hash: string | null;
+  max_downloads: number | null;
+  download_count: number;
+  /** 1 if a folder share may be downloaded as a ZIP */
+  allow_zip: number;
+  last_accessed_at: string | null;
+  revoked_at: string | null;
+}
+
+export interface CreateShareParams {
+  shareId: string;
+  bucketName: string;
+  objectKey: string;
+  shareType: ShareType;
+  createdBy: string;
+  expiresAt: Date;
+  passwordHash?: string | undefined;
+  maxDownloads?: number | undefined;
+  allowZip: boolean;
+}
+
+export interface CreateShareBody {
+  bucket: string;
+  key: string;
+  type: ShareType;
+  password?: string;
+  expiresIn?: number;
+  maxDownloads?: number;
+  allowZip?: boolean;
+}
+
+// Access Control Types - roles and per-bucket/per-prefix grants
+export type AccessRole = "viewer" | "editor" | "admin";
+
+export interface AccessGrant {
+  id: number;
+  user_email: string;
+  role: AccessRole;
+  /** null grants the role on every bucket */
+  bucket_name: string | null;
+  /** Key prefix the grant is limited to ("" for the whole bucket) */
+  prefix: string;
+  created_by: string;
+  created_at: string;
+}
+
+/** The part of a grant that decides what it allows */
+export type AccessGrantScope = Pick<
+  AccessGrant,
+  "role" | "bucket_name" | "prefix"
+>;
+
+/** Maps an identity provider group to a role, like a grant for every member */
+export interface AccessGroupMapping {
+  id: number;
+  group_name: string;
+  role: AccessRole;
+  bucket_name: string | null;
+  prefix: string;
+  created_by: string;
+  created_at: string;
+}
+
+/** Identity from a validated Cloudflare Access JWT or API token */
+export interface AccessIdentity {
+  email: string;
+  /** IdP groups from the configured claims (and get-identity, if enabled) */
+  groups: string[];
+  /** Set when the request authenticated with an API token */
+  token?: ApiTokenScope | undefined;
+}
+
+export interface UserAccess {
+  email: string;
+  groups: string[];
+  /** false if access control is not configured, or for bootstrap admins */
+  enforced: boolean;
+  /** The user's own grants plus those of their mapped groups */
+  grants: AccessGrantScope[];
+  /** API token restrictions, applied on top of the grants */
+  token?: ApiTokenScope | undefined;
+}
+
+export interface CreateAccessGrantBody {
+  userEmail: string;
+  role: AccessRole;
+  bucketName?: string | null;
+  prefix?: string;
+}
+
+export interface CreateAccessGroupMappingBody {
+  groupName: string;
+  role: AccessRole;
+  bucketName?: string | null;
+  prefix?: string;
+}
+
+// API Token Types - personal/service tokens for non-browser clients
+export type ApiTokenPermission = "read" | "write" | "delete";
+
+export type ApiTokenStatus = "active" | "expired" | "revoked";
+
+export interface ApiToken {
+  token_id: string;
+  name: string;
+  /** First characters of the token, to help users recognise it */
+  token_prefix: string;
+  /** SHA-256 hash of the token - the token itself is never stored */
+  token_hash: string;
+  /** The token acts as this user, limited by its own scope */
+  created_by: string;
+  /** Unused: tokens do not carry the creator's IdP groups (always "[]") */
+  creator_groups: string;
+  created_at: string;
+  expires_at: string;
+  /** JSON array of bucket names, null for all buckets */
+  buckets: string | null;
+  /** Comma-separated ApiTokenPermission values */
+  permissions: string;
+  last_used_at: string | null;
+  revoked_at: string | null;
+}
+
+/** What an API token may do, beyond its creator's own grants */
+export interface ApiTokenScope {
+  tokenId: string;
+  name: string;
+  buckets: string[] | null;
+  permissions: ApiTokenPermission[];
+}
+
+export interface CreateApiTokenBody {
+  name: string;
+  permissions: ApiTokenPermission[];
+  buckets?: string[] | null;
+  /** Lifetime in seconds */
+  expiresIn?: number;
+}
+
+// Trash Types - soft-deleted and overwritten objects kept for restore
+export type TrashReason = "delete" | "overwrite";
+
+export interface BucketTrashSettings {
+  bucket_name: string;
+  /** 1 if deletes and overwrites go to the trash */
+  enabled: number;
+  retention_days: number;
+  updated_by: string;
+  updated_at: string;
+}
+
+export interface TrashItem {
+  item_id: string;
+  bucket_name: string;
+  original_key: string;
+  /** Where the object is kept, under the hidden trash prefix */
+  trash_key: string;
+  size: number;
+  etag: string | null;
+  reason: TrashReason;
+  deleted_by: string;
+  deleted_at: string;
+  /** Purged automatically after this time */
+  expires_at: string;
+}
+
+export interface UpdateTrashSettingsBody {
+  enabled: boolean;
+  retentionDays?: number;
+}
+
 // Audit Log Types - for tracking individual user actions
 export type AuditOperationType =
   | "file_upload"
@@ -546,6 +987,38 @@ export interface S3ImportJobResponse {
   dashboardUrl?: string;
 }
 
+// Sippy Types (incremental migration)
+export type SippyProvider = "aws" | "gcs";
+
+/**
+ * Sippy status of a bucket. The source is absent when Sippy is disabled.
+ */
+export interface SippyConfig {
+  enabled: boolean;
+  source?: {
+    provider: SippyProvider;
+    bucket: string;
+    region?: string | undefined;
+  };
+}
+
+/**
+ * Request body for enabling Sippy on a bucket. AWS sources need an access
+ * key pair and region, GCS sources a service account email and private key.
+ * The R2 key pair is used by Sippy to write copied objects to the bucket.
+ */
+export interface EnableSippyBody {
+  provider: SippyProvider;
+  sourceBucket: string;
+  region?: string;
+  accessKeyId?: string;
+  secretAccessKey?: string;
+  clientEmail?: string;
+  privateKey?: string;
+  r2AccessKeyId: string;
+  r2SecretAccessKey: string;
+}
+
 // ============================================
 // Webhook Types
 // ============================================
@@ -565,10 +1038,37 @@ export type WebhookEventType =
   | "bucket_rename"
   | "folder_create"
   | "folder_delete"
+  | "folder_move"
+  | "folder_copy"
+  | "folder_rename"
   | "bulk_download_complete"
   | "s3_import_complete"
+  | "lifecycle_updated"
+  | "bucket_tags_changed"
+  | "ai_search_sync_complete"
   | "job_failed"
-  | "job_completed";
+  | "job_completed"
+  | "job_cancelled"
+  | "rate_limit_exceeded";
+
+/**
+ * Body format sent to a webhook: the raw payload JSON, a chat message for
+ * Slack, Discord or Microsoft Teams, or a user-defined JSON template
+ */
+export type WebhookFormat = "raw" | "slack" | "discord" | "teams" | "custom";
+
+/**
+ * Conditions a webhook applies on top of its event subscriptions. Bucket,
+ * key and user entries are globs (* and ?); sizes are in bytes.
+ */
+export interface WebhookFilters {
+  buckets?: string[];
+  bucketTags?: string[];
+  keyPatterns?: string[];
+  minSize?: number;
+  maxSize?: number;
+  userEmails?: string[];
+}
 
 /**
  * Webhook configuration stored in database
@@ -578,8 +1078,13 @@ export interface Webhook {
   name: string;
   url: string;
   secret: string | null;
+  previous_secret: string | null; // Still signs requests during rotation
+  previous_secret_expires_at: string | null;
   events: string; // JSON array of WebhookEventType
   enabled: number; // SQLite boolean (0 or 1)
+  format: WebhookFormat;
+  template: string | null; // Only used by the custom format
+  filters: string | null; // JSON WebhookFilters, null if unfiltered
   created_at: string;
   updated_at: string;
 }
@@ -593,6 +1098,29 @@ export interface WebhookInput {
   secret?: string | null;
   events: WebhookEventType[];
   enabled?: boolean;
+  format?: WebhookFormat;
+  template?: string | null;
+  filters?: WebhookFilters | null;
+}
+
+/**
+ * Webhook test request body. format and template override the saved ones,
+ * so unsaved changes can be previewed, and dryRun renders the body without
+ * sending it.
+ */
+export interface WebhookTestInput {
+  format?: WebhookFormat;
+  template?: string | null;
+  dryRun?: boolean;
+}
+
+/**
+ * Webhook secret rotation request body. Without a secret a random one is
+ * generated; the old secret keeps signing requests for gracePeriodHours.
+ */
+export interface WebhookRotateSecretInput {
+  secret?: string;
+  gracePeriodHours?: number;
 }
 
 /**
@@ -603,6 +1131,7 @@ export interface WebhookTestResult {
   message: string;
   statusCode?: number;
   error?: string;
+  body?: string; // Rendered body that was (or would be) sent
 }
 
 /**
@@ -621,6 +1150,34 @@ export interface WebhookResult {
   success: boolean;
   statusCode?: number;
   error?: string;
+  latencyMs?: number;
+  responseBody?: string;
+}
+
+/**
+ * Why a webhook delivery attempt was made
+ */
+export type WebhookDeliveryReason = "event" | "retry" | "redeliver";
+
+/**
+ * One attempt to deliver an event to a webhook. Attempts of the same event
+ * share a delivery_id.
+ */
+export interface WebhookDelivery {
+  id: string;
+  delivery_id: string;
+  webhook_id: string;
+  event: WebhookEventType;
+  payload: string;
+  attempt: number;
+  reason: WebhookDeliveryReason;
+  status: "succeeded" | "failed";
+  status_code: number | null;
+  latency_ms: number | null;
+  response_body: string | null;
+  error: string | null;
+  next_retry_at: string | null;
+  created_at: string;
 }
 
 /**
@@ -735,3 +1292,167 @@ export interface LifecycleRulesResponse {
   rules: LifecycleRule[];
   bucketName: string;
 }
+
+// ============================================
+// Bucket Settings Types (Cloudflare REST API format)
+// ============================================
+
+/**
+ * Default storage class of new objects in a bucket
+ */
+export type R2StorageClass = "Standard" | "InfrequentAccess";
+
+/**
+ * Location hints accepted when creating a bucket
+ */
+export type R2LocationHint = "apac" | "eeur" | "enam" | "weur" | "wnam" | "oc";
+
+/**
+ * Request body for creating a bucket
+ */
+export interface CreateBucketBody {
+  name: string;
+  locationHint?: R2LocationHint;
+  storageClass?: R2StorageClass;
+}
+
+export type CorsMethod = "GET" | "PUT" | "POST" | "DELETE" | "HEAD";
+
+/**
+ * Single bucket CORS rule
+ */
+export interface BucketCorsRule {
+  id?: string;
+  allowed: {
+    origins: string[];
+    methods: CorsMethod[];
+    headers?: string[];
+  };
+  exposeHeaders?: string[];
+  maxAgeSeconds?: number;
+}
+
+/**
+ * Bucket CORS policy - collection of rules
+ */
+export interface BucketCorsPolicy {
+  rules: BucketCorsRule[];
+}
+
+/**
+ * Custom domain attached to a bucket
+ */
+export interface CustomDomain {
+  domain: string;
+  enabled: boolean;
+  status: {
+    ownership: string;
+    ssl: string;
+  };
+  minTLS?: string;
+  zoneId?: string;
+  zoneName?: string;
+}
+
+/**
+ * Request body for attaching a custom domain. The zone is looked up from
+ * the domain name when zoneId is omitted.
+ */
+export interface AttachCustomDomainBody {
+  domain: string;
+  zoneId?: string;
+  minTLS?: "1.0" | "1.1" | "1.2" | "1.3";
+}
+
+/**
+ * Managed r2.dev public access of a bucket
+ */
+export interface ManagedDomain {
+  bucketId: string;
+  domain: string;
+  enabled: boolean;
+}
+
+/**
+ * How long a bucket lock rule keeps objects: for a number of seconds after
+ * upload, until a date, or indefinitely
+ */
+export type BucketLockCondition =
+  | { type: "Age"; maxAgeSeconds: number }
+  | { type: "Date"; date: string }
+  | { type: "Indefinite" };
+
+/**
+ * Bucket lock (WORM retention) rule. Objects under the prefix cannot be
+ * deleted or overwritten while the condition holds.
+ */
+export interface BucketLockRule {
+  id: string;
+  enabled: boolean;
+  prefix?: string;
+  condition: BucketLockCondition;
+}
+
+/**
+ * Bucket lock configuration - collection of rules
+ */
+export interface BucketLockConfiguration {
+  rules: BucketLockRule[];
+}
+
+// ============================================
+// Event Notification Types (Cloudflare REST API format)
+// ============================================
+
+/**
+ * Object actions that can trigger an event notification
+ */
+export type EventNotificationAction =
+  | "PutObject"
+  | "CopyObject"
+  | "DeleteObject"
+  | "CompleteMultipartUpload"
+  | "LifecycleDeletion";
+
+/**
+ * Event notification rule as returned by the Cloudflare API
+ */
+export interface EventNotificationRule {
+  ruleId: string;
+  actions: EventNotificationAction[];
+  prefix?: string;
+  suffix?: string;
+  description?: string;
+  createdAt?: string;
+}
+
+/**
+ * Event notification configuration of a bucket, grouped by queue
+ */
+export interface EventNotificationConfiguration {
+  bucketName: string;
+  queues: {
+    queueId: string;
+    queueName: string;
+    rules: EventNotificationRule[];
+  }[];
+}
+
+/**
+ * Event notification rule with the queue it sends to
+ */
+export interface BucketEventNotificationRule extends EventNotificationRule {
+  queueId: string;
+  queueName: string;
+}
+
+/**
+ * Request body for creating an event notification rule
+ */
+export interface CreateEventNotificationBody {
+  queueId: string;
+  actions: EventNotificationAction[];
+  prefix?: string;
+  suffix?: string;
+  description?: string;
+}
@@ -0,0 +1,199 @@
+/**
+ * API Tokens
+ *
+ * Personal/service tokens for clients that cannot sign in through Cloudflare
+ * Access (CI pipelines, scripts). A token is sent as
+ * "Authorization: Bearer r2m_..." and acts as the user who created it,
+ * limited to the token's buckets and permissions. The creator's IdP groups
+ * are recorded when the token is created. Only a SHA-256 hash of the
+ * token is stored; the token itself is shown once, when it is created.
+ *
+ * For the S3-compatible endpoint a token also has SigV4 credentials: the
+ * access key ID is the token ID and the secret access key is an HMAC of the
+ * token ID under URL_SIGNING_KEY, so it can be checked without storing it.
+ */
+
+import type {
+  AccessIdentity,
+  ApiToken,
+  ApiTokenPermission,
+  ApiTokenScope,
+  ApiTokenStatus,
+  Env,
+} from "../types";
+import { logInfo } from "./error-logger";
+import { getSigningKey } from "./signing";
+
+/** Default lifetime of a token (90 days) */
+export const DEFAULT_API_TOKEN_TTL_SECONDS = 90 * 24 * 60 * 60;
+
+/** Longest lifetime a token may be given (1 year) */
+export const MAX_API_TOKEN_TTL_SECONDS = 365 * 24 * 60 * 60;
+
+export const API_TOKEN_PERMISSIONS: ApiTokenPermission[] = [
+  "read",
+  "write",
+  "delete",
+];
+
+const TOKEN_PREFIX = "r2m_";
+
+/** How often last_used_at is updated for a busy token */
+const LAST_USED_UPDATE_INTERVAL_MS = 60 * 1000;
+
+const encoder = new TextEncoder();
+
+function toHex(bytes: Uint8Array): string {
+  return Array.from(bytes)
+    .map((b) => b.toString(16).padStart(2, "0"))
+    .join("");
+}
+
+function randomHex(length: number): string {
+  const bytes = new Uint8Array(length);
+  crypto.getRandomValues(bytes);
+  return toHex(bytes);
+}
+
+/**
+ * Generate a token ID and the secret token for a new API token
+ */
+export function generateApiToken(): { tokenId: string; token: string } {
+  return {
+    tokenId: "tok_" + randomHex(8),
+    token: TOKEN_PREFIX + randomHex(32),
+  };
+}
+
+/**
+ * Hash a token for storage and lookup. Tokens are 256-bit random values, so
+ * a plain SHA-256 hash is enough.
+ */
+export async function hashApiToken(token: string): Promise<string> {
+  const digest = await crypto.subtle.digest("SHA-256", encoder.encode(token));
+  return toHex(new Uint8Array(digest));
+}
+
+export function getApiTokenStatus(
+  token: Pick<ApiToken, "expires_at" | "revoked_at">,
+  now = Date.now(),
+): ApiTokenStatus {
+  if (token.revoked_at !== null) return "revoked";
+  if (new Date(token.expires_at).getTime() <= now) return "expired";
+  return "active";
+}
+
+/**
+ * Decode the stored bucket list and permissions of a token
+ */
+export function getApiTokenScope(token: ApiToken): ApiTokenScope {
+  return {
+    tokenId: token.token_id,
+    name: token.name,
+    buckets:
+      token.buckets !== null ? (JSON.parse(token.buckets) as string[]) : null,
+    permissions: token.permissions
+      .split(",")
+      .filter((permission): permission is ApiTokenPermission =>
+        API_TOKEN_PERMISSIONS.includes(permission as ApiTokenPermission),
+      ),
+  };
+}
+
+/**
+ * The bearer token sent with a request, if it looks like an API token
+ */
+export function getBearerApiToken(request: Request): string | null {
+  const header = request.headers.get("Authorization") ?? "";
+  const match = /^Bearer\s+(\S+)$/i.exec(header);
+  const token = match?.[1];
+  return token?.startsWith(TOKEN_PREFIX) === true ? token : null;
+}
+
+/**
+ * Secret access key of a token's S3 (SigV4) credentials
+ */
+export async function getS3SecretKey(
+  tokenId: string,
+  env: Env,
+): Promise<string> {
+  const key = await crypto.subtle.importKey(
+    "raw",
+    encoder.encode(getSigningKey(env)),
+    { name: "HMAC", hash: "SHA-256" },
+    false,
+    ["sign"],
+  );
+  const signature = await crypto.subtle.sign(
+    "HMAC",
+    key,
+    encoder.encode("s3:" + tokenId),
+  );
+  return toHex(new Uint8Array(signature));
+}
+
+/**
+ * Look up an API token. Returns the identity it acts as, or null if the token
+ * is unknown, expired or revoked.
+ */
+export async function validateApiToken(
+  token: string,
+  env: Env,
+): Promise<AccessIdentity | null> {
+  return findActiveApiToken(env, "token_hash", await hashApiToken(token));
+}
+
+/**
+ * Look up an API token by ID, for requests signed with its S3 credentials
+ * (the signature must already have been verified)
+ */
+export async function validateApiTokenId(
+  tokenId: string,
+  env: Env,
+): Promise<AccessIdentity | null> {
+  return findActiveApiToken(env, "token_id", tokenId);
+}
+
+async function findActiveApiToken(
+  env: Env,
+  column: "token_hash" | "token_id",
+  value: string,
+): Promise<AccessIdentity | null> {
+  const db = env.METADATA;
+  if (!db) {
+    return null;
+  }
+
+  const record = await db
+    .prepare(`SELECT * FROM api_tokens WHERE ${column} = ?`)
+    .bind(value)
+    .first<ApiToken>();
+  if (record === null || getApiTokenStatus(record) !== "active") {
+    return null;
+  }
+
+  const now = new Date();
+  const lastUsed =
+    record.last_used_at !== null ? new Date(record.last_used_at).getTime() : 0;
+  if (now.getTime() - lastUsed > LAST_USED_UPDATE_INTERVAL_MS) {
+    await db
+      .prepare("UPDATE api_tokens SET last_used_at = ? WHERE token_id = ?")
+      .bind(now.toISOString(), record.token_id)
+      .run();
+  }
+
+  logInfo(`API token validated for user: ${record.created_by}`, {
+    module: "tokens",
+    operation: "validate",
+    userId: record.created_by,
+    metadata: { tokenId: record.token_id },
+  });
+
+  // Group membership cannot be rechecked without the creator's IdP session,
+  // so tokens only carry the creator's own grants
+  return {
+    email: record.created_by,
+    groups: [],
+    token: getApiTokenScope(record),
+  };
+}
@@ -1,16 +1,130 @@
-import type { Env } from "../types";
-import { logInfo, logError } from "./error-logger";
+import type { AccessIdentity, Env } from "../types";
+import { logInfo, logError, logWarning } from "./error-logger";
 
 interface JWTPayload {
   email?: string;
   [key: string]: unknown;
 }
 
+const DEFAULT_GROUP_CLAIMS = ["groups"];
+
+/** get-identity responses cached per token, to avoid a fetch per request */
+const IDENTITY_CACHE_TTL_MS = 5 * 60 * 1000;
+const IDENTITY_CACHE_MAX_ENTRIES = 500;
+const identityCache = new Map<
+  string,
+  { groups: string[]; expiresAt: number }
+>();
+
+/**
+ * Claims to read groups from, e.g. "groups" or "custom.groups"
+ */
+function getGroupClaims(env: Env): string[] {
+  const claims = (env.ACCESS_GROUP_CLAIMS ?? "")
+    .split(",")
+    .map((claim) => claim.trim())
+    .filter((claim) => claim !== "");
+  return claims.length > 0 ? claims : DEFAULT_GROUP_CLAIMS;
+}
+
+/**
+ * Read a (possibly dotted) claim path from a JWT or get-identity payload
+ */
+function readClaim(payload: Record<string, unknown>, path: string): unknown {
+  let value: unknown = payload;
+  for (const segment of path.split(".")) {
+    if (value === null || typeof value !== "object") {
+      return undefined;
+    }
+    value = (value as Record<string, unknown>)[segment];
+  }
+  return value;
+}
+
+/**
+ * Normalize a group claim. IdPs send arrays of names, arrays of objects
+ * ({ id, name } from get-identity) or a single comma-separated string.
+ */
+function parseGroupClaim(value: unknown): string[] {
+  const entries = typeof value === "string" ? value.split(",") : value;
+  if (!Array.isArray(entries)) {
+    return [];
+  }
+
+  const groups: string[] = [];
+  for (const entry of entries as unknown[]) {
+    if (typeof entry === "string") {
+      groups.push(entry.trim());
+    } else if (entry !== null && typeof entry === "object") {
+      const { name, id } = entry as { name?: unknown; id?: unknown };
+      if (typeof name === "string") groups.push(name.trim());
+      if (typeof id === "string") groups.push(id.trim());
+    }
+  }
+  return groups.filter((group) => group !== "");
+}
+
+function extractGroups(
+  payload: Record<string, unknown>,
+  claims: string[],
+): string[] {
+  return claims.flatMap((claim) => parseGroupClaim(readClaim(payload, claim)));
+}
+
+/**
+ * Fetch the user's groups from the Access get-identity endpoint. Group
+ * membership from some IdPs is only available there, not in the JWT.
+ */
+async function fetchIdentityGroups(
+  token: string,
+  env: Env,
+  claims: string[],
+): Promise<string[]> {
+  const cached = identityCache.get(token);
+  if (cached !== undefined && cached.expiresAt > Date.now()) {
+    return cached.groups;
+  }
+
+  try {
+    const response = await fetch(
+      `${env.TEAM_DOMAIN}/cdn-cgi/access/get-identity`,
+      { headers: { Cookie: `CF_Authorization=${token}` } },
+    );
+    if (!response.ok) {
+      logWarning(`get-identity returned ${String(response.status)}`, {
+        module: "auth",
+        operation: "get_identity",
+      });
+      return [];
+    }
+
+    const identity = (await response.json()) as Record<string, unknown>;
+    const groups = extractGroups(identity, claims);
+
+    if (identityCache.size >= IDENTITY_CACHE_MAX_ENTRIES) {
+      identityCache.clear();
+    }
+    identityCache.set(token, {
+      groups,
+      expiresAt: Date.now() + IDENTITY_CACHE_TTL_MS,
+    });
+    return groups;
+  } catch (error) {
+    void logError(
+      env,
+      error instanceof Error ? error : new Error(String(error)),
+      { module: "auth", operation: "get_identity" },
+      false,
+    );
+    return [];
+  }
+}
+
 // JWT validation for Cloudflare Access
 export async function validateAccessJWT(
   request: Request,
   env: Env,
-): Promise<string | null> {
+): Promise<AccessIdentity | null> {
   const token = request.headers.get("cf-access-jwt-assertion");
 
   if (token === null) {
@@ -45,12 +159,19 @@ export async function validateAccessJWT(
       return null;
     }
 
+    // Extract IdP groups from the configured claims
+    const claims = getGroupClaims(env);
+    const groups = extractGroups(typedPayload, claims);
+    if (env.ACCESS_IDENTITY_GROUPS === "true") {
+      groups.push(...(await fetchIdentityGroups(token, env, claims)));
+    }
+
     logInfo(`JWT validated for user: ${email}`, {
       module: "auth",
       operation: "validate",
-      metadata: { email },
+      metadata: { email, groups: groups.length },
     });
-    return email;
+    return { email, groups: [...new Set(groups)] };
   } catch (error) {
     void logError(
       env,
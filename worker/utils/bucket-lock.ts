@@ -0,0 +1,174 @@
+/**
+ * Bucket Lock
+ *
+ * R2 bucket lock rules keep objects under a prefix from being deleted or
+ * overwritten for a number of seconds after upload, until a date, or
+ * indefinitely. R2 enforces the rules itself; they are read here (and
+ * cached briefly) so that deletes and overwrites can be refused up front
+ * with the date the lock ends instead of failing upstream.
+ */
+
+import type {
+  BucketLockConfiguration,
+  BucketLockRule,
+  CloudflareApiResponse,
+  Env,
+} from "../types";
+import { CF_API } from "../types";
+import { createErrorResponse } from "./error-response";
+import { logWarning } from "./error-logger";
+import { getCloudflareHeaders } from "./helpers";
+import { getObjectStorage } from "./storage";
+
+/** Rules are read on every delete and overwrite, so cache them briefly */
+const RULES_CACHE_TTL = 60 * 1000;
+const rulesCache = new Map<
+  string,
+  { rules: BucketLockRule[]; timestamp: number }
+>();
+
+/**
+ * Lock that currently protects an object
+ */
+export interface ObjectLock {
+  key: string;
+  ruleId: string;
+  /** When the lock ends, or null if the object is locked indefinitely */
+  lockedUntil: Date | null;
+}
+
+export function invalidateBucketLockCache(bucketName: string): void {
+  rulesCache.delete(bucketName);
+}
+
+/**
+ * Enabled bucket lock rules of a bucket. Returns an empty list if the rules
+ * cannot be read, so that R2 remains the one enforcing them.
+ */
+export async function getBucketLockRules(
+  env: Env,
+  bucketName: string,
+): Promise<BucketLockRule[]> {
+  const cached = rulesCache.get(bucketName);
+  if (
+    cached !== undefined &&
+    Date.now() - cached.timestamp < RULES_CACHE_TTL
+  ) {
+    return cached.rules;
+  }
+
+  let rules: BucketLockRule[] = [];
+  try {
+    const response = await fetch(
+      `${CF_API}/accounts/${env.ACCOUNT_ID}/r2/buckets/${encodeURIComponent(bucketName)}/lock`,
+      { headers: getCloudflareHeaders(env) },
+    );
+    if (response.ok) {
+      const data =
+        (await response.json()) as CloudflareApiResponse<BucketLockConfiguration>;
+      rules = (data.result?.rules ?? []).filter((rule) => rule.enabled);
+    } else if (response.status !== 404) {
+      logWarning(`Could not read bucket lock rules: ${response.status}`, {
+        module: "bucket_settings",
+        operation: "get_lock_rules",
+        bucketName,
+      });
+    }
+  } catch (error) {
+    logWarning(`Could not read bucket lock rules: ${String(error)}`, {
+      module: "bucket_settings",
+      operation: "get_lock_rules",
+      bucketName,
+    });
+  }
+
+  rulesCache.set(bucketName, { rules, timestamp: Date.now() });
+  return rules;
+}
+
+/**
+ * The lock that protects an object the longest, or null if no rule
+ * protects it now
+ */
+export function findObjectLock(
+  rules: BucketLockRule[],
+  key: string,
+  uploaded: Date,
+  now = Date.now(),
+): ObjectLock | null {
+  let lock: ObjectLock | null = null;
+  for (const rule of rules) {
+    if (!key.startsWith(rule.prefix ?? "")) {
+      continue;
+    }
+
+    let lockedUntil: Date | null;
+    if (rule.condition.type === "Indefinite") {
+      lockedUntil = null;
+    } else if (rule.condition.type === "Age") {
+      lockedUntil = new Date(
+        uploaded.getTime() + rule.condition.maxAgeSeconds * 1000,
+      );
+    } else {
+      lockedUntil = new Date(rule.condition.date);
+    }
+    if (lockedUntil !== null && lockedUntil.getTime() <= now) {
+      continue;
+    }
+
+    if (
+      lock === null ||
+      (lock.lockedUntil !== null &&
+        (lockedUntil === null || lockedUntil > lock.lockedUntil))
+    ) {
+      lock = { key, ruleId: rule.id, lockedUntil };
+    }
+  }
+  return lock;
+}
+
+/**
+ * The lock that keeps an object from being deleted or overwritten, or null
+ * if it is not locked (or does not exist)
+ */
+export async function checkObjectLock(
+  env: Env,
+  bucketName: string,
+  key: string,
+): Promise<ObjectLock | null> {
+  const rules = (await getBucketLockRules(env, bucketName)).filter((rule) =>
+    key.startsWith(rule.prefix ?? ""),
+  );
+  if (rules.length === 0) {
+    return null;
+  }
+
+  const object = await getObjectStorage(env, bucketName).head(key);
+  if (object === null) {
+    return null;
+  }
+  return findObjectLock(rules, key, new Date(object.uploaded));
+}
+
+/**
+ * Why a locked object cannot be deleted or overwritten
+ */
+export function describeObjectLock(lock: ObjectLock): string {
+  const until =
+    lock.lockedUntil !== null
+      ? `until ${lock.lockedUntil.toISOString()}`
+      : "indefinitely";
+  return `Object "${lock.key}" is retention-locked ${until} by bucket lock rule "${lock.ruleId}"`;
+}
+
+/**
+ * 409 response explaining which object is locked and until when
+ */
+export function createObjectLockedResponse(
+  lock: ObjectLock,
+  corsHeaders: HeadersInit,
+): Response {
+  return createErrorResponse(describeObjectLock(lock), corsHeaders, 409, {
+    code: "OBJECT_LOCKED",
+  });
+}
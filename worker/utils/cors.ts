@@ -24,7 +24,7 @@ export function getCorsHeaders(request: Request): CorsHeaders {
           : url.origin,
     "Access-Control-Allow-Methods": "GET, POST, DELETE, OPTIONS, PATCH",
     "Access-Control-Allow-Headers":
-      "Content-Type, Authorization, X-File-Name, X-Chunk-Index, X-Total-Chunks, cf-access-jwt-assertion",
+      "Content-Type, Authorization, X-File-Name, X-Chunk-Index, X-Total-Chunks, X-Chunk-MD5, cf-access-jwt-assertion",
     "Access-Control-Allow-Credentials": "true",
     Vary: "Origin", // Important for caching with different origins
   };
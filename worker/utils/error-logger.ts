@@ -40,6 +40,15 @@ const ERROR_CODE_PREFIXES: Record<string, string> = {
   helpers: "HELP",
   ratelimit: "RATE",
   signing: "SIGN",
+  shares: "SHR",
+  access: "ACL",
+  tokens: "TOK",
+  s3_api: "S3API",
+  trash: "TRSH",
+  bucket_settings: "BSET",
+  event_notifications: "EVNT",
+  maintenance: "MNT",
+  storage: "STOR",
 };
 
 /**
@@ -1,11 +1,6 @@
-import type { Env, CloudflareApiResponse } from "../types";
+import type { Env } from "../types";
 import { logError } from "./error-logger";
-import { CF_API } from "../types";
-
-interface R2ObjectWithSize {
-  key: string;
-  size: number;
-}
+import { getObjectStorage } from "./storage";
 
 /**
  * Bucket statistics returned by getBucketStats
@@ -32,7 +27,7 @@ export async function getBucketStats(
   bucketName: string,
   env: Env,
 ): Promise<BucketStats> {
-  const cfHeaders = getCloudflareHeaders(env);
+  const storage = getObjectStorage(env, bucketName);
 
   let totalSize = 0;
   let objectCount = 0;
@@ -40,48 +35,19 @@ export async function getBucketStats(
   let hasMore = true;
 
   while (hasMore) {
-    let apiUrl =
-      CF_API +
-      "/accounts/" +
-      env.ACCOUNT_ID +
-      "/r2/buckets/" +
-      bucketName +
-      "/objects?limit=100";
-
-    if (cursor !== undefined) {
-      apiUrl += "&cursor=" + cursor;
-    }
-
     try {
-      const response = await fetch(apiUrl, { headers: cfHeaders });
-
-      if (!response.ok) {
-        void logError(
-          env,
-          new Error("Failed to list objects: " + String(response.status)),
-          { module: "helpers", operation: "bucket_stats", bucketName },
-          false,
-        );
-        return { size: 0, objectCount: 0 };
-      }
-
-      const data = (await response.json()) as CloudflareApiResponse<
-        R2ObjectWithSize[]
-      >;
-      const fileList: R2ObjectWithSize[] = Array.isArray(data.result)
-        ? data.result
-        : [];
+      const page = await storage.list({ cursor, limit: 1000 });
 
-      for (const obj of fileList) {
+      for (const obj of page.objects) {
         totalSize += obj.size;
         objectCount++;
       }
 
-      cursor = data.result_info?.cursor as string | undefined;
-      hasMore = data.result_info?.is_truncated ?? false;
+      cursor = page.cursor;
+      hasMore = page.truncated;
 
-      // Add small delay to avoid rate limiting
-      if (hasMore) {
+      // Add small delay to avoid rate limiting (REST API only)
+      if (hasMore && !storage.isNative) {
         await new Promise((resolve) => setTimeout(resolve, 100));
       }
     } catch (err) {
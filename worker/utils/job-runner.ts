@@ -0,0 +1,1026 @@
+/**
+ * Background Job Runner
+ *
+ * Executes long-running folder and bucket operations (copy, move, rename,
+ * delete) in resumable batches. Job state lives in bulk_jobs and job_tasks,
+ * and the JOB_QUEUE consumer drives it: each message processes batches until
+ * its time budget is spent, saves the list cursor and re-enqueues the job.
+ * Without a queue binding (or without D1) the same batches run inline within
+ * the request, as bulk operations did before.
+ *
+ * Before every batch the runner re-reads the job status, so cancelling or
+ * pausing a job (POST /api/jobs/:id/cancel, /pause) takes effect within one
+ * batch. Failed items are recorded in job_items and can be replayed with
+ * /retry-failed.
+ */
+
+import type {
+  Env,
+  JobItem,
+  JobQueueMessage,
+  JobStatus,
+  JobTask,
+  JobTaskParams,
+  JobTaskPhase,
+  JobTaskType,
+} from "../types";
+import { CF_API } from "../types";
+import { getCloudflareHeaders } from "./helpers";
+import { copyObject, getObjectStorage } from "./storage";
+import {
+  deleteOrTrashObject,
+  trashBeforeOverwrite,
+  updateBucketTrash,
+} from "./trash";
+import { checkObjectLock, describeObjectLock } from "./bucket-lock";
+import { logError, logInfo, logWarning } from "./error-logger";
+import { createErrorResponse } from "./error-response";
+import {
+  triggerWebhooks,
+  createBucketRenamePayload,
+  createFolderDeletePayload,
+  createFolderTransferPayload,
+  createJobCompletedPayload,
+  createJobFailedPayload,
+  retryWebhookDelivery,
+} from "./webhooks";
+import {
+  createJob,
+  completeJob,
+  generateJobId,
+  getJobStatus,
+  logJobEvent,
+  recordJobItem,
+  updateJobProgress,
+} from "../routes/jobs";
+import { logAuditEvent } from "../routes/audit";
+
+/** Objects listed (and processed) per batch */
+const BATCH_SIZE = 100;
+/** Objects listed per page while counting */
+const COUNT_PAGE_SIZE = 1000;
+/** Wall-clock time a queue message may spend before re-enqueueing the job */
+const QUEUE_TIME_BUDGET_MS = 25_000;
+/** Delay between batches for REST-backed buckets to avoid rate limiting */
+const REST_BATCH_DELAY_MS = 300;
+/** Consecutive failed queue invocations before the job is marked failed */
+const MAX_JOB_ATTEMPTS = 5;
+const RETRY_DELAY_SECONDS = 30;
+
+/** Jobs that copy objects and then delete the source */
+const MOVE_TASKS: readonly JobTaskType[] = [
+  "folder_move",
+  "folder_rename",
+  "bucket_rename",
+];
+
+/** Jobs that only delete objects */
+const DELETE_TASKS: readonly JobTaskType[] = ["folder_delete", "bucket_delete"];
+
+/** Audit log metadata keys used for each job type's counts */
+const AUDIT_COUNT_KEYS: Record<JobTaskType, [string, string]> = {
+  folder_copy: ["filesCopied", "filesFailed"],
+  folder_move: ["filesMoved", "filesFailed"],
+  folder_rename: ["filesCopied", "filesFailed"],
+  folder_delete: ["filesDeleted", "filesFailed"],
+  bucket_delete: ["objectsDeleted", "objectsFailed"],
+  bucket_rename: ["objectsCopied", "objectsFailed"],
+};
+
+interface JobRunState {
+  jobId: string;
+  taskType: JobTaskType;
+  params: JobTaskParams;
+  userEmail: string;
+  phase: JobTaskPhase;
+  cursor: string | undefined;
+  totalItems: number | null;
+  processedItems: number;
+  errorCount: number;
+}
+
+interface JobOutcome {
+  status: "completed" | "failed";
+  errorMessage?: string | undefined;
+}
+
+export interface StartJobOptions {
+  taskType: JobTaskType;
+  params: JobTaskParams;
+  userEmail: string;
+  isLocalDev: boolean;
+}
+
+export interface JobRunResult {
+  jobId: string;
+  /** True when the job was handed to the queue and is still running */
+  queued: boolean;
+  status: JobStatus;
+  processedItems: number;
+  errorCount: number;
+  errorMessage?: string | undefined;
+}
+
+/**
+ * Start a background job. With a queue and D1 available the job is
+ * persisted and enqueued; otherwise it runs to completion before returning.
+ */
+export async function startJob(
+  env: Env,
+  options: StartJobOptions,
+): Promise<JobRunResult> {
+  const db = env.METADATA;
+  const queue = env.JOB_QUEUE;
+  const jobId = generateJobId(options.taskType);
+
+  const state: JobRunState = {
+    jobId,
+    taskType: options.taskType,
+    params: options.params,
+    userEmail: options.userEmail,
+    phase: "count",
+    cursor: undefined,
+    totalItems: null,
+    processedItems: 0,
+    errorCount: 0,
+  };
+
+  let persisted = false;
+  if (db) {
+    try {
+      await createJob(db, {
+        jobId,
+        bucketName: options.params.sourceBucket,
+        operationType: options.taskType,
+        status: queue ? "queued" : "running",
+        userEmail: options.userEmail,
+        metadata: { ...options.params },
+      });
+      await db
+        .prepare(
+          "INSERT INTO job_tasks (job_id, task_type, params, phase, updated_at) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(
+          jobId,
+          options.taskType,
+          JSON.stringify(options.params),
+          state.phase,
+          new Date().toISOString(),
+        )
+        .run();
+      persisted = true;
+    } catch (error) {
+      // Older schemas reject the new operation types or lack job_tasks
+      logWarning(
+        `Job tracking unavailable, running ${options.taskType} inline - apply migrations: ${String(error)}`,
+        {
+          module: "jobs",
+          operation: "start_job",
+          metadata: { jobId },
+        },
+      );
+    }
+  }
+
+  if (persisted && queue) {
+    await queue.send({ jobId });
+    logInfo(`Queued ${options.taskType} job`, {
+      module: "jobs",
+      operation: "start_job",
+      bucketName: options.params.sourceBucket,
+      metadata: { jobId },
+    });
+    return {
+      jobId,
+      queued: true,
+      status: "queued",
+      processedItems: 0,
+      errorCount: 0,
+    };
+  }
+
+  const progress = await advanceJob(env, state, {
+    persist: persisted,
+    deadline: null,
+    isLocalDev: options.isLocalDev,
+  });
+
+  if (progress === "cancelled" || progress === "paused") {
+    return {
+      jobId,
+      queued: false,
+      status: progress,
+      processedItems: state.processedItems,
+      errorCount: state.errorCount,
+    };
+  }
+
+  const outcome = await finishJob(env, state, persisted, options.isLocalDev);
+  return {
+    jobId,
+    queued: false,
+    status: outcome.status,
+    processedItems: state.processedItems,
+    errorCount: state.errorCount,
+    errorMessage: outcome.errorMessage,
+  };
+}
+
+/**
+ * Queue consumer for JOB_QUEUE. Each message carries a job ID; failed
+ * invocations are retried with a delay until MAX_JOB_ATTEMPTS is reached.
+ * Messages for webhook delivery retries are handed to the webhook sender,
+ * which schedules its own next retry.
+ */
+export async function handleJobQueue(
+  batch: MessageBatch<JobQueueMessage>,
+  env: Env,
+): Promise<void> {
+  for (const message of batch.messages) {
+    if ("webhookDeliveryId" in message.body) {
+      await retryWebhookDelivery(env, message.body.webhookDeliveryId).catch(
+        (error: unknown) =>
+          logWarning(`Webhook retry failed: ${String(error)}`, {
+            module: "webhooks",
+            operation: "retry",
+          }),
+      );
+      message.ack();
+      continue;
+    }
+
+    const { jobId } = message.body;
+    try {
+      await processQueuedJob(env, jobId);
+      message.ack();
+    } catch (error) {
+      void logError(
+        env,
+        error instanceof Error ? error : new Error(String(error)),
+        { module: "jobs", operation: "process_job", metadata: { jobId } },
+        false,
+      );
+
+      const attempts = await recordFailedAttempt(env, jobId);
+      if (attempts >= MAX_JOB_ATTEMPTS) {
+        await failJob(env, jobId, String(error));
+        message.ack();
+      } else {
+        message.retry({ delaySeconds: RETRY_DELAY_SECONDS * attempts });
+      }
+    }
+  }
+}
+
+/**
+ * Run one queue invocation of a job: process batches until the time budget
+ * is spent, then re-enqueue or finish the job.
+ */
+async function processQueuedJob(env: Env, jobId: string): Promise<void> {
+  const db = env.METADATA;
+  if (!db) {
+    logWarning("Received queued job without a METADATA database", {
+      module: "jobs",
+      operation: "process_job",
+      metadata: { jobId },
+    });
+    return;
+  }
+
+  const progress = await runStoredJob(env, db, jobId, {
+    deadline: Date.now() + QUEUE_TIME_BUDGET_MS,
+    isLocalDev: false,
+  });
+
+  if (progress === "yielded") {
+    if (!env.JOB_QUEUE) {
+      throw new Error("JOB_QUEUE binding is required to continue the job");
+    }
+    await env.JOB_QUEUE.send({ jobId });
+  }
+}
+
+/**
+ * Continue a resumed or retried job: hand it back to the queue, or run it
+ * inline when no queue is bound. Returns true if the job was queued.
+ */
+export async function continueJob(
+  env: Env,
+  jobId: string,
+  isLocalDev: boolean,
+): Promise<boolean> {
+  if (env.JOB_QUEUE) {
+    await env.JOB_QUEUE.send({ jobId });
+    return true;
+  }
+
+  const db = env.METADATA;
+  if (db) {
+    await runStoredJob(env, db, jobId, { deadline: null, isLocalDev });
+  }
+  return false;
+}
+
+/**
+ * Load a persisted job and process it until it finishes, is stopped, or
+ * (with a deadline) needs another invocation
+ */
+async function runStoredJob(
+  env: Env,
+  db: D1Database,
+  jobId: string,
+  options: { deadline: number | null; isLocalDev: boolean },
+): Promise<"done" | "yielded" | "stopped"> {
+  const row = await db
+    .prepare(
+      `
+    SELECT t.*, j.status, j.user_email, j.total_items,
+      j.processed_items, j.error_count
+    FROM job_tasks t JOIN bulk_jobs j ON j.job_id = t.job_id
+    WHERE t.job_id = ?
+  `,
+    )
+    .bind(jobId)
+    .first<
+      JobTask & {
+        status: JobStatus;
+        user_email: string;
+        total_items: number | null;
+        processed_items: number | null;
+        error_count: number | null;
+      }
+    >();
+
+  if (row === null) {
+    logWarning("Queued job not found", {
+      module: "jobs",
+      operation: "process_job",
+      metadata: { jobId },
+    });
+    return "stopped";
+  }
+
+  if (row.status !== "queued" && row.status !== "running") {
+    logInfo(`Skipping ${row.status} job`, {
+      module: "jobs",
+      operation: "process_job",
+      metadata: { jobId },
+    });
+    return "stopped";
+  }
+
+  if (row.status === "queued") {
+    await db
+      .prepare(
+        "UPDATE bulk_jobs SET status = 'running' WHERE job_id = ? AND status = 'queued'",
+      )
+      .bind(jobId)
+      .run();
+    await logJobEvent(db, {
+      jobId,
+      eventType: "started",
+      userEmail: row.user_email,
+      details: { phase: row.phase },
+    });
+  }
+
+  const state: JobRunState = {
+    jobId,
+    taskType: row.task_type,
+    params: JSON.parse(row.params) as JobTaskParams,
+    userEmail: row.user_email,
+    phase: row.phase,
+    cursor: row.cursor ?? undefined,
+    totalItems: row.total_items,
+    processedItems: row.processed_items ?? 0,
+    errorCount: row.error_count ?? 0,
+  };
+
+  const progress = await advanceJob(env, state, {
+    persist: true,
+    deadline: options.deadline,
+    isLocalDev: options.isLocalDev,
+  });
+
+  if (progress === "cancelled" || progress === "paused") {
+    logInfo(`Job was ${progress}, stopping`, {
+      module: "jobs",
+      operation: "process_job",
+      metadata: { jobId, processed: state.processedItems },
+    });
+    return "stopped";
+  }
+
+  if (progress === "yielded") {
+    return "yielded";
+  }
+
+  await finishJob(env, state, true, options.isLocalDev);
+  return "done";
+}
+
+/**
+ * Process batches until the job reaches its final phase, is cancelled or
+ * paused, or runs past the deadline
+ */
+async function advanceJob(
+  env: Env,
+  state: JobRunState,
+  options: { persist: boolean; deadline: number | null; isLocalDev: boolean },
+): Promise<"done" | "yielded" | "cancelled" | "paused"> {
+  const db = options.persist ? env.METADATA : undefined;
+  const storage = getObjectStorage(env, state.params.sourceBucket);
+
+  for (;;) {
+    // A job that is no longer running was cancelled or paused, or was
+    // resumed before this invocation noticed the pause and is now owned by
+    // a newer one
+    const status = db ? await getJobStatus(db, state.jobId) : "running";
+    if (status !== "running") {
+      return status === "cancelled" ? "cancelled" : "paused";
+    }
+    if (state.phase === "finalize") {
+      return "done";
+    }
+
+    await runJobBatch(env, state, db, options.isLocalDev);
+
+    if (db) {
+      await saveJobState(db, state);
+    }
+
+    if (state.phase === "finalize") {
+      return "done";
+    }
+    if (options.deadline !== null && Date.now() >= options.deadline) {
+      return "yielded";
+    }
+    if (!storage.isNative) {
+      await new Promise((resolve) => setTimeout(resolve, REST_BATCH_DELAY_MS));
+    }
+  }
+}
+
+/**
+ * Process a single list page for the job's current phase
+ */
+async function runJobBatch(
+  env: Env,
+  state: JobRunState,
+  db: D1Database | undefined,
+  isLocalDev: boolean,
+): Promise<void> {
+  if (state.phase === "retry") {
+    await runRetryBatch(env, state, db, isLocalDev);
+    return;
+  }
+
+  const { params } = state;
+  const storage = getObjectStorage(env, params.sourceBucket);
+  const isCounting = state.phase === "count";
+
+  const listData = await storage.list({
+    prefix: params.sourcePrefix === "" ? undefined : params.sourcePrefix,
+    cursor: state.cursor,
+    limit: isCounting ? COUNT_PAGE_SIZE : BATCH_SIZE,
+  });
+  const objects = listData.objects;
+  const nextCursor =
+    objects.length > 0 && listData.cursor !== undefined
+      ? listData.cursor
+      : undefined;
+
+  if (isCounting) {
+    state.totalItems = (state.totalItems ?? 0) + objects.length;
+    state.cursor = nextCursor;
+    if (nextCursor === undefined) {
+      state.phase = DELETE_TASKS.includes(state.taskType)
+        ? "delete"
+        : "transfer";
+    }
+    return;
+  }
+
+  for (const obj of objects) {
+    const error = await processJobItem(env, state, obj.key);
+
+    if (error === null) {
+      state.processedItems++;
+      continue;
+    }
+
+    state.errorCount++;
+    await recordItemFailure(env, state, db, obj.key, error, isLocalDev);
+  }
+
+  state.cursor = nextCursor;
+  if (nextCursor === undefined) {
+    state.phase = "finalize";
+  }
+}
+
+/**
+ * Replay one batch of the job's failed items (ordered by key, with the last
+ * replayed key as the cursor)
+ */
+async function runRetryBatch(
+  env: Env,
+  state: JobRunState,
+  db: D1Database | undefined,
+  isLocalDev: boolean,
+): Promise<void> {
+  if (!db) {
+    state.phase = "finalize";
+    return;
+  }
+
+  const items = await db
+    .prepare(
+      "SELECT object_key FROM job_items WHERE job_id = ? AND status = 'failed' AND object_key > ? ORDER BY object_key LIMIT ?",
+    )
+    .bind(state.jobId, state.cursor ?? "", BATCH_SIZE)
+    .all<Pick<JobItem, "object_key">>();
+
+  for (const { object_key: key } of items.results) {
+    const error = await processJobItem(env, state, key);
+
+    if (error === null) {
+      state.processedItems++;
+      state.errorCount = Math.max(0, state.errorCount - 1);
+      await recordJobItem(db, {
+        jobId: state.jobId,
+        objectKey: key,
+        status: "succeeded",
+      });
+      continue;
+    }
+
+    await recordItemFailure(env, state, db, key, error, isLocalDev);
+  }
+
+  const last = items.results[items.results.length - 1];
+  state.cursor = last?.object_key;
+  if (items.results.length < BATCH_SIZE) {
+    state.phase = "finalize";
+  }
+}
+
+/**
+ * Delete or transfer a single object depending on the job type
+ */
+async function processJobItem(
+  env: Env,
+  state: JobRunState,
+  key: string,
+): Promise<Error | null> {
+  return DELETE_TASKS.includes(state.taskType)
+    ? await deleteJobObject(env, state, key)
+    : await transferJobObject(env, state, key);
+}
+
+/**
+ * Log a failed item and record it in job_items so it can be listed and
+ * retried
+ */
+async function recordItemFailure(
+  env: Env,
+  state: JobRunState,
+  db: D1Database | undefined,
+  key: string,
+  error: Error,
+  isLocalDev: boolean,
+): Promise<void> {
+  void logError(
+    env,
+    error,
+    {
+      module: "jobs",
+      operation: state.taskType,
+      bucketName: state.params.sourceBucket,
+      fileName: key,
+      metadata: { jobId: state.jobId },
+    },
+    isLocalDev,
+  );
+
+  if (!db) return;
+
+  await recordJobItem(db, {
+    jobId: state.jobId,
+    objectKey: key,
+    status: "failed",
+    error,
+  });
+}
+
+/**
+ * Copy one object to its destination, deleting the source for moves.
+ * Returns the failure, or null on success. Retention-locked objects are
+ * checked here, one at a time, rather than before the job is queued.
+ */
+async function transferJobObject(
+  env: Env,
+  state: JobRunState,
+  key: string,
+): Promise<Error | null> {
+  const { params } = state;
+  const destBucket = params.destBucket ?? params.sourceBucket;
+  const destKey =
+    (params.destPrefix ?? "") + key.substring(params.sourcePrefix.length);
+
+  try {
+    // Moves delete the source, and any copy overwrites the destination
+    const lock =
+      (MOVE_TASKS.includes(state.taskType)
+        ? await checkObjectLock(env, params.sourceBucket, key)
+        : null) ?? (await checkObjectLock(env, destBucket, destKey));
+    if (lock !== null) {
+      return new Error(describeObjectLock(lock));
+    }
+
+    // An object already at the destination is kept in the trash, if enabled
+    await trashBeforeOverwrite(env, destBucket, destKey, state.userEmail);
+    const copied = await copyObject(
+      env,
+      params.sourceBucket,
+      key,
+      destBucket,
+      destKey,
+    );
+    if (copied === null) {
+      return new Error("Source object not found");
+    }
+
+    // Sources whose copy failed are kept so nothing is lost
+    if (MOVE_TASKS.includes(state.taskType)) {
+      await getObjectStorage(env, params.sourceBucket).delete(key);
+    }
+    return null;
+  } catch (error) {
+    return error instanceof Error ? error : new Error(String(error));
+  }
+}
+
+async function deleteJobObject(
+  env: Env,
+  state: JobRunState,
+  key: string,
+): Promise<Error | null> {
+  try {
+    const lock = await checkObjectLock(env, state.params.sourceBucket, key);
+    if (lock !== null) {
+      return new Error(describeObjectLock(lock));
+    }
+
+    // Folder deletes go to the trash if the bucket has it enabled; deleting
+    // a bucket removes its trash along with everything else
+    if (state.taskType === "folder_delete") {
+      await deleteOrTrashObject(
+        env,
+        state.params.sourceBucket,
+        key,
+        state.userEmail,
+      );
+    } else {
+      await getObjectStorage(env, state.params.sourceBucket).delete(key);
+    }
+    return null;
+  } catch (error) {
+    return error instanceof Error ? error : new Error(String(error));
+  }
+}
+
+/**
+ * Run the final step of a job, record audit events and webhooks, and mark
+ * the job as completed or failed (or leave it cancelled)
+ */
+async function finishJob(
+  env: Env,
+  state: JobRunState,
+  persisted: boolean,
+  isLocalDev: boolean,
+): Promise<Pick<JobRunResult, "status" | "errorMessage">> {
+  const { params } = state;
+  let outcome: JobOutcome = { status: "completed" };
+
+  if (
+    state.taskType === "bucket_delete" ||
+    state.taskType === "bucket_rename"
+  ) {
+    // Only delete the bucket once every object is gone
+    if (state.errorCount > 0) {
+      const action = state.taskType === "bucket_delete" ? "deleted" : "moved";
+      outcome = {
+        status: "failed",
+        errorMessage: `${String(state.errorCount)} object(s) could not be ${action}; bucket ${params.sourceBucket} was kept`,
+      };
+    } else {
+      outcome = await deleteBucket(env, params.sourceBucket);
+      if (outcome.status === "completed") {
+        await updateBucketTrash(
+          env,
+          params.sourceBucket,
+          state.taskType === "bucket_rename"
+            ? (params.destBucket ?? null)
+            : null,
+        );
+      }
+    }
+  }
+
+  logInfo(`Finished ${state.taskType} job`, {
+    module: "jobs",
+    operation: state.taskType,
+    bucketName: params.sourceBucket,
+    metadata: {
+      jobId: state.jobId,
+      status: outcome.status,
+      processed: state.processedItems,
+      errors: state.errorCount,
+    },
+  });
+
+  const [countKey, failedKey] = AUDIT_COUNT_KEYS[state.taskType];
+  await logAuditEvent(
+    env,
+    {
+      operationType: state.taskType,
+      bucketName: params.sourceBucket,
+      objectKey: params.sourcePrefix === "" ? undefined : params.sourcePrefix,
+      userEmail: state.userEmail,
+      status: outcome.status === "completed" ? "success" : "failed",
+      destinationBucket: params.destBucket,
+      destinationKey: params.destPrefix,
+      metadata: {
+        jobId: state.jobId,
+        [countKey]: state.processedItems,
+        [failedKey]: state.errorCount,
+        error: outcome.errorMessage,
+      },
+    },
+    isLocalDev,
+  );
+
+  // A job cancelled while its last batch ran stays cancelled, and none of
+  // the completion webhooks are sent. One paused meanwhile has finished its
+  // work, so it is completed.
+  const db = env.METADATA;
+  if (persisted && db) {
+    const recorded = await completeJob(db, {
+      jobId: state.jobId,
+      status: outcome.status,
+      processedItems: state.processedItems,
+      errorCount: state.errorCount,
+      userEmail: state.userEmail,
+      fromStatuses: ["running", "paused"],
+      ...(outcome.errorMessage !== undefined && {
+        errorMessage: outcome.errorMessage,
+      }),
+    });
+    if (!recorded) {
+      logInfo("Job was cancelled before it finished", {
+        module: "jobs",
+        operation: state.taskType,
+        metadata: { jobId: state.jobId },
+      });
+      return { status: "cancelled" };
+    }
+  }
+
+  if (outcome.status === "completed") {
+    if (state.taskType === "folder_delete") {
+      void triggerWebhooks(
+        env,
+        "folder_delete",
+        createFolderDeletePayload(
+          params.sourceBucket,
+          params.sourcePrefix,
+          state.processedItems,
+          state.userEmail,
+        ),
+        isLocalDev,
+      );
+    } else if (state.taskType === "bucket_rename") {
+      void triggerWebhooks(
+        env,
+        "bucket_rename",
+        createBucketRenamePayload(
+          params.sourceBucket,
+          params.destBucket ?? "",
+          state.userEmail,
+        ),
+        isLocalDev,
+      );
+    } else if (state.taskType !== "bucket_delete") {
+      // folder_copy, folder_move and folder_rename share an event payload
+      void triggerWebhooks(
+        env,
+        state.taskType,
+        createFolderTransferPayload(
+          params.sourceBucket,
+          params.sourcePrefix,
+          params.destBucket ?? params.sourceBucket,
+          params.destPrefix ?? "",
+          state.processedItems,
+          state.userEmail,
+        ),
+        isLocalDev,
+      );
+    }
+  }
+
+  if (persisted && db) {
+    if (outcome.status === "completed") {
+      void triggerWebhooks(
+        env,
+        "job_completed",
+        createJobCompletedPayload(
+          state.jobId,
+          state.taskType,
+          state.totalItems ?? state.processedItems,
+          state.processedItems,
+          state.errorCount,
+          params.sourceBucket,
+          state.userEmail,
+        ),
+        isLocalDev,
+      );
+    } else {
+      void triggerWebhooks(
+        env,
+        "job_failed",
+        createJobFailedPayload(
+          state.jobId,
+          state.taskType,
+          outcome.errorMessage ?? "Job failed",
+          params.sourceBucket,
+          state.userEmail,
+        ),
+        isLocalDev,
+      );
+    }
+  }
+
+  return outcome;
+}
+
+/**
+ * Delete an (emptied) bucket through the Cloudflare API
+ */
+async function deleteBucket(env: Env, bucketName: string): Promise<JobOutcome> {
+  const response = await fetch(
+    CF_API + "/accounts/" + env.ACCOUNT_ID + "/r2/buckets/" + bucketName,
+    { method: "DELETE", headers: getCloudflareHeaders(env) },
+  );
+
+  if (response.ok) {
+    return { status: "completed" };
+  }
+  return {
+    status: "failed",
+    errorMessage: `Failed to delete bucket ${bucketName} (status ${String(response.status)})`,
+  };
+}
+
+async function saveJobState(db: D1Database, state: JobRunState): Promise<void> {
+  await updateJobProgress(db, {
+    jobId: state.jobId,
+    processedItems: state.processedItems,
+    ...(state.totalItems !== null && { totalItems: state.totalItems }),
+    errorCount: state.errorCount,
+  });
+  await db
+    .prepare(
+      "UPDATE job_tasks SET phase = ?, cursor = ?, attempts = 0, updated_at = ? WHERE job_id = ?",
+    )
+    .bind(
+      state.phase,
+      state.cursor ?? null,
+      new Date().toISOString(),
+      state.jobId,
+    )
+    .run();
+}
+
+async function recordFailedAttempt(env: Env, jobId: string): Promise<number> {
+  const db = env.METADATA;
+  if (!db) return MAX_JOB_ATTEMPTS;
+
+  const task = await db
+    .prepare(
+      "UPDATE job_tasks SET attempts = attempts + 1, updated_at = ? WHERE job_id = ? RETURNING attempts",
+    )
+    .bind(new Date().toISOString(), jobId)
+    .first<{ attempts: number }>()
+    .catch(() => null);
+  return task?.attempts ?? MAX_JOB_ATTEMPTS;
+}
+
+async function failJob(env: Env, jobId: string, error: string): Promise<void> {
+  const db = env.METADATA;
+  if (!db) return;
+
+  const job = await db
+    .prepare(
+      "SELECT bucket_name, operation_type, user_email FROM bulk_jobs WHERE job_id = ?",
+    )
+    .bind(jobId)
+    .first<{
+      bucket_name: string;
+      operation_type: string;
+      user_email: string;
+    }>();
+  if (job === null) return;
+
+  const failed = await completeJob(db, {
+    jobId,
+    status: "failed",
+    userEmail: job.user_email,
+    errorMessage: error,
+    fromStatuses: ["queued", "running"],
+  });
+  if (!failed) return;
+
+  void triggerWebhooks(
+    env,
+    "job_failed",
+    createJobFailedPayload(
+      jobId,
+      job.operation_type,
+      error,
+      job.bucket_name,
+      job.user_email,
+    ),
+    false,
+  );
+}
+
+/**
+ * Mark running jobs that have made no progress since staleBefore as failed.
+ * Progress is the last save of the job's task; inline jobs (such as streaming
+ * ZIP downloads) have no task and are left alone. Returns the number of jobs
+ * failed.
+ */
+export async function failStaleJobs(
+  env: Env,
+  staleBefore: Date,
+): Promise<number> {
+  const db = env.METADATA;
+  if (!db) return 0;
+
+  const stale = await db
+    .prepare(
+      `
+    SELECT b.job_id FROM bulk_jobs b
+    JOIN job_tasks t ON t.job_id = b.job_id
+    WHERE b.status = 'running' AND t.updated_at < ?
+  `,
+    )
+    .bind(staleBefore.toISOString())
+    .all<{ job_id: string }>();
+
+  for (const job of stale.results) {
+    await failJob(
+      env,
+      job.job_id,
+      `Job made no progress since ${staleBefore.toISOString()}`,
+    );
+    logWarning(`Marked stale job as failed: ${job.job_id}`, {
+      module: "jobs",
+      operation: "fail_stale_job",
+      metadata: { jobId: job.job_id },
+    });
+  }
+  return stale.results.length;
+}
+
+/**
+ * Respond with the job ID (202 Accepted) for queued or paused jobs, or with
+ * the final counts when the job already ran inline
+ */
+export function createJobResponse(
+  result: JobRunResult,
+  inlineBody: Record<string, unknown>,
+  corsHeaders: HeadersInit,
+): Response {
+  if (result.status === "cancelled") {
+    return createErrorResponse("Job was cancelled", corsHeaders, 409, {
+      details: `${String(result.processedItems)} item(s) processed before cancellation`,
+    });
+  }
+
+  if (result.queued || result.status === "paused") {
+    return new Response(
+      JSON.stringify({ success: true, jobId: result.jobId, queued: true }),
+      {
+        status: 202,
+        headers: { "Content-Type": "application/json", ...corsHeaders },
+      },
+    );
+  }
+
+  return new Response(
+    JSON.stringify({ success: true, jobId: result.jobId, ...inlineBody }),
+    { headers: { "Content-Type": "application/json", ...corsHeaders } },
+  );
+}
@@ -0,0 +1,165 @@
+/**
+ * Scheduled Maintenance
+ *
+ * Tasks run by the cron-triggered scheduled() handler. Each task runs on its
+ * own, so one failing does not stop the others, and reports how many rows or
+ * jobs it handled. Retention and staleness limits come from optional vars:
+ * AUDIT_LOG_RETENTION_DAYS, JOB_EVENT_RETENTION_DAYS,
+ * WEBHOOK_DELIVERY_RETENTION_DAYS and STALE_JOB_MINUTES (0 turns the task
+ * off).
+ */
+
+import type { Env } from "../types";
+import { logError, logInfo } from "./error-logger";
+import { failStaleJobs } from "./job-runner";
+import { retryDueWebhookDeliveries } from "./webhooks";
+import { syncAISearchCompletions } from "../routes/ai-search";
+import { snapshotStorageMetrics } from "../routes/metrics";
+import { syncS3ImportCompletions } from "../routes/s3-import";
+
+const DEFAULT_AUDIT_LOG_RETENTION_DAYS = 90;
+const DEFAULT_JOB_EVENT_RETENTION_DAYS = 90;
+const DEFAULT_WEBHOOK_DELIVERY_RETENTION_DAYS = 30;
+const DEFAULT_STALE_JOB_MINUTES = 60;
+
+export interface MaintenanceTask {
+  name: string;
+  /** Runs the task and returns the number of rows or jobs it handled */
+  run: (env: Env) => Promise<number>;
+}
+
+/**
+ * Read a non-negative whole number setting, falling back to the default if
+ * it is unset or invalid
+ */
+function readSetting(value: string | undefined, fallback: number): number {
+  const parsed = Number.parseInt(value ?? "", 10);
+  return Number.isNaN(parsed) || parsed < 0 ? fallback : parsed;
+}
+
+/**
+ * Delete rows of a table whose timestamp column is older than the retention
+ */
+async function pruneOlderThan(
+  db: D1Database,
+  table: "audit_log" | "job_audit_events" | "webhook_deliveries",
+  column: "timestamp" | "created_at",
+  retentionDays: number,
+): Promise<number> {
+  const result = await db
+    .prepare(
+      `DELETE FROM ${table} WHERE datetime(${column}) < datetime('now', ?)`,
+    )
+    .bind(`-${String(retentionDays)} days`)
+    .run();
+  return result.meta.changes;
+}
+
+export const MAINTENANCE_TASKS: MaintenanceTask[] = [
+  {
+    name: "prune_audit_log",
+    run: async (env) => {
+      const days = readSetting(
+        env.AUDIT_LOG_RETENTION_DAYS,
+        DEFAULT_AUDIT_LOG_RETENTION_DAYS,
+      );
+      if (!env.METADATA || days === 0) return 0;
+      return await pruneOlderThan(env.METADATA, "audit_log", "timestamp", days);
+    },
+  },
+  {
+    name: "prune_job_events",
+    run: async (env) => {
+      const days = readSetting(
+        env.JOB_EVENT_RETENTION_DAYS,
+        DEFAULT_JOB_EVENT_RETENTION_DAYS,
+      );
+      if (!env.METADATA || days === 0) return 0;
+      return await pruneOlderThan(
+        env.METADATA,
+        "job_audit_events",
+        "timestamp",
+        days,
+      );
+    },
+  },
+  {
+    name: "prune_webhook_deliveries",
+    run: async (env) => {
+      const days = readSetting(
+        env.WEBHOOK_DELIVERY_RETENTION_DAYS,
+        DEFAULT_WEBHOOK_DELIVERY_RETENTION_DAYS,
+      );
+      if (!env.METADATA || days === 0) return 0;
+      return await pruneOlderThan(
+        env.METADATA,
+        "webhook_deliveries",
+        "created_at",
+        days,
+      );
+    },
+  },
+  {
+    name: "fail_stale_jobs",
+    run: async (env) => {
+      const minutes = readSetting(
+        env.STALE_JOB_MINUTES,
+        DEFAULT_STALE_JOB_MINUTES,
+      );
+      if (minutes === 0) return 0;
+      return await failStaleJobs(
+        env,
+        new Date(Date.now() - minutes * 60 * 1000),
+      );
+    },
+  },
+  {
+    name: "snapshot_storage_metrics",
+    run: snapshotStorageMetrics,
+  },
+  {
+    name: "sync_s3_import_completions",
+    run: syncS3ImportCompletions,
+  },
+  {
+    name: "sync_ai_search_completions",
+    run: syncAISearchCompletions,
+  },
+  {
+    name: "retry_webhook_deliveries",
+    run: retryDueWebhookDeliveries,
+  },
+];
+
+/**
+ * Run every maintenance task in order, logging each task's result
+ */
+export async function runScheduledMaintenance(
+  env: Env,
+  cron: string,
+): Promise<void> {
+  logInfo(`Running scheduled maintenance (${cron})`, {
+    module: "maintenance",
+    operation: "run",
+    metadata: { cron, tasks: MAINTENANCE_TASKS.length },
+  });
+
+  for (const task of MAINTENANCE_TASKS) {
+    const startedAt = Date.now();
+    try {
+      const handled = await task.run(env);
+      logInfo(`Maintenance task ${task.name} handled ${String(handled)}`, {
+        module: "maintenance",
+        operation: task.name,
+        metadata: { handled, durationMs: Date.now() - startedAt },
+      });
+    } catch (error) {
+      await logError(
+        env,
+        error instanceof Error ? error : String(error),
+        { module: "maintenance", operation: task.name },
+        false,
+      );
+    }
+  }
+}
@@ -208,6 +208,563 @@ export const MIGRATIONS: Migration[] = [
       CREATE INDEX IF NOT EXISTS idx_bucket_colors_updated ON bucket_colors(updated_at DESC);
     `,
   },
+  {
+    version: 5,
+    name: "upload_sessions",
+    description:
+      "Add upload_sessions and upload_session_parts tables for resumable multipart uploads",
+    sql: `
+      CREATE TABLE IF NOT EXISTS upload_sessions (
+        upload_id TEXT PRIMARY KEY,
+        bucket_name TEXT NOT NULL,
+        object_key TEXT NOT NULL,
+        file_fingerprint TEXT,
+        file_size INTEGER,
+        part_size INTEGER,
+        total_parts INTEGER,
+        content_type TEXT,
+        status TEXT NOT NULL DEFAULT 'in_progress' CHECK (status IN (
+          'in_progress',
+          'completed',
+          'aborted'
+        )),
+        user_email TEXT NOT NULL,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL
+      );
+
+      CREATE INDEX IF NOT EXISTS idx_upload_sessions_bucket ON upload_sessions(bucket_name, status);
+      CREATE INDEX IF NOT EXISTS idx_upload_sessions_fingerprint ON upload_sessions(file_fingerprint);
+
+      CREATE TABLE IF NOT EXISTS upload_session_parts (
+        upload_id TEXT NOT NULL,
+        part_number INTEGER NOT NULL,
+        etag TEXT NOT NULL,
+        md5 TEXT,
+        uploaded_at TEXT NOT NULL,
+        PRIMARY KEY (upload_id, part_number),
+        FOREIGN KEY (upload_id) REFERENCES upload_sessions(upload_id) ON DELETE CASCADE
+      );
+    `,
+  },
+  {
+    version: 6,
+    name: "background_jobs",
+    description:
+      "Allow folder and bucket rename/delete jobs in bulk_jobs and add job_tasks for queue-backed job execution",
+    sql: `
+      -- Rebuild bulk_jobs with the extended operation_type constraint.
+      -- job_audit_events is parked in a table without a foreign key so the
+      -- cascade on DROP TABLE bulk_jobs does not remove existing events.
+      CREATE TABLE IF NOT EXISTS bulk_jobs_new (
+        job_id TEXT PRIMARY KEY,
+        bucket_name TEXT NOT NULL,
+        operation_type TEXT NOT NULL CHECK (operation_type IN (
+          'bulk_upload',
+          'bulk_download',
+          'bulk_delete',
+          'bucket_delete',
+          'bucket_rename',
+          'file_move',
+          'file_copy',
+          'folder_move',
+          'folder_copy',
+          'folder_rename',
+          'folder_delete',
+          'ai_search_sync'
+        )),
+        status TEXT NOT NULL DEFAULT 'queued' CHECK (status IN (
+          'queued',
+          'running',
+          'completed',
+          'failed',
+          'cancelled'
+        )),
+        total_items INTEGER,
+        processed_items INTEGER DEFAULT 0,
+        error_count INTEGER DEFAULT 0,
+        percentage REAL DEFAULT 0,
+        started_at TEXT NOT NULL,
+        completed_at TEXT,
+        user_email TEXT NOT NULL,
+        metadata TEXT
+      );
+
+      INSERT OR IGNORE INTO bulk_jobs_new (
+        job_id, bucket_name, operation_type, status, total_items,
+        processed_items, error_count, percentage, started_at, completed_at,
+        user_email, metadata
+      )
+      SELECT
+        job_id, bucket_name, operation_type, status, total_items,
+        processed_items, error_count, percentage, started_at, completed_at,
+        user_email, metadata
+      FROM bulk_jobs;
+
+      CREATE TABLE IF NOT EXISTS job_audit_events_backup (
+        id INTEGER PRIMARY KEY,
+        job_id TEXT NOT NULL,
+        event_type TEXT NOT NULL,
+        user_email TEXT NOT NULL,
+        timestamp TEXT,
+        details TEXT
+      );
+
+      INSERT OR IGNORE INTO job_audit_events_backup (
+        id, job_id, event_type, user_email, timestamp, details
+      )
+      SELECT id, job_id, event_type, user_email, timestamp, details
+      FROM job_audit_events;
+
+      DROP TABLE IF EXISTS job_audit_events;
+      DROP TABLE IF EXISTS bulk_jobs;
+      ALTER TABLE bulk_jobs_new RENAME TO bulk_jobs;
+
+      CREATE INDEX IF NOT EXISTS idx_bulk_jobs_bucket ON bulk_jobs(bucket_name);
+      CREATE INDEX IF NOT EXISTS idx_bulk_jobs_status ON bulk_jobs(status);
+      CREATE INDEX IF NOT EXISTS idx_bulk_jobs_operation ON bulk_jobs(operation_type);
+      CREATE INDEX IF NOT EXISTS idx_bulk_jobs_started ON bulk_jobs(started_at DESC);
+      CREATE INDEX IF NOT EXISTS idx_bulk_jobs_user ON bulk_jobs(user_email);
+
+      CREATE TABLE IF NOT EXISTS job_audit_events (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        job_id TEXT NOT NULL,
+        event_type TEXT NOT NULL,
+        user_email TEXT NOT NULL,
+        timestamp TEXT DEFAULT (datetime('now')),
+        details TEXT,
+        FOREIGN KEY (job_id) REFERENCES bulk_jobs(job_id) ON DELETE CASCADE
+      );
+
+      INSERT INTO job_audit_events (
+        id, job_id, event_type, user_email, timestamp, details
+      )
+      SELECT id, job_id, event_type, user_email, timestamp, details
+      FROM job_audit_events_backup
+      WHERE job_id IN (SELECT job_id FROM bulk_jobs);
+
+      DROP TABLE IF EXISTS job_audit_events_backup;
+
+      CREATE INDEX IF NOT EXISTS idx_job_events_job ON job_audit_events(job_id);
+      CREATE INDEX IF NOT EXISTS idx_job_events_timestamp ON job_audit_events(timestamp DESC);
+
+      -- Resumable state for jobs executed by the queue consumer
+      CREATE TABLE IF NOT EXISTS job_tasks (
+        job_id TEXT PRIMARY KEY,
+        task_type TEXT NOT NULL,
+        params TEXT NOT NULL,
+        phase TEXT NOT NULL DEFAULT 'count',
+        cursor TEXT,
+        attempts INTEGER DEFAULT 0,
+        updated_at TEXT NOT NULL,
+        FOREIGN KEY (job_id) REFERENCES bulk_jobs(job_id) ON DELETE CASCADE
+      );
+    `,
+  },
+  {
+    version: 7,
+    name: "job_controls",
+    description:
+      "Allow paused bulk jobs and add job_items to record failed items for retry",
+    sql: `
+      -- Rebuild bulk_jobs with the 'paused' status (same steps as migration 6).
+      -- job_tasks also references bulk_jobs, so it is parked the same way.
+      CREATE TABLE IF NOT EXISTS bulk_jobs_new (
+        job_id TEXT PRIMARY KEY,
+        bucket_name TEXT NOT NULL,
+        operation_type TEXT NOT NULL CHECK (operation_type IN (
+          'bulk_upload',
+          'bulk_download',
+          'bulk_delete',
+          'bucket_delete',
+          'bucket_rename',
+          'file_move',
+          'file_copy',
+          'folder_move',
+          'folder_copy',
+          'folder_rename',
+          'folder_delete',
+          'ai_search_sync'
+        )),
+        status TEXT NOT NULL DEFAULT 'queued' CHECK (status IN (
+          'queued',
+          'running',
+          'completed',
+          'failed',
+          'cancelled',
+          'paused'
+        )),
+        total_items INTEGER,
+        processed_items INTEGER DEFAULT 0,
+        error_count INTEGER DEFAULT 0,
+        percentage REAL DEFAULT 0,
+        started_at TEXT NOT NULL,
+        completed_at TEXT,
+        user_email TEXT NOT NULL,
+        metadata TEXT
+      );
+
+      INSERT OR IGNORE INTO bulk_jobs_new (
+        job_id, bucket_name, operation_type, status, total_items,
+        processed_items, error_count, percentage, started_at, completed_at,
+        user_email, metadata
+      )
+      SELECT
+        job_id, bucket_name, operation_type, status, total_items,
+        processed_items, error_count, percentage, started_at, completed_at,
+        user_email, metadata
+      FROM bulk_jobs;
+
+      CREATE TABLE IF NOT EXISTS job_audit_events_backup (
+        id INTEGER PRIMARY KEY,
+        job_id TEXT NOT NULL,
+        event_type TEXT NOT NULL,
+        user_email TEXT NOT NULL,
+        timestamp TEXT,
+        details TEXT
+      );
+
+      INSERT OR IGNORE INTO job_audit_events_backup (
+        id, job_id, event_type, user_email, timestamp, details
+      )
+      SELECT id, job_id, event_type, user_email, timestamp, details
+      FROM job_audit_events;
+
+      CREATE TABLE IF NOT EXISTS job_tasks_backup (
+        job_id TEXT PRIMARY KEY,
+        task_type TEXT NOT NULL,
+        params TEXT NOT NULL,
+        phase TEXT NOT NULL,
+        cursor TEXT,
+        attempts INTEGER,
+        updated_at TEXT NOT NULL
+      );
+
+      INSERT OR IGNORE INTO job_tasks_backup (
+        job_id, task_type, params, phase, cursor, attempts, updated_at
+      )
+      SELECT job_id, task_type, params, phase, cursor, attempts, updated_at
+      FROM job_tasks;
+
+      DROP TABLE IF EXISTS job_audit_events;
+      DROP TABLE IF EXISTS job_tasks;
+      DROP TABLE IF EXISTS bulk_jobs;
+      ALTER TABLE bulk_jobs_new RENAME TO bulk_jobs;
+
+      CREATE INDEX IF NOT EXISTS idx_bulk_jobs_bucket ON bulk_jobs(bucket_name);
+      CREATE INDEX IF NOT EXISTS idx_bulk_jobs_status ON bulk_jobs(status);
+      CREATE INDEX IF NOT EXISTS idx_bulk_jobs_operation ON bulk_jobs(operation_type);
+      CREATE INDEX IF NOT EXISTS idx_bulk_jobs_started ON bulk_jobs(started_at DESC);
+      CREATE INDEX IF NOT EXISTS idx_bulk_jobs_user ON bulk_jobs(user_email);
+
+      CREATE TABLE IF NOT EXISTS job_audit_events (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        job_id TEXT NOT NULL,
+        event_type TEXT NOT NULL,
+        user_email TEXT NOT NULL,
+        timestamp TEXT DEFAULT (datetime('now')),
+        details TEXT,
+        FOREIGN KEY (job_id) REFERENCES bulk_jobs(job_id) ON DELETE CASCADE
+      );
+
+      INSERT INTO job_audit_events (
+        id, job_id, event_type, user_email, timestamp, details
+      )
+      SELECT id, job_id, event_type, user_email, timestamp, details
+      FROM job_audit_events_backup
+      WHERE job_id IN (SELECT job_id FROM bulk_jobs);
+
+      DROP TABLE IF EXISTS job_audit_events_backup;
+
+      CREATE INDEX IF NOT EXISTS idx_job_events_job ON job_audit_events(job_id);
+      CREATE INDEX IF NOT EXISTS idx_job_events_timestamp ON job_audit_events(timestamp DESC);
+
+      CREATE TABLE IF NOT EXISTS job_tasks (
+        job_id TEXT PRIMARY KEY,
+        task_type TEXT NOT NULL,
+        params TEXT NOT NULL,
+        phase TEXT NOT NULL DEFAULT 'count',
+        cursor TEXT,
+        attempts INTEGER DEFAULT 0,
+        updated_at TEXT NOT NULL,
+        FOREIGN KEY (job_id) REFERENCES bulk_jobs(job_id) ON DELETE CASCADE
+      );
+
+      INSERT INTO job_tasks (
+        job_id, task_type, params, phase, cursor, attempts, updated_at
+      )
+      SELECT job_id, task_type, params, phase, cursor, attempts, updated_at
+      FROM job_tasks_backup
+      WHERE job_id IN (SELECT job_id FROM bulk_jobs);
+
+      DROP TABLE IF EXISTS job_tasks_backup;
+
+      -- Per-item failures of a job, replayed by retry-failed
+      CREATE TABLE IF NOT EXISTS job_items (
+        job_id TEXT NOT NULL,
+        object_key TEXT NOT NULL,
+        status TEXT NOT NULL DEFAULT 'failed' CHECK (status IN (
+          'failed',
+          'succeeded'
+        )),
+        error TEXT,
+        attempts INTEGER DEFAULT 1,
+        updated_at TEXT NOT NULL,
+        PRIMARY KEY (job_id, object_key),
+        FOREIGN KEY (job_id) REFERENCES bulk_jobs(job_id) ON DELETE CASCADE
+      );
+
+      CREATE INDEX IF NOT EXISTS idx_job_items_status ON job_items(job_id, status);
+    `,
+  },
+  {
+    version: 8,
+    name: "job_item_error_codes",
+    description:
+      "Add an error_code column to job_items for the per-item failure manifest",
+    sql: `
+      ALTER TABLE job_items ADD COLUMN error_code TEXT;
+    `,
+  },
+  {
+    version: 9,
+    name: "signed_links",
+    description:
+      "Add signed_links table for download counters and revocation of signed URLs",
+    sql: `
+      CREATE TABLE IF NOT EXISTS signed_links (
+        link_id TEXT PRIMARY KEY,
+        bucket_name TEXT NOT NULL,
+        object_key TEXT NOT NULL,
+        created_by TEXT NOT NULL,
+        created_at TEXT NOT NULL,
+        expires_at TEXT NOT NULL,
+        ip TEXT,
+        max_downloads INTEGER,
+        disposition TEXT NOT NULL DEFAULT 'attachment' CHECK (disposition IN (
+          'attachment',
+          'inline'
+        )),
+        download_count INTEGER NOT NULL DEFAULT 0,
+        last_download_at TEXT,
+        revoked_at TEXT
+      );
+
+      CREATE INDEX IF NOT EXISTS idx_signed_links_creator ON signed_links(created_by, expires_at);
+      CREATE INDEX IF NOT EXISTS idx_signed_links_bucket ON signed_links(bucket_name, object_key);
+    `,
+  },
+  {
+    version: 10,
+    name: "shares",
+    description:
+      "Add shares table for public file and folder share links with optional passwords",
+    sql: `
+      CREATE TABLE IF NOT EXISTS shares (
+        share_id TEXT PRIMARY KEY,
+        bucket_name TEXT NOT NULL,
+        object_key TEXT NOT NULL,
+        share_type TEXT NOT NULL CHECK (share_type IN ('file', 'folder')),
+        created_by TEXT NOT NULL,
+        created_at TEXT NOT NULL,
+        expires_at TEXT NOT NULL,
+        password_hash TEXT,
+        max_downloads INTEGER,
+        download_count INTEGER NOT NULL DEFAULT 0,
+        allow_zip INTEGER NOT NULL DEFAULT 0,
+        last_accessed_at TEXT,
+        revoked_at TEXT
+      );
+
+      CREATE INDEX IF NOT EXISTS idx_shares_creator ON shares(created_by, expires_at);
+      CREATE INDEX IF NOT EXISTS idx_shares_bucket ON shares(bucket_name, object_key);
+    `,
+  },
+  {
+    version: 11,
+    name: "access_grants",
+    description:
+      "Add access_grants table for viewer/editor/admin roles on buckets and key prefixes",
+    sql: `
+      CREATE TABLE IF NOT EXISTS access_grants (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        user_email TEXT NOT NULL,
+        role TEXT NOT NULL CHECK (role IN ('viewer', 'editor', 'admin')),
+        bucket_name TEXT,
+        prefix TEXT NOT NULL DEFAULT '',
+        created_by TEXT NOT NULL,
+        created_at TEXT DEFAULT (datetime('now'))
+      );
+
+      CREATE UNIQUE INDEX IF NOT EXISTS idx_access_grants_scope ON access_grants(user_email, IFNULL(bucket_name, ''), prefix);
+    `,
+  },
+  {
+    version: 12,
+    name: "access_group_mappings",
+    description:
+      "Add access_group_mappings table mapping identity provider groups to roles",
+    sql: `
+      CREATE TABLE IF NOT EXISTS access_group_mappings (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        group_name TEXT NOT NULL,
+        role TEXT NOT NULL CHECK (role IN ('viewer', 'editor', 'admin')),
+        bucket_name TEXT,
+        prefix TEXT NOT NULL DEFAULT '',
+        created_by TEXT NOT NULL,
+        created_at TEXT DEFAULT (datetime('now'))
+      );
+
+      CREATE UNIQUE INDEX IF NOT EXISTS idx_access_group_mappings_scope ON access_group_mappings(lower(group_name), IFNULL(bucket_name, ''), prefix);
+    `,
+  },
+  {
+    version: 13,
+    name: "api_tokens",
+    description:
+      "Add api_tokens table for hashed, scoped personal and service API tokens",
+    sql: `
+      CREATE TABLE IF NOT EXISTS api_tokens (
+        token_id TEXT PRIMARY KEY,
+        name TEXT NOT NULL,
+        token_prefix TEXT NOT NULL,
+        token_hash TEXT NOT NULL UNIQUE,
+        created_by TEXT NOT NULL,
+        creator_groups TEXT NOT NULL DEFAULT '[]',
+        created_at TEXT NOT NULL,
+        expires_at TEXT NOT NULL,
+        buckets TEXT,
+        permissions TEXT NOT NULL,
+        last_used_at TEXT,
+        revoked_at TEXT
+      );
+
+      CREATE INDEX IF NOT EXISTS idx_api_tokens_creator ON api_tokens(created_by, expires_at);
+    `,
+  },
+  {
+    version: 14,
+    name: "trash",
+    description:
+      "Add bucket_trash_settings and trash_items tables for soft-deleted objects",
+    sql: `
+      CREATE TABLE IF NOT EXISTS bucket_trash_settings (
+        bucket_name TEXT PRIMARY KEY,
+        enabled INTEGER NOT NULL DEFAULT 1,
+        retention_days INTEGER NOT NULL DEFAULT 30,
+        updated_by TEXT NOT NULL,
+        updated_at TEXT NOT NULL
+      );
+
+      CREATE TABLE IF NOT EXISTS trash_items (
+        item_id TEXT PRIMARY KEY,
+        bucket_name TEXT NOT NULL,
+        original_key TEXT NOT NULL,
+        trash_key TEXT NOT NULL,
+        size INTEGER NOT NULL DEFAULT 0,
+        etag TEXT,
+        reason TEXT NOT NULL CHECK (reason IN ('delete', 'overwrite')),
+        deleted_by TEXT NOT NULL,
+        deleted_at TEXT NOT NULL,
+        expires_at TEXT NOT NULL
+      );
+
+      CREATE INDEX IF NOT EXISTS idx_trash_items_bucket ON trash_items(bucket_name, deleted_at DESC);
+      CREATE INDEX IF NOT EXISTS idx_trash_items_expires ON trash_items(expires_at);
+    `,
+  },
+  {
+    version: 15,
+    name: "scheduled_maintenance",
+    description:
+      "Add storage_snapshots and s3_import_notifications tables for the scheduled maintenance handler",
+    sql: `
+      CREATE TABLE IF NOT EXISTS storage_snapshots (
+        snapshot_date TEXT NOT NULL,
+        bucket_name TEXT NOT NULL,
+        storage_bytes INTEGER NOT NULL DEFAULT 0,
+        object_count INTEGER NOT NULL DEFAULT 0,
+        captured_at TEXT NOT NULL,
+        PRIMARY KEY (snapshot_date, bucket_name)
+      );
+
+      CREATE TABLE IF NOT EXISTS s3_import_notifications (
+        job_id TEXT PRIMARY KEY,
+        status TEXT NOT NULL,
+        notified_at TEXT NOT NULL
+      );
+    `,
+  },
+  {
+    version: 16,
+    name: "webhook_deliveries",
+    description:
+      "Add webhook_deliveries table recording every webhook delivery attempt and its scheduled retry",
+    sql: `
+      CREATE TABLE IF NOT EXISTS webhook_deliveries (
+        id TEXT PRIMARY KEY,
+        delivery_id TEXT NOT NULL,
+        webhook_id TEXT NOT NULL,
+        event TEXT NOT NULL,
+        payload TEXT NOT NULL,
+        attempt INTEGER NOT NULL DEFAULT 1,
+        reason TEXT NOT NULL DEFAULT 'event' CHECK (reason IN ('event', 'retry', 'redeliver')),
+        status TEXT NOT NULL CHECK (status IN ('succeeded', 'failed')),
+        status_code INTEGER,
+        latency_ms INTEGER,
+        response_body TEXT,
+        error TEXT,
+        next_retry_at TEXT,
+        created_at TEXT NOT NULL,
+        FOREIGN KEY (webhook_id) REFERENCES webhooks(id) ON DELETE CASCADE
+      );
+
+      CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_webhook ON webhook_deliveries(webhook_id, created_at DESC);
+      CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_retry ON webhook_deliveries(next_retry_at);
+    `,
+  },
+  {
+    version: 17,
+    name: "webhook_formats",
+    description:
+      "Add format and template columns to webhooks for Slack, Discord, Teams and custom payloads",
+    sql: `
+      ALTER TABLE webhooks ADD COLUMN format TEXT NOT NULL DEFAULT 'raw' CHECK (format IN ('raw', 'slack', 'discord', 'teams', 'custom'));
+      ALTER TABLE webhooks ADD COLUMN template TEXT;
+    `,
+  },
+  {
+    version: 18,
+    name: "webhook_filters",
+    description:
+      "Add filters column to webhooks for bucket, tag, key, size and user conditions",
+    sql: `
+      ALTER TABLE webhooks ADD COLUMN filters TEXT;
+    `,
+  },
+  {
+    version: 19,
+    name: "webhook_secret_rotation",
+    description:
+      "Add previous_secret columns to webhooks so a rotated-out secret keeps signing during a grace period",
+    sql: `
+      ALTER TABLE webhooks ADD COLUMN previous_secret TEXT;
+      ALTER TABLE webhooks ADD COLUMN previous_secret_expires_at TEXT;
+    `,
+  },
+  {
+    version: 20,
+    name: "webhook_event_types",
+    description:
+      "Add ai_search_sync_notifications table for ai_search_sync_complete webhook events",
+    sql: `
+      CREATE TABLE IF NOT EXISTS ai_search_sync_notifications (
+        job_id TEXT PRIMARY KEY,
+        instance_name TEXT NOT NULL,
+        notified_at TEXT NOT NULL
+      );
+    `,
+  },
 ];
 
 // ============================================
@@ -470,6 +1027,79 @@ export async function detectLegacyInstallation(
     if (existingTables.includes("bucket_colors")) {
       suggestedVersion = 4;
     }
+    if (existingTables.includes("upload_sessions")) {
+      suggestedVersion = 5;
+    }
+    if (existingTables.includes("job_tasks")) {
+      suggestedVersion = 6;
+    }
+    if (existingTables.includes("job_items")) {
+      suggestedVersion = 7;
+
+      const errorCodeColumn = await db
+        .prepare(
+          "SELECT COUNT(*) AS count FROM pragma_table_info('job_items') WHERE name = 'error_code'",
+        )
+        .first<{ count: number }>();
+      if ((errorCodeColumn?.count ?? 0) > 0) {
+        suggestedVersion = 8;
+      }
+    }
+    if (existingTables.includes("signed_links")) {
+      suggestedVersion = 9;
+    }
+    if (existingTables.includes("shares")) {
+      suggestedVersion = 10;
+    }
+    if (existingTables.includes("access_grants")) {
+      suggestedVersion = 11;
+    }
+    if (existingTables.includes("access_group_mappings")) {
+      suggestedVersion = 12;
+    }
+    if (existingTables.includes("api_tokens")) {
+      suggestedVersion = 13;
+    }
+    if (existingTables.includes("trash_items")) {
+      suggestedVersion = 14;
+    }
+    if (existingTables.includes("storage_snapshots")) {
+      suggestedVersion = 15;
+    }
+    if (existingTables.includes("webhook_deliveries")) {
+      suggestedVersion = 16;
+    }
+    if (existingTables.includes("webhooks")) {
+      const formatColumn = await db
+        .prepare(
+          "SELECT COUNT(*) AS count FROM pragma_table_info('webhooks') WHERE name = 'format'",
+        )
+        .first<{ count: number }>();
+      if ((formatColumn?.count ?? 0) > 0) {
+        suggestedVersion = 17;
+      }
+
+      const filtersColumn = await db
+        .prepare(
+          "SELECT COUNT(*) AS count FROM pragma_table_info('webhooks') WHERE name = 'filters'",
+        )
+        .first<{ count: number }>();
+      if ((filtersColumn?.count ?? 0) > 0) {
+        suggestedVersion = 18;
+      }
+
+      const previousSecretColumn = await db
+        .prepare(
+          "SELECT COUNT(*) AS count FROM pragma_table_info('webhooks') WHERE name = 'previous_secret'",
+        )
+        .first<{ count: number }>();
+      if ((previousSecretColumn?.count ?? 0) > 0) {
+        suggestedVersion = 19;
+      }
+    }
+    if (existingTables.includes("ai_search_sync_notifications")) {
+      suggestedVersion = 20;
+    }
 
     return {
       isLegacy: suggestedVersion > 0,
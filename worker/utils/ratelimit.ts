@@ -1,5 +1,42 @@
 import type { Env } from "../types";
 import { logWarning } from "./error-logger";
+import { triggerWebhooks, createRateLimitExceededPayload } from "./webhooks";
+
+/**
+ * Until when each rate limit key and tier stays quiet after sending a
+ * rate_limit_exceeded webhook. Kept per isolate, so a key over its limit
+ * notifies at most once per period on each isolate rather than on every
+ * rejected request. Expired entries are dropped and the oldest are evicted
+ * past MAX_NOTIFY_KEYS, since share keys are client IPs.
+ */
+const notifyQuietUntil = new Map<string, number>();
+const MAX_NOTIFY_KEYS = 1000;
+
+/**
+ * Whether a key over its limit should send a webhook now, starting its quiet
+ * period if so
+ */
+function shouldNotify(notifyKey: string, period: number): boolean {
+  const now = Date.now();
+  if ((notifyQuietUntil.get(notifyKey) ?? 0) > now) {
+    return false;
+  }
+
+  for (const [key, until] of notifyQuietUntil) {
+    if (until <= now) {
+      notifyQuietUntil.delete(key);
+    }
+  }
+  // Map order is insertion order, so the first key is the oldest
+  while (notifyQuietUntil.size >= MAX_NOTIFY_KEYS) {
+    const oldest = notifyQuietUntil.keys().next().value;
+    if (oldest === undefined) break;
+    notifyQuietUntil.delete(oldest);
+  }
+
+  notifyQuietUntil.set(notifyKey, now + period * 1000);
+  return true;
+}
 
 /**
  * Rate limit tier types for different operation categories
@@ -39,9 +76,9 @@ function getRateLimitTier(method: string, pathname: string): RateLimitTier {
     return "READ";
   }
 
-  // POST and PATCH operations (writes)
+  // POST, PUT and PATCH operations (writes)
   // This includes: uploads, renames, copies, moves, folder creation
-  if (method === "POST" || method === "PATCH") {
+  if (method === "POST" || method === "PUT" || method === "PATCH") {
     return "WRITE";
   }
 
@@ -112,6 +149,23 @@ export async function checkRateLimit(
         limit: `${limit} requests per ${period} seconds`,
       },
     });
+
+    if (shouldNotify(`${userEmail}:${tier}`, period)) {
+      // Share password attempts are keyed by client IP, not by a user
+      void triggerWebhooks(
+        env,
+        "rate_limit_exceeded",
+        createRateLimitExceededPayload(
+          tier,
+          limit,
+          period,
+          method,
+          pathname,
+          userEmail.startsWith("share:") ? null : userEmail,
+        ),
+        false,
+      );
+    }
   }
 
   return {
@@ -0,0 +1,233 @@
+import { describe, expect, it } from "vitest";
+import type { AccessGrantScope, ApiTokenScope, UserAccess } from "../types";
+import { canSeeKey, satisfiesCheck } from "./rbac";
+
+function accessWith(
+  grants: AccessGrantScope[],
+  token?: Partial<ApiTokenScope>,
+): UserAccess {
+  return {
+    email: "user@example.com",
+    groups: [],
+    enforced: true,
+    grants,
+    ...(token && {
+      token: {
+        tokenId: "token-1",
+        name: "CI",
+        buckets: null,
+        permissions: ["read", "write", "delete"],
+        ...token,
+      },
+    }),
+  };
+}
+
+describe("satisfiesCheck", () => {
+  it("allows everything while access control is not enforced", () => {
+    const access = { ...accessWith([]), enforced: false };
+
+    expect(satisfiesCheck(access, { role: "admin", scope: "account" })).toBe(
+      true,
+    );
+    expect(
+      satisfiesCheck(access, {
+        role: "editor",
+        scope: "key",
+        bucket: "media",
+        key: "a.txt",
+      }),
+    ).toBe(true);
+  });
+
+  it("denies everything without grants", () => {
+    const access = accessWith([]);
+
+    expect(satisfiesCheck(access, { role: "viewer", scope: "any" })).toBe(
+      false,
+    );
+    expect(
+      satisfiesCheck(access, { role: "viewer", scope: "bucket", bucket: "a" }),
+    ).toBe(false);
+  });
+
+  it("lets higher roles satisfy lower ones only", () => {
+    const editor = accessWith([
+      { role: "editor", bucket_name: "media", prefix: "" },
+    ]);
+
+    for (const role of ["viewer", "editor"] as const) {
+      expect(
+        satisfiesCheck(editor, { role, scope: "bucket", bucket: "media" }),
+      ).toBe(true);
+    }
+    expect(
+      satisfiesCheck(editor, {
+        role: "admin",
+        scope: "bucket",
+        bucket: "media",
+      }),
+    ).toBe(false);
+  });
+
+  it("requires a grant on every bucket for account checks", () => {
+    const everywhere = accessWith([
+      { role: "admin", bucket_name: null, prefix: "" },
+    ]);
+    const oneBucket = accessWith([
+      { role: "admin", bucket_name: "media", prefix: "" },
+    ]);
+    const prefixed = accessWith([
+      { role: "admin", bucket_name: null, prefix: "shared/" },
+    ]);
+
+    const check = { role: "admin", scope: "account" } as const;
+    expect(satisfiesCheck(everywhere, check)).toBe(true);
+    expect(satisfiesCheck(oneBucket, check)).toBe(false);
+    expect(satisfiesCheck(prefixed, check)).toBe(false);
+  });
+
+  it("requires a whole-bucket grant for bucket checks", () => {
+    const check = { role: "viewer", scope: "bucket", bucket: "media" } as const;
+
+    expect(
+      satisfiesCheck(
+        accessWith([{ role: "viewer", bucket_name: "media", prefix: "" }]),
+        check,
+      ),
+    ).toBe(true);
+    expect(
+      satisfiesCheck(
+        accessWith([{ role: "viewer", bucket_name: null, prefix: "" }]),
+        check,
+      ),
+    ).toBe(true);
+    expect(
+      satisfiesCheck(
+        accessWith([{ role: "viewer", bucket_name: "media", prefix: "docs/" }]),
+        check,
+      ),
+    ).toBe(false);
+    expect(
+      satisfiesCheck(
+        accessWith([{ role: "viewer", bucket_name: "backups", prefix: "" }]),
+        check,
+      ),
+    ).toBe(false);
+  });
+
+  it("matches key checks against the grant's prefix", () => {
+    const access = accessWith([
+      { role: "editor", bucket_name: "media", prefix: "reports/" },
+    ]);
+    const keyCheck = (bucket: string, key: string): boolean =>
+      satisfiesCheck(access, { role: "editor", scope: "key", bucket, key });
+
+    expect(keyCheck("media", "reports/2024.csv")).toBe(true);
+    expect(keyCheck("media", "reports/")).toBe(true);
+    expect(keyCheck("media", "private/salaries.csv")).toBe(false);
+    expect(keyCheck("media", "reports")).toBe(false);
+    expect(keyCheck("backups", "reports/2024.csv")).toBe(false);
+  });
+
+  it("accepts a grant at any prefix for any checks", () => {
+    const access = accessWith([
+      { role: "viewer", bucket_name: "media", prefix: "reports/" },
+    ]);
+
+    expect(satisfiesCheck(access, { role: "viewer", scope: "any" })).toBe(
+      true,
+    );
+    expect(
+      satisfiesCheck(access, { role: "viewer", scope: "any", bucket: "media" }),
+    ).toBe(true);
+    expect(
+      satisfiesCheck(access, {
+        role: "viewer",
+        scope: "any",
+        bucket: "backups",
+      }),
+    ).toBe(false);
+  });
+
+  it("limits API tokens to their buckets on top of the grants", () => {
+    const access = accessWith(
+      [{ role: "admin", bucket_name: null, prefix: "" }],
+      { buckets: ["media"] },
+    );
+
+    expect(
+      satisfiesCheck(access, {
+        role: "editor",
+        scope: "key",
+        bucket: "media",
+        key: "a.txt",
+      }),
+    ).toBe(true);
+    expect(
+      satisfiesCheck(access, {
+        role: "editor",
+        scope: "key",
+        bucket: "backups",
+        key: "a.txt",
+      }),
+    ).toBe(false);
+    expect(satisfiesCheck(access, { role: "admin", scope: "account" })).toBe(
+      false,
+    );
+    expect(satisfiesCheck(access, { role: "viewer", scope: "any" })).toBe(
+      true,
+    );
+  });
+
+  it("applies token bucket limits even when access is not enforced", () => {
+    const access = {
+      ...accessWith([], { buckets: ["media"] }),
+      enforced: false,
+    };
+
+    expect(
+      satisfiesCheck(access, {
+        role: "admin",
+        scope: "bucket",
+        bucket: "media",
+      }),
+    ).toBe(true);
+    expect(
+      satisfiesCheck(access, {
+        role: "admin",
+        scope: "bucket",
+        bucket: "backups",
+      }),
+    ).toBe(false);
+  });
+
+  it("never lets a token exceed the creator's grants", () => {
+    const access = accessWith(
+      [{ role: "viewer", bucket_name: "media", prefix: "" }],
+      { buckets: null },
+    );
+
+    expect(
+      satisfiesCheck(access, {
+        role: "editor",
+        scope: "key",
+        bucket: "media",
+        key: "a.txt",
+      }),
+    ).toBe(false);
+  });
+});
+
+describe("canSeeKey", () => {
+  const access = accessWith([
+    { role: "viewer", bucket_name: "media", prefix: "reports/2024/" },
+  ]);
+
+  it("shows keys under a granted prefix and the folders leading to it", () => {
+    expect(canSeeKey(access, "media", "reports/2024/q1.csv")).toBe(true);
+    expect(canSeeKey(access, "media", "reports/")).toBe(true);
+    expect(canSeeKey(access, "media", "private/")).toBe(false);
+    expect(canSeeKey(access, "backups", "reports/2024/q1.csv")).toBe(false);
+  });
+});
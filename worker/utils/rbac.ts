@@ -0,0 +1,780 @@
+/**
+ * Role-Based Access Control
+ *
+ * Cloudflare Access decides who can reach the app; the grants in D1 decide
+ * what each user may do. Roles are ordered viewer < editor < admin:
+ *
+ *   viewer  list, preview and download objects
+ *   editor  also upload, rename, move, copy and delete objects and folders
+ *   admin   also manage buckets, bucket settings, webhooks, imports, the
+ *           audit log and the grants themselves
+ *
+ * A grant applies to every bucket (bucket_name NULL) or one bucket, and can
+ * be limited to a key prefix. Grants are made to a user's email or, through
+ * access_group_mappings, to an identity provider group from the Access JWT
+ * (see validateAccessJWT). Until the first grant or mapping is created access
+ * control is not enforced, so existing installations keep working; users in
+ * ADMIN_EMAILS are always unrestricted admins, so nobody can lock themselves
+ * out.
+ *
+ * Every /api/ request is resolved to the access checks it needs (see
+ * resolveAccessChecks) and authorized centrally in handleApiRequest before
+ * any route runs. List endpoints additionally filter their results with
+ * canSeeKey / hasBucketAccess.
+ */
+
+import type {
+  AccessGrantScope,
+  AccessIdentity,
+  AccessRole,
+  ApiTokenPermission,
+  ApiTokenScope,
+  Env,
+  UserAccess,
+} from "../types";
+
+const ROLE_RANK: Record<AccessRole, number> = {
+  viewer: 1,
+  editor: 2,
+  admin: 3,
+};
+
+export const ACCESS_ROLES: AccessRole[] = ["viewer", "editor", "admin"];
+
+/**
+ * A permission a request needs:
+ * - account: a grant on every bucket (account-level operations)
+ * - any: a grant on some bucket (or on the given bucket, at any prefix)
+ * - bucket: a grant on the whole bucket
+ * - key: a grant covering the key (or folder prefix)
+ */
+export type AccessCheck =
+  | { role: AccessRole; scope: "account" }
+  | { role: AccessRole; scope: "any"; bucket?: string | undefined }
+  | { role: AccessRole; scope: "bucket"; bucket: string }
+  | { role: AccessRole; scope: "key"; bucket: string; key: string };
+
+export type AuthorizationResult =
+  | { allowed: true; access: UserAccess }
+  | {
+      allowed: false;
+      access: UserAccess;
+      reason: string;
+      status: 400 | 403;
+    };
+
+/** Access resolved for a request, for routes that filter their results */
+const requestAccess = new WeakMap<Request, UserAccess>();
+
+function isBootstrapAdmin(env: Env, email: string): boolean {
+  return (env.ADMIN_EMAILS ?? "")
+    .split(",")
+    .map((entry) => entry.trim().toLowerCase())
+    .includes(email.toLowerCase());
+}
+
+/**
+ * Load a user's grants, including those mapped from their IdP groups. Access
+ * is unrestricted when no grants or group mappings exist yet, when there is
+ * no METADATA database, or for ADMIN_EMAILS users.
+ */
+export async function loadUserAccess(
+  env: Env,
+  identity: AccessIdentity,
+): Promise<UserAccess> {
+  const { email, groups, token } = identity;
+  const unrestricted: UserAccess = {
+    email,
+    groups,
+    enforced: false,
+    grants: [],
+    token,
+  };
+  const db = env.METADATA;
+  if (!db || isBootstrapAdmin(env, email)) {
+    return unrestricted;
+  }
+
+  const configuredQuery = db.prepare(
+    "SELECT EXISTS (SELECT 1 FROM access_grants) OR EXISTS (SELECT 1 FROM access_group_mappings) AS configured",
+  );
+  const userGrantsQuery = db
+    .prepare(
+      "SELECT role, bucket_name, prefix FROM access_grants WHERE user_email = ?",
+    )
+    .bind(email.toLowerCase());
+  const groupGrantsQuery = db
+    .prepare(
+      "SELECT role, bucket_name, prefix FROM access_group_mappings WHERE lower(group_name) IN (SELECT value FROM json_each(?))",
+    )
+    .bind(JSON.stringify(groups.map((group) => group.toLowerCase())));
+
+  let results: D1Result[];
+  try {
+    results = await db.batch([
+      configuredQuery,
+      userGrantsQuery,
+      groupGrantsQuery,
+    ]);
+  } catch (error) {
+    if (!String(error).includes("no such table")) {
+      throw error;
+    }
+    // The grant tables are created by migrations. Until the group mappings
+    // migration has run, enforce the user grants on their own.
+    try {
+      results = await db.batch([
+        db.prepare(
+          "SELECT EXISTS (SELECT 1 FROM access_grants) AS configured",
+        ),
+        userGrantsQuery,
+      ]);
+    } catch (fallbackError) {
+      if (String(fallbackError).includes("no such table")) {
+        return unrestricted;
+      }
+      throw fallbackError;
+    }
+  }
+
+  const [configured, userGrants, groupGrants] = results;
+  const flag = configured?.results[0] as { configured: number } | undefined;
+  if (flag?.configured !== 1) {
+    return unrestricted;
+  }
+
+  return {
+    email,
+    groups,
+    enforced: true,
+    token,
+    grants: [
+      ...((userGrants?.results ?? []) as AccessGrantScope[]),
+      ...((groupGrants?.results ?? []) as AccessGrantScope[]),
+    ],
+  };
+}
+
+function grantCoversBucket(grant: AccessGrantScope, bucket: string): boolean {
+  return grant.bucket_name === null || grant.bucket_name === bucket;
+}
+
+/**
+ * Whether an API token's bucket list allows a check. Tokens limited to some
+ * buckets can never pass account-level checks.
+ */
+function tokenCoversCheck(token: ApiTokenScope, check: AccessCheck): boolean {
+  if (token.buckets === null) {
+    return true;
+  }
+  switch (check.scope) {
+    case "account":
+      return false;
+    case "any":
+      return check.bucket === undefined || token.buckets.includes(check.bucket);
+    case "bucket":
+    case "key":
+      return token.buckets.includes(check.bucket);
+  }
+}
+
+/**
+ * Whether the user's grants satisfy a single check
+ */
+export function satisfiesCheck(
+  access: UserAccess,
+  check: AccessCheck,
+): boolean {
+  if (access.token && !tokenCoversCheck(access.token, check)) {
+    return false;
+  }
+  if (!access.enforced) {
+    return true;
+  }
+
+  const rank = ROLE_RANK[check.role];
+  return access.grants.some((grant) => {
+    if (ROLE_RANK[grant.role] < rank) {
+      return false;
+    }
+    switch (check.scope) {
+      case "account":
+        return grant.bucket_name === null && grant.prefix === "";
+      case "any":
+        return (
+          check.bucket === undefined || grantCoversBucket(grant, check.bucket)
+        );
+      case "bucket":
+        return grantCoversBucket(grant, check.bucket) && grant.prefix === "";
+      case "key":
+        return (
+          grantCoversBucket(grant, check.bucket) &&
+          check.key.startsWith(grant.prefix)
+        );
+    }
+  });
+}
+
+/**
+ * Whether the user can see a bucket at all (used to filter bucket lists)
+ */
+export function hasBucketAccess(access: UserAccess, bucket: string): boolean {
+  return satisfiesCheck(access, { role: "viewer", scope: "any", bucket });
+}
+
+/**
+ * Whether a key, or a folder prefix, should appear in a listing: the user
+ * can view it, or it is a parent folder of a prefix the user can view
+ */
+export function canSeeKey(
+  access: UserAccess,
+  bucket: string,
+  key: string,
+): boolean {
+  if (access.token?.buckets && !access.token.buckets.includes(bucket)) {
+    return false;
+  }
+  if (!access.enforced) {
+    return true;
+  }
+  return access.grants.some(
+    (grant) =>
+      grantCoversBucket(grant, bucket) &&
+      (key.startsWith(grant.prefix) || grant.prefix.startsWith(key)),
+  );
+}
+
+/**
+ * Highest role the user holds anywhere, or null if none
+ */
+export function getHighestRole(access: UserAccess): AccessRole | null {
+  if (!access.enforced) {
+    return "admin";
+  }
+  let highest: AccessRole | null = null;
+  for (const grant of access.grants) {
+    if (highest === null || ROLE_RANK[grant.role] > ROLE_RANK[highest]) {
+      highest = grant.role;
+    }
+  }
+  return highest;
+}
+
+/**
+ * Access resolved for this request by authorizeRequest
+ */
+export function getRequestAccess(request: Request): UserAccess | undefined {
+  return requestAccess.get(request);
+}
+
+// ============================================
+// Route → access check resolution
+// ============================================
+
+interface TransferBody {
+  destinationBucket?: string;
+  destinationPath?: string;
+}
+
+function decodeKey(part: string | undefined): string {
+  return decodeURIComponent(part ?? "");
+}
+
+function decodePath(parts: string[]): string {
+  return decodeURIComponent(parts.join("/"));
+}
+
+function asFolder(path: string): string {
+  return path === "" || path.endsWith("/") ? path : path + "/";
+}
+
+function joinKey(folder: string, name: string): string {
+  return folder === "" ? name : asFolder(folder) + name;
+}
+
+/**
+ * Read a JSON body without consuming the request the route will read
+ */
+async function peekJson<T>(request: Request): Promise<Partial<T>> {
+  try {
+    const body = (await request.clone().json()) as Partial<T> | null;
+    return body ?? {};
+  } catch {
+    return {};
+  }
+}
+
+/**
+ * Destination check for file/folder copy and move
+ */
+function destinationCheck(
+  body: Partial<TransferBody>,
+  key: string,
+): AccessCheck[] {
+  if (!body.destinationBucket) {
+    return [];
+  }
+  return [
+    {
+      role: "editor",
+      scope: "key",
+      bucket: body.destinationBucket,
+      key,
+    },
+  ];
+}
+
+/**
+ * Checks for a file route, or null if its body is malformed
+ */
+async function resolveFileChecks(
+  request: Request,
+  url: URL,
+): Promise<AccessCheck[] | null> {
+  const method = request.method;
+  const parts = url.pathname.split("/");
+  const bucket = decodeURIComponent(parts[3] ?? "");
+  const action = parts[4];
+  const last = parts[parts.length - 1];
+
+  if (method === "POST" && url.pathname === "/api/files/download-buckets-zip") {
+    const body = await peekJson<{
+      buckets: { bucketName: string; files: string[] }[];
+    }>(request);
+    const buckets: unknown = body.buckets ?? [];
+    if (
+      !Array.isArray(buckets) ||
+      !buckets.every(
+        (entry: { bucketName?: unknown; files?: unknown } | null) =>
+          typeof entry?.bucketName === "string" &&
+          Array.isArray(entry.files) &&
+          entry.files.every((key) => typeof key === "string"),
+      )
+    ) {
+      return null;
+    }
+    return (body.buckets ?? []).flatMap((entry) =>
+      entry.files.map(
+        (key): AccessCheck => ({
+          role: "viewer",
+          scope: "key",
+          bucket: entry.bucketName,
+          key,
+        }),
+      ),
+    );
+  }
+
+  // GET /api/files/:bucket - listing, filtered to what the user can see
+  if (method === "GET" && parts.length === 4) {
+    return [{ role: "viewer", scope: "any", bucket }];
+  }
+
+  if (method === "POST" && action === "download-zip") {
+    const body = await peekJson<{ files: string[] }>(request);
+    const files = body.files ?? [];
+    return files.length > 0
+      ? files.map(
+          (key): AccessCheck => ({ role: "viewer", scope: "key", bucket, key }),
+        )
+      : [{ role: "viewer", scope: "bucket", bucket }];
+  }
+
+  if (method === "GET" && action === "signed-url") {
+    return [
+      { role: "viewer", scope: "key", bucket, key: decodeKey(parts[5]) },
+    ];
+  }
+
+  if (method === "GET" && action === "uploads") {
+    const key = url.searchParams.get("key");
+    return key
+      ? [{ role: "editor", scope: "key", bucket, key }]
+      : [{ role: "editor", scope: "any", bucket }];
+  }
+
+  if (method === "POST" && action === "upload") {
+    const fileName = request.headers.get("X-File-Name") ?? "";
+    return [
+      {
+        role: "editor",
+        scope: "key",
+        bucket,
+        key: decodeURIComponent(fileName),
+      },
+    ];
+  }
+
+  if (method === "POST" && action === "multipart") {
+    const key =
+      parts[5] === "part"
+        ? (url.searchParams.get("key") ?? "")
+        : ((await peekJson<{ key: string }>(request)).key ?? "");
+    return [{ role: "editor", scope: "key", bucket, key }];
+  }
+
+  if (method === "DELETE" && action === "delete") {
+    return [
+      { role: "editor", scope: "key", bucket, key: decodeKey(parts[5]) },
+    ];
+  }
+
+  if (
+    (method === "POST" && (last === "move" || last === "copy")) ||
+    (method === "PATCH" && last === "rename")
+  ) {
+    const sourceKey = decodePath(parts.slice(4, -1));
+    const sourceCheck: AccessCheck = {
+      role: last === "copy" ? "viewer" : "editor",
+      scope: "key",
+      bucket,
+      key: sourceKey,
+    };
+
+    if (last === "rename") {
+      const body = await peekJson<{ newKey: string }>(request);
+      return [
+        sourceCheck,
+        { role: "editor", scope: "key", bucket, key: body.newKey ?? "" },
+      ];
+    }
+
+    const body = await peekJson<TransferBody>(request);
+    const fileName = sourceKey.split("/").pop() ?? sourceKey;
+    return [
+      sourceCheck,
+      ...destinationCheck(body, joinKey(body.destinationPath ?? "", fileName)),
+    ];
+  }
+
+  // Anything else under a bucket needs edit rights on the whole bucket
+  return [
+    method === "GET"
+      ? { role: "viewer", scope: "bucket", bucket }
+      : { role: "editor", scope: "bucket", bucket },
+  ];
+}
+
+async function resolveFolderChecks(
+  request: Request,
+  url: URL,
+): Promise<AccessCheck[]> {
+  const method = request.method;
+  const parts = url.pathname.split("/");
+  const bucket = decodeURIComponent(parts[3] ?? "");
+  const last = parts[parts.length - 1];
+
+  if (method === "POST" && parts[4] === "create") {
+    const body = await peekJson<{ folderName: string }>(request);
+    const key = asFolder(body.folderName?.trim() ?? "");
+    return [{ role: "editor", scope: "key", bucket, key }];
+  }
+
+  if (method === "PATCH" && parts[4] === "rename") {
+    const body = await peekJson<{ oldPath: string; newPath: string }>(request);
+    return [
+      {
+        role: "editor",
+        scope: "key",
+        bucket,
+        key: asFolder(body.oldPath?.trim() ?? ""),
+      },
+      {
+        role: "editor",
+        scope: "key",
+        bucket,
+        key: asFolder(body.newPath?.trim() ?? ""),
+      },
+    ];
+  }
+
+  if (method === "POST" && (last === "copy" || last === "move")) {
+    const folderPath = asFolder(decodePath(parts.slice(4, -1)));
+    const body = await peekJson<TransferBody>(request);
+    return [
+      {
+        role: last === "copy" ? "viewer" : "editor",
+        scope: "key",
+        bucket,
+        key: folderPath,
+      },
+      ...destinationCheck(body, asFolder(body.destinationPath ?? folderPath)),
+    ];
+  }
+
+  if (method === "DELETE" && parts.length >= 5) {
+    return [
+      {
+        role: "editor",
+        scope: "key",
+        bucket,
+        key: asFolder(decodePath(parts.slice(4))),
+      },
+    ];
+  }
+
+  return [{ role: "editor", scope: "bucket", bucket }];
+}
+
+function resolveBucketChecks(request: Request, url: URL): AccessCheck[] {
+  const method = request.method;
+  const path = url.pathname;
+
+  if (path === "/api/buckets") {
+    // Listing is filtered to the buckets the user can see
+    return method === "GET"
+      ? [{ role: "viewer", scope: "any" }]
+      : [{ role: "admin", scope: "account" }];
+  }
+  if (path === "/api/buckets/colors") {
+    return [{ role: "viewer", scope: "any" }];
+  }
+
+  const bucket = decodeURIComponent(path.split("/")[3] ?? "");
+  if (method === "GET") {
+    return [{ role: "viewer", scope: "any", bucket }];
+  }
+  if (path.endsWith("/color")) {
+    return [{ role: "editor", scope: "bucket", bucket }];
+  }
+  // Renaming creates a new bucket, so it is an account-level operation
+  if (method === "PATCH" && /^\/api\/buckets\/[^/]+$/.test(path)) {
+    return [{ role: "admin", scope: "account" }];
+  }
+  return [{ role: "admin", scope: "bucket", bucket }];
+}
+
+/**
+ * Work out which permissions a request needs. Unknown routes require an
+ * account-wide admin grant. Returns null if the request body is too
+ * malformed to tell.
+ */
+export async function resolveAccessChecks(
+  request: Request,
+  url: URL,
+): Promise<AccessCheck[] | null> {
+  const method = request.method;
+  const path = url.pathname;
+  const readRole: AccessRole = method === "GET" ? "viewer" : "admin";
+
+  if (path === "/api/access/me") {
+    return [];
+  }
+  if (path.startsWith("/api/access")) {
+    return [{ role: "admin", scope: "account" }];
+  }
+  if (path.startsWith("/api/tokens")) {
+    return [{ role: "viewer", scope: "any" }];
+  }
+  if (path.startsWith("/api/files/")) {
+    return resolveFileChecks(request, url);
+  }
+  if (path.startsWith("/api/folders/")) {
+    return resolveFolderChecks(request, url);
+  }
+  if (path.startsWith("/api/buckets")) {
+    return resolveBucketChecks(request, url);
+  }
+  if (
+    path.startsWith("/api/metrics") ||
+    path === "/api/health" ||
+    path.startsWith("/api/search") ||
+    path.startsWith("/api/signed-links")
+  ) {
+    return [{ role: "viewer", scope: "any" }];
+  }
+  if (path.startsWith("/api/jobs")) {
+    return [{ role: method === "GET" ? "viewer" : "editor", scope: "any" }];
+  }
+  if (path.startsWith("/api/shares")) {
+    if (method !== "POST") {
+      return [{ role: "viewer", scope: "any" }];
+    }
+    const body = await peekJson<{ bucket: string; key: string }>(request);
+    return [
+      {
+        role: "editor",
+        scope: "key",
+        bucket: body.bucket ?? "",
+        key: body.key ?? "",
+      },
+    ];
+  }
+  if (path.startsWith("/api/tags")) {
+    return [
+      method === "GET"
+        ? { role: "viewer", scope: "any" }
+        : { role: "admin", scope: "account" },
+    ];
+  }
+
+  const trashRoute = /^\/api\/trash\/([^/]+)(\/.*)?$/.exec(path);
+  if (trashRoute !== null) {
+    const bucket = decodeURIComponent(trashRoute[1] ?? "");
+    // Trash settings and emptying the trash are bucket settings. Items are
+    // checked against their original keys by the route.
+    const bucketWide =
+      trashRoute[2] === "/settings" ||
+      (method === "DELETE" && trashRoute[2] === undefined);
+    return [
+      bucketWide
+        ? { role: "admin", scope: "bucket", bucket }
+        : { role: "editor", scope: "any", bucket },
+    ];
+  }
+
+  const bucketRoute =
+    /^\/api\/(lifecycle|local-uploads|bucket-settings|event-notifications)\/([^/]+)(\/.*)?$/.exec(
+      path,
+    );
+  if (bucketRoute !== null) {
+    return [
+      {
+        role: readRole,
+        scope: "bucket",
+        bucket: decodeURIComponent(bucketRoute[2] ?? ""),
+      },
+    ];
+  }
+
+  const compatibility = /^\/api\/ai-search\/compatibility\/([^/]+)$/.exec(path);
+  if (compatibility !== null) {
+    return [
+      {
+        role: "viewer",
+        scope: "bucket",
+        bucket: decodeURIComponent(compatibility[1] ?? ""),
+      },
+    ];
+  }
+  if (path.startsWith("/api/ai-search")) {
+    // Searching an instance reads every bucket it indexes
+    if (method === "POST" && /\/(search|ai-search)$/.test(path)) {
+      return [{ role: "viewer", scope: "account" }];
+    }
+    return [
+      method === "GET"
+        ? { role: "viewer", scope: "any" }
+        : { role: "admin", scope: "account" },
+    ];
+  }
+
+  // Audit log, webhooks, S3 import, migrations and anything new
+  return [{ role: "admin", scope: "account" }];
+}
+
+/**
+ * Permissions an API token needs for a request: reads need "read", deletes
+ * need "delete", moves and renames need "write" and "delete", and anything
+ * else that changes data needs "write"
+ */
+export function resolveTokenPermissions(
+  request: Request,
+  url: URL,
+): ApiTokenPermission[] {
+  const method = request.method;
+  const path = url.pathname;
+
+  if (method === "GET" || method === "HEAD") {
+    return ["read"];
+  }
+  if (method === "DELETE") {
+    return ["delete"];
+  }
+  if (
+    method === "POST" &&
+    (path === "/api/files/download-buckets-zip" ||
+      path.endsWith("/download-zip") ||
+      /^\/api\/ai-search\/[^/]+\/(search|ai-search)$/.test(path))
+  ) {
+    return ["read"];
+  }
+  if (
+    (path.startsWith("/api/files/") || path.startsWith("/api/folders/")) &&
+    (path.endsWith("/move") || path.endsWith("/rename"))
+  ) {
+    return ["write", "delete"];
+  }
+  return ["write"];
+}
+
+/**
+ * Refusal reason if an API token may not make this request, null otherwise.
+ * Tokens cannot manage access grants or other tokens.
+ */
+function checkTokenPermissions(
+  token: ApiTokenScope,
+  request: Request,
+  url: URL,
+): string | null {
+  const path = url.pathname;
+  if (
+    path.startsWith("/api/tokens") ||
+    (path.startsWith("/api/access") && path !== "/api/access/me")
+  ) {
+    return "API tokens cannot manage access or API tokens";
+  }
+
+  const missing = resolveTokenPermissions(request, url).find(
+    (permission) => !token.permissions.includes(permission),
+  );
+  return missing !== undefined
+    ? `API token "${token.name}" lacks the ${missing} permission`
+    : null;
+}
+
+function describeCheckTarget(check: AccessCheck): string {
+  switch (check.scope) {
+    case "account":
+      return "all buckets";
+    case "any":
+      return check.bucket ?? "a bucket";
+    case "bucket":
+      return check.bucket;
+    case "key":
+      return `${check.bucket}/${check.key}`;
+  }
+}
+
+/**
+ * Resolve and check the permissions a request needs
+ */
+export async function authorizeRequest(
+  request: Request,
+  env: Env,
+  url: URL,
+  identity: AccessIdentity,
+): Promise<AuthorizationResult> {
+  const access = await loadUserAccess(env, identity);
+  requestAccess.set(request, access);
+
+  if (access.token) {
+    const tokenRefusal = checkTokenPermissions(access.token, request, url);
+    if (tokenRefusal !== null) {
+      return { allowed: false, access, reason: tokenRefusal, status: 403 };
+    }
+  } else if (!access.enforced) {
+    return { allowed: true, access };
+  }
+
+  const checks = await resolveAccessChecks(request, url);
+  if (checks === null) {
+    return {
+      allowed: false,
+      access,
+      reason: "Malformed request body",
+      status: 400,
+    };
+  }
+  const failed = checks.find((check) => !satisfiesCheck(access, check));
+  if (failed === undefined) {
+    return { allowed: true, access };
+  }
+
+  return {
+    allowed: false,
+    access,
+    reason: `${failed.role} access to ${describeCheckTarget(failed)} is required`,
+    status: 403,
+  };
+}
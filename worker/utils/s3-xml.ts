@@ -0,0 +1,286 @@
+/**
+ * S3 XML Documents
+ *
+ * Renders the XML responses of the S3-compatible endpoint and parses the XML
+ * request bodies of DeleteObjects and CompleteMultipartUpload.
+ */
+
+import type { StorageObject } from "../types";
+
+const XML_DECLARATION = '<?xml version="1.0" encoding="UTF-8"?>';
+const S3_NAMESPACE = "http://s3.amazonaws.com/doc/2006-03-01/";
+
+export function escapeXml(value: string): string {
+  return value
+    .replace(/&/g, "&amp;")
+    .replace(/</g, "&lt;")
+    .replace(/>/g, "&gt;")
+    .replace(/"/g, "&quot;")
+    .replace(/'/g, "&apos;");
+}
+
+function unescapeXml(value: string): string {
+  return value
+    .replace(/&lt;/g, "<")
+    .replace(/&gt;/g, ">")
+    .replace(/&quot;/g, '"')
+    .replace(/&apos;/g, "'")
+    .replace(/&#x([0-9a-f]+);/gi, (_, hex: string) =>
+      String.fromCodePoint(parseInt(hex, 16)),
+    )
+    .replace(/&#(\d+);/g, (_, code: string) =>
+      String.fromCodePoint(parseInt(code, 10)),
+    )
+    .replace(/&amp;/g, "&");
+}
+
+function element(name: string, value: string | number | boolean): string {
+  return `<${name}>${escapeXml(String(value))}</${name}>`;
+}
+
+function document(root: string, body: string): string {
+  return `${XML_DECLARATION}<${root} xmlns="${S3_NAMESPACE}">${body}</${root}>`;
+}
+
+/**
+ * Raw contents of every <name>...</name> element in an XML fragment
+ */
+function matchElements(xml: string, name: string): string[] {
+  const pattern = new RegExp(`<${name}>([\\s\\S]*?)</${name}>`, "g");
+  return Array.from(xml.matchAll(pattern), (match) => match[1] ?? "");
+}
+
+/**
+ * Text of every <name>...</name> element in an XML fragment
+ */
+function readElements(xml: string, name: string): string[] {
+  return matchElements(xml, name).map(unescapeXml);
+}
+
+/**
+ * Keys in list responses are URL-encoded (keeping "/") when the client asks
+ * for encoding-type=url
+ */
+function encodeKey(key: string, urlEncode: boolean): string {
+  return urlEncode ? encodeURIComponent(key).replace(/%2F/g, "/") : key;
+}
+
+const quoteEtag = (etag: string): string => `"${etag}"`;
+
+export function renderS3Error(
+  code: string,
+  message: string,
+  resource: string,
+  requestId: string,
+): string {
+  return (
+    XML_DECLARATION +
+    "<Error>" +
+    element("Code", code) +
+    element("Message", message) +
+    element("Resource", resource) +
+    element("RequestId", requestId) +
+    "</Error>"
+  );
+}
+
+export function renderListBuckets(
+  buckets: { name: string; creationDate: string }[],
+  owner: string,
+): string {
+  return document(
+    "ListAllMyBucketsResult",
+    `<Owner>${element("ID", owner)}${element("DisplayName", owner)}</Owner>` +
+      "<Buckets>" +
+      buckets
+        .map(
+          (bucket) =>
+            "<Bucket>" +
+            element("Name", bucket.name) +
+            element("CreationDate", bucket.creationDate) +
+            "</Bucket>",
+        )
+        .join("") +
+      "</Buckets>",
+  );
+}
+
+export interface S3ListObjectsResult {
+  /** 2 for ListObjectsV2, 1 for the original ListObjects */
+  version: 1 | 2;
+  bucket: string;
+  prefix: string;
+  delimiter?: string | undefined;
+  maxKeys: number;
+  urlEncode: boolean;
+  objects: StorageObject[];
+  commonPrefixes: string[];
+  truncated: boolean;
+  /** V2: continuation-token, start-after and NextContinuationToken */
+  continuationToken?: string | undefined;
+  startAfter?: string | undefined;
+  nextContinuationToken?: string | undefined;
+  /** V1: marker and NextMarker */
+  marker?: string | undefined;
+  nextMarker?: string | undefined;
+}
+
+export function renderListObjects(result: S3ListObjectsResult): string {
+  const { urlEncode } = result;
+  const parts = [
+    element("Name", result.bucket),
+    element("Prefix", encodeKey(result.prefix, urlEncode)),
+  ];
+
+  if (result.version === 2) {
+    if (result.continuationToken !== undefined) {
+      parts.push(element("ContinuationToken", result.continuationToken));
+    }
+    if (result.startAfter !== undefined) {
+      parts.push(
+        element("StartAfter", encodeKey(result.startAfter, urlEncode)),
+      );
+    }
+    parts.push(
+      element("KeyCount", result.objects.length + result.commonPrefixes.length),
+    );
+    if (result.nextContinuationToken !== undefined) {
+      parts.push(
+        element("NextContinuationToken", result.nextContinuationToken),
+      );
+    }
+  } else {
+    parts.push(element("Marker", encodeKey(result.marker ?? "", urlEncode)));
+    if (result.nextMarker !== undefined) {
+      parts.push(
+        element("NextMarker", encodeKey(result.nextMarker, urlEncode)),
+      );
+    }
+  }
+
+  parts.push(element("MaxKeys", result.maxKeys));
+  if (result.delimiter !== undefined) {
+    parts.push(element("Delimiter", encodeKey(result.delimiter, urlEncode)));
+  }
+  if (urlEncode) {
+    parts.push(element("EncodingType", "url"));
+  }
+  parts.push(element("IsTruncated", result.truncated));
+
+  for (const object of result.objects) {
+    parts.push(
+      "<Contents>" +
+        element("Key", encodeKey(object.key, urlEncode)) +
+        element("LastModified", new Date(object.uploaded).toISOString()) +
+        element("ETag", quoteEtag(object.etag)) +
+        element("Size", object.size) +
+        element("StorageClass", "STANDARD") +
+        "</Contents>",
+    );
+  }
+  for (const prefix of result.commonPrefixes) {
+    parts.push(
+      "<CommonPrefixes>" +
+        element("Prefix", encodeKey(prefix, urlEncode)) +
+        "</CommonPrefixes>",
+    );
+  }
+
+  return document("ListBucketResult", parts.join(""));
+}
+
+export function renderLocationConstraint(): string {
+  return document("LocationConstraint", "");
+}
+
+export function renderCopyObjectResult(
+  etag: string,
+  lastModified: string,
+): string {
+  return document(
+    "CopyObjectResult",
+    element("LastModified", new Date(lastModified).toISOString()) +
+      element("ETag", quoteEtag(etag)),
+  );
+}
+
+export function renderInitiateMultipartUpload(
+  bucket: string,
+  key: string,
+  uploadId: string,
+): string {
+  return document(
+    "InitiateMultipartUploadResult",
+    element("Bucket", bucket) +
+      element("Key", key) +
+      element("UploadId", uploadId),
+  );
+}
+
+export function renderCompleteMultipartUpload(
+  location: string,
+  bucket: string,
+  key: string,
+  etag: string,
+): string {
+  return document(
+    "CompleteMultipartUploadResult",
+    element("Location", location) +
+      element("Bucket", bucket) +
+      element("Key", key) +
+      element("ETag", quoteEtag(etag)),
+  );
+}
+
+export function renderDeleteResult(
+  deleted: string[],
+  errors: { key: string; code: string; message: string }[],
+  quiet: boolean,
+): string {
+  return document(
+    "DeleteResult",
+    (quiet
+      ? ""
+      : deleted
+          .map((key) => `<Deleted>${element("Key", key)}</Deleted>`)
+          .join("")) +
+      errors
+        .map(
+          (error) =>
+            "<Error>" +
+            element("Key", error.key) +
+            element("Code", error.code) +
+            element("Message", error.message) +
+            "</Error>",
+        )
+        .join(""),
+  );
+}
+
+/**
+ * Keys and quiet flag of a DeleteObjects request body
+ */
+export function parseDeleteRequest(xml: string): {
+  keys: string[];
+  quiet: boolean;
+} {
+  return {
+    keys: matchElements(xml, "Object").flatMap((object) =>
+      readElements(object, "Key"),
+    ),
+    quiet: readElements(xml, "Quiet")[0]?.trim().toLowerCase() === "true",
+  };
+}
+
+/**
+ * Parts listed in a CompleteMultipartUpload request body, with unquoted
+ * ETags
+ */
+export function parseCompleteMultipartUpload(
+  xml: string,
+): { partNumber: number; etag: string }[] {
+  return matchElements(xml, "Part").map((part) => ({
+    partNumber: Number(readElements(part, "PartNumber")[0]),
+    etag: (readElements(part, "ETag")[0] ?? "").trim().replace(/"/g, ""),
+  }));
+}
@@ -0,0 +1,218 @@
+/**
+ * Share Landing Pages
+ *
+ * Minimal, self-contained HTML pages served to share link visitors. Visitors
+ * are not behind Cloudflare Access, so the pages reference no app assets:
+ * styles are inlined and the Content-Security-Policy blocks everything else.
+ */
+
+export interface ShareFileEntry {
+  name: string;
+  size: number;
+  downloadUrl: string;
+}
+
+export interface ShareFolderEntry {
+  name: string;
+  browseUrl: string;
+}
+
+export interface ShareBreadcrumb {
+  name: string;
+  url: string;
+}
+
+const PAGE_STYLES = `
+  :root { color-scheme: light dark; --accent: #f6821f; --muted: #6b7280; --border: #e5e7eb; --card: #ffffff; --bg: #f9fafb; --text: #111827; }
+  @media (prefers-color-scheme: dark) { :root { --muted: #9ca3af; --border: #374151; --card: #1f2937; --bg: #111827; --text: #f9fafb; } }
+  * { box-sizing: border-box; }
+  body { margin: 0; font-family: system-ui, -apple-system, "Segoe UI", Roboto, sans-serif; background: var(--bg); color: var(--text); }
+  header { display: flex; align-items: center; gap: 0.5rem; padding: 1rem 1.5rem; border-bottom: 1px solid var(--border); font-weight: 600; }
+  header span { color: var(--accent); }
+  main { max-width: 48rem; margin: 2rem auto; padding: 0 1rem; }
+  .card { background: var(--card); border: 1px solid var(--border); border-radius: 0.75rem; padding: 1.5rem; }
+  h1 { margin: 0 0 0.25rem; font-size: 1.25rem; word-break: break-all; }
+  .meta { color: var(--muted); font-size: 0.875rem; margin: 0 0 1.25rem; }
+  .button { display: inline-block; padding: 0.625rem 1.25rem; border: none; border-radius: 0.5rem; background: var(--accent); color: #fff; font-size: 0.9375rem; text-decoration: none; cursor: pointer; }
+  .button.secondary { background: transparent; color: var(--text); border: 1px solid var(--border); }
+  .list { list-style: none; margin: 0 0 1.25rem; padding: 0; border: 1px solid var(--border); border-radius: 0.5rem; }
+  .list li { display: flex; justify-content: space-between; align-items: center; gap: 1rem; padding: 0.625rem 1rem; border-top: 1px solid var(--border); }
+  .list li:first-child { border-top: none; }
+  .list a { color: inherit; word-break: break-all; }
+  .list .size { color: var(--muted); font-size: 0.8125rem; white-space: nowrap; }
+  .crumbs { font-size: 0.875rem; margin-bottom: 1rem; color: var(--muted); }
+  .crumbs a { color: var(--accent); }
+  .actions { display: flex; gap: 0.75rem; flex-wrap: wrap; }
+  input[type="password"] { width: 100%; padding: 0.625rem; margin: 0 0 1rem; border: 1px solid var(--border); border-radius: 0.5rem; background: var(--bg); color: var(--text); font-size: 1rem; }
+  .error { color: #dc2626; font-size: 0.875rem; margin: 0 0 1rem; }
+  footer { text-align: center; color: var(--muted); font-size: 0.75rem; margin: 2rem 0; }
+`;
+
+export function escapeHtml(value: string): string {
+  return value
+    .replace(/&/g, "&amp;")
+    .replace(/</g, "&lt;")
+    .replace(/>/g, "&gt;")
+    .replace(/"/g, "&quot;")
+    .replace(/'/g, "&#39;");
+}
+
+function formatBytes(bytes: number): string {
+  if (bytes < 1024) return `${String(bytes)} B`;
+  const units = ["KB", "MB", "GB", "TB"];
+  let value = bytes / 1024;
+  let unit = 0;
+  while (value >= 1024 && unit < units.length - 1) {
+    value /= 1024;
+    unit++;
+  }
+  return `${value.toFixed(1)} ${units[unit] ?? "TB"}`;
+}
+
+function formatExpiry(expiresAt: string): string {
+  return new Date(expiresAt).toUTCString();
+}
+
+/**
+ * Wrap page content in the branded layout
+ */
+export function renderSharePage(
+  title: string,
+  content: string,
+  status = 200,
+  headers: Record<string, string> = {},
+): Response {
+  const html = `<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<meta name="robots" content="noindex, nofollow">
+<title>${escapeHtml(title)} - R2 Bucket Manager</title>
+<style>${PAGE_STYLES}</style>
+</head>
+<body>
+<header>&#9729;&#65039; <span>R2</span> Bucket Manager</header>
+<main>${content}</main>
+<footer>Shared with R2 Bucket Manager</footer>
+</body>
+</html>`;
+
+  return new Response(html, {
+    status,
+    headers: {
+      "Content-Type": "text/html; charset=utf-8",
+      "Content-Security-Policy":
+        "default-src 'none'; style-src 'unsafe-inline'; form-action 'self'; frame-ancestors 'none'",
+      "Referrer-Policy": "no-referrer",
+      "X-Content-Type-Options": "nosniff",
+      "Cache-Control": "no-store",
+      ...headers,
+    },
+  });
+}
+
+/**
+ * Page shown for unknown, revoked, expired or used-up shares
+ */
+export function renderShareUnavailable(
+  message: string,
+  status: number,
+): Response {
+  return renderSharePage(
+    "Share unavailable",
+    `<div class="card"><h1>Share unavailable</h1><p class="meta">${escapeHtml(message)}</p></div>`,
+    status,
+  );
+}
+
+/**
+ * Password prompt for protected shares
+ */
+export function renderSharePasswordForm(
+  shareId: string,
+  error?: string,
+): Response {
+  return renderSharePage(
+    "Password required",
+    `<div class="card">
+<h1>Password required</h1>
+<p class="meta">Enter the password you were given to open this share.</p>
+<form method="post" action="/share/${escapeHtml(shareId)}">
+${error !== undefined ? `<p class="error">${escapeHtml(error)}</p>` : ""}
+<input type="password" name="password" autocomplete="current-password" required autofocus>
+<button class="button" type="submit">Open</button>
+</form>
+</div>`,
+    error !== undefined ? 401 : 200,
+  );
+}
+
+/**
+ * Landing page for a single shared file
+ */
+export function renderFileShare(
+  file: ShareFileEntry,
+  expiresAt: string,
+): Response {
+  return renderSharePage(
+    file.name,
+    `<div class="card">
+<h1>${escapeHtml(file.name)}</h1>
+<p class="meta">${formatBytes(file.size)} &middot; Link expires ${escapeHtml(formatExpiry(expiresAt))}</p>
+<a class="button" href="${escapeHtml(file.downloadUrl)}">Download</a>
+</div>`,
+  );
+}
+
+/**
+ * Landing page for a shared folder (one level of the folder at a time)
+ */
+export function renderFolderShare(params: {
+  title: string;
+  expiresAt: string;
+  breadcrumbs: ShareBreadcrumb[];
+  folders: ShareFolderEntry[];
+  files: ShareFileEntry[];
+  zipUrl?: string | undefined;
+  nextPageUrl?: string | undefined;
+}): Response {
+  const crumbs = params.breadcrumbs
+    .map((crumb, index) =>
+      index === params.breadcrumbs.length - 1
+        ? escapeHtml(crumb.name)
+        : `<a href="${escapeHtml(crumb.url)}">${escapeHtml(crumb.name)}</a>`,
+    )
+    .join(" / ");
+
+  const rows = [
+    ...params.folders.map(
+      (folder) =>
+        `<li><a href="${escapeHtml(folder.browseUrl)}">&#128193; ${escapeHtml(folder.name)}</a></li>`,
+    ),
+    ...params.files.map(
+      (file) =>
+        `<li><a href="${escapeHtml(file.downloadUrl)}">${escapeHtml(file.name)}</a><span class="size">${formatBytes(file.size)}</span></li>`,
+    ),
+  ];
+
+  const actions = [
+    params.zipUrl !== undefined
+      ? `<a class="button" href="${escapeHtml(params.zipUrl)}">Download all as ZIP</a>`
+      : "",
+    params.nextPageUrl !== undefined
+      ? `<a class="button secondary" href="${escapeHtml(params.nextPageUrl)}">More files</a>`
+      : "",
+  ].join("");
+
+  return renderSharePage(
+    params.title,
+    `<div class="card">
+<h1>${escapeHtml(params.title)}</h1>
+<p class="meta">Shared folder &middot; Link expires ${escapeHtml(formatExpiry(params.expiresAt))}</p>
+<div class="crumbs">${crumbs}</div>
+${rows.length > 0 ? `<ul class="list">${rows.join("")}</ul>` : `<p class="meta">This folder is empty.</p>`}
+<div class="actions">${actions}</div>
+</div>`,
+  );
+}
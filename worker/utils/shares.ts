@@ -0,0 +1,282 @@
+/**
+ * Share Links
+ *
+ * Public share links for a file or a whole folder. Share records live in D1;
+ * visitors open a landing page at /share/:id, optionally unlock it with a
+ * password, and download through the signed /download/ route, which counts
+ * each download against the share.
+ */
+
+import type { CreateShareParams, Env, Share, ShareStatus } from "../types";
+import { generateSignature, verifySignature } from "./signing";
+
+/** Default lifetime of a share (7 days) */
+export const DEFAULT_SHARE_TTL_SECONDS = 7 * 24 * 60 * 60;
+
+/** Longest lifetime a share may be given (30 days) */
+export const MAX_SHARE_TTL_SECONDS = 30 * 24 * 60 * 60;
+
+/** How long an unlocked password-protected share stays unlocked (12 hours) */
+export const SHARE_SESSION_TTL_SECONDS = 12 * 60 * 60;
+
+/** Lifetime of the download URLs rendered on a landing page (1 hour) */
+export const SHARE_DOWNLOAD_URL_TTL_SECONDS = 60 * 60;
+
+export const MIN_SHARE_PASSWORD_LENGTH = 6;
+
+const SHARE_SESSION_COOKIE = "share_session";
+const PBKDF2_ITERATIONS = 100_000;
+
+const encoder = new TextEncoder();
+
+function toHex(bytes: Uint8Array): string {
+  return Array.from(bytes)
+    .map((b) => b.toString(16).padStart(2, "0"))
+    .join("");
+}
+
+/**
+ * Generate a random, unguessable share ID
+ */
+export function generateShareId(): string {
+  const bytes = new Uint8Array(16);
+  crypto.getRandomValues(bytes);
+  return "shr_" + toHex(bytes);
+}
+
+async function derivePasswordHash(
+  password: string,
+  salt: Uint8Array,
+  iterations: number,
+): Promise<Uint8Array> {
+  const key = await crypto.subtle.importKey(
+    "raw",
+    encoder.encode(password),
+    "PBKDF2",
+    false,
+    ["deriveBits"],
+  );
+  const bits = await crypto.subtle.deriveBits(
+    { name: "PBKDF2", hash: "SHA-256", salt, iterations },
+    key,
+    256,
+  );
+  return new Uint8Array(bits);
+}
+
+/**
+ * Hash a share password as "pbkdf2$iterations$salt$hash"
+ */
+export async function hashSharePassword(password: string): Promise<string> {
+  const salt = new Uint8Array(16);
+  crypto.getRandomValues(salt);
+  const hash = await derivePasswordHash(password, salt, PBKDF2_ITERATIONS);
+  return `pbkdf2$${String(PBKDF2_ITERATIONS)}$${toHex(salt)}$${toHex(hash)}`;
+}
+
+/**
+ * Check a password against a stored hash (constant-time comparison)
+ */
+export async function verifySharePassword(
+  password: string,
+  storedHash: string,
+): Promise<boolean> {
+  const [scheme, iterations, saltHex, hashHex] = storedHash.split("$");
+  if (
+    scheme !== "pbkdf2" ||
+    iterations === undefined ||
+    saltHex === undefined ||
+    hashHex === undefined
+  ) {
+    return false;
+  }
+
+  const salt = new Uint8Array(
+    (saltHex.match(/.{2}/g) ?? []).map((byte) => parseInt(byte, 16)),
+  );
+  const hash = toHex(
+    await derivePasswordHash(password, salt, Number(iterations)),
+  );
+  if (hash.length !== hashHex.length) {
+    return false;
+  }
+
+  let diff = 0;
+  for (let i = 0; i < hash.length; i++) {
+    diff |= hash.charCodeAt(i) ^ hashHex.charCodeAt(i);
+  }
+  return diff === 0;
+}
+
+/**
+ * Record a newly created share
+ */
+export async function createShare(
+  db: D1Database,
+  params: CreateShareParams,
+): Promise<void> {
+  await db
+    .prepare(
+      `
+    INSERT INTO shares (
+      share_id, bucket_name, object_key, share_type, created_by, created_at,
+      expires_at, password_hash, max_downloads, allow_zip
+    )
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+  `,
+    )
+    .bind(
+      params.shareId,
+      params.bucketName,
+      params.objectKey,
+      params.shareType,
+      params.createdBy,
+      new Date().toISOString(),
+      params.expiresAt.toISOString(),
+      params.passwordHash ?? null,
+      params.maxDownloads ?? null,
+      params.allowZip ? 1 : 0,
+    )
+    .run();
+}
+
+export async function getShare(
+  db: D1Database,
+  shareId: string,
+): Promise<Share | null> {
+  return db
+    .prepare("SELECT * FROM shares WHERE share_id = ?")
+    .bind(shareId)
+    .first<Share>();
+}
+
+/**
+ * Derive a share's status from its row
+ */
+export function getShareStatus(share: Share, now = Date.now()): ShareStatus {
+  if (share.revoked_at !== null) return "revoked";
+  if (new Date(share.expires_at).getTime() <= now) return "expired";
+  if (
+    share.max_downloads !== null &&
+    share.download_count >= share.max_downloads
+  ) {
+    return "exhausted";
+  }
+  return "active";
+}
+
+/**
+ * Check whether a share can still be used, without counting a download.
+ * Returns null if it can, or the reason it cannot.
+ */
+export async function checkShare(
+  db: D1Database,
+  shareId: string,
+): Promise<Exclude<ShareStatus, "active"> | null> {
+  const share = await getShare(db, shareId);
+  if (share === null) {
+    return "revoked";
+  }
+  const status = getShareStatus(share);
+  return status === "active" ? null : status;
+}
+
+/**
+ * Count a download against a share. Like signed links, the counter is only
+ * incremented while the share is usable, so concurrent downloads can never
+ * exceed max_downloads. Returns null on success, or the reason the download
+ * was refused.
+ */
+export async function consumeShareDownload(
+  db: D1Database,
+  shareId: string,
+): Promise<Exclude<ShareStatus, "active"> | null> {
+  const now = new Date().toISOString();
+  const updated = await db
+    .prepare(
+      `
+    UPDATE shares SET
+      download_count = download_count + 1,
+      last_accessed_at = ?
+    WHERE share_id = ?
+      AND revoked_at IS NULL
+      AND expires_at > ?
+      AND (max_downloads IS NULL OR download_count < max_downloads)
+    RETURNING download_count
+  `,
+    )
+    .bind(now, shareId, now)
+    .first<{ download_count: number }>();
+
+  if (updated !== null) {
+    return null;
+  }
+  return (await checkShare(db, shareId)) ?? "exhausted";
+}
+
+/**
+ * Record that a share's landing page was opened
+ */
+export async function touchShare(
+  db: D1Database,
+  shareId: string,
+): Promise<void> {
+  await db
+    .prepare("UPDATE shares SET last_accessed_at = ? WHERE share_id = ?")
+    .bind(new Date().toISOString(), shareId)
+    .run();
+}
+
+function getShareSessionMessage(shareId: string, expires: number): string {
+  return `share:${shareId}:${String(expires)}`;
+}
+
+/**
+ * Set-Cookie value that keeps a password-protected share unlocked. The
+ * cookie is scoped to the share's path and signed with URL_SIGNING_KEY.
+ */
+export async function createShareSessionCookie(
+  shareId: string,
+  env: Env,
+): Promise<string> {
+  const expires = Math.floor(Date.now() / 1000) + SHARE_SESSION_TTL_SECONDS;
+  const signature = await generateSignature(
+    getShareSessionMessage(shareId, expires),
+    env,
+  );
+  return `${SHARE_SESSION_COOKIE}=${String(expires)}.${signature}; Path=/share/${shareId}; Max-Age=${String(SHARE_SESSION_TTL_SECONDS)}; HttpOnly; Secure; SameSite=Lax`;
+}
+
+/**
+ * Whether the request carries a valid, unexpired session cookie for a share
+ */
+export async function hasShareSession(
+  request: Request,
+  shareId: string,
+  env: Env,
+): Promise<boolean> {
+  const cookies = request.headers.get("Cookie") ?? "";
+  const value = cookies
+    .split(";")
+    .map((cookie) => cookie.trim())
+    .find((cookie) => cookie.startsWith(SHARE_SESSION_COOKIE + "="))
+    ?.substring(SHARE_SESSION_COOKIE.length + 1);
+  if (value === undefined) {
+    return false;
+  }
+
+  const [expiresPart, signature] = value.split(".");
+  const expires = Number(expiresPart);
+  if (
+    signature === undefined ||
+    !Number.isInteger(expires) ||
+    Date.now() >= expires * 1000
+  ) {
+    return false;
+  }
+  return verifySignature(
+    getShareSessionMessage(shareId, expires),
+    signature,
+    env,
+  );
+}
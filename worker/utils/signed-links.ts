@@ -0,0 +1,144 @@
+/**
+ * Signed Link Tracking
+ *
+ * Persists signed download links in D1 so that per-link download limits can
+ * be enforced and outstanding links can be listed and revoked.
+ */
+
+import type {
+  CreateSignedLinkParams,
+  SignedLink,
+  SignedLinkStatus,
+} from "../types";
+
+/** Default lifetime of a signed link (24 hours) */
+export const DEFAULT_SIGNED_LINK_TTL_SECONDS = 24 * 60 * 60;
+
+/** Longest lifetime a signed link may be given (7 days) */
+export const MAX_SIGNED_LINK_TTL_SECONDS = 7 * 24 * 60 * 60;
+
+/**
+ * Generate a random link ID
+ */
+export function generateLinkId(): string {
+  const bytes = new Uint8Array(12);
+  crypto.getRandomValues(bytes);
+  return (
+    "lnk_" +
+    Array.from(bytes)
+      .map((b) => b.toString(16).padStart(2, "0"))
+      .join("")
+  );
+}
+
+/**
+ * Record a newly issued signed link
+ */
+export async function createSignedLink(
+  db: D1Database,
+  params: CreateSignedLinkParams,
+): Promise<void> {
+  await db
+    .prepare(
+      `
+    INSERT INTO signed_links (
+      link_id, bucket_name, object_key, created_by, created_at, expires_at,
+      ip, max_downloads, disposition
+    )
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+  `,
+    )
+    .bind(
+      params.linkId,
+      params.bucketName,
+      params.objectKey,
+      params.createdBy,
+      new Date().toISOString(),
+      params.expiresAt.toISOString(),
+      params.ip ?? null,
+      params.maxDownloads ?? null,
+      params.disposition,
+    )
+    .run();
+}
+
+/**
+ * Count a download against a link. The counter is only incremented while
+ * the link is not revoked and below its limit, so concurrent downloads can
+ * never exceed max_downloads. Returns null on success, or the reason the
+ * download was refused.
+ */
+export async function consumeSignedLinkDownload(
+  db: D1Database,
+  linkId: string,
+): Promise<"revoked" | "exhausted" | null> {
+  const updated = await db
+    .prepare(
+      `
+    UPDATE signed_links SET
+      download_count = download_count + 1,
+      last_download_at = ?
+    WHERE link_id = ?
+      AND revoked_at IS NULL
+      AND (max_downloads IS NULL OR download_count < max_downloads)
+    RETURNING download_count
+  `,
+    )
+    .bind(new Date().toISOString(), linkId)
+    .first<{ download_count: number }>();
+
+  if (updated !== null) {
+    return null;
+  }
+
+  const link = await getSignedLink(db, linkId);
+  return link === null || link.revoked_at !== null ? "revoked" : "exhausted";
+}
+
+/**
+ * Check whether a link can still be used, without counting a download
+ */
+export async function checkSignedLink(
+  db: D1Database,
+  linkId: string,
+): Promise<"revoked" | "exhausted" | null> {
+  const link = await getSignedLink(db, linkId);
+  if (link === null || link.revoked_at !== null) {
+    return "revoked";
+  }
+  if (
+    link.max_downloads !== null &&
+    link.download_count >= link.max_downloads
+  ) {
+    return "exhausted";
+  }
+  return null;
+}
+
+export async function getSignedLink(
+  db: D1Database,
+  linkId: string,
+): Promise<SignedLink | null> {
+  return db
+    .prepare("SELECT * FROM signed_links WHERE link_id = ?")
+    .bind(linkId)
+    .first<SignedLink>();
+}
+
+/**
+ * Derive a link's status from its row
+ */
+export function getSignedLinkStatus(
+  link: SignedLink,
+  now = Date.now(),
+): SignedLinkStatus {
+  if (link.revoked_at !== null) return "revoked";
+  if (new Date(link.expires_at).getTime() <= now) return "expired";
+  if (
+    link.max_downloads !== null &&
+    link.download_count >= link.max_downloads
+  ) {
+    return "exhausted";
+  }
+  return "active";
+}
@@ -0,0 +1,173 @@
+import { describe, expect, it } from "vitest";
+import type { Env } from "../types";
+import {
+  createSignedPath,
+  isActiveContentType,
+  resolveDisposition,
+  validateSignature,
+  verifySignature,
+} from "./signing";
+
+const env = { URL_SIGNING_KEY: "test-signing-key" } as Env;
+const otherEnv = { URL_SIGNING_KEY: "other-signing-key" } as Env;
+
+const ORIGIN = "https://r2.example.com";
+const PATH = "/api/files/media/download/holiday photo.jpg";
+
+function inOneHour(): number {
+  return Math.floor(Date.now() / 1000) + 3600;
+}
+
+function requestFor(path: string, ip = "203.0.113.7"): Request {
+  return new Request(ORIGIN + path, {
+    headers: { "CF-Connecting-IP": ip },
+  });
+}
+
+/** Replace a query parameter of a signed path, keeping its signature */
+function withParam(path: string, name: string, value: string | null): string {
+  const url = new URL(ORIGIN + path);
+  if (value === null) {
+    url.searchParams.delete(name);
+  } else {
+    url.searchParams.set(name, value);
+  }
+  return url.pathname + url.search;
+}
+
+describe("isActiveContentType", () => {
+  it("flags types a browser could run script from", () => {
+    expect(isActiveContentType("text/html")).toBe(true);
+    expect(isActiveContentType("text/html; charset=utf-8")).toBe(true);
+    expect(isActiveContentType("IMAGE/SVG+XML")).toBe(true);
+    expect(isActiveContentType("application/javascript")).toBe(true);
+    expect(isActiveContentType("application/rss+xml")).toBe(true);
+  });
+
+  it("allows passive types", () => {
+    expect(isActiveContentType("image/png")).toBe(false);
+    expect(isActiveContentType("application/pdf")).toBe(false);
+    expect(isActiveContentType("text/plain")).toBe(false);
+    expect(isActiveContentType("")).toBe(false);
+  });
+});
+
+describe("resolveDisposition", () => {
+  it("keeps inline for passive content", () => {
+    expect(resolveDisposition("inline", "image/png")).toBe("inline");
+  });
+
+  it("downloads active content even from an inline link", () => {
+    expect(resolveDisposition("inline", "text/html")).toBe("attachment");
+    expect(resolveDisposition("inline", "image/svg+xml")).toBe("attachment");
+  });
+
+  it("defaults to attachment", () => {
+    expect(resolveDisposition(undefined, "image/png")).toBe("attachment");
+    expect(resolveDisposition("attachment", "image/png")).toBe("attachment");
+  });
+});
+
+describe("signed download links", () => {
+  it("accepts a link as signed and returns its constraints", async () => {
+    const expires = inOneHour();
+    const path = await createSignedPath(
+      PATH,
+      {
+        expires,
+        ip: "203.0.113.7",
+        maxDownloads: 3,
+        disposition: "inline",
+        linkId: "link-1",
+      },
+      env,
+    );
+
+    expect(await validateSignature(requestFor(path), env)).toEqual({
+      valid: true,
+      params: {
+        expires,
+        ip: "203.0.113.7",
+        maxDownloads: 3,
+        disposition: "inline",
+        linkId: "link-1",
+      },
+    });
+  });
+
+  it("rejects a link whose constraints were changed or removed", async () => {
+    const path = await createSignedPath(
+      PATH,
+      { expires: inOneHour(), ip: "203.0.113.7", maxDownloads: 3 },
+      env,
+    );
+
+    for (const tampered of [
+      withParam(path, "expires", String(inOneHour() + 86400)),
+      withParam(path, "max_downloads", "1000"),
+      withParam(path, "ip", null),
+      withParam(path, "disposition", "inline"),
+      path.replace("holiday", "other"),
+    ]) {
+      expect(await validateSignature(requestFor(tampered), env)).toEqual({
+        valid: false,
+        reason: "Invalid signature",
+        status: 403,
+      });
+    }
+  });
+
+  it("rejects other keys and missing signatures", async () => {
+    const path = await createSignedPath(PATH, { expires: inOneHour() }, env);
+
+    expect(await validateSignature(requestFor(path), otherEnv)).toMatchObject({
+      valid: false,
+      status: 403,
+    });
+    expect(
+      await validateSignature(requestFor(withParam(path, "sig", null)), env),
+    ).toMatchObject({ valid: false, status: 403 });
+    expect(
+      await validateSignature(requestFor(withParam(path, "sig", "zz")), env),
+    ).toMatchObject({ valid: false, status: 403 });
+  });
+
+  it("rejects expired links with 410", async () => {
+    const expires = Math.floor(Date.now() / 1000) - 60;
+    const path = await createSignedPath(PATH, { expires }, env);
+
+    expect(await validateSignature(requestFor(path), env)).toEqual({
+      valid: false,
+      reason: "Link has expired",
+      status: 410,
+    });
+  });
+
+  it("rejects links without an expiry", async () => {
+    const path = await createSignedPath(PATH, { expires: 0 }, env);
+
+    expect(await validateSignature(requestFor(path), env)).toMatchObject({
+      valid: false,
+      reason: "Link has no expiry",
+    });
+  });
+
+  it("rejects requests from another IP address", async () => {
+    const path = await createSignedPath(
+      PATH,
+      { expires: inOneHour(), ip: "203.0.113.7" },
+      env,
+    );
+
+    expect(
+      await validateSignature(requestFor(path, "198.51.100.1"), env),
+    ).toMatchObject({ valid: false, status: 403 });
+  });
+});
+
+describe("verifySignature", () => {
+  it("rejects signatures that are not hex", async () => {
+    expect(await verifySignature("/path", "not-hex", env)).toBe(false);
+    expect(await verifySignature("/path", "", env)).toBe(false);
+  });
+});
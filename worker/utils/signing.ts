@@ -29,22 +29,114 @@ export function getSigningKey(env: Env): string {
   return localSigningKey;
 }
 
-// URL signing functions using HMAC-SHA256
-export async function generateSignature(
-  path: string,
-  env: Env,
-): Promise<string> {
-  const encoder = new TextEncoder();
-  const keyString = getSigningKey(env);
+/**
+ * Signed download links carry their constraints in the query string, and
+ * the HMAC covers the path plus every query parameter, so none of them can
+ * be altered or removed:
+ *
+ *   expires        Unix time (seconds) after which the link is rejected
+ *   ip             Client IP the link is restricted to
+ *   max_downloads  Download limit, counted in D1 against the link ID
+ *   disposition    "attachment" (default) or "inline"
+ *   lid            Link ID in signed_links (counters and revocation)
+ *   share          Share ID in shares (counters, expiry and revocation)
+ *   ts             Object version, used to bust caches for listing URLs
+ */
+export interface SignedUrlParams {
+  expires: number;
+  ip?: string | undefined;
+  maxDownloads?: number | undefined;
+  disposition?: SignedUrlDisposition | undefined;
+  linkId?: string | undefined;
+  shareId?: string | undefined;
+  version?: number | undefined;
+}
+
+export type SignedUrlDisposition = "attachment" | "inline";
+
+/** Content types a browser could run script from if shown inline */
+const ACTIVE_CONTENT_TYPES = [
+  "text/html",
+  "application/xhtml+xml",
+  "image/svg+xml",
+  "text/xml",
+  "application/xml",
+  "text/javascript",
+  "application/javascript",
+  "application/x-javascript",
+  "application/ecmascript",
+];
+
+/**
+ * Whether a browser could run script from a content type if shown inline
+ */
+export function isActiveContentType(contentType: string): boolean {
+  const type = (contentType.split(";")[0] ?? "").trim().toLowerCase();
+  return ACTIVE_CONTENT_TYPES.includes(type) || type.endsWith("+xml");
+}
+
+/**
+ * The Content-Disposition type to serve an object with. HTML, SVG, XML and
+ * JavaScript are always downloaded, even from an inline link, because
+ * uploaded content is served from the app's own origin.
+ */
+export function resolveDisposition(
+  disposition: SignedUrlDisposition | undefined,
+  contentType: string,
+): SignedUrlDisposition {
+  return disposition === "inline" && !isActiveContentType(contentType)
+    ? "inline"
+    : "attachment";
+}
+
+export type SignedUrlValidation =
+  | { valid: true; params: SignedUrlParams }
+  | { valid: false; reason: string; status: 403 | 410 };
+
+const encoder = new TextEncoder();
 
-  // Import the signing key for HMAC
-  const key = await crypto.subtle.importKey(
+async function importSigningKey(
+  env: Env,
+  usage: "sign" | "verify",
+): Promise<CryptoKey> {
+  return crypto.subtle.importKey(
     "raw",
-    encoder.encode(keyString),
+    encoder.encode(getSigningKey(env)),
     { name: "HMAC", hash: "SHA-256" },
     false,
-    ["sign"],
+    [usage],
   );
+}
+
+/**
+ * Canonical form of a link: the decoded path plus its query parameters
+ * (without sig) sorted by name
+ */
+function canonicalize(path: string, searchParams: URLSearchParams): string {
+  const params = new URLSearchParams(searchParams);
+  params.delete("sig");
+  params.sort();
+  const query = params.toString();
+  return query ? path + "?" + query : path;
+}
+
+function hexToBytes(hex: string): Uint8Array | null {
+  if (hex.length === 0 || hex.length % 2 !== 0 || !/^[0-9a-f]+$/i.test(hex)) {
+    return null;
+  }
+  const bytes = new Uint8Array(hex.length / 2);
+  for (let i = 0; i < bytes.length; i++) {
+    bytes[i] = parseInt(hex.substring(i * 2, i * 2 + 2), 16);
+  }
+  return bytes;
+}
+
+// URL signing functions using HMAC-SHA256
+export async function generateSignature(
+  path: string,
+  env: Env,
+): Promise<string> {
+  const key = await importSigningKey(env, "sign");
 
   // Generate HMAC-SHA256 signature
   const signature = await crypto.subtle.sign("HMAC", key, encoder.encode(path));
@@ -55,43 +147,117 @@ export async function generateSignature(
     .join("");
 }
 
+/**
+ * Check an HMAC-SHA256 signature (hex) over a message in constant time
+ */
+export async function verifySignature(
+  message: string,
+  signatureHex: string,
+  env: Env,
+): Promise<boolean> {
+  const signature = hexToBytes(signatureHex);
+  if (signature === null) {
+    return false;
+  }
+  const key = await importSigningKey(env, "verify");
+  return crypto.subtle.verify("HMAC", key, signature, encoder.encode(message));
+}
+
+/**
+ * Build a signed download path (path plus query string including sig)
+ */
+export async function createSignedPath(
+  path: string,
+  params: SignedUrlParams,
+  env: Env,
+): Promise<string> {
+  const searchParams = new URLSearchParams();
+  searchParams.set("expires", String(params.expires));
+  if (params.ip) searchParams.set("ip", params.ip);
+  if (params.maxDownloads !== undefined) {
+    searchParams.set("max_downloads", String(params.maxDownloads));
+  }
+  if (params.disposition) searchParams.set("disposition", params.disposition);
+  if (params.linkId) searchParams.set("lid", params.linkId);
+  if (params.shareId) searchParams.set("share", params.shareId);
+  if (params.version !== undefined) {
+    searchParams.set("ts", String(params.version));
+  }
+
+  const canonical = canonicalize(path, searchParams);
+  const signature = await generateSignature(canonical, env);
+  searchParams.sort();
+  searchParams.set("sig", signature);
+  return path + "?" + searchParams.toString();
+}
+
+/**
+ * Verify a signed download request: the HMAC is checked in constant time
+ * (crypto.subtle.verify), then the expiry and IP restriction are enforced.
+ * Download limits and revocation are checked against D1 by the caller.
+ */
 export async function validateSignature(
   request: Request,
   env: Env,
-): Promise<boolean> {
+): Promise<SignedUrlValidation> {
   const url = new URL(request.url);
-  const signature = url.searchParams.get("sig");
-  if (!signature) {
+  const signature = url.searchParams.get("sig") ?? "";
+  if (signature === "") {
     logWarning("[Signature] No signature provided", {
       module: "signing",
       operation: "validate",
     });
-    return false;
+    return { valid: false, reason: "Invalid signature", status: 403 };
   }
 
-  // Decode the path before generating signature
+  // Decode the path before verifying the signature
   const decodedPath = decodeURIComponent(url.pathname);
-  const searchParams = new URLSearchParams(url.search);
-  searchParams.delete("sig");
-  const queryString = searchParams.toString();
-  const pathWithQuery = queryString
-    ? decodedPath + "?" + queryString
-    : decodedPath;
+  const canonical = canonicalize(decodedPath, url.searchParams);
 
-  const expectedSignature = await generateSignature(pathWithQuery, env);
+  const matches = await verifySignature(canonical, signature, env);
 
   logInfo("[Signature] Validation", {
     module: "signing",
     operation: "validate",
-    metadata: {
-      path: url.pathname,
-      decodedPath,
-      pathWithQuery,
-      providedSig: signature,
-      expectedSig: expectedSignature,
-      match: signature === expectedSignature,
-    },
+    metadata: { path: url.pathname, match: matches },
   });
 
-  return signature === expectedSignature;
+  if (!matches) {
+    return { valid: false, reason: "Invalid signature", status: 403 };
+  }
+
+  // Links signed before expiry was enforced have no expires value
+  const expires = Number(url.searchParams.get("expires"));
+  if (!Number.isInteger(expires) || expires <= 0) {
+    return { valid: false, reason: "Link has no expiry", status: 403 };
+  }
+  if (Date.now() >= expires * 1000) {
+    return { valid: false, reason: "Link has expired", status: 410 };
+  }
+
+  const ip = url.searchParams.get("ip");
+  if (ip && request.headers.get("CF-Connecting-IP") !== ip) {
+    return {
+      valid: false,
+      reason: "Link is not valid from this IP address",
+      status: 403,
+    };
+  }
+
+  const maxDownloads = url.searchParams.get("max_downloads");
+  const disposition = url.searchParams.get("disposition");
+  const linkId = url.searchParams.get("lid");
+  const shareId = url.searchParams.get("share");
+
+  return {
+    valid: true,
+    params: {
+      expires,
+      ...(ip && { ip }),
+      ...(maxDownloads !== null && { maxDownloads: Number(maxDownloads) }),
+      disposition: disposition === "inline" ? "inline" : "attachment",
+      ...(linkId && { linkId }),
+      ...(shareId && { shareId }),
+    },
+  };
 }
@@ -0,0 +1,424 @@
+/**
+ * AWS Signature Version 4
+ *
+ * Parses and verifies SigV4-signed requests to the S3-compatible endpoint,
+ * either signed in the Authorization header or presigned in the query
+ * string. The access key ID is an API token ID and the secret comes from
+ * getS3SecretKey (see utils/api-tokens.ts).
+ */
+
+export const UNSIGNED_PAYLOAD = "UNSIGNED-PAYLOAD";
+export const STREAMING_UNSIGNED_PAYLOAD_TRAILER =
+  "STREAMING-UNSIGNED-PAYLOAD-TRAILER";
+
+const ALGORITHM = "AWS4-HMAC-SHA256";
+
+/** Largest difference allowed between the request time and our clock */
+const MAX_CLOCK_SKEW_MS = 15 * 60 * 1000;
+
+/** Longest lifetime of a presigned URL (7 days, as in S3) */
+const MAX_PRESIGNED_EXPIRES_SECONDS = 7 * 24 * 60 * 60;
+
+const encoder = new TextEncoder();
+
+export interface SigV4Request {
+  accessKeyId: string;
+  /** Request time, e.g. 20260115T093000Z */
+  amzDate: string;
+  /** Credential scope, e.g. 20260115/auto/s3/aws4_request */
+  scope: string;
+  signedHeaders: string[];
+  signature: string;
+  /** x-amz-content-sha256: a hex SHA-256, or an UNSIGNED/STREAMING marker */
+  payloadHash: string;
+  presigned: boolean;
+}
+
+export type SigV4ParseResult =
+  | { ok: true; auth: SigV4Request }
+  | { ok: false; code: string; message: string; status: 400 | 403 };
+
+function toHex(bytes: ArrayBuffer): string {
+  return Array.from(new Uint8Array(bytes))
+    .map((b) => b.toString(16).padStart(2, "0"))
+    .join("");
+}
+
+function hexToBytes(hex: string): Uint8Array | null {
+  if (hex.length === 0 || hex.length % 2 !== 0 || !/^[0-9a-f]+$/i.test(hex)) {
+    return null;
+  }
+  const bytes = new Uint8Array(hex.length / 2);
+  for (let i = 0; i < bytes.length; i++) {
+    bytes[i] = parseInt(hex.substring(i * 2, i * 2 + 2), 16);
+  }
+  return bytes;
+}
+
+export async function sha256Hex(data: string | Uint8Array): Promise<string> {
+  return toHex(
+    await crypto.subtle.digest(
+      "SHA-256",
+      typeof data === "string" ? encoder.encode(data) : data,
+    ),
+  );
+}
+
+async function hmac(key: Uint8Array, message: string): Promise<Uint8Array> {
+  const cryptoKey = await crypto.subtle.importKey(
+    "raw",
+    key,
+    { name: "HMAC", hash: "SHA-256" },
+    false,
+    ["sign"],
+  );
+  return new Uint8Array(
+    await crypto.subtle.sign("HMAC", cryptoKey, encoder.encode(message)),
+  );
+}
+
+/**
+ * RFC 3986 encoding used by SigV4 (everything but A-Z a-z 0-9 - _ . ~)
+ */
+export function uriEncode(value: string): string {
+  return encodeURIComponent(value).replace(
+    /[!'()*]/g,
+    (c) => "%" + c.charCodeAt(0).toString(16).toUpperCase(),
+  );
+}
+
+function parseAmzDate(amzDate: string): number | null {
+  const match = /^(\d{4})(\d{2})(\d{2})T(\d{2})(\d{2})(\d{2})Z$/.exec(amzDate);
+  if (match === null) {
+    return null;
+  }
+  const [, year, month, day, hour, minute, second] = match.map(Number);
+  return Date.UTC(
+    year ?? 0,
+    (month ?? 1) - 1,
+    day ?? 0,
+    hour ?? 0,
+    minute ?? 0,
+    second ?? 0,
+  );
+}
+
+function invalid(
+  code: string,
+  message: string,
+  status: 400 | 403 = 403,
+): SigV4ParseResult {
+  return { ok: false, code, message, status };
+}
+
+/**
+ * Read the SigV4 parameters of a request and check its credential scope and
+ * time. The signature itself is checked by verifySigV4.
+ */
+export function parseSigV4Request(
+  request: Request,
+  url: URL,
+  now = Date.now(),
+): SigV4ParseResult {
+  const header = request.headers.get("Authorization");
+  const query = url.searchParams;
+  const presigned = query.get("X-Amz-Algorithm") !== null;
+
+  let credential: string;
+  let signedHeaders: string;
+  let signature: string;
+  let amzDate: string;
+  let payloadHash: string;
+
+  if (header?.startsWith(ALGORITHM + " ") === true) {
+    const fields = new Map(
+      header
+        .slice(ALGORITHM.length + 1)
+        .split(",")
+        .map((field): [string, string] => {
+          const [name = "", ...rest] = field.trim().split("=");
+          return [name, rest.join("=")];
+        }),
+    );
+    credential = fields.get("Credential") ?? "";
+    signedHeaders = fields.get("SignedHeaders") ?? "";
+    signature = fields.get("Signature") ?? "";
+    amzDate = request.headers.get("x-amz-date") ?? "";
+    payloadHash = request.headers.get("x-amz-content-sha256") ?? "";
+    if (payloadHash === "") {
+      return invalid(
+        "InvalidRequest",
+        "Missing required header for this request: x-amz-content-sha256",
+        400,
+      );
+    }
+  } else if (presigned) {
+    if (query.get("X-Amz-Algorithm") !== ALGORITHM) {
+      return invalid(
+        "AuthorizationQueryParametersError",
+        `X-Amz-Algorithm only supports "${ALGORITHM}"`,
+        400,
+      );
+    }
+    credential = query.get("X-Amz-Credential") ?? "";
+    signedHeaders = query.get("X-Amz-SignedHeaders") ?? "";
+    signature = query.get("X-Amz-Signature") ?? "";
+    amzDate = query.get("X-Amz-Date") ?? "";
+    payloadHash = query.get("X-Amz-Content-Sha256") ?? UNSIGNED_PAYLOAD;
+  } else if (header?.startsWith("AWS ") === true) {
+    return invalid(
+      "InvalidRequest",
+      "Signature Version 2 is not supported, use Signature Version 4",
+      400,
+    );
+  } else {
+    return invalid("AccessDenied", "Anonymous access is not allowed");
+  }
+
+  const [accessKeyId = "", date = "", region = "", service = "", terminator] =
+    credential.split("/");
+  if (
+    accessKeyId === "" ||
+    region === "" ||
+    service !== "s3" ||
+    terminator !== "aws4_request" ||
+    signedHeaders === "" ||
+    signature === ""
+  ) {
+    return invalid(
+      "AuthorizationHeaderMalformed",
+      "The authorization header or query parameters are malformed",
+      400,
+    );
+  }
+
+  const requestTime = parseAmzDate(amzDate);
+  if (requestTime === null || !amzDate.startsWith(date)) {
+    return invalid(
+      "AuthorizationHeaderMalformed",
+      "X-Amz-Date is missing or does not match the credential date",
+      400,
+    );
+  }
+
+  if (presigned) {
+    const expires = Number(query.get("X-Amz-Expires"));
+    if (
+      !Number.isInteger(expires) ||
+      expires < 1 ||
+      expires > MAX_PRESIGNED_EXPIRES_SECONDS
+    ) {
+      return invalid(
+        "AuthorizationQueryParametersError",
+        "X-Amz-Expires must be between 1 and 604800 seconds",
+        400,
+      );
+    }
+    if (now > requestTime + expires * 1000) {
+      return invalid("AccessDenied", "Request has expired");
+    }
+    // A future X-Amz-Date would stretch the URL past the expiry limit
+    if (requestTime - now > MAX_CLOCK_SKEW_MS) {
+      return invalid(
+        "AccessDenied",
+        "Request is not yet valid: X-Amz-Date is in the future",
+      );
+    }
+  } else if (Math.abs(now - requestTime) > MAX_CLOCK_SKEW_MS) {
+    return invalid(
+      "RequestTimeTooSkewed",
+      "The difference between the request time and the current time is too large",
+    );
+  }
+
+  return {
+    ok: true,
+    auth: {
+      accessKeyId,
+      amzDate,
+      scope: [date, region, service, terminator].join("/"),
+      signedHeaders: signedHeaders.split(";"),
+      signature,
+      payloadHash,
+      presigned,
+    },
+  };
+}
+
+function compareCodeUnits(a: string, b: string): number {
+  return a < b ? -1 : a > b ? 1 : 0;
+}
+
+/**
+ * Canonical query string: every parameter except the signature, decoded,
+ * re-encoded and sorted by name and then value
+ */
+function canonicalQuery(search: string): string {
+  return search
+    .replace(/^\?/, "")
+    .split("&")
+    .filter((pair) => pair !== "")
+    .map((pair): [string, string] => {
+      const separator = pair.indexOf("=");
+      const name = separator === -1 ? pair : pair.slice(0, separator);
+      const value = separator === -1 ? "" : pair.slice(separator + 1);
+      return [
+        uriEncode(decodeURIComponent(name)),
+        uriEncode(decodeURIComponent(value)),
+      ];
+    })
+    .filter(([name]) => name !== "X-Amz-Signature")
+    .sort(
+      ([nameA, valueA], [nameB, valueB]) =>
+        compareCodeUnits(nameA, nameB) || compareCodeUnits(valueA, valueB),
+    )
+    .map(([name, value]) => `${name}=${value}`)
+    .join("&");
+}
+
+/**
+ * Check the signature of a request parsed by parseSigV4Request. The path is
+ * used as sent, since S3 clients encode each key segment themselves.
+ */
+export async function verifySigV4(
+  request: Request,
+  url: URL,
+  auth: SigV4Request,
+  secretKey: string,
+): Promise<boolean> {
+  const signature = hexToBytes(auth.signature);
+  if (signature === null) {
+    return false;
+  }
+
+  let query: string;
+  try {
+    query = canonicalQuery(url.search);
+  } catch {
+    return false;
+  }
+
+  const canonicalHeaders = auth.signedHeaders
+    .map(
+      (name) =>
+        `${name}:${(request.headers.get(name) ?? "").trim().replace(/\s+/g, " ")}\n`,
+    )
+    .join("");
+
+  const canonicalRequest = [
+    request.method,
+    url.pathname,
+    query,
+    canonicalHeaders,
+    auth.signedHeaders.join(";"),
+    auth.payloadHash,
+  ].join("\n");
+
+  const stringToSign = [
+    ALGORITHM,
+    auth.amzDate,
+    auth.scope,
+    await sha256Hex(canonicalRequest),
+  ].join("\n");
+
+  let signingKey: Uint8Array = encoder.encode("AWS4" + secretKey);
+  for (const part of auth.scope.split("/")) {
+    signingKey = await hmac(signingKey, part);
+  }
+
+  const key = await crypto.subtle.importKey(
+    "raw",
+    signingKey,
+    { name: "HMAC", hash: "SHA-256" },
+    false,
+    ["verify"],
+  );
+  return crypto.subtle.verify(
+    "HMAC",
+    key,
+    signature,
+    encoder.encode(stringToSign),
+  );
+}
+
+/**
+ * Whether a payload hash is a SHA-256 of the body rather than a marker such
+ * as UNSIGNED-PAYLOAD
+ */
+export function isSignedPayloadHash(payloadHash: string): boolean {
+  return /^[0-9a-f]{64}$/i.test(payloadHash);
+}
+
+/**
+ * Hash a request body while it streams. Returns the stream to read the body
+ * from and a promise of whether the body matched the signed hash, which
+ * settles once the body has been read.
+ */
+export function verifyPayloadHash(
+  body: ReadableStream<Uint8Array>,
+  payloadHash: string,
+): { body: ReadableStream<Uint8Array>; verified: Promise<boolean> } {
+  const [stream, hashed] = body.tee();
+  const digest = new crypto.DigestStream("SHA-256");
+  const verified = hashed.pipeTo(digest).then(
+    async () => toHex(await digest.digest) === payloadHash.toLowerCase(),
+    () => false,
+  );
+  return { body: stream, verified };
+}
+
+/**
+ * Decode an aws-chunked request body (used by newer AWS SDKs and CLIs for
+ * uploads) into the raw object bytes. Trailing checksums are skipped.
+ */
+export function createAwsChunkedDecoder(): TransformStream<
+  Uint8Array,
+  Uint8Array
+> {
+  const decoder = new TextDecoder();
+  let header = "";
+  let remaining = 0;
+  let separator = 0;
+  let finished = false;
+
+  return new TransformStream<Uint8Array, Uint8Array>({
+    transform(chunk, controller): void {
+      let offset = 0;
+      while (offset < chunk.length && !finished) {
+        if (remaining > 0) {
+          const end = Math.min(chunk.length, offset + remaining);
+          controller.enqueue(chunk.subarray(offset, end));
+          remaining -= end - offset;
+          offset = end;
+          if (remaining === 0) {
+            separator = 2;
+          }
+        } else if (separator > 0) {
+          separator--;
+          offset++;
+        } else {
+          const newline = chunk.indexOf(0x0a, offset);
+          if (newline === -1) {
+            header += decoder.decode(chunk.subarray(offset));
+            return;
+          }
+          header += decoder.decode(chunk.subarray(offset, newline));
+          offset = newline + 1;
+
+          // "<hex size>[;chunk-signature=...]\r"
+          const size = parseInt(header.split(";")[0] ?? "", 16);
+          header = "";
+          if (Number.isNaN(size)) {
+            controller.error(new Error("Invalid aws-chunked body"));
+            return;
+          }
+          if (size === 0) {
+            finished = true;
+          } else {
+            remaining = size;
+          }
+        }
+      }
+    },
+  });
+}
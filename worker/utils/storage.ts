@@ -0,0 +1,622 @@
+/**
+ * Object Storage Abstraction
+ *
+ * Routes object I/O (get/put/head/list/delete/copy) through native R2 bucket
+ * bindings when a bucket is mapped in R2_BUCKET_BINDINGS, and falls back to
+ * the Cloudflare REST API for buckets without a binding. Account-level bucket
+ * management (create/delete/configure buckets) stays on the REST API.
+ */
+
+import type {
+  Env,
+  CloudflareApiResponse,
+  ObjectStorage,
+  StorageHttpMetadata,
+  StorageListOptions,
+  StorageListResult,
+  StorageObject,
+  StorageObjectBody,
+  StorageGetOptions,
+  StoragePutBody,
+  StoragePutOptions,
+} from "../types";
+import { CF_API } from "../types";
+import { getCloudflareHeaders } from "./helpers";
+import { logWarning } from "./error-logger";
+
+/**
+ * Parsed bucket → binding map, cached per isolate keyed by the raw JSON string
+ */
+let cachedBindingMap: { raw: string; map: Record<string, string> } | null =
+  null;
+
+/**
+ * Parse the R2_BUCKET_BINDINGS variable.
+ *
+ * Format: JSON object mapping bucket names to R2 binding names, e.g.
+ * `{"media-bucket": "R2_MEDIA", "backups": "R2_BACKUPS"}`
+ */
+function getBindingMap(env: Env): Record<string, string> {
+  const raw = env.R2_BUCKET_BINDINGS;
+  if (raw === undefined || raw === "") {
+    return {};
+  }
+  if (cachedBindingMap?.raw === raw) {
+    return cachedBindingMap.map;
+  }
+
+  let map: Record<string, string> = {};
+  try {
+    const parsed = JSON.parse(raw) as unknown;
+    if (parsed !== null && typeof parsed === "object") {
+      map = Object.fromEntries(
+        Object.entries(parsed as Record<string, unknown>).filter(
+          (entry): entry is [string, string] => typeof entry[1] === "string",
+        ),
+      );
+    }
+  } catch {
+    logWarning("R2_BUCKET_BINDINGS is not valid JSON - ignoring", {
+      module: "storage",
+      operation: "parse_bindings",
+    });
+  }
+
+  cachedBindingMap = { raw, map };
+  return map;
+}
+
+/**
+ * Resolve the native R2 binding for a bucket, if one is configured.
+ * Returns null when the bucket has no binding.
+ */
+export function getBucketBinding(
+  env: Env,
+  bucketName: string,
+): R2Bucket | null {
+  const bindingName = getBindingMap(env)[bucketName];
+  if (bindingName === undefined) {
+    return null;
+  }
+
+  const binding = (env as unknown as Record<string, unknown>)[bindingName];
+  if (
+    binding === null ||
+    typeof binding !== "object" ||
+    typeof (binding as Partial<R2Bucket>).createMultipartUpload !== "function"
+  ) {
+    logWarning(`Binding ${bindingName} for bucket ${bucketName} not found`, {
+      module: "storage",
+      operation: "resolve_binding",
+      bucketName,
+      metadata: { bindingName },
+    });
+    return null;
+  }
+
+  return binding as R2Bucket;
+}
+
+/**
+ * Get the object storage for a bucket (native binding, or REST fallback)
+ */
+export function getObjectStorage(env: Env, bucketName: string): ObjectStorage {
+  const binding = getBucketBinding(env, bucketName);
+  return binding !== null
+    ? createBindingStorage(bucketName, binding)
+    : createRestStorage(env, bucketName);
+}
+
+// ============================================
+// Native binding implementation
+// ============================================
+
+function toStorageHttpMetadata(
+  metadata: R2HTTPMetadata | undefined,
+): StorageHttpMetadata {
+  const result: StorageHttpMetadata = {};
+  if (metadata?.contentType) result.contentType = metadata.contentType;
+  if (metadata?.contentLanguage)
+    result.contentLanguage = metadata.contentLanguage;
+  if (metadata?.contentDisposition)
+    result.contentDisposition = metadata.contentDisposition;
+  if (metadata?.contentEncoding)
+    result.contentEncoding = metadata.contentEncoding;
+  if (metadata?.cacheControl) result.cacheControl = metadata.cacheControl;
+  return result;
+}
+
+function fromR2Object(object: R2Object): StorageObject {
+  return {
+    key: object.key,
+    size: object.size,
+    uploaded: object.uploaded.toISOString(),
+    etag: object.etag,
+    httpMetadata: toStorageHttpMetadata(object.httpMetadata),
+    customMetadata: object.customMetadata ?? {},
+  };
+}
+
+function createBindingStorage(
+  bucketName: string,
+  bucket: R2Bucket,
+): ObjectStorage {
+  return {
+    bucketName,
+    isNative: true,
+
+    async get(
+      key: string,
+      options?: StorageGetOptions,
+    ): Promise<StorageObjectBody | null> {
+      const object = await bucket.get(
+        key,
+        options?.range !== undefined ? { range: options.range } : undefined,
+      );
+      if (object === null) {
+        return null;
+      }
+      return { ...fromR2Object(object), body: object.body };
+    },
+
+    async head(key: string): Promise<StorageObject | null> {
+      const object = await bucket.head(key);
+      return object === null ? null : fromR2Object(object);
+    },
+
+    async put(
+      key: string,
+      body: StoragePutBody,
+      options?: StoragePutOptions,
+    ): Promise<StorageObject> {
+      const object = await bucket.put(key, body, {
+        ...(options?.httpMetadata && { httpMetadata: options.httpMetadata }),
+        ...(options?.customMetadata && {
+          customMetadata: options.customMetadata,
+        }),
+      });
+      if (object === null) {
+        throw new Error(`Failed to write object: ${key}`);
+      }
+      return fromR2Object(object);
+    },
+
+    async delete(key: string): Promise<void> {
+      await bucket.delete(key);
+    },
+
+    async list(options: StorageListOptions = {}): Promise<StorageListResult> {
+      const result = await bucket.list({
+        include: ["httpMetadata", "customMetadata"],
+        ...(options.prefix !== undefined && { prefix: options.prefix }),
+        ...(options.cursor !== undefined && { cursor: options.cursor }),
+        ...(options.limit !== undefined && { limit: options.limit }),
+        ...(options.delimiter !== undefined && {
+          delimiter: options.delimiter,
+        }),
+        ...(options.startAfter !== undefined && {
+          startAfter: options.startAfter,
+        }),
+      });
+      return {
+        objects: result.objects.map(fromR2Object),
+        delimitedPrefixes: result.delimitedPrefixes,
+        truncated: result.truncated,
+        cursor: result.truncated ? result.cursor : undefined,
+      };
+    },
+  };
+}
+
+// ============================================
+// REST API fallback implementation
+// ============================================
+
+interface RestObjectInfo {
+  key: string;
+  size?: number;
+  uploaded?: string;
+  last_modified?: string;
+  etag?: string;
+  httpEtag?: string;
+  http_metadata?: Record<string, string>;
+  httpMetadata?: Record<string, string>;
+  custom_metadata?: Record<string, string>;
+  customMetadata?: Record<string, string>;
+}
+
+function fromRestObject(object: RestObjectInfo): StorageObject {
+  const httpMetadata = object.httpMetadata ?? object.http_metadata ?? {};
+  return {
+    key: object.key,
+    size: object.size ?? 0,
+    uploaded:
+      object.uploaded ?? object.last_modified ?? new Date().toISOString(),
+    etag: (object.etag ?? object.httpEtag ?? "").replace(/"/g, ""),
+    httpMetadata: {
+      ...(httpMetadata["contentType"] && {
+        contentType: httpMetadata["contentType"],
+      }),
+      ...(httpMetadata["contentDisposition"] && {
+        contentDisposition: httpMetadata["contentDisposition"],
+      }),
+      ...(httpMetadata["contentEncoding"] && {
+        contentEncoding: httpMetadata["contentEncoding"],
+      }),
+      ...(httpMetadata["contentLanguage"] && {
+        contentLanguage: httpMetadata["contentLanguage"],
+      }),
+      ...(httpMetadata["cacheControl"] && {
+        cacheControl: httpMetadata["cacheControl"],
+      }),
+    },
+    customMetadata: object.customMetadata ?? object.custom_metadata ?? {},
+  };
+}
+
+function fromRestHeaders(key: string, headers: Headers): StorageObject {
+  const lastModified = headers.get("Last-Modified");
+  const httpMetadata: StorageHttpMetadata = {};
+  const contentType = headers.get("Content-Type");
+  if (contentType) httpMetadata.contentType = contentType;
+  const contentDisposition = headers.get("Content-Disposition");
+  if (contentDisposition) httpMetadata.contentDisposition = contentDisposition;
+  const contentEncoding = headers.get("Content-Encoding");
+  if (contentEncoding) httpMetadata.contentEncoding = contentEncoding;
+  const contentLanguage = headers.get("Content-Language");
+  if (contentLanguage) httpMetadata.contentLanguage = contentLanguage;
+  const cacheControl = headers.get("Cache-Control");
+  if (cacheControl) httpMetadata.cacheControl = cacheControl;
+
+  return {
+    key,
+    size: parseInt(headers.get("Content-Length") ?? "0"),
+    uploaded:
+      lastModified !== null
+        ? new Date(lastModified).toISOString()
+        : new Date().toISOString(),
+    etag: (headers.get("ETag") ?? "").replace(/"/g, ""),
+    httpMetadata,
+    customMetadata: {},
+  };
+}
+
+function createRestStorage(env: Env, bucketName: string): ObjectStorage {
+  const cfHeaders = getCloudflareHeaders(env);
+  const bucketUrl =
+    CF_API +
+    "/accounts/" +
+    env.ACCOUNT_ID +
+    "/r2/buckets/" +
+    encodeURIComponent(bucketName);
+  const objectUrl = (key: string): string =>
+    bucketUrl + "/objects/" + encodeURIComponent(key);
+
+  return {
+    bucketName,
+    isNative: false,
+
+    async get(
+      key: string,
+      options?: StorageGetOptions,
+    ): Promise<StorageObjectBody | null> {
+      const range = options?.range;
+      const response = await fetch(objectUrl(key), {
+        headers:
+          range !== undefined
+            ? {
+                ...cfHeaders,
+                Range: `bytes=${String(range.offset)}-${String(range.offset + range.length - 1)}`,
+              }
+            : cfHeaders,
+      });
+      if (response.status === 404) {
+        return null;
+      }
+      if (!response.ok || response.body === null) {
+        throw new Error(`Failed to get object: ${String(response.status)}`);
+      }
+      if (range !== undefined && response.status !== 206) {
+        throw new Error("Range requests are not supported for this object");
+      }
+
+      const object = fromRestHeaders(key, response.headers);
+      // Content-Length is the range length; the total is in Content-Range
+      const totalSize = response.headers.get("Content-Range")?.split("/")[1];
+      if (totalSize !== undefined && totalSize !== "*") {
+        object.size = parseInt(totalSize);
+      }
+      return { ...object, body: response.body };
+    },
+
+    async head(key: string): Promise<StorageObject | null> {
+      // A prefix listing includes custom metadata, which HEAD does not return
+      const listUrl = new URL(bucketUrl + "/objects");
+      listUrl.searchParams.set("include", "customMetadata,httpMetadata");
+      listUrl.searchParams.set("prefix", key);
+      listUrl.searchParams.set("per_page", "1");
+
+      const response = await fetch(listUrl.toString(), { headers: cfHeaders });
+      if (!response.ok) {
+        throw new Error(`Failed to head object: ${String(response.status)}`);
+      }
+
+      const data = (await response.json()) as CloudflareApiResponse<
+        RestObjectInfo[]
+      >;
+      const match = Array.isArray(data.result)
+        ? data.result.find((object) => object.key === key)
+        : undefined;
+      return match !== undefined ? fromRestObject(match) : null;
+    },
+
+    async put(
+      key: string,
+      body: StoragePutBody,
+      options?: StoragePutOptions,
+    ): Promise<StorageObject> {
+      const headers: Record<string, string> = {
+        ...cfHeaders,
+        "Content-Type":
+          options?.httpMetadata?.contentType ?? "application/octet-stream",
+      };
+      if (options?.httpMetadata?.contentDisposition) {
+        headers["Content-Disposition"] =
+          options.httpMetadata.contentDisposition;
+      }
+      if (options?.httpMetadata?.contentEncoding) {
+        headers["Content-Encoding"] = options.httpMetadata.contentEncoding;
+      }
+      if (options?.httpMetadata?.contentLanguage) {
+        headers["Content-Language"] = options.httpMetadata.contentLanguage;
+      }
+      if (options?.httpMetadata?.cacheControl) {
+        headers["Cache-Control"] = options.httpMetadata.cacheControl;
+      }
+
+      const response = await fetch(objectUrl(key), {
+        method: "PUT",
+        headers,
+        body,
+      });
+      if (!response.ok) {
+        throw new Error(`Failed to put object: ${String(response.status)}`);
+      }
+
+      // The REST API returns the stored object; fall back to HEAD if it doesn't
+      try {
+        const data =
+          (await response.json()) as CloudflareApiResponse<RestObjectInfo>;
+        if (data.result?.key !== undefined) {
+          return fromRestObject(data.result);
+        }
+      } catch {
+        // Response might not be JSON
+      }
+      return (
+        (await this.head(key)) ?? {
+          key,
+          size: 0,
+          uploaded: new Date().toISOString(),
+          etag: "",
+          httpMetadata: options?.httpMetadata ?? {},
+          customMetadata: options?.customMetadata ?? {},
+        }
+      );
+    },
+
+    async delete(key: string): Promise<void> {
+      const response = await fetch(objectUrl(key), {
+        method: "DELETE",
+        headers: cfHeaders,
+      });
+      if (!response.ok && response.status !== 404) {
+        throw new Error(`Failed to delete object: ${String(response.status)}`);
+      }
+    },
+
+    async list(options: StorageListOptions = {}): Promise<StorageListResult> {
+      const listUrl = new URL(bucketUrl + "/objects");
+      listUrl.searchParams.set("include", "customMetadata,httpMetadata");
+      listUrl.searchParams.set("per_page", String(options.limit ?? 1000));
+      if (options.prefix !== undefined && options.prefix !== "") {
+        listUrl.searchParams.set("prefix", options.prefix);
+      }
+      if (options.cursor !== undefined && options.cursor !== "") {
+        listUrl.searchParams.set("cursor", options.cursor);
+      }
+      if (options.delimiter !== undefined) {
+        listUrl.searchParams.set("delimiter", options.delimiter);
+      }
+      if (options.newestFirst === true) {
+        listUrl.searchParams.set("order", "desc");
+        listUrl.searchParams.set("sort_by", "last_modified");
+      }
+
+      const response = await fetch(listUrl.toString(), { headers: cfHeaders });
+      if (!response.ok) {
+        throw new Error(`Failed to list objects: ${String(response.status)}`);
+      }
+
+      const data = (await response.json()) as CloudflareApiResponse<
+        RestObjectInfo[]
+      >;
+      const objects = Array.isArray(data.result) ? data.result : [];
+      const cursor = data.result_info?.cursor;
+      const truncated =
+        (data.result_info?.is_truncated ?? false) &&
+        cursor !== undefined &&
+        cursor !== "";
+
+      return {
+        objects: objects.map(fromRestObject),
+        delimitedPrefixes: data.result_info?.delimited ?? [],
+        truncated,
+        cursor: truncated ? cursor : undefined,
+      };
+    },
+  };
+}
+
+// ============================================
+// Helpers
+// ============================================
+
+/**
+ * Iterate over every object under a prefix, page by page
+ */
+export async function* listAllObjects(
+  storage: ObjectStorage,
+  prefix?: string,
+  pageSize = 1000,
+): AsyncGenerator<StorageObject[]> {
+  let cursor: string | undefined;
+  do {
+    const page = await storage.list({ prefix, cursor, limit: pageSize });
+    if (page.objects.length > 0) {
+      yield page.objects;
+    }
+    cursor = page.truncated ? page.cursor : undefined;
+  } while (cursor !== undefined);
+}
+
+/**
+ * Largest object written with a single PUT; bigger objects are copied with a
+ * multipart upload (R2 rejects single PUTs above 5 GiB - 5 MiB)
+ */
+const SINGLE_PUT_COPY_LIMIT = 1024 * 1024 * 1024;
+const MAX_SINGLE_PUT_SIZE = 5 * 1024 * 1024 * 1024 - 5 * 1024 * 1024;
+const COPY_PART_SIZE = 100 * 1024 * 1024;
+const COPY_PART_ATTEMPTS = 3;
+
+/**
+ * Copy an object between (or within) buckets without buffering it in Worker
+ * memory, keeping its HTTP and custom metadata.
+ *
+ * The source body is streamed into the destination; objects over 1 GiB are
+ * copied part by part with a multipart upload (requires a binding for the
+ * destination bucket), retrying individual parts on failure.
+ *
+ * Custom metadata can only be written through a binding, so it is dropped
+ * (with a warning) when streaming into a REST-only destination.
+ *
+ * Returns the source object's metadata, or null if it does not exist.
+ */
+export async function copyObject(
+  env: Env,
+  sourceBucket: string,
+  sourceKey: string,
+  destBucket: string,
+  destKey: string,
+): Promise<StorageObject | null> {
+  const source = getObjectStorage(env, sourceBucket);
+  const dest = getObjectStorage(env, destBucket);
+
+  const sourceObject = await source.head(sourceKey);
+  if (sourceObject === null) {
+    return null;
+  }
+
+  const destBinding = getBucketBinding(env, destBucket);
+  if (sourceObject.size > SINGLE_PUT_COPY_LIMIT && destBinding !== null) {
+    await copyObjectMultipart(source, sourceObject, destBinding, destKey);
+    return sourceObject;
+  }
+
+  if (sourceObject.size > MAX_SINGLE_PUT_SIZE) {
+    throw new Error(
+      `Objects larger than 5 GB can only be copied into buckets with an R2 binding (${destBucket})`,
+    );
+  }
+
+  if (!dest.isNative && Object.keys(sourceObject.customMetadata).length > 0) {
+    logWarning("Custom metadata is not preserved for REST destinations", {
+      module: "storage",
+      operation: "copy_object",
+      bucketName: destBucket,
+      fileName: destKey,
+    });
+  }
+
+  const object = await source.get(sourceKey);
+  if (object === null) {
+    return null;
+  }
+  await dest.put(
+    destKey,
+    object.body.pipeThrough(new FixedLengthStream(object.size)),
+    {
+      httpMetadata: object.httpMetadata,
+      customMetadata: sourceObject.customMetadata,
+    },
+  );
+  return sourceObject;
+}
+
+/**
+ * Copy a large object into a bound bucket with a multipart upload, streaming
+ * one ranged read of the source per part
+ */
+async function copyObjectMultipart(
+  source: ObjectStorage,
+  sourceObject: StorageObject,
+  destBinding: R2Bucket,
+  destKey: string,
+): Promise<void> {
+  const upload = await destBinding.createMultipartUpload(destKey, {
+    httpMetadata: sourceObject.httpMetadata,
+    customMetadata: sourceObject.customMetadata,
+  });
+
+  try {
+    const uploadedParts: R2UploadedPart[] = [];
+    const totalParts = Math.ceil(sourceObject.size / COPY_PART_SIZE);
+
+    for (let index = 0; index < totalParts; index++) {
+      const offset = index * COPY_PART_SIZE;
+      const length = Math.min(COPY_PART_SIZE, sourceObject.size - offset);
+      const partNumber = index + 1;
+
+      for (let attempt = 1; ; attempt++) {
+        try {
+          const part = await source.get(sourceObject.key, {
+            range: { offset, length },
+          });
+          if (part === null) {
+            throw new Error("Source object was deleted during copy");
+          }
+          if (part.etag !== "" && part.etag !== sourceObject.etag) {
+            throw new Error("Source object changed during copy");
+          }
+
+          uploadedParts.push(
+            await upload.uploadPart(
+              partNumber,
+              part.body.pipeThrough(new FixedLengthStream(length)),
+            ),
+          );
+          break;
+        } catch (error) {
+          const message = error instanceof Error ? error.message : "";
+          if (
+            attempt >= COPY_PART_ATTEMPTS ||
+            message.includes("during copy")
+          ) {
+            throw error;
+          }
+          logWarning(`Retrying part ${String(partNumber)} of ${destKey}`, {
+            module: "storage",
+            operation: "copy_object",
+            fileName: destKey,
+            metadata: { attempt, error: message },
+          });
+        }
+      }
+    }
+
+    await upload.complete(uploadedParts);
+  } catch (error) {
+    await upload.abort().catch(() => undefined);
+    throw error;
+  }
+}
@@ -0,0 +1,377 @@
+/**
+ * Trash
+ *
+ * Optional per-bucket soft delete. When trash is enabled for a bucket (in
+ * bucket_trash_settings), objects that are deleted, or about to be
+ * overwritten, are first copied under the hidden TRASH_PREFIX in the same
+ * bucket and recorded in trash_items with their original key, who removed
+ * them and when. Items can be restored or purged until their retention
+ * period ends, after which purgeExpiredTrash removes them.
+ */
+
+import type {
+  BucketTrashSettings,
+  Env,
+  TrashItem,
+  TrashReason,
+} from "../types";
+import { createErrorResponse } from "./error-response";
+import { logInfo, logWarning } from "./error-logger";
+import { copyObject, getObjectStorage } from "./storage";
+
+/** Hidden prefix trashed objects are kept under */
+export const TRASH_PREFIX = ".r2m-trash/";
+
+export const DEFAULT_TRASH_RETENTION_DAYS = 30;
+export const MAX_TRASH_RETENTION_DAYS = 365;
+
+/** Expired items purged per call to purgeExpiredTrash */
+const PURGE_BATCH_SIZE = 200;
+
+/** Settings are read on every delete and upload, so cache them briefly */
+const SETTINGS_CACHE_TTL = 60 * 1000;
+const settingsCache = new Map<
+  string,
+  { settings: BucketTrashSettings | null; timestamp: number }
+>();
+
+export function isTrashKey(key: string): boolean {
+  return key.startsWith(TRASH_PREFIX);
+}
+
+/**
+ * 403 response for a write, delete, copy or rename of a key in the trash,
+ * which only the trash routes may change (its trash_items rows point at it)
+ */
+export function createTrashKeyResponse(corsHeaders: HeadersInit): Response {
+  return createErrorResponse(
+    `Keys under ${TRASH_PREFIX} are managed by the trash`,
+    corsHeaders,
+    403,
+    { code: "TRASH_KEY" },
+  );
+}
+
+export function invalidateTrashSettingsCache(bucketName: string): void {
+  settingsCache.delete(bucketName);
+}
+
+function generateTrashItemId(): string {
+  const bytes = new Uint8Array(12);
+  crypto.getRandomValues(bytes);
+  return (
+    "trs_" +
+    Array.from(bytes)
+      .map((b) => b.toString(16).padStart(2, "0"))
+      .join("")
+  );
+}
+
+/**
+ * Trash settings of a bucket, or null if trash is not enabled for it (or
+ * the database or migration is missing)
+ */
+export async function getTrashSettings(
+  env: Env,
+  bucketName: string,
+): Promise<BucketTrashSettings | null> {
+  const db = env.METADATA;
+  if (!db) {
+    return null;
+  }
+
+  const cached = settingsCache.get(bucketName);
+  if (
+    cached !== undefined &&
+    Date.now() - cached.timestamp < SETTINGS_CACHE_TTL
+  ) {
+    return cached.settings;
+  }
+
+  let settings: BucketTrashSettings | null = null;
+  try {
+    settings = await db
+      .prepare(
+        "SELECT * FROM bucket_trash_settings WHERE bucket_name = ? AND enabled = 1",
+      )
+      .bind(bucketName)
+      .first<BucketTrashSettings>();
+  } catch (error) {
+    // Deletes must keep working before the trash migration is applied
+    logWarning(`Could not read trash settings: ${String(error)}`, {
+      module: "trash",
+      operation: "get_settings",
+      bucketName,
+    });
+  }
+
+  settingsCache.set(bucketName, { settings, timestamp: Date.now() });
+  return settings;
+}
+
+/**
+ * Copy an object into the trash and record it. Returns null if the object
+ * does not exist.
+ */
+async function trashObject(
+  env: Env,
+  settings: BucketTrashSettings,
+  key: string,
+  userEmail: string,
+  reason: TrashReason,
+): Promise<TrashItem | null> {
+  const db = env.METADATA;
+  if (!db || isTrashKey(key)) {
+    return null;
+  }
+
+  const bucketName = settings.bucket_name;
+  const itemId = generateTrashItemId();
+  const trashKey = `${TRASH_PREFIX}${itemId}/${key}`;
+
+  const copied = await copyObject(env, bucketName, key, bucketName, trashKey);
+  if (copied === null) {
+    return null;
+  }
+
+  const deletedAt = new Date();
+  const item: TrashItem = {
+    item_id: itemId,
+    bucket_name: bucketName,
+    original_key: key,
+    trash_key: trashKey,
+    size: copied.size,
+    etag: copied.etag !== "" ? copied.etag : null,
+    reason,
+    deleted_by: userEmail,
+    deleted_at: deletedAt.toISOString(),
+    expires_at: new Date(
+      deletedAt.getTime() + settings.retention_days * 24 * 60 * 60 * 1000,
+    ).toISOString(),
+  };
+
+  try {
+    await db
+      .prepare(
+        "INSERT INTO trash_items (item_id, bucket_name, original_key, trash_key, size, etag, reason, deleted_by, deleted_at, expires_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+      )
+      .bind(
+        item.item_id,
+        item.bucket_name,
+        item.original_key,
+        item.trash_key,
+        item.size,
+        item.etag,
+        item.reason,
+        item.deleted_by,
+        item.deleted_at,
+        item.expires_at,
+      )
+      .run();
+  } catch (error) {
+    // Without a record the copy could never be restored or purged
+    await getObjectStorage(env, bucketName).delete(trashKey);
+    throw error;
+  }
+
+  logInfo(`Moved ${key} to trash`, {
+    module: "trash",
+    operation: reason,
+    bucketName,
+    fileName: key,
+    userId: userEmail,
+    metadata: { itemId },
+  });
+  return item;
+}
+
+/**
+ * Delete an object, keeping it in the trash first if the bucket has trash
+ * enabled. Returns the trash item, or null if the object was deleted outright.
+ */
+export async function deleteOrTrashObject(
+  env: Env,
+  bucketName: string,
+  key: string,
+  userEmail: string,
+): Promise<TrashItem | null> {
+  const settings = await getTrashSettings(env, bucketName);
+  const item =
+    settings !== null
+      ? await trashObject(env, settings, key, userEmail, "delete")
+      : null;
+  await getObjectStorage(env, bucketName).delete(key);
+  return item;
+}
+
+/**
+ * Keep the current version of an object in the trash before it is
+ * overwritten, if the bucket has trash enabled and the object exists
+ */
+export async function trashBeforeOverwrite(
+  env: Env,
+  bucketName: string,
+  key: string,
+  userEmail: string,
+): Promise<TrashItem | null> {
+  const settings = await getTrashSettings(env, bucketName);
+  return settings !== null
+    ? await trashObject(env, settings, key, userEmail, "overwrite")
+    : null;
+}
+
+export type RestoreTrashResult = "restored" | "exists" | "missing";
+
+/**
+ * Copy a trashed object back to its original key and remove it from the
+ * trash. Unless overwrite is set, an object now at the original key is left
+ * alone ("exists"); with overwrite it is itself moved to the trash first.
+ */
+export async function restoreTrashItem(
+  env: Env,
+  item: TrashItem,
+  userEmail: string,
+  overwrite: boolean,
+): Promise<RestoreTrashResult> {
+  const storage = getObjectStorage(env, item.bucket_name);
+
+  if ((await storage.head(item.original_key)) !== null) {
+    if (!overwrite) {
+      return "exists";
+    }
+    await trashBeforeOverwrite(
+      env,
+      item.bucket_name,
+      item.original_key,
+      userEmail,
+    );
+  }
+
+  const copied = await copyObject(
+    env,
+    item.bucket_name,
+    item.trash_key,
+    item.bucket_name,
+    item.original_key,
+  );
+  await purgeTrashItem(env, item);
+  return copied !== null ? "restored" : "missing";
+}
+
+/**
+ * Permanently delete a trashed object and its record
+ */
+export async function purgeTrashItem(env: Env, item: TrashItem): Promise<void> {
+  await getObjectStorage(env, item.bucket_name).delete(item.trash_key);
+  const db = env.METADATA;
+  if (db) {
+    await db
+      .prepare("DELETE FROM trash_items WHERE item_id = ?")
+      .bind(item.item_id)
+      .run();
+  }
+}
+
+/**
+ * Purge trash items past their retention period, optionally for one bucket.
+ * Returns the number of items purged.
+ */
+export async function purgeExpiredTrash(
+  env: Env,
+  bucketName?: string,
+): Promise<number> {
+  const db = env.METADATA;
+  if (!db) {
+    return 0;
+  }
+
+  const conditions = ["expires_at <= ?"];
+  const bindings: string[] = [new Date().toISOString()];
+  if (bucketName !== undefined) {
+    conditions.push("bucket_name = ?");
+    bindings.push(bucketName);
+  }
+
+  const expired = await db
+    .prepare(
+      `SELECT * FROM trash_items WHERE ${conditions.join(" AND ")} ORDER BY expires_at LIMIT ${PURGE_BATCH_SIZE}`,
+    )
+    .bind(...bindings)
+    .all<TrashItem>();
+
+  let purged = 0;
+  for (const item of expired.results) {
+    try {
+      await purgeTrashItem(env, item);
+      purged++;
+    } catch (error) {
+      logWarning(`Failed to purge expired trash item: ${String(error)}`, {
+        module: "trash",
+        operation: "purge_expired",
+        bucketName: item.bucket_name,
+        fileName: item.original_key,
+        metadata: { itemId: item.item_id },
+      });
+    }
+  }
+
+  if (purged > 0) {
+    logInfo(`Purged ${purged} expired trash item(s)`, {
+      module: "trash",
+      operation: "purge_expired",
+      ...(bucketName !== undefined && { bucketName }),
+      metadata: { purged },
+    });
+  }
+  return purged;
+}
+
+/**
+ * Keep trash records in step with a renamed or deleted bucket. Renaming
+ * copies the hidden trash prefix along with every other object.
+ */
+export async function updateBucketTrash(
+  env: Env,
+  bucketName: string,
+  newBucketName: string | null,
+): Promise<void> {
+  const db = env.METADATA;
+  if (!db) {
+    return;
+  }
+
+  try {
+    if (newBucketName === null) {
+      await db.batch([
+        db
+          .prepare("DELETE FROM trash_items WHERE bucket_name = ?")
+          .bind(bucketName),
+        db
+          .prepare("DELETE FROM bucket_trash_settings WHERE bucket_name = ?")
+          .bind(bucketName),
+      ]);
+    } else {
+      await db.batch([
+        db
+          .prepare(
+            "UPDATE trash_items SET bucket_name = ? WHERE bucket_name = ?",
+          )
+          .bind(newBucketName, bucketName),
+        db
+          .prepare(
+            "UPDATE bucket_trash_settings SET bucket_name = ? WHERE bucket_name = ?",
+          )
+          .bind(newBucketName, bucketName),
+      ]);
+    }
+  } catch (error) {
+    logWarning(`Failed to update trash records: ${String(error)}`, {
+      module: "trash",
+      operation: "update_bucket",
+      bucketName,
+      metadata: { newBucketName },
+    });
+  }
+
+  invalidateTrashSettingsCache(bucketName);
+}
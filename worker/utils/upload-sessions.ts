@@ -0,0 +1,216 @@
+/**
+ * Upload Session Tracking
+ *
+ * Persists multipart upload sessions and their completed parts in D1 so that
+ * an interrupted upload can be resumed after a browser reload.
+ */
+
+import type {
+  UploadSession,
+  UploadSessionPart,
+  CreateUploadSessionParams,
+} from "../types";
+import { logWarning } from "./error-logger";
+
+/**
+ * Session tracking is best-effort: uploads keep working (without resume
+ * support) until the upload_sessions migration has been applied.
+ */
+function handleMissingTable(error: unknown, operation: string): void {
+  const errorMessage = error instanceof Error ? error.message : String(error);
+  if (!errorMessage.includes("no such table")) {
+    throw error;
+  }
+  logWarning("upload_sessions table does not exist - apply migrations", {
+    module: "files",
+    operation,
+  });
+}
+
+/**
+ * Record a newly created multipart upload session
+ */
+export async function createUploadSession(
+  db: D1Database,
+  params: CreateUploadSessionParams,
+): Promise<void> {
+  const now = new Date().toISOString();
+
+  try {
+    await db
+      .prepare(
+        `
+      INSERT INTO upload_sessions (
+        upload_id, bucket_name, object_key, file_fingerprint, file_size,
+        part_size, total_parts, content_type, status, user_email,
+        created_at, updated_at
+      ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, 'in_progress', ?, ?, ?)
+    `,
+      )
+      .bind(
+        params.uploadId,
+        params.bucketName,
+        params.objectKey,
+        params.fileFingerprint ?? null,
+        params.fileSize ?? null,
+        params.partSize ?? null,
+        params.totalParts ?? null,
+        params.contentType ?? null,
+        params.userEmail,
+        now,
+        now,
+      )
+      .run();
+  } catch (error) {
+    handleMissingTable(error, "upload_session_create");
+  }
+}
+
+/**
+ * Record a completed part (re-uploading a part replaces its ETag)
+ */
+export async function recordUploadedPart(
+  db: D1Database,
+  uploadId: string,
+  part: { partNumber: number; etag: string; md5?: string | null },
+): Promise<void> {
+  const now = new Date().toISOString();
+
+  try {
+    await db.batch([
+      db
+        .prepare(
+          `
+        INSERT OR REPLACE INTO upload_session_parts (
+          upload_id, part_number, etag, md5, uploaded_at
+        ) VALUES (?, ?, ?, ?, ?)
+      `,
+        )
+        .bind(uploadId, part.partNumber, part.etag, part.md5 ?? null, now),
+      db
+        .prepare(
+          "UPDATE upload_sessions SET updated_at = ? WHERE upload_id = ?",
+        )
+        .bind(now, uploadId),
+    ]);
+  } catch (error) {
+    handleMissingTable(error, "upload_session_part");
+  }
+}
+
+/**
+ * Get the completed parts of a session, ordered by part number
+ */
+export async function getUploadedParts(
+  db: D1Database,
+  uploadId: string,
+): Promise<UploadSessionPart[]> {
+  try {
+    const result = await db
+      .prepare(
+        "SELECT part_number, etag, md5, uploaded_at FROM upload_session_parts WHERE upload_id = ? ORDER BY part_number ASC",
+      )
+      .bind(uploadId)
+      .all<UploadSessionPart>();
+
+    return result.results;
+  } catch (error) {
+    handleMissingTable(error, "upload_session_parts");
+    return [];
+  }
+}
+
+/**
+ * Mark a session as completed or aborted and drop its part bookkeeping
+ */
+export async function finishUploadSession(
+  db: D1Database,
+  uploadId: string,
+  status: "completed" | "aborted",
+): Promise<void> {
+  const now = new Date().toISOString();
+
+  try {
+    await db.batch([
+      db
+        .prepare(
+          "UPDATE upload_sessions SET status = ?, updated_at = ? WHERE upload_id = ?",
+        )
+        .bind(status, now, uploadId),
+      db
+        .prepare("DELETE FROM upload_session_parts WHERE upload_id = ?")
+        .bind(uploadId),
+    ]);
+  } catch (error) {
+    handleMissingTable(error, "upload_session_finish");
+  }
+}
+
+/**
+ * Get a session without its parts, or null if it is not tracked
+ */
+export async function getUploadSession(
+  db: D1Database,
+  uploadId: string,
+): Promise<Omit<UploadSession, "parts"> | null> {
+  try {
+    return await db
+      .prepare("SELECT * FROM upload_sessions WHERE upload_id = ?")
+      .bind(uploadId)
+      .first<Omit<UploadSession, "parts">>();
+  } catch (error) {
+    handleMissingTable(error, "upload_session_get");
+    return null;
+  }
+}
+
+/**
+ * List in-progress sessions for a bucket, each with its completed parts
+ */
+export async function listUploadSessions(
+  db: D1Database,
+  bucketName: string,
+  filters: {
+    objectKey?: string | null;
+    fileFingerprint?: string | null;
+    userEmail?: string | null;
+  } = {},
+): Promise<UploadSession[]> {
+  let query =
+    "SELECT * FROM upload_sessions WHERE bucket_name = ? AND status = 'in_progress'";
+  const bindings: string[] = [bucketName];
+
+  if (filters.objectKey) {
+    query += " AND object_key = ?";
+    bindings.push(filters.objectKey);
+  }
+
+  if (filters.fileFingerprint) {
+    query += " AND file_fingerprint = ?";
+    bindings.push(filters.fileFingerprint);
+  }
+
+  if (filters.userEmail) {
+    query += " AND user_email = ?";
+    bindings.push(filters.userEmail);
+  }
+
+  query += " ORDER BY updated_at DESC LIMIT 100";
+
+  try {
+    const sessions = await db
+      .prepare(query)
+      .bind(...bindings)
+      .all<Omit<UploadSession, "parts">>();
+
+    return await Promise.all(
+      sessions.results.map(async (session) => ({
+        ...session,
+        parts: await getUploadedParts(db, session.upload_id),
+      })),
+    );
+  } catch (error) {
+    handleMissingTable(error, "list_uploads");
+    return [];
+  }
+}
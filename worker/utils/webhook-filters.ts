@@ -0,0 +1,200 @@
+/**
+ * Webhook Filters
+ *
+ * Optional conditions a webhook applies on top of its event subscriptions.
+ * Every filter that is set must pass; a filter with a list passes if any
+ * entry matches. An event that does not carry the filtered field (e.g. a
+ * bucket delete under a key filter) does not pass.
+ *
+ * Bucket, key and user patterns are globs: * matches any run of characters,
+ * including "/", and ? matches one character.
+ */
+
+import type { WebhookFilters } from "../types";
+
+/** Largest number of entries in each filter list */
+const MAX_FILTER_ENTRIES = 50;
+
+/** Event data fields that name a bucket */
+const BUCKET_FIELDS = [
+  "bucket_name",
+  "source_bucket",
+  "destination_bucket",
+  "old_bucket_name",
+  "new_bucket_name",
+];
+
+/** Event data fields that hold an object key or folder path */
+const KEY_FIELDS = [
+  "file_name",
+  "source_file",
+  "destination_path",
+  "old_file_name",
+  "new_file_name",
+  "folder_path",
+];
+
+/** Event data fields that hold a size in bytes */
+const SIZE_FIELDS = ["size_bytes", "total_size_bytes"];
+
+const LIST_FILTERS = [
+  "buckets",
+  "bucketTags",
+  "keyPatterns",
+  "userEmails",
+] as const;
+
+function globToRegExp(pattern: string, flags = ""): RegExp {
+  const source = pattern
+    .split("")
+    .map((char) => {
+      if (char === "*") return ".*";
+      if (char === "?") return ".";
+      return char.replace(/[.+^${}()|[\]\\]/g, "\\$&");
+    })
+    .join("");
+  return new RegExp(`^${source}$`, flags);
+}
+
+function matchesAnyGlob(
+  values: string[],
+  patterns: string[],
+  flags = "",
+): boolean {
+  const expressions = patterns.map((p) => globToRegExp(p, flags));
+  return values.some((value) => expressions.some((re) => re.test(value)));
+}
+
+function stringFields(
+  data: Record<string, unknown>,
+  fields: string[],
+): string[] {
+  return fields
+    .map((field) => data[field])
+    .filter((value): value is string => typeof value === "string" && !!value);
+}
+
+/**
+ * Parse the filters column of a webhook, treating invalid JSON as no filters
+ */
+export function parseWebhookFilters(json: string | null): WebhookFilters {
+  if (!json) return {};
+  try {
+    const parsed = JSON.parse(json) as unknown;
+    return typeof parsed === "object" && parsed !== null
+      ? (parsed as WebhookFilters)
+      : {};
+  } catch {
+    return {};
+  }
+}
+
+/**
+ * Whether any filter is set
+ */
+export function hasWebhookFilters(filters: WebhookFilters): boolean {
+  return (
+    LIST_FILTERS.some((name) => (filters[name]?.length ?? 0) > 0) ||
+    filters.minSize !== undefined ||
+    filters.maxSize !== undefined
+  );
+}
+
+/**
+ * Check filters from a create or update request, returning an error message
+ * if they are invalid
+ */
+export function validateWebhookFilters(filters: unknown): string | null {
+  if (filters === null) return null;
+  if (typeof filters !== "object" || Array.isArray(filters)) {
+    return "filters must be an object";
+  }
+  const input = filters as Record<string, unknown>;
+
+  for (const name of LIST_FILTERS) {
+    const list = input[name];
+    if (list === undefined) continue;
+    if (
+      !Array.isArray(list) ||
+      !list.every((entry) => typeof entry === "string" && entry.trim() !== "")
+    ) {
+      return `filters.${name} must be a list of non-empty strings`;
+    }
+    if (list.length > MAX_FILTER_ENTRIES) {
+      return `filters.${name} can have at most ${String(MAX_FILTER_ENTRIES)} entries`;
+    }
+  }
+
+  for (const name of ["minSize", "maxSize"] as const) {
+    const size = input[name];
+    if (
+      size !== undefined &&
+      (typeof size !== "number" || !Number.isInteger(size) || size < 0)
+    ) {
+      return `filters.${name} must be a whole number of bytes`;
+    }
+  }
+
+  if (
+    typeof input["minSize"] === "number" &&
+    typeof input["maxSize"] === "number" &&
+    input["minSize"] > input["maxSize"]
+  ) {
+    return "filters.minSize cannot be larger than filters.maxSize";
+  }
+
+  return null;
+}
+
+/**
+ * Whether an event's data passes a webhook's filters. getBucketTags is only
+ * called when a bucket tag filter is set.
+ */
+export async function matchesWebhookFilters(
+  filters: WebhookFilters,
+  data: Record<string, unknown>,
+  getBucketTags: (bucketName: string) => Promise<string[]>,
+): Promise<boolean> {
+  const buckets = stringFields(data, BUCKET_FIELDS);
+
+  if (filters.buckets?.length && !matchesAnyGlob(buckets, filters.buckets)) {
+    return false;
+  }
+
+  if (
+    filters.keyPatterns?.length &&
+    !matchesAnyGlob(stringFields(data, KEY_FIELDS), filters.keyPatterns)
+  ) {
+    return false;
+  }
+
+  if (
+    filters.userEmails?.length &&
+    !matchesAnyGlob(stringFields(data, ["user_email"]), filters.userEmails, "i")
+  ) {
+    return false;
+  }
+
+  if (filters.minSize !== undefined || filters.maxSize !== undefined) {
+    const size = SIZE_FIELDS.map((field) => data[field]).find(
+      (value): value is number => typeof value === "number",
+    );
+    if (
+      size === undefined ||
+      (filters.minSize !== undefined && size < filters.minSize) ||
+      (filters.maxSize !== undefined && size > filters.maxSize)
+    ) {
+      return false;
+    }
+  }
+
+  if (filters.bucketTags?.length) {
+    const wanted = filters.bucketTags.map((tag) => tag.trim().toLowerCase());
+    const tags = (await Promise.all(buckets.map(getBucketTags))).flat();
+    if (!tags.some((tag) => wanted.includes(tag))) {
+      return false;
+    }
+  }
+
+  return true;
+}
@@ -0,0 +1,277 @@
+/**
+ * Webhook Formats
+ *
+ * Renders an event payload into the body sent to a webhook: the raw
+ * {event, timestamp, data} JSON, a Slack, Discord or Microsoft Teams
+ * message, or a user-defined JSON template with {{placeholders}} over the
+ * payload. Chat formats list the event's top-level data values as fields.
+ */
+
+import type {
+  Webhook,
+  WebhookEventType,
+  WebhookFormat,
+  WebhookPayload,
+} from "../types";
+
+export const WEBHOOK_FORMATS: WebhookFormat[] = [
+  "raw",
+  "slack",
+  "discord",
+  "teams",
+  "custom",
+];
+
+/** Longest custom template accepted */
+export const MAX_TEMPLATE_LENGTH = 10000;
+/** Data fields shown in chat messages (Slack allows at most 10) */
+const MAX_MESSAGE_FIELDS = 10;
+/** Characters of each field value shown in chat messages */
+const MAX_FIELD_VALUE_LENGTH = 1000;
+/** Discord embed colors */
+const COLOR_DEFAULT = 0xf38020;
+const COLOR_FAILURE = 0xd73a49;
+
+const EVENT_TITLES: Record<WebhookEventType, string> = {
+  file_upload: "File uploaded",
+  file_download: "File downloaded",
+  file_delete: "File deleted",
+  file_move: "File moved",
+  file_copy: "File copied",
+  file_rename: "File renamed",
+  bucket_create: "Bucket created",
+  bucket_delete: "Bucket deleted",
+  bucket_rename: "Bucket renamed",
+  folder_create: "Folder created",
+  folder_delete: "Folder deleted",
+  folder_move: "Folder moved",
+  folder_copy: "Folder copied",
+  folder_rename: "Folder renamed",
+  bulk_download_complete: "Bulk download complete",
+  s3_import_complete: "S3 import complete",
+  lifecycle_updated: "Lifecycle rules updated",
+  bucket_tags_changed: "Bucket tags changed",
+  ai_search_sync_complete: "AI Search sync complete",
+  job_failed: "Job failed",
+  job_completed: "Job completed",
+  job_cancelled: "Job cancelled",
+  rate_limit_exceeded: "Rate limit exceeded",
+};
+
+const FAILURE_EVENTS: WebhookEventType[] = [
+  "job_failed",
+  "rate_limit_exceeded",
+];
+
+/** Sample payload used to validate custom templates */
+const SAMPLE_PAYLOAD: WebhookPayload = {
+  event: "file_upload",
+  timestamp: "2024-01-01T00:00:00.000Z",
+  data: {
+    bucket_name: "media",
+    file_name: "incoming/photo.jpg",
+    size_bytes: 1024,
+    user_email: "user@example.com",
+  },
+};
+
+/** Every event a webhook can subscribe to */
+const WEBHOOK_EVENTS = Object.keys(EVENT_TITLES) as WebhookEventType[];
+
+export function isWebhookFormat(value: unknown): value is WebhookFormat {
+  return WEBHOOK_FORMATS.includes(value as WebhookFormat);
+}
+
+export function isWebhookEvent(value: unknown): value is WebhookEventType {
+  return WEBHOOK_EVENTS.includes(value as WebhookEventType);
+}
+
+interface MessageField {
+  name: string;
+  value: string;
+}
+
+/**
+ * Top-level data values as labelled fields, e.g. bucket_name -> Bucket name
+ */
+function messageFields(data: Record<string, unknown>): MessageField[] {
+  return Object.entries(data)
+    .filter(([, value]) => value !== null && value !== undefined)
+    .slice(0, MAX_MESSAGE_FIELDS)
+    .map(([key, value]) => {
+      const label = key.replace(/_/g, " ");
+      const text =
+        typeof value === "object"
+          ? JSON.stringify(value)
+          : String(value as string | number | boolean);
+      return {
+        name: label.charAt(0).toUpperCase() + label.slice(1),
+        value: text.slice(0, MAX_FIELD_VALUE_LENGTH),
+      };
+    });
+}
+
+function renderSlack(payload: WebhookPayload, title: string): unknown {
+  const fields = messageFields(payload.data);
+  return {
+    text: title,
+    blocks: [
+      { type: "header", text: { type: "plain_text", text: title } },
+      ...(fields.length > 0
+        ? [
+            {
+              type: "section",
+              fields: fields.map((field) => ({
+                type: "mrkdwn",
+                text: `*${field.name}*\n${field.value}`,
+              })),
+            },
+          ]
+        : []),
+      {
+        type: "context",
+        elements: [
+          {
+            type: "mrkdwn",
+            text: `R2 Manager · \`${payload.event}\` · ${payload.timestamp}`,
+          },
+        ],
+      },
+    ],
+  };
+}
+
+function renderDiscord(payload: WebhookPayload, title: string): unknown {
+  return {
+    username: "R2 Manager",
+    embeds: [
+      {
+        title,
+        color: FAILURE_EVENTS.includes(payload.event)
+          ? COLOR_FAILURE
+          : COLOR_DEFAULT,
+        fields: messageFields(payload.data).map((field) => ({
+          ...field,
+          inline: true,
+        })),
+        footer: { text: `R2 Manager · ${payload.event}` },
+        timestamp: payload.timestamp,
+      },
+    ],
+  };
+}
+
+function renderTeams(payload: WebhookPayload, title: string): unknown {
+  return {
+    type: "message",
+    attachments: [
+      {
+        contentType: "application/vnd.microsoft.card.adaptive",
+        contentUrl: null,
+        content: {
+          $schema: "http://adaptivecards.io/schemas/adaptive-card.json",
+          type: "AdaptiveCard",
+          version: "1.4",
+          body: [
+            {
+              type: "TextBlock",
+              text: title,
+              size: "Medium",
+              weight: "Bolder",
+              wrap: true,
+              ...(FAILURE_EVENTS.includes(payload.event) && {
+                color: "Attention",
+              }),
+            },
+            {
+              type: "FactSet",
+              facts: messageFields(payload.data).map((field) => ({
+                title: field.name,
+                value: field.value,
+              })),
+            },
+            {
+              type: "TextBlock",
+              text: `R2 Manager · ${payload.event} · ${payload.timestamp}`,
+              size: "Small",
+              isSubtle: true,
+              wrap: true,
+            },
+          ],
+        },
+      },
+    ],
+  };
+}
+
+/**
+ * Replace {{path}} placeholders (event, title, timestamp, data.<key>...)
+ * with values from the payload. Strings are JSON-escaped so they can be
+ * placed inside quotes; objects and arrays are inserted as JSON, and
+ * missing values as an empty string.
+ */
+function renderTemplate(
+  template: string,
+  payload: WebhookPayload,
+  title: string,
+): string {
+  const root: Record<string, unknown> = { ...payload, title };
+  return template.replace(/\{\{\s*([\w.]+)\s*\}\}/g, (_match, path) => {
+    let value: unknown = root;
+    for (const key of (path as string).split(".")) {
+      value =
+        typeof value === "object" && value !== null
+          ? (value as Record<string, unknown>)[key]
+          : undefined;
+    }
+    if (value === undefined || value === null) return "";
+    if (typeof value === "string") return JSON.stringify(value).slice(1, -1);
+    if (typeof value === "object") return JSON.stringify(value);
+    return String(value as number | boolean);
+  });
+}
+
+/**
+ * Render the body sent to a webhook for an event payload
+ */
+export function renderWebhookBody(
+  webhook: Pick<Webhook, "format" | "template">,
+  payload: WebhookPayload,
+): string {
+  const title = EVENT_TITLES[payload.event];
+  switch (webhook.format) {
+    case "slack":
+      return JSON.stringify(renderSlack(payload, title));
+    case "discord":
+      return JSON.stringify(renderDiscord(payload, title));
+    case "teams":
+      return JSON.stringify(renderTeams(payload, title));
+    case "custom":
+      return renderTemplate(webhook.template ?? "", payload, title);
+    default:
+      return JSON.stringify(payload);
+  }
+}
+
+/**
+ * Check a custom template, returning an error message if it is empty, too
+ * long, or does not render to valid JSON
+ */
+export function validateWebhookTemplate(
+  template: string | null | undefined,
+): string | null {
+  if (!template?.trim()) {
+    return "A template is required for the custom format";
+  }
+  if (template.length > MAX_TEMPLATE_LENGTH) {
+    return `Template must be at most ${String(MAX_TEMPLATE_LENGTH)} characters`;
+  }
+  try {
+    JSON.parse(
+      renderTemplate(template, SAMPLE_PAYLOAD, EVENT_TITLES.file_upload),
+    );
+    return null;
+  } catch (error) {
+    return `Template does not render to valid JSON: ${error instanceof Error ? error.message : String(error)}`;
+  }
+}
@@ -0,0 +1,102 @@
+import { describe, expect, it } from "vitest";
+import {
+  createSignatureHeader,
+  verifyWebhookSignature,
+  WEBHOOK_SIGNATURE_TEST_VECTORS,
+  type VerifyWebhookSignatureOptions,
+} from "./webhook-signature";
+
+const BODY = '{"event":"file_upload","data":{"file_name":"photo.jpg"}}';
+const TIMESTAMP = 1700000000;
+
+describe("WEBHOOK_SIGNATURE_TEST_VECTORS", () => {
+  it.each(WEBHOOK_SIGNATURE_TEST_VECTORS)(
+    "$description",
+    async ({ options, valid }) => {
+      expect(await verifyWebhookSignature(options)).toBe(valid);
+    },
+  );
+
+  it("match the signatures createSignatureHeader produces", async () => {
+    const [signed, rotating] = WEBHOOK_SIGNATURE_TEST_VECTORS;
+    if (signed === undefined || rotating === undefined) {
+      throw new Error("Missing test vectors");
+    }
+
+    expect(
+      await createSignatureHeader(
+        ["whsec_test_secret"],
+        TIMESTAMP,
+        signed.options.body,
+      ),
+    ).toBe(signed.options.signature);
+    expect(
+      await createSignatureHeader(
+        ["whsec_test_secret", "whsec_previous_secret"],
+        TIMESTAMP,
+        rotating.options.body,
+      ),
+    ).toBe(rotating.options.signature);
+  });
+});
+
+describe("verifyWebhookSignature", () => {
+  async function verify(
+    overrides: Partial<VerifyWebhookSignatureOptions> = {},
+  ): Promise<boolean> {
+    return verifyWebhookSignature({
+      body: BODY,
+      timestamp: String(TIMESTAMP),
+      signature: await createSignatureHeader(["secret"], TIMESTAMP, BODY),
+      secret: "secret",
+      now: TIMESTAMP,
+      ...overrides,
+    });
+  }
+
+  it("accepts a request signed with the secret", async () => {
+    expect(await verify()).toBe(true);
+  });
+
+  it("rejects another secret or timestamp", async () => {
+    expect(await verify({ secret: "other" })).toBe(false);
+    expect(
+      await verify({ timestamp: String(TIMESTAMP + 1), now: TIMESTAMP + 1 }),
+    ).toBe(false);
+  });
+
+  it("accepts either of two secrets while the receiver rotates", async () => {
+    expect(await verify({ secret: ["new-secret", "secret"] })).toBe(true);
+    expect(await verify({ secret: ["new-secret", "older-secret"] })).toBe(
+      false,
+    );
+  });
+
+  it("rejects timestamps outside the tolerance", async () => {
+    expect(await verify({ now: TIMESTAMP + 300 })).toBe(true);
+    expect(await verify({ now: TIMESTAMP - 300 })).toBe(true);
+    expect(await verify({ now: TIMESTAMP + 301 })).toBe(false);
+    expect(await verify({ now: TIMESTAMP - 301 })).toBe(false);
+    expect(await verify({ now: TIMESTAMP + 30, toleranceSeconds: 10 })).toBe(
+      false,
+    );
+  });
+
+  it("rejects missing or malformed headers", async () => {
+    expect(await verify({ timestamp: null })).toBe(false);
+    expect(await verify({ signature: null })).toBe(false);
+    expect(await verify({ timestamp: "1700000000.5" })).toBe(false);
+    expect(await verify({ timestamp: "-1700000000" })).toBe(false);
+  });
+
+  it("only reads v1 entries of the signature header", async () => {
+    const [, hex] = (
+      await createSignatureHeader(["secret"], TIMESTAMP, BODY)
+    ).split("=");
+
+    expect(await verify({ signature: `v0=${hex ?? ""}` })).toBe(false);
+    expect(await verify({ signature: `v0=deadbeef, v1=${hex ?? ""}` })).toBe(
+      true,
+    );
+  });
+});
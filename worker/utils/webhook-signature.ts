@@ -0,0 +1,179 @@
+/**
+ * Webhook Signatures
+ *
+ * Each webhook request carries an X-Webhook-Timestamp header (Unix seconds)
+ * and an X-Webhook-Signature header of one or more comma-separated
+ * "v1=<hex>" entries, each an HMAC-SHA256 of "<timestamp>.<body>" under one
+ * of the webhook's active secrets. While a secret is being rotated, both the
+ * new and the previous secret sign the request.
+ *
+ * This file has no imports and only uses Web Crypto, so receivers running
+ * on Workers, Node.js 20+, Deno or Bun can copy it and call
+ * verifyWebhookSignature. WEBHOOK_SIGNATURE_TEST_VECTORS can be used to
+ * check a port to another language.
+ */
+
+/** Signature scheme prefix of each X-Webhook-Signature entry */
+const SIGNATURE_VERSION = "v1";
+
+/** How far the timestamp may be from the receiver's clock by default */
+export const DEFAULT_SIGNATURE_TOLERANCE_SECONDS = 300;
+
+async function hmacSha256Hex(secret: string, message: string): Promise<string> {
+  const encoder = new TextEncoder();
+  const key = await crypto.subtle.importKey(
+    "raw",
+    encoder.encode(secret),
+    { name: "HMAC", hash: "SHA-256" },
+    false,
+    ["sign"],
+  );
+  const signature = await crypto.subtle.sign(
+    "HMAC",
+    key,
+    encoder.encode(message),
+  );
+  return Array.from(new Uint8Array(signature))
+    .map((b) => b.toString(16).padStart(2, "0"))
+    .join("");
+}
+
+/**
+ * Compare two strings in time that does not depend on where they differ
+ */
+function timingSafeEqual(a: string, b: string): boolean {
+  if (a.length !== b.length) return false;
+  let difference = 0;
+  for (let i = 0; i < a.length; i++) {
+    difference |= a.charCodeAt(i) ^ b.charCodeAt(i);
+  }
+  return difference === 0;
+}
+
+/**
+ * Build the X-Webhook-Signature value for a body, with one entry per secret
+ */
+export async function createSignatureHeader(
+  secrets: string[],
+  timestamp: number,
+  body: string,
+): Promise<string> {
+  const signatures = await Promise.all(
+    secrets.map((secret) =>
+      hmacSha256Hex(secret, `${String(timestamp)}.${body}`),
+    ),
+  );
+  return signatures
+    .map((signature) => `${SIGNATURE_VERSION}=${signature}`)
+    .join(",");
+}
+
+export interface VerifyWebhookSignatureOptions {
+  /** Raw request body, exactly as received */
+  body: string;
+  /** X-Webhook-Timestamp header */
+  timestamp: string | null;
+  /** X-Webhook-Signature header */
+  signature: string | null;
+  /** Your secret, or both secrets while you rotate on your side */
+  secret: string | string[];
+  /** Largest accepted clock difference in seconds (default 300) */
+  toleranceSeconds?: number;
+  /** Current Unix time in seconds, for tests */
+  now?: number;
+}
+
+/**
+ * Check that a webhook request was signed with the secret and is recent.
+ * Rejecting old timestamps stops captured requests from being replayed.
+ */
+export async function verifyWebhookSignature(
+  options: VerifyWebhookSignatureOptions,
+): Promise<boolean> {
+  if (!options.timestamp || !options.signature) return false;
+  if (!/^\d+$/.test(options.timestamp)) return false;
+
+  const timestamp = Number(options.timestamp);
+  const now = options.now ?? Math.floor(Date.now() / 1000);
+  const tolerance =
+    options.toleranceSeconds ?? DEFAULT_SIGNATURE_TOLERANCE_SECONDS;
+  if (Math.abs(now - timestamp) > tolerance) return false;
+
+  const received = options.signature
+    .split(",")
+    .map((entry) => entry.trim())
+    .filter((entry) => entry.startsWith(`${SIGNATURE_VERSION}=`))
+    .map((entry) => entry.slice(SIGNATURE_VERSION.length + 1));
+
+  const secrets = Array.isArray(options.secret)
+    ? options.secret
+    : [options.secret];
+  for (const secret of secrets) {
+    const expected = await hmacSha256Hex(
+      secret,
+      `${options.timestamp}.${options.body}`,
+    );
+    if (received.some((signature) => timingSafeEqual(signature, expected))) {
+      return true;
+    }
+  }
+  return false;
+}
+
+/**
+ * Known inputs and the result verifyWebhookSignature must give for them
+ */
+export const WEBHOOK_SIGNATURE_TEST_VECTORS: {
+  description: string;
+  options: VerifyWebhookSignatureOptions;
+  valid: boolean;
+}[] = [
+  {
+    description: "Signed with the secret",
+    options: {
+      body: '{"event":"file_upload","timestamp":"2023-11-14T22:13:20.000Z","data":{"bucket_name":"media","file_name":"photo.jpg"}}',
+      timestamp: "1700000000",
+      signature:
+        "v1=180479730b3e3a3235fcd68d4a5c2a5352cb38635008f41e4a07f66024b17746",
+      secret: "whsec_test_secret",
+      now: 1700000060,
+    },
+    valid: true,
+  },
+  {
+    description: "Signed with the new and previous secret during rotation",
+    options: {
+      body: '{"event":"file_upload","timestamp":"2023-11-14T22:13:20.000Z","data":{"bucket_name":"media","file_name":"photo.jpg"}}',
+      timestamp: "1700000000",
+      signature:
+        "v1=180479730b3e3a3235fcd68d4a5c2a5352cb38635008f41e4a07f66024b17746,v1=d2264f37c30cdb3d6d83e64a2909c981e5f9da75b187c631616f1f7329bbf583",
+      secret: "whsec_previous_secret",
+      now: 1700000060,
+    },
+    valid: true,
+  },
+  {
+    description: "Timestamp older than the tolerance (replayed request)",
+    options: {
+      body: '{"event":"file_upload","timestamp":"2023-11-14T22:13:20.000Z","data":{"bucket_name":"media","file_name":"photo.jpg"}}',
+      timestamp: "1700000000",
+      signature:
+        "v1=180479730b3e3a3235fcd68d4a5c2a5352cb38635008f41e4a07f66024b17746",
+      secret: "whsec_test_secret",
+      now: 1700000301,
+    },
+    valid: false,
+  },
+  {
+    description: "Body changed after signing",
+    options: {
+      body: '{"event":"file_upload","timestamp":"2023-11-14T22:13:20.000Z","data":{"bucket_name":"media","file_name":"other.jpg"}}',
+      timestamp: "1700000000",
+      signature:
+        "v1=180479730b3e3a3235fcd68d4a5c2a5352cb38635008f41e4a07f66024b17746",
+      secret: "whsec_test_secret",
+      now: 1700000060,
+    },
+    valid: false,
+  },
+];
@@ -1,18 +1,42 @@
 /**
  * Webhook Utilities
  *
- * Handles sending webhook notifications to configured endpoints.
- * Supports HMAC-SHA256 signatures for secure payload verification.
+ * Handles sending webhook notifications to configured endpoints, in the
+ * webhook's body format (see webhook-formats.ts), to the webhooks whose
+ * filters match the event (see webhook-filters.ts).
+ * Supports timestamped HMAC-SHA256 signatures for secure payload
+ * verification (see webhook-signature.ts).
+ * Every attempt is recorded in webhook_deliveries before it is sent, and
+ * failed or interrupted attempts are retried with exponential backoff.
  */
 
 import type {
   Env,
   Webhook,
+  WebhookDelivery,
+  WebhookDeliveryReason,
   WebhookEventType,
   WebhookPayload,
   WebhookResult,
 } from "../types";
 import { logInfo, logWarning } from "./error-logger";
+import {
+  matchesWebhookFilters,
+  parseWebhookFilters,
+} from "./webhook-filters";
+import { renderWebhookBody } from "./webhook-formats";
+import { createSignatureHeader } from "./webhook-signature";
+
+/** Attempts per delivery, including the first, before it is given up */
+export const MAX_WEBHOOK_ATTEMPTS = 5;
+/** Delay before the first retry; each later retry waits four times longer */
+const RETRY_BASE_DELAY_SECONDS = 30;
+/** Due retries sent per scheduled run */
+const RETRY_SWEEP_LIMIT = 50;
+/** Time an endpoint has to respond; shorter than the first retry delay */
+const WEBHOOK_TIMEOUT_MS = 10_000;
+/** Characters of the endpoint's response kept with each attempt */
+const MAX_RESPONSE_BODY_LENGTH = 1000;
 
 /**
  * Generate current ISO timestamp
@@ -22,87 +46,384 @@ function nowISO(): string {
 }
 
 /**
- * Generate HMAC-SHA256 signature for webhook payload
+ * Generate a unique ID for a delivery or delivery attempt
  */
-async function generateSignature(
-  payload: string,
-  secret: string,
-): Promise<string> {
-  const encoder = new TextEncoder();
-  const key = await crypto.subtle.importKey(
-    "raw",
-    encoder.encode(secret),
-    { name: "HMAC", hash: "SHA-256" },
-    false,
-    ["sign"],
-  );
-  const signature = await crypto.subtle.sign(
-    "HMAC",
-    key,
-    encoder.encode(payload),
-  );
-  const hashArray = Array.from(new Uint8Array(signature));
-  return hashArray.map((b) => b.toString(16).padStart(2, "0")).join("");
+function generateDeliveryId(): string {
+  return `whd_${crypto.randomUUID().replace(/-/g, "")}`;
 }
 
 /**
- * Send a webhook to a configured endpoint
+ * Secrets that sign a webhook's requests: its secret and, until the rotation
+ * grace period ends, the secret it replaced
  */
-export async function sendWebhook(
-  webhook: Webhook,
+function activeSecrets(webhook: Webhook): string[] {
+  const secrets = webhook.secret ? [webhook.secret] : [];
+  if (
+    webhook.previous_secret &&
+    webhook.previous_secret_expires_at &&
+    new Date(webhook.previous_secret_expires_at).getTime() > Date.now()
+  ) {
+    secrets.push(webhook.previous_secret);
+  }
+  return secrets;
+}
+
+/**
+ * Build the body sent to a webhook for an event, in the webhook's format
+ */
+export function createWebhookBody(
+  webhook: Pick<Webhook, "format" | "template">,
   event: WebhookEventType,
   data: Record<string, unknown>,
-): Promise<WebhookResult> {
+): string {
   const payload: WebhookPayload = {
     event,
     timestamp: nowISO(),
     data,
   };
+  return renderWebhookBody(webhook, payload);
+}
 
-  const body = JSON.stringify(payload);
+/**
+ * POST a webhook body to its endpoint, timing the request. Each attempt is
+ * signed with the current time, so retries are not rejected as replays.
+ */
+async function postWebhook(
+  webhook: Webhook,
+  event: WebhookEventType,
+  body: string,
+  deliveryId?: string,
+): Promise<WebhookResult> {
   const headers: Record<string, string> = {
     "Content-Type": "application/json",
     "User-Agent": "R2-Manager-Webhook/1.0",
     "X-Webhook-Event": event,
   };
 
-  // Add HMAC signature if secret is configured
-  if (webhook.secret) {
-    const signature = await generateSignature(body, webhook.secret);
-    headers["X-Webhook-Signature"] = `sha256=${signature}`;
+  // Same on every attempt, so receivers can skip duplicates
+  if (deliveryId) {
+    headers["X-Webhook-Delivery"] = deliveryId;
   }
 
+  // Sign "<timestamp>.<body>" with each active secret
+  const timestamp = Math.floor(Date.now() / 1000);
+  headers["X-Webhook-Timestamp"] = String(timestamp);
+  const secrets = activeSecrets(webhook);
+  if (secrets.length > 0) {
+    headers["X-Webhook-Signature"] = await createSignatureHeader(
+      secrets,
+      timestamp,
+      body,
+    );
+  }
+
+  const startedAt = Date.now();
   try {
     const response = await fetch(webhook.url, {
       method: "POST",
       headers,
       body,
+      signal: AbortSignal.timeout(WEBHOOK_TIMEOUT_MS),
     });
+    const responseText = await response.text().catch(() => "");
+    const latencyMs = Date.now() - startedAt;
 
     if (response.ok) {
-      return { success: true, statusCode: response.status };
+      return {
+        success: true,
+        statusCode: response.status,
+        latencyMs,
+        responseBody: responseText.slice(0, MAX_RESPONSE_BODY_LENGTH),
+      };
     } else {
-      const errorText = await response.text().catch(() => "Unknown error");
       return {
         success: false,
         statusCode: response.status,
-        error: errorText.slice(0, 200),
+        error: (responseText || "Unknown error").slice(0, 200),
+        latencyMs,
+        responseBody: responseText.slice(0, MAX_RESPONSE_BODY_LENGTH),
       };
     }
   } catch (error) {
     return {
       success: false,
       error: error instanceof Error ? error.message : "Unknown error",
+      latencyMs: Date.now() - startedAt,
     };
   }
 }
 
 /**
- * Get all enabled webhooks for a specific event type
+ * Send a body built with createWebhookBody to a configured endpoint, without
+ * recording it as a delivery
+ */
+export async function sendWebhook(
+  webhook: Webhook,
+  event: WebhookEventType,
+  body: string,
+): Promise<WebhookResult> {
+  return postWebhook(webhook, event, body);
+}
+
+/**
+ * Whether a failed attempt may succeed if sent again: network errors,
+ * timeouts, rate limits and server errors
+ */
+function isRetryableResult(result: WebhookResult): boolean {
+  const status = result.statusCode;
+  return (
+    status === undefined || status === 408 || status === 429 || status >= 500
+  );
+}
+
+/**
+ * Retry delay of a failed attempt, or null if it is not retried again
+ */
+function getRetryDelaySeconds(
+  attempt: number,
+  reason: WebhookDeliveryReason,
+): number | null {
+  return reason !== "redeliver" && attempt < MAX_WEBHOOK_ATTEMPTS
+    ? RETRY_BASE_DELAY_SECONDS * 4 ** (attempt - 1)
+    : null;
+}
+
+/**
+ * Send a webhook body and record the attempt in webhook_deliveries. Failed
+ * event and retry attempts are retried with exponential backoff until
+ * MAX_WEBHOOK_ATTEMPTS, through JOB_QUEUE when it is bound and otherwise by
+ * the scheduled maintenance handler. Manual redeliveries are not retried.
+ *
+ * The attempt is recorded as failed, with its retry scheduled, before the
+ * request is sent. If the worker stops before the endpoint answers, the
+ * scheduled handler still retries it.
+ */
+async function deliverWebhook(
+  env: Env,
+  db: D1Database,
+  webhook: Webhook,
+  event: WebhookEventType,
+  body: string,
+  delivery: {
+    deliveryId: string;
+    attempt: number;
+    reason: WebhookDeliveryReason;
+  },
+): Promise<WebhookDelivery> {
+  const retryAfter = getRetryDelaySeconds(delivery.attempt, delivery.reason);
+  const retryAt = (delaySeconds: number | null): string | null =>
+    delaySeconds !== null
+      ? new Date(Date.now() + delaySeconds * 1000).toISOString()
+      : null;
+
+  const record: WebhookDelivery = {
+    id: generateDeliveryId(),
+    delivery_id: delivery.deliveryId,
+    webhook_id: webhook.id,
+    event,
+    payload: body,
+    attempt: delivery.attempt,
+    reason: delivery.reason,
+    status: "failed",
+    status_code: null,
+    latency_ms: null,
+    response_body: null,
+    error: "Interrupted before the endpoint responded",
+    next_retry_at: retryAt(retryAfter),
+    created_at: nowISO(),
+  };
+
+  await db
+    .prepare(
+      `
+    INSERT INTO webhook_deliveries (
+      id, delivery_id, webhook_id, event, payload, attempt, reason, status,
+      status_code, latency_ms, response_body, error, next_retry_at, created_at
+    ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+  `,
+    )
+    .bind(
+      record.id,
+      record.delivery_id,
+      record.webhook_id,
+      record.event,
+      record.payload,
+      record.attempt,
+      record.reason,
+      record.status,
+      record.status_code,
+      record.latency_ms,
+      record.response_body,
+      record.error,
+      record.next_retry_at,
+      record.created_at,
+    )
+    .run();
+
+  const result = await postWebhook(webhook, event, body, delivery.deliveryId);
+
+  const retryDelaySeconds =
+    !result.success && isRetryableResult(result) ? retryAfter : null;
+
+  record.status = result.success ? "succeeded" : "failed";
+  record.status_code = result.statusCode ?? null;
+  record.latency_ms = result.latencyMs ?? null;
+  record.response_body = result.responseBody ?? null;
+  record.error = result.error ?? null;
+  record.next_retry_at = retryAt(retryDelaySeconds);
+
+  await db
+    .prepare(
+      `
+    UPDATE webhook_deliveries
+    SET status = ?, status_code = ?, latency_ms = ?, response_body = ?,
+      error = ?, next_retry_at = ?
+    WHERE id = ?
+  `,
+    )
+    .bind(
+      record.status,
+      record.status_code,
+      record.latency_ms,
+      record.response_body,
+      record.error,
+      record.next_retry_at,
+      record.id,
+    )
+    .run();
+
+  if (retryDelaySeconds !== null && env.JOB_QUEUE) {
+    await env.JOB_QUEUE.send(
+      { webhookDeliveryId: record.id },
+      { delaySeconds: retryDelaySeconds },
+    );
+  }
+
+  return record;
+}
+
+/**
+ * Retry a failed delivery attempt whose retry is due. The retry is claimed
+ * first, so an attempt picked up by both the queue and the scheduled handler
+ * is only sent once. Returns true if the webhook was sent again.
+ */
+export async function retryWebhookDelivery(
+  env: Env,
+  attemptId: string,
+): Promise<boolean> {
+  const db = env.METADATA;
+  if (!db) return false;
+
+  const previous = await db
+    .prepare(
+      "UPDATE webhook_deliveries SET next_retry_at = NULL WHERE id = ? AND next_retry_at IS NOT NULL RETURNING *",
+    )
+    .bind(attemptId)
+    .first<WebhookDelivery>();
+  if (previous === null) return false;
+
+  const webhook = await db
+    .prepare("SELECT * FROM webhooks WHERE id = ? AND enabled = 1")
+    .bind(previous.webhook_id)
+    .first<Webhook>();
+  if (webhook === null) return false;
+
+  const record = await deliverWebhook(
+    env,
+    db,
+    webhook,
+    previous.event,
+    previous.payload,
+    {
+      deliveryId: previous.delivery_id,
+      attempt: previous.attempt + 1,
+      reason: "retry",
+    },
+  );
+  if (record.status === "failed") {
+    logWarning(
+      `Retry ${String(record.attempt)} to ${webhook.name} failed: ${record.error ?? "Unknown error"}`,
+      {
+        module: "webhooks",
+        operation: "retry",
+        metadata: {
+          webhookName: webhook.name,
+          event: record.event,
+          deliveryId: record.delivery_id,
+          statusCode: record.status_code,
+          willRetry: record.next_retry_at !== null,
+        },
+      },
+    );
+  }
+  return true;
+}
+
+/**
+ * Retry every delivery attempt whose retry is due. Used by the scheduled
+ * handler, which also catches retries whose queue message was lost.
+ * Returns the number of attempts retried.
+ */
+export async function retryDueWebhookDeliveries(env: Env): Promise<number> {
+  const db = env.METADATA;
+  if (!db) return 0;
+
+  const due = await db
+    .prepare(
+      "SELECT id FROM webhook_deliveries WHERE next_retry_at IS NOT NULL AND next_retry_at <= ? ORDER BY next_retry_at LIMIT ?",
+    )
+    .bind(nowISO(), RETRY_SWEEP_LIMIT)
+    .all<{ id: string }>();
+
+  let retried = 0;
+  for (const attempt of due.results) {
+    if (await retryWebhookDelivery(env, attempt.id)) {
+      retried++;
+    }
+  }
+  return retried;
+}
+
+/**
+ * Send a delivery again as a new attempt, reusing its original body. Any
+ * pending automatic retry of the delivery is cancelled.
+ */
+export async function redeliverWebhook(
+  env: Env,
+  db: D1Database,
+  webhook: Webhook,
+  deliveryId: string,
+): Promise<WebhookDelivery | null> {
+  const latest = await db
+    .prepare(
+      "SELECT * FROM webhook_deliveries WHERE delivery_id = ? AND webhook_id = ? ORDER BY attempt DESC LIMIT 1",
+    )
+    .bind(deliveryId, webhook.id)
+    .first<WebhookDelivery>();
+  if (latest === null) return null;
+
+  await db
+    .prepare(
+      "UPDATE webhook_deliveries SET next_retry_at = NULL WHERE delivery_id = ?",
+    )
+    .bind(deliveryId)
+    .run();
+
+  return deliverWebhook(env, db, webhook, latest.event, latest.payload, {
+    deliveryId,
+    attempt: latest.attempt + 1,
+    reason: "redeliver",
+  });
+}
+
+/**
+ * Get all enabled webhooks subscribed to an event type whose filters match
+ * the event's data
  */
 export async function getWebhooksForEvent(
   db: D1Database,
   event: WebhookEventType,
+  data: Record<string, unknown>,
 ): Promise<Webhook[]> {
   try {
     const result = await db
@@ -110,7 +431,7 @@ export async function getWebhooksForEvent(
       .all<Webhook>();
 
     // Filter webhooks that are subscribed to this event
-    return result.results.filter((webhook) => {
+    const subscribed = result.results.filter((webhook) => {
       try {
         const events = JSON.parse(webhook.events) as string[];
         return events.includes(event);
@@ -118,6 +439,34 @@ export async function getWebhooksForEvent(
         return false;
       }
     });
+
+    // Bucket tags are looked up at most once per bucket, and only when a
+    // webhook filters on them
+    const tagLookups = new Map<string, Promise<string[]>>();
+    const getBucketTags = (bucketName: string): Promise<string[]> => {
+      let lookup = tagLookups.get(bucketName);
+      if (!lookup) {
+        lookup = db
+          .prepare("SELECT tag FROM bucket_tags WHERE bucket_name = ?")
+          .bind(bucketName)
+          .all<{ tag: string }>()
+          .then((rows) => rows.results.map((row) => row.tag))
+          .catch(() => []);
+        tagLookups.set(bucketName, lookup);
+      }
+      return lookup;
+    };
+
+    const matches = await Promise.all(
+      subscribed.map((webhook) =>
+        matchesWebhookFilters(
+          parseWebhookFilters(webhook.filters),
+          data,
+          getBucketTags,
+        ),
+      ),
+    );
+    return subscribed.filter((_webhook, index) => matches[index] === true);
   } catch (error) {
     logWarning(
       `Failed to get webhooks: ${error instanceof Error ? error.message : String(error)}`,
@@ -153,12 +502,13 @@ export async function triggerWebhooks(
     return;
   }
 
-  if (!env.METADATA) {
+  const db = env.METADATA;
+  if (!db) {
     return;
   }
 
   try {
-    const webhooks = await getWebhooksForEvent(env.METADATA, event);
+    const webhooks = await getWebhooksForEvent(db, event, data);
 
     if (webhooks.length === 0) {
       return;
@@ -173,11 +523,18 @@ export async function triggerWebhooks(
       },
     );
 
-    // Send webhooks in parallel, don't await completion
+    // Send webhooks in parallel, don't await completion. Each attempt is
+    // recorded before it is sent, so one cut short is retried by the
+    // scheduled handler.
     const promises = webhooks.map(async (webhook) => {
       try {
-        const result = await sendWebhook(webhook, event, data);
-        if (!result.success) {
+        const body = createWebhookBody(webhook, event, data);
+        const result = await deliverWebhook(env, db, webhook, event, body, {
+          deliveryId: generateDeliveryId(),
+          attempt: 1,
+          reason: "event",
+        });
+        if (result.status === "failed") {
           logWarning(
             `Failed to send to ${webhook.name}: ${result.error ?? "Unknown error"}`,
             {
@@ -187,7 +544,8 @@ export async function triggerWebhooks(
                 webhookName: webhook.name,
                 event,
                 error: result.error,
-                statusCode: result.statusCode,
+                statusCode: result.status_code,
+                willRetry: result.next_retry_at !== null,
               },
             },
           );
@@ -208,7 +566,6 @@ export async function triggerWebhooks(
       }
     });
 
-    // Fire and forget - use waitUntil pattern in production
     void Promise.all(promises);
   } catch (error) {
     logWarning(
@@ -344,6 +701,25 @@ export function createJobCompletedPayload(
   };
 }
 
+/**
+ * Create webhook payload for job cancellation events
+ */
+export function createJobCancelledPayload(
+  jobId: string,
+  jobType: string,
+  processedItems: number,
+  bucketName: string | null,
+  userEmail: string | null,
+): Record<string, unknown> {
+  return {
+    job_id: jobId,
+    job_type: jobType,
+    processed_items: processedItems,
+    bucket_name: bucketName,
+    user_email: userEmail,
+  };
+}
+
 /**
  * Create webhook payload for file move events
  */
@@ -446,6 +822,27 @@ export function createFolderDeletePayload(
   };
 }
 
+/**
+ * Create webhook payload for folder move, copy and rename events
+ */
+export function createFolderTransferPayload(
+  sourceBucket: string,
+  folderPath: string,
+  destBucket: string,
+  destPath: string,
+  filesProcessed: number,
+  userEmail: string | null,
+): Record<string, unknown> {
+  return {
+    source_bucket: sourceBucket,
+    folder_path: folderPath,
+    destination_bucket: destBucket,
+    destination_path: destPath,
+    files_processed: filesProcessed,
+    user_email: userEmail,
+  };
+}
+
 /**
  * Create webhook payload for bulk download completion events
  */
@@ -485,3 +882,79 @@ export function createS3ImportCompletePayload(
     user_email: userEmail,
   };
 }
+
+/**
+ * Create webhook payload for lifecycle rule updates
+ */
+export function createLifecycleUpdatedPayload(
+  bucketName: string,
+  ruleCount: number,
+  userEmail: string | null,
+): Record<string, unknown> {
+  return {
+    bucket_name: bucketName,
+    rule_count: ruleCount,
+    user_email: userEmail,
+  };
+}
+
+/**
+ * Create webhook payload for bucket tag changes
+ */
+export function createBucketTagsChangedPayload(
+  bucketName: string,
+  tags: string[],
+  addedTags: string[],
+  removedTags: string[],
+  userEmail: string | null,
+): Record<string, unknown> {
+  return {
+    bucket_name: bucketName,
+    tags,
+    added_tags: addedTags,
+    removed_tags: removedTags,
+    user_email: userEmail,
+  };
+}
+
+/**
+ * Create webhook payload for AI Search sync completion events
+ */
+export function createAISearchSyncCompletePayload(
+  instanceName: string,
+  bucketName: string | null,
+  jobId: string,
+  source: string,
+  startedAt: string,
+  endedAt: string,
+): Record<string, unknown> {
+  return {
+    instance_name: instanceName,
+    bucket_name: bucketName,
+    job_id: jobId,
+    source,
+    started_at: startedAt,
+    ended_at: endedAt,
+  };
+}
+
+/**
+ * Create webhook payload for rate limit violations
+ */
+export function createRateLimitExceededPayload(
+  tier: string,
+  limit: number,
+  periodSeconds: number,
+  method: string,
+  path: string,
+  userEmail: string | null,
+): Record<string, unknown> {
+  return {
+    tier,
+    limit,
+    period_seconds: periodSeconds,
+    method,
+    path,
+    user_email: userEmail,
+  };
+}
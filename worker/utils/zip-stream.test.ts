@@ -0,0 +1,256 @@
+import { describe, expect, it, vi } from "vitest";
+import { crc32, createZipStream, type ZipEntry } from "./zip-stream";
+
+const encoder = new TextEncoder();
+const decoder = new TextDecoder();
+
+interface CentralRecord {
+  name: string;
+  versionNeeded: number;
+  flags: number;
+  crc: number;
+  size: number;
+  offset: number;
+  dosTime: number;
+  dosDate: number;
+  extra: Uint8Array;
+}
+
+function streamOf(...chunks: string[]): ReadableStream<Uint8Array> {
+  return new ReadableStream({
+    start(controller) {
+      for (const chunk of chunks) {
+        controller.enqueue(encoder.encode(chunk));
+      }
+      controller.close();
+    },
+  });
+}
+
+function entry(
+  name: string,
+  chunks: string[],
+  lastModified = new Date("2024-03-15T10:20:30Z"),
+): ZipEntry {
+  const size = chunks.reduce(
+    (total, chunk) => total + encoder.encode(chunk).length,
+    0,
+  );
+  return { name, size, lastModified, body: streamOf(...chunks) };
+}
+
+async function* entriesOf(entries: ZipEntry[]): AsyncGenerator<ZipEntry> {
+  for (const zipEntry of entries) {
+    yield zipEntry;
+  }
+}
+
+async function readAll(
+  stream: ReadableStream<Uint8Array>,
+): Promise<Uint8Array> {
+  const chunks: Uint8Array[] = [];
+  const reader = stream.getReader();
+  for (;;) {
+    const { done, value } = await reader.read();
+    if (done) break;
+    chunks.push(value);
+  }
+  const bytes = new Uint8Array(
+    chunks.reduce((total, chunk) => total + chunk.length, 0),
+  );
+  let offset = 0;
+  for (const chunk of chunks) {
+    bytes.set(chunk, offset);
+    offset += chunk.length;
+  }
+  return bytes;
+}
+
+function viewOf(bytes: Uint8Array): DataView {
+  return new DataView(bytes.buffer, bytes.byteOffset, bytes.byteLength);
+}
+
+function readCentralDirectory(zip: Uint8Array): CentralRecord[] {
+  const view = viewOf(zip);
+  const end = zip.length - 22;
+  expect(view.getUint32(end, true)).toBe(0x06054b50);
+
+  const records: CentralRecord[] = [];
+  let pos = view.getUint32(end + 16, true);
+  for (let i = 0; i < view.getUint16(end + 10, true); i++) {
+    expect(view.getUint32(pos, true)).toBe(0x02014b50);
+    const nameLength = view.getUint16(pos + 28, true);
+    const extraLength = view.getUint16(pos + 30, true);
+    const extraStart = pos + 46 + nameLength;
+    records.push({
+      name: decoder.decode(zip.subarray(pos + 46, extraStart)),
+      versionNeeded: view.getUint16(pos + 6, true),
+      flags: view.getUint16(pos + 8, true),
+      crc: view.getUint32(pos + 16, true),
+      size: view.getUint32(pos + 24, true),
+      offset: view.getUint32(pos + 42, true),
+      dosTime: view.getUint16(pos + 12, true),
+      dosDate: view.getUint16(pos + 14, true),
+      extra: zip.subarray(extraStart, extraStart + extraLength),
+    });
+    pos = extraStart + extraLength;
+  }
+  return records;
+}
+
+/** Offset of an entry's data, after its local file header */
+function dataOffset(zip: Uint8Array, headerOffset: number): number {
+  const view = viewOf(zip);
+  expect(view.getUint32(headerOffset, true)).toBe(0x04034b50);
+  return (
+    headerOffset +
+    30 +
+    view.getUint16(headerOffset + 26, true) +
+    view.getUint16(headerOffset + 28, true)
+  );
+}
+
+describe("crc32", () => {
+  it("matches the CRC-32 check value", () => {
+    expect(crc32(encoder.encode("123456789"))).toBe(0xcbf43926);
+    expect(crc32(new Uint8Array(0))).toBe(0);
+  });
+
+  it("continues a running CRC across chunks", () => {
+    const whole = crc32(encoder.encode("hello world"));
+    const running = crc32(
+      encoder.encode(" world"),
+      crc32(encoder.encode("hello")),
+    );
+    expect(running).toBe(whole);
+  });
+});
+
+describe("createZipStream", () => {
+  it("writes each entry with its data, CRC and size", async () => {
+    const zip = await readAll(
+      createZipStream(
+        entriesOf([
+          entry("docs/readme.txt", ["hello ", "world"]),
+          entry("photos/ファイル.txt", ["ünïcode"]),
+        ]),
+      ),
+    );
+
+    const records = readCentralDirectory(zip);
+    expect(records.map((record) => record.name)).toEqual([
+      "docs/readme.txt",
+      "photos/ファイル.txt",
+    ]);
+
+    const contents = ["hello world", "ünïcode"];
+    records.forEach((record, i) => {
+      const data = encoder.encode(contents[i] ?? "");
+      expect(record.size).toBe(data.length);
+      expect(record.crc).toBe(crc32(data));
+      // Data descriptor and UTF-8 name flags
+      expect(record.flags).toBe(0x0808);
+      expect(record.versionNeeded).toBe(20);
+
+      const start = dataOffset(zip, record.offset);
+      expect(zip.subarray(start, start + record.size)).toEqual(data);
+
+      const descriptor = viewOf(zip.subarray(start + record.size));
+      expect(descriptor.getUint32(0, true)).toBe(0x08074b50);
+      expect(descriptor.getUint32(4, true)).toBe(record.crc);
+      expect(descriptor.getUint32(8, true)).toBe(record.size);
+    });
+  });
+
+  it("strips leading slashes from entry names", async () => {
+    const zip = await readAll(
+      createZipStream(entriesOf([entry("//folder/file.txt", ["x"])])),
+    );
+    expect(readCentralDirectory(zip)[0]?.name).toBe("folder/file.txt");
+  });
+
+  it("stores modification times as DOS dates", async () => {
+    const zip = await readAll(
+      createZipStream(
+        entriesOf([
+          entry("new.txt", ["a"], new Date("2024-03-15T10:20:30Z")),
+          entry("old.txt", ["b"], new Date("1970-01-01T00:00:00Z")),
+        ]),
+      ),
+    );
+    const [recent, old] = readCentralDirectory(zip);
+
+    expect(recent?.dosTime).toBe((10 << 11) | (20 << 5) | 15);
+    expect(recent?.dosDate).toBe(((2024 - 1980) << 9) | (3 << 5) | 15);
+    // Clamped to 1980-01-01 00:00
+    expect(old?.dosTime).toBe(0);
+    expect(old?.dosDate).toBe((1 << 5) | 1);
+  });
+
+  it("writes an empty archive when there are no entries", async () => {
+    const zip = await readAll(createZipStream(entriesOf([])));
+    expect(zip.length).toBe(22);
+    expect(readCentralDirectory(zip)).toEqual([]);
+  });
+
+  it("uses ZIP64 records for entries declared at 4 GiB or more", async () => {
+    const zip = await readAll(
+      createZipStream(
+        entriesOf([
+          {
+            name: "large.bin",
+            size: 2 ** 32,
+            lastModified: new Date("2024-03-15T10:20:30Z"),
+            body: streamOf("not actually large"),
+          },
+        ]),
+      ),
+    );
+    const view = viewOf(zip);
+
+    // Local header: sizes deferred to a ZIP64 extra field
+    expect(view.getUint16(4, true)).toBe(45);
+    expect(view.getUint32(18, true)).toBe(0xffffffff);
+    expect(view.getUint32(22, true)).toBe(0xffffffff);
+    expect(view.getUint16(30 + "large.bin".length, true)).toBe(0x0001);
+
+    const [record] = readCentralDirectory(zip);
+    expect(record?.versionNeeded).toBe(45);
+    expect(record?.size).toBe(0xffffffff);
+
+    // The real size is in the central directory's ZIP64 extra field
+    const extra = viewOf(record?.extra ?? new Uint8Array(0));
+    expect(extra.getUint16(0, true)).toBe(0x0001);
+    expect(extra.getUint16(2, true)).toBe(16);
+    expect(extra.getBigUint64(4, true)).toBe(18n);
+    expect(extra.getBigUint64(12, true)).toBe(18n);
+
+    // 64-bit data descriptor
+    const start = dataOffset(zip, 0);
+    const descriptor = viewOf(zip.subarray(start + 18));
+    expect(descriptor.getUint32(0, true)).toBe(0x08074b50);
+    expect(descriptor.getBigUint64(8, true)).toBe(18n);
+    expect(descriptor.getBigUint64(16, true)).toBe(18n);
+  });
+
+  it("fails the archive and reports the error when a body fails", async () => {
+    const onError = vi.fn();
+    const failing: ZipEntry = {
+      name: "broken.txt",
+      size: 10,
+      lastModified: new Date("2024-03-15T10:20:30Z"),
+      body: new ReadableStream({
+        pull(controller) {
+          controller.error(new Error("upstream failed"));
+        },
+      }),
+    };
+
+    await expect(
+      readAll(createZipStream(entriesOf([failing]), { onError })),
+    ).rejects.toThrow("upstream failed");
+    await vi.waitFor(() => {
+      expect(onError).toHaveBeenCalledOnce();
+    });
+  });
+});
@@ -0,0 +1,333 @@
+/**
+ * Streaming ZIP Writer
+ *
+ * Produces a ZIP archive (stored, no compression) as a ReadableStream while
+ * the entry bodies are still being fetched, so archive size is bounded by R2
+ * rather than Worker memory. Sizes and CRCs are written in data descriptors
+ * after each entry, and ZIP64 records are emitted for entries, offsets or
+ * entry counts that overflow the classic 32-bit/16-bit fields.
+ */
+
+export interface ZipEntry {
+  /** Path of the entry inside the archive */
+  name: string;
+  /** Expected size in bytes (used to decide on ZIP64 local headers) */
+  size: number;
+  lastModified: Date;
+  body: ReadableStream<Uint8Array>;
+}
+
+export interface ZipStreamOptions {
+  /** Called if the archive fails after streaming has started */
+  onError?: (error: unknown) => void | Promise<void>;
+}
+
+interface CentralDirectoryRecord {
+  name: Uint8Array;
+  crc: number;
+  size: number;
+  offset: number;
+  dosTime: number;
+  dosDate: number;
+  zip64: boolean;
+}
+
+const MAX_UINT32 = 0xffffffff;
+const MAX_UINT16 = 0xffff;
+const ZIP64_VERSION = 45;
+const DEFAULT_VERSION = 20;
+// Bit 3: sizes/CRC follow in a data descriptor, bit 11: UTF-8 file names
+const GENERAL_PURPOSE_FLAGS = 0x0808;
+
+let crcTable: Uint32Array | null = null;
+
+function getCrcTable(): Uint32Array {
+  if (crcTable !== null) {
+    return crcTable;
+  }
+  const table = new Uint32Array(256);
+  for (let n = 0; n < 256; n++) {
+    let c = n;
+    for (let k = 0; k < 8; k++) {
+      c = c & 1 ? 0xedb88320 ^ (c >>> 1) : c >>> 1;
+    }
+    table[n] = c >>> 0;
+  }
+  crcTable = table;
+  return table;
+}
+
+/**
+ * Update a running CRC-32 (pass 0 for the first chunk)
+ */
+export function crc32(chunk: Uint8Array, crc = 0): number {
+  const table = getCrcTable();
+  let c = crc ^ MAX_UINT32;
+  for (const byte of chunk) {
+    c = (table[(c ^ byte) & 0xff] ?? 0) ^ (c >>> 8);
+  }
+  return (c ^ MAX_UINT32) >>> 0;
+}
+
+function toDosDateTime(date: Date): { dosTime: number; dosDate: number } {
+  const year = date.getUTCFullYear();
+  if (Number.isNaN(year) || year < 1980) {
+    // Earliest representable DOS date: 1980-01-01 00:00
+    return { dosTime: 0, dosDate: (1 << 5) | 1 };
+  }
+  return {
+    dosTime:
+      (date.getUTCHours() << 11) |
+      (date.getUTCMinutes() << 5) |
+      Math.floor(date.getUTCSeconds() / 2),
+    dosDate:
+      ((Math.min(year, 2107) - 1980) << 9) |
+      ((date.getUTCMonth() + 1) << 5) |
+      date.getUTCDate(),
+  };
+}
+
+function setUint64(view: DataView, offset: number, value: number): void {
+  view.setBigUint64(offset, BigInt(value), true);
+}
+
+function localFileHeader(
+  name: Uint8Array,
+  dosTime: number,
+  dosDate: number,
+  zip64: boolean,
+): Uint8Array {
+  const extraLength = zip64 ? 20 : 0;
+  const header = new Uint8Array(30 + name.length + extraLength);
+  const view = new DataView(header.buffer);
+
+  view.setUint32(0, 0x04034b50, true);
+  view.setUint16(4, zip64 ? ZIP64_VERSION : DEFAULT_VERSION, true);
+  view.setUint16(6, GENERAL_PURPOSE_FLAGS, true);
+  view.setUint16(8, 0, true); // stored
+  view.setUint16(10, dosTime, true);
+  view.setUint16(12, dosDate, true);
+  view.setUint32(14, 0, true); // CRC in data descriptor
+  view.setUint32(18, zip64 ? MAX_UINT32 : 0, true);
+  view.setUint32(22, zip64 ? MAX_UINT32 : 0, true);
+  view.setUint16(26, name.length, true);
+  view.setUint16(28, extraLength, true);
+  header.set(name, 30);
+
+  if (zip64) {
+    // ZIP64 extended information; sizes follow in the data descriptor
+    const extra = 30 + name.length;
+    view.setUint16(extra, 0x0001, true);
+    view.setUint16(extra + 2, 16, true);
+    setUint64(view, extra + 4, 0);
+    setUint64(view, extra + 12, 0);
+  }
+
+  return header;
+}
+
+function dataDescriptor(crc: number, size: number, zip64: boolean): Uint8Array {
+  const descriptor = new Uint8Array(zip64 ? 24 : 16);
+  const view = new DataView(descriptor.buffer);
+
+  view.setUint32(0, 0x08074b50, true);
+  view.setUint32(4, crc, true);
+  if (zip64) {
+    setUint64(view, 8, size);
+    setUint64(view, 16, size);
+  } else {
+    view.setUint32(8, size, true);
+    view.setUint32(12, size, true);
+  }
+
+  return descriptor;
+}
+
+function centralDirectoryHeader(record: CentralDirectoryRecord): Uint8Array {
+  const sizeOverflow = record.zip64 || record.size >= MAX_UINT32;
+  const offsetOverflow = record.offset >= MAX_UINT32;
+  const extraLength =
+    sizeOverflow || offsetOverflow
+      ? 4 + (sizeOverflow ? 16 : 0) + (offsetOverflow ? 8 : 0)
+      : 0;
+  const needsZip64 = extraLength > 0;
+
+  const header = new Uint8Array(46 + record.name.length + extraLength);
+  const view = new DataView(header.buffer);
+
+  view.setUint32(0, 0x02014b50, true);
+  view.setUint16(4, needsZip64 ? ZIP64_VERSION : DEFAULT_VERSION, true);
+  view.setUint16(6, needsZip64 ? ZIP64_VERSION : DEFAULT_VERSION, true);
+  view.setUint16(8, GENERAL_PURPOSE_FLAGS, true);
+  view.setUint16(10, 0, true);
+  view.setUint16(12, record.dosTime, true);
+  view.setUint16(14, record.dosDate, true);
+  view.setUint32(16, record.crc, true);
+  view.setUint32(20, sizeOverflow ? MAX_UINT32 : record.size, true);
+  view.setUint32(24, sizeOverflow ? MAX_UINT32 : record.size, true);
+  view.setUint16(28, record.name.length, true);
+  view.setUint16(30, extraLength, true);
+  view.setUint16(32, 0, true); // comment length
+  view.setUint16(34, 0, true); // disk number
+  view.setUint16(36, 0, true); // internal attributes
+  view.setUint32(38, 0, true); // external attributes
+  view.setUint32(42, offsetOverflow ? MAX_UINT32 : record.offset, true);
+  header.set(record.name, 46);
+
+  if (needsZip64) {
+    let extra = 46 + record.name.length;
+    view.setUint16(extra, 0x0001, true);
+    view.setUint16(extra + 2, extraLength - 4, true);
+    extra += 4;
+    if (sizeOverflow) {
+      setUint64(view, extra, record.size);
+      setUint64(view, extra + 8, record.size);
+      extra += 16;
+    }
+    if (offsetOverflow) {
+      setUint64(view, extra, record.offset);
+    }
+  }
+
+  return header;
+}
+
+function endOfCentralDirectory(
+  entryCount: number,
+  directorySize: number,
+  directoryOffset: number,
+): Uint8Array {
+  const needsZip64 =
+    entryCount >= MAX_UINT16 ||
+    directorySize >= MAX_UINT32 ||
+    directoryOffset >= MAX_UINT32;
+
+  const end = new Uint8Array((needsZip64 ? 56 + 20 : 0) + 22);
+  const view = new DataView(end.buffer);
+  let pos = 0;
+
+  if (needsZip64) {
+    const zip64EndOffset = directoryOffset + directorySize;
+
+    // ZIP64 end of central directory record
+    view.setUint32(pos, 0x06064b50, true);
+    setUint64(view, pos + 4, 44);
+    view.setUint16(pos + 12, ZIP64_VERSION, true);
+    view.setUint16(pos + 14, ZIP64_VERSION, true);
+    view.setUint32(pos + 16, 0, true);
+    view.setUint32(pos + 20, 0, true);
+    setUint64(view, pos + 24, entryCount);
+    setUint64(view, pos + 32, entryCount);
+    setUint64(view, pos + 40, directorySize);
+    setUint64(view, pos + 48, directoryOffset);
+    pos += 56;
+
+    // ZIP64 end of central directory locator
+    view.setUint32(pos, 0x07064b50, true);
+    view.setUint32(pos + 4, 0, true);
+    setUint64(view, pos + 8, zip64EndOffset);
+    view.setUint32(pos + 16, 1, true);
+    pos += 20;
+  }
+
+  view.setUint32(pos, 0x06054b50, true);
+  view.setUint16(pos + 4, 0, true);
+  view.setUint16(pos + 6, 0, true);
+  view.setUint16(pos + 8, Math.min(entryCount, MAX_UINT16), true);
+  view.setUint16(pos + 10, Math.min(entryCount, MAX_UINT16), true);
+  view.setUint32(pos + 12, Math.min(directorySize, MAX_UINT32), true);
+  view.setUint32(pos + 16, Math.min(directoryOffset, MAX_UINT32), true);
+  view.setUint16(pos + 20, 0, true);
+
+  return end;
+}
+
+/**
+ * Create a streaming ZIP archive from an (async) sequence of entries.
+ *
+ * Entries are pulled one at a time, so the next object is only fetched once
+ * the previous one has been fully written to the response.
+ */
+export function createZipStream(
+  entries: AsyncIterable<ZipEntry>,
+  options: ZipStreamOptions = {},
+): ReadableStream<Uint8Array> {
+  const { readable, writable } = new TransformStream<Uint8Array, Uint8Array>();
+  const writer = writable.getWriter();
+  const encoder = new TextEncoder();
+
+  const pump = async (): Promise<void> => {
+    const records: CentralDirectoryRecord[] = [];
+    let offset = 0;
+
+    const write = async (chunk: Uint8Array): Promise<void> => {
+      await writer.write(chunk);
+      offset += chunk.length;
+    };
+
+    for await (const entry of entries) {
+      const name = encoder.encode(entry.name.replace(/^\/+/, ""));
+      const zip64 = entry.size >= MAX_UINT32;
+      const { dosTime, dosDate } = toDosDateTime(entry.lastModified);
+      const entryOffset = offset;
+
+      await write(localFileHeader(name, dosTime, dosDate, zip64));
+
+      // Compute CRC and size as the body passes through
+      let crc = 0;
+      let size = 0;
+      const reader = entry.body
+        .pipeThrough(
+          new TransformStream<Uint8Array, Uint8Array>({
+            transform(chunk, controller) {
+              crc = crc32(chunk, crc);
+              size += chunk.length;
+              controller.enqueue(chunk);
+            },
+          }),
+        )
+        .getReader();
+
+      for (;;) {
+        const { done, value } = await reader.read();
+        if (done) break;
+        await write(value);
+      }
+
+      if (!zip64 && size >= MAX_UINT32) {
+        throw new Error(
+          `Entry ${entry.name} is larger than its declared size (${String(entry.size)} bytes)`,
+        );
+      }
+
+      await write(dataDescriptor(crc, size, zip64));
+      records.push({
+        name,
+        crc,
+        size,
+        offset: entryOffset,
+        dosTime,
+        dosDate,
+        zip64,
+      });
+    }
+
+    const directoryOffset = offset;
+    for (const record of records) {
+      await write(centralDirectoryHeader(record));
+    }
+    const directorySize = offset - directoryOffset;
+
+    await write(
+      endOfCentralDirectory(records.length, directorySize, directoryOffset),
+    );
+    await writer.close();
+  };
+
+  pump().catch(async (error: unknown) => {
+    await writer.abort(error).catch(() => undefined);
+    await options.onError?.(error);
+  });
+
+  return readable;
+}